@@ -0,0 +1,393 @@
+use crate::{Scalar, Vec2, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 2D axis-aligned bounding box described by its `min` and `max` corners.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Aabb2, Vec2};
+///
+/// let b = Aabb2::from_center_half_extents(Vec2::ZERO, Vec2::ONE);
+/// assert!(b.contains_point(Vec2::new(0.5, -1.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb2 {
+    /// Minimum corner.
+    pub min: Vec2,
+    /// Maximum corner.
+    pub max: Vec2,
+}
+
+impl Aabb2 {
+    #[inline(always)]
+    pub const fn new(min: Vec2, max: Vec2) -> Self {
+        Aabb2 { min, max }
+    }
+
+    /// Creates a box centered at `center` extending `half_extents` along each axis.
+    #[inline]
+    pub const fn from_center_half_extents(center: Vec2, half_extents: Vec2) -> Self {
+        Aabb2::new(
+            Vec2::new(center.x - half_extents.x, center.y - half_extents.y),
+            Vec2::new(center.x + half_extents.x, center.y + half_extents.y),
+        )
+    }
+
+    /// Returns the center of the box.
+    #[inline]
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns half the size of the box along each axis.
+    #[inline]
+    pub fn half_extents(&self) -> Vec2 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Returns the point at fraction `unit` of the way from `min` to `max` along each axis, so
+    /// `(0, 0)` is `min` and `(1, 1)` is `max`.
+    #[inline]
+    pub fn point_at(&self, unit: Vec2) -> Vec2 {
+        let size = self.max - self.min;
+        self.min + Vec2::new(size.x * unit.x, size.y * unit.y)
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of the box.
+    #[inline]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns the point inside or on the box closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        point.max(self.min).min(self.max)
+    }
+
+    /// Returns the squared distance from the box to `point`, or `0.0` if it is inside.
+    #[inline]
+    pub fn distance_squared_to_point(&self, point: Vec2) -> Scalar {
+        self.closest_point(point).distance_squared(point)
+    }
+
+    /// Returns `true` if this box and `other` overlap (touching counts).
+    #[inline]
+    pub fn overlaps(&self, other: &Aabb2) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
+    /// Returns `true` if `other` lies entirely inside this box.
+    #[inline]
+    pub fn contains_aabb(&self, other: &Aabb2) -> bool {
+        self.min.x <= other.min.x
+            && self.max.x >= other.max.x
+            && self.min.y <= other.min.y
+            && self.max.y >= other.max.y
+    }
+
+    /// Returns the smallest box containing both this box and `other`.
+    #[inline]
+    pub fn union(&self, other: &Aabb2) -> Aabb2 {
+        Aabb2::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Returns the overlapping region of this box and `other`, or `None` if they are disjoint.
+    #[inline]
+    pub fn intersection(&self, other: &Aabb2) -> Option<Aabb2> {
+        self.overlaps(other)
+            .then(|| Aabb2::new(self.min.max(other.min), self.max.min(other.max)))
+    }
+
+    /// Returns this box grown by `margin` on every side (shrunk if negative).
+    #[inline]
+    pub fn expand_by(&self, margin: Scalar) -> Aabb2 {
+        let margin = Vec2::splat(margin);
+        Aabb2::new(self.min - margin, self.max + margin)
+    }
+
+    /// Grows this box in place so it contains `point`.
+    #[inline]
+    pub fn grow_to_include(&mut self, point: Vec2) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+}
+
+/// A 3D axis-aligned bounding box described by its `min` and `max` corners.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Aabb3, Vec3};
+///
+/// let b = Aabb3::new(Vec3::ZERO, Vec3::new(2.0, 2.0, 2.0));
+/// assert_eq!(b.center(), Vec3::ONE);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb3 {
+    /// Minimum corner.
+    pub min: Vec3,
+    /// Maximum corner.
+    pub max: Vec3,
+}
+
+impl Aabb3 {
+    #[inline(always)]
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb3 { min, max }
+    }
+
+    /// Creates a box centered at `center` extending `half_extents` along each axis.
+    #[inline]
+    pub const fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        Aabb3::new(
+            Vec3::new(
+                center.x - half_extents.x,
+                center.y - half_extents.y,
+                center.z - half_extents.z,
+            ),
+            Vec3::new(
+                center.x + half_extents.x,
+                center.y + half_extents.y,
+                center.z + half_extents.z,
+            ),
+        )
+    }
+
+    /// Returns the center of the box.
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns half the size of the box along each axis.
+    #[inline]
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Returns the point at fraction `unit` of the way from `min` to `max` along each axis, so
+    /// `(0, 0, 0)` is `min` and `(1, 1, 1)` is `max`.
+    #[inline]
+    pub fn point_at(&self, unit: Vec3) -> Vec3 {
+        let size = self.max - self.min;
+        self.min + Vec3::new(size.x * unit.x, size.y * unit.y, size.z * unit.z)
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of the box.
+    #[inline]
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+            && point.z >= self.min.z
+            && point.z <= self.max.z
+    }
+
+    /// Returns the point inside or on the box closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        point.max(self.min).min(self.max)
+    }
+
+    /// Returns the corner furthest along `direction`.
+    #[inline]
+    pub fn support(&self, direction: Vec3) -> Vec3 {
+        Vec3::new(
+            if direction.x >= 0.0 {
+                self.max.x
+            } else {
+                self.min.x
+            },
+            if direction.y >= 0.0 {
+                self.max.y
+            } else {
+                self.min.y
+            },
+            if direction.z >= 0.0 {
+                self.max.z
+            } else {
+                self.min.z
+            },
+        )
+    }
+
+    /// Returns the squared distance from the box to `point`, or `0.0` if it is inside.
+    #[inline]
+    pub fn distance_squared_to_point(&self, point: Vec3) -> Scalar {
+        self.closest_point(point).distance_squared(point)
+    }
+
+    /// Returns `true` if this box and `other` overlap (touching counts).
+    #[inline]
+    pub fn overlaps(&self, other: &Aabb3) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+
+    /// Returns `true` if `other` lies entirely inside this box.
+    #[inline]
+    pub fn contains_aabb(&self, other: &Aabb3) -> bool {
+        self.min.x <= other.min.x
+            && self.max.x >= other.max.x
+            && self.min.y <= other.min.y
+            && self.max.y >= other.max.y
+            && self.min.z <= other.min.z
+            && self.max.z >= other.max.z
+    }
+
+    /// Returns the smallest box containing both this box and `other`.
+    #[inline]
+    pub fn union(&self, other: &Aabb3) -> Aabb3 {
+        Aabb3::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Returns the overlapping region of this box and `other`, or `None` if they are disjoint.
+    #[inline]
+    pub fn intersection(&self, other: &Aabb3) -> Option<Aabb3> {
+        self.overlaps(other)
+            .then(|| Aabb3::new(self.min.max(other.min), self.max.min(other.max)))
+    }
+
+    /// Returns this box grown by `margin` on every side (shrunk if negative).
+    #[inline]
+    pub fn expand_by(&self, margin: Scalar) -> Aabb3 {
+        let margin = Vec3::splat(margin);
+        Aabb3::new(self.min - margin, self.max + margin)
+    }
+
+    /// Grows this box in place so it contains `point`.
+    #[inline]
+    pub fn grow_to_include(&mut self, point: Vec3) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aabb2_center_and_half_extents_round_trip() {
+        let center = Vec2::new(1.0 as Scalar, -2.0 as Scalar);
+        let half = Vec2::new(3.0 as Scalar, 0.5 as Scalar);
+        let b = Aabb2::from_center_half_extents(center, half);
+        assert_eq!(b.center(), center);
+        assert_eq!(b.half_extents(), half);
+    }
+
+    #[test]
+    fn aabb3_center_and_half_extents_round_trip() {
+        let center = Vec3::new(1.0 as Scalar, -2.0 as Scalar, 4.0 as Scalar);
+        let half = Vec3::new(3.0 as Scalar, 0.5 as Scalar, 2.0 as Scalar);
+        let b = Aabb3::from_center_half_extents(center, half);
+        assert_eq!(b.center(), center);
+        assert_eq!(b.half_extents(), half);
+    }
+
+    #[test]
+    fn aabb3_contains_point_includes_boundary() {
+        let b = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        assert!(b.contains_point(Vec3::ONE));
+        assert!(!b.contains_point(Vec3::new(0.5, 0.5, 1.5)));
+    }
+
+    #[test]
+    fn aabb2_set_operations() {
+        let a = Aabb2::new(Vec2::ZERO, Vec2::splat(2.0));
+        let b = Aabb2::new(Vec2::ONE, Vec2::splat(3.0));
+        let far = Aabb2::new(Vec2::splat(5.0), Vec2::splat(6.0));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&far));
+        assert_eq!(a.union(&b), Aabb2::new(Vec2::ZERO, Vec2::splat(3.0)));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb2::new(Vec2::ONE, Vec2::splat(2.0)))
+        );
+        assert_eq!(a.intersection(&far), None);
+        assert!(a.union(&b).contains_aabb(&a));
+        assert!(!a.contains_aabb(&b));
+    }
+
+    #[test]
+    fn aabb2_grow_expand_and_distance() {
+        let mut a = Aabb2::new(Vec2::ZERO, Vec2::ONE);
+        a.grow_to_include(Vec2::new(-1.0, 3.0));
+        assert_eq!(a, Aabb2::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 3.0)));
+        assert_eq!(
+            a.expand_by(0.5),
+            Aabb2::new(Vec2::new(-1.5, -0.5), Vec2::new(1.5, 3.5))
+        );
+        assert_eq!(
+            a.distance_squared_to_point(Vec2::new(4.0, 7.0)),
+            25.0 as Scalar
+        );
+        assert_eq!(
+            a.distance_squared_to_point(Vec2::new(0.0, 1.0)),
+            0.0 as Scalar
+        );
+    }
+
+    #[test]
+    fn aabb3_set_operations() {
+        let a = Aabb3::new(Vec3::ZERO, Vec3::splat(2.0));
+        let b = Aabb3::new(Vec3::ONE, Vec3::splat(3.0));
+        let touching = Aabb3::new(Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 1.0));
+        let far = Aabb3::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(1.0, 1.0, 6.0));
+        assert!(a.overlaps(&b));
+        assert!(a.overlaps(&touching));
+        assert!(!a.overlaps(&far));
+        assert_eq!(
+            a.union(&far),
+            Aabb3::new(Vec3::ZERO, Vec3::new(2.0, 2.0, 6.0))
+        );
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb3::new(Vec3::ONE, Vec3::splat(2.0)))
+        );
+        assert_eq!(a.intersection(&far), None);
+        assert!(a.expand_by(1.0).contains_aabb(&b));
+    }
+
+    #[test]
+    fn aabb3_grow_and_distance() {
+        let mut a = Aabb3::new(Vec3::ZERO, Vec3::ZERO);
+        a.grow_to_include(Vec3::new(1.0, -1.0, 2.0));
+        assert_eq!(
+            a,
+            Aabb3::new(Vec3::new(0.0, -1.0, 0.0), Vec3::new(1.0, 0.0, 2.0))
+        );
+        assert_eq!(
+            a.distance_squared_to_point(Vec3::new(3.0, 2.0, 2.0)),
+            8.0 as Scalar
+        );
+    }
+
+    #[test]
+    fn aabb3_closest_point_clamps_to_box() {
+        let b = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        let inside = Vec3::new(0.25, 0.5, 0.75);
+        assert_eq!(b.closest_point(inside), inside);
+        assert_eq!(
+            b.closest_point(Vec3::new(2.0, -1.0, 0.5)),
+            Vec3::new(1.0, 0.0, 0.5)
+        );
+    }
+}
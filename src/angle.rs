@@ -0,0 +1,125 @@
+//! Helpers for angles in radians, such as headings and yaw.
+//!
+//! Angles are periodic, so naive arithmetic breaks at the seam: lerping from `3.1` to `-3.1`
+//! sweeps almost a full turn the long way round instead of crossing `±π`. These functions
+//! always take the short way and return angles wrapped to `(-π, π]`.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{Scalar, angle};
+//!
+//! let (from, to): (Scalar, Scalar) = (angle::to_radians(170.0), angle::to_radians(-170.0));
+//! // Twenty degrees across the seam, not 340 degrees back around.
+//! assert!((angle::delta_angle(from, to) - angle::to_radians(20.0)).abs() < 1.0e-5);
+//! let halfway = angle::lerp_angle(from, to, 0.5);
+//! assert!((halfway.abs() - angle::to_radians(180.0)).abs() < 1.0e-5);
+//! ```
+
+use crate::consts::{DEG_TO_RAD, PI, RAD_TO_DEG, TAU};
+use crate::{Scalar, float};
+
+/// Wraps `angle` to `(-π, π]`.
+#[inline]
+pub fn wrap_angle(angle: Scalar) -> Scalar {
+    let wrapped = angle - TAU * float::floor((angle + PI) / TAU);
+    if wrapped <= -PI {
+        wrapped + TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Returns the signed shortest rotation from `from` to `to`, in `(-π, π]`; positive is
+/// counter-clockwise.
+#[inline]
+pub fn delta_angle(from: Scalar, to: Scalar) -> Scalar {
+    wrap_angle(to - from)
+}
+
+/// Interpolates from `from` (at `t = 0`) to `to` (at `t = 1`) along the shorter arc,
+/// without clamping `t`. The result is wrapped to `(-π, π]`.
+#[inline]
+pub fn lerp_angle(from: Scalar, to: Scalar, t: Scalar) -> Scalar {
+    wrap_angle(from + delta_angle(from, to) * t)
+}
+
+/// Turns `from` towards `to` along the shorter arc by at most `max_delta` radians, stopping
+/// at `to` instead of overshooting, e.g. to cap a turn rate per tick. A negative or NaN
+/// `max_delta` is treated as zero.
+#[inline]
+pub fn move_towards_angle(from: Scalar, to: Scalar, max_delta: Scalar) -> Scalar {
+    let max = max_delta.max(0.0);
+    let delta = delta_angle(from, to);
+    wrap_angle(from + delta.clamp(-max, max))
+}
+
+/// Clamps `angle` to within `max_deviation` radians of `center` either way, e.g. a turret's
+/// arc of fire or a head turning within its neck's range. The result is wrapped to
+/// `(-π, π]`. A negative or NaN `max_deviation` is treated as zero.
+#[inline]
+pub fn clamp_angle(angle: Scalar, center: Scalar, max_deviation: Scalar) -> Scalar {
+    let max = max_deviation.max(0.0);
+    let offset = delta_angle(center, angle).clamp(-max, max);
+    wrap_angle(center + offset)
+}
+
+/// Converts radians to degrees.
+#[inline]
+pub const fn to_degrees(radians: Scalar) -> Scalar {
+    radians * RAD_TO_DEG
+}
+
+/// Converts degrees to radians.
+#[inline]
+pub const fn to_radians(degrees: Scalar) -> Scalar {
+    degrees * DEG_TO_RAD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn wrapping_lands_in_the_half_open_range() {
+        assert_eq!(wrap_angle(PI), PI);
+        assert_eq!(wrap_angle(-PI), PI);
+        assert_eq!(wrap_angle(0.0), 0.0);
+        assert!((wrap_angle(3.0 * PI + 0.5) - (-PI + 0.5)).abs() <= EPS);
+        assert!((wrap_angle(-7.0 * TAU + 1.0) - 1.0).abs() <= EPS);
+        for i in -100..100 {
+            let a = wrap_angle(i as Scalar * 0.37);
+            assert!(a > -PI && a <= PI, "{a}");
+        }
+    }
+
+    #[test]
+    fn interpolation_takes_the_short_way() {
+        let (from, to) = (to_radians(-170.0), to_radians(170.0));
+        assert!((delta_angle(from, to) - to_radians(-20.0)).abs() <= EPS);
+        assert!((lerp_angle(from, to, 0.25) - to_radians(-175.0)).abs() <= EPS);
+        assert!((lerp_angle(from, to, 0.75) - to_radians(175.0)).abs() <= EPS);
+        assert!((move_towards_angle(from, to, 0.1) - (from - 0.1)).abs() <= EPS);
+        assert_eq!(move_towards_angle(from, to, 1.0), wrap_angle(to));
+    }
+
+    #[test]
+    fn clamping_works_across_the_seam() {
+        let center = to_radians(170.0);
+        let limit = to_radians(30.0);
+        let clamped = clamp_angle(to_radians(-150.0), center, limit);
+        assert!((clamped - to_radians(-160.0)).abs() <= EPS);
+        let inside = to_radians(-175.0);
+        assert!((clamp_angle(inside, center, limit) - inside).abs() <= EPS);
+        assert!((to_degrees(to_radians(42.0)) - 42.0).abs() <= EPS);
+    }
+
+    #[test]
+    fn invalid_bounds_do_not_panic() {
+        for bound in [-1.0 as Scalar, Scalar::NAN, Scalar::NEG_INFINITY] {
+            assert_eq!(move_towards_angle(0.5, 2.0, bound), 0.5);
+            assert_eq!(clamp_angle(2.0, 0.5, bound), 0.5);
+        }
+    }
+}
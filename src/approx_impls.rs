@@ -0,0 +1,219 @@
+//! [`approx`] trait implementations, so tests can use `assert_relative_eq!` and friends on
+//! the crate's types.
+//!
+//! Every comparison is component-wise and passes only if each component does. That makes
+//! [`Quat`] compare its components, not rotations: `q` and `-q` are different values here.
+//! Integer, boolean and enum fields, such as a [`WorldPos`] sector or a hit's `front_face`,
+//! must be equal. Slices of points, such as polygon vertices, must have the same length.
+//!
+//! Left out on purpose:
+//! - integer types ([`IVec2`](crate::IVec2), [`Hex`](crate::Hex), [`QPos3`](crate::QPos3),
+//!   the deltas, packed directions and fixed-point types), which compare exactly with `==`;
+//! - containers and indexes ([`DynamicBvh`](crate::DynamicBvh), [`KdTree`](crate::KdTree),
+//!   the grids and the history and snapshot buffers), [`Heightfield`](crate::Heightfield)
+//!   and spline paths with their cached tables, whose layout isn't a meaningful value;
+//! - stateful controllers and generators (springs, PID, tweens, corrections, noise and
+//!   random number generators).
+
+use crate::conventions::{Axes, CoordinateConversion};
+use crate::gjk::Proximity;
+use crate::intersect::{AabbHit, PlaneHit, RayHit, TriangleHit};
+use crate::overlap::Contact;
+use crate::sweep::SweepHit;
+use crate::{
+    Aabb2, Aabb3, Capsule3, ChunkCoords, Circle, Cone, ConvexPolygon2, CubicBezier, CubicHermite,
+    Cylinder, FloatingOrigin, Fov, Frustum, GeoSphere, Geodetic, HexLayout, IsoProjection, Mat3,
+    Mat4, MotionState, Obb3, Plane, Polygon2, PolygonContact, Polyline2, Polyline3,
+    QuadraticBezier, Quat, Ray2, Ray3, Scalar, Segment2, Segment3, Sphere, Transform, Triangle3,
+    Vec2, Vec3, Vec4, WorldPos, WrapWorld,
+};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+/// Implements the three traits for each listed type.
+///
+/// `Type { a, b; exact: c; slices: d }` compares `a` and `b` approximately, requires `c` to
+/// be equal and compares the elements of the slice or `Vec` `d` approximately. A generic type
+/// names its parameters after a `where`, as `Type<P> where P { .. }`, and each must implement
+/// the trait being implemented.
+macro_rules! impl_approx {
+    ($(
+        $t:ty $(where $($g:ident),+)? {
+            $($field:ident),* $(; exact: $($exact:ident),+)? $(; slices: $($slice:ident),+)?
+        }
+    ),* $(,)?) => {$(
+        impl$(<$($g: AbsDiffEq<Epsilon = Scalar>),+>)? AbsDiffEq for $t {
+            type Epsilon = Scalar;
+
+            #[inline]
+            fn default_epsilon() -> Scalar {
+                Scalar::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: Scalar) -> bool {
+                $($(if self.$exact != other.$exact {
+                    return false;
+                })+)?
+                $(if !self.$field.abs_diff_eq(&other.$field, epsilon) {
+                    return false;
+                })*
+                $($(if !self.$slice[..].abs_diff_eq(&other.$slice[..], epsilon) {
+                    return false;
+                })+)?
+                true
+            }
+        }
+
+        impl$(<$($g: RelativeEq<Epsilon = Scalar>),+>)? RelativeEq for $t {
+            #[inline]
+            fn default_max_relative() -> Scalar {
+                Scalar::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &Self, epsilon: Scalar, max_relative: Scalar) -> bool {
+                $($(if self.$exact != other.$exact {
+                    return false;
+                })+)?
+                $(if !self.$field.relative_eq(&other.$field, epsilon, max_relative) {
+                    return false;
+                })*
+                $($(if !self.$slice[..].relative_eq(&other.$slice[..], epsilon, max_relative) {
+                    return false;
+                })+)?
+                true
+            }
+        }
+
+        impl$(<$($g: UlpsEq<Epsilon = Scalar>),+>)? UlpsEq for $t {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                Scalar::default_max_ulps()
+            }
+
+            #[inline]
+            fn ulps_eq(&self, other: &Self, epsilon: Scalar, max_ulps: u32) -> bool {
+                $($(if self.$exact != other.$exact {
+                    return false;
+                })+)?
+                $(if !self.$field.ulps_eq(&other.$field, epsilon, max_ulps) {
+                    return false;
+                })*
+                $($(if !self.$slice[..].ulps_eq(&other.$slice[..], epsilon, max_ulps) {
+                    return false;
+                })+)?
+                true
+            }
+        }
+    )*};
+}
+
+impl_approx!(
+    Vec2 { x, y },
+    Vec3 { x, y, z },
+    Vec4 { x, y, z, w },
+    Quat { x, y, z, w },
+    Mat3 { x_axis, y_axis, z_axis },
+    Mat4 { x_axis, y_axis, z_axis, w_axis },
+    Transform { translation, rotation, scale },
+    Axes { up, forward, right },
+    CoordinateConversion { basis, unit_scale },
+    Aabb2 { min, max },
+    Aabb3 { min, max },
+    Circle { center, radius },
+    Sphere { center, radius },
+    Plane { normal, d },
+    Ray2 { origin, direction },
+    Ray3 { origin, direction },
+    Segment2 { start, end },
+    Segment3 { start, end },
+    Triangle3 { a, b, c },
+    Capsule3 { start, end, radius },
+    Obb3 { center, half_extents, rotation },
+    Cylinder { center, axis, half_height, radius },
+    Cone { apex, axis, height, radius },
+    Frustum { ; slices: planes },
+    Polygon2 { ; slices: vertices },
+    ConvexPolygon2 { ; slices: vertices },
+    Polyline2 { ; slices: points },
+    Polyline3 { ; slices: points },
+    QuadraticBezier<P> where P { p0, p1, p2 },
+    CubicBezier<P> where P { p0, p1, p2, p3 },
+    CubicHermite<P> where P { p0, m0, p1, m1 },
+    Fov { half_angle, max_range },
+    RayHit { t, normal },
+    AabbHit { t_enter, t_exit, normal },
+    PlaneHit { t; exact: front_face },
+    TriangleHit { t, barycentric; exact: front_face },
+    SweepHit { time, normal },
+    Contact { normal, depth },
+    PolygonContact { mtv, point },
+    Proximity { distance, point_a, point_b },
+    WorldPos { local; exact: sector },
+    Geodetic { latitude, longitude, altitude },
+    GeoSphere { radius },
+    ChunkCoords { chunk_size },
+    FloatingOrigin { origin, recenter_distance },
+    WrapWorld { size_x, size_z },
+    HexLayout { size, origin; exact: orientation },
+    IsoProjection { tile_width, tile_height, origin; exact: layout },
+    MotionState { time, position, velocity, acceleration, rotation, angular_velocity },
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    #[test]
+    fn nested_types_compare_component_wise() {
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5);
+        let round_trip = Quat::from_mat3(&Mat3::from_quat(q));
+        assert_relative_eq!(round_trip, q, epsilon = 1.0e-6);
+        assert_relative_ne!(-q, q);
+
+        let t = Transform::new(Vec3::new(1.0e4, 0.0, 0.0), q, Vec3::ONE);
+        let mut moved = t;
+        moved.translation.x += 1.0e-3;
+        assert_relative_eq!(moved, t, max_relative = 1.0e-6);
+        assert_abs_diff_eq!(moved, t, epsilon = 1.0e-2);
+        assert!(!t.abs_diff_eq(&moved, 1.0e-4));
+
+        let s = Sphere::new(Vec3::new(0.1 + 0.2, 0.0, 0.0), 1.0);
+        assert_ulps_eq!(s, Sphere::new(Vec3::new(0.3, 0.0, 0.0), 1.0));
+    }
+
+    #[test]
+    fn slices_and_exact_fields_must_match() {
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        let a = Polygon2::new(square.to_vec());
+        let mut b = a.clone();
+        b.vertices[2].x += 1.0e-7;
+        assert_abs_diff_eq!(a, b, epsilon = 1.0e-6);
+        b.vertices.pop();
+        assert!(!a.abs_diff_eq(&b, 1.0));
+
+        let here = WorldPos::new(crate::IVec3::new(1, 0, 0), Vec3::new(0.5, 0.0, 0.0));
+        let next_sector = WorldPos::new(crate::IVec3::new(2, 0, 0), here.local);
+        assert_relative_eq!(here, here);
+        assert!(!here.abs_diff_eq(&next_sector, Scalar::INFINITY));
+
+        let hit = PlaneHit {
+            t: 1.0,
+            front_face: true,
+        };
+        let back = PlaneHit {
+            front_face: false,
+            ..hit
+        };
+        assert!(!hit.ulps_eq(&back, 1.0, u32::MAX));
+
+        let curve = QuadraticBezier::new(Vec2::ZERO, Vec2::new(1.0, 2.0), Vec2::new(3.0, 0.0));
+        assert_ulps_eq!(curve, curve);
+    }
+}
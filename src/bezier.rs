@@ -0,0 +1,357 @@
+use crate::{Scalar, SplinePoint, Vec2, Vec3};
+use alloc::{vec, vec::Vec};
+
+/// Subdivision depth at which flattening stops even if the tolerance isn't met.
+const MAX_FLATTEN_DEPTH: u32 = 16;
+
+/// A quadratic Bezier curve from `p0` to `p2` with control point `p1`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{QuadraticBezier, Vec2};
+///
+/// // A projectile arc 10 units long peaking at 5 units high.
+/// let arc = QuadraticBezier::new(Vec2::ZERO, Vec2::new(5.0, 10.0), Vec2::new(10.0, 0.0));
+/// assert_eq!(arc.evaluate(0.5), Vec2::new(5.0, 5.0));
+/// assert_eq!(arc.derivative(0.5), Vec2::new(10.0, 0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadraticBezier<P> {
+    pub p0: P,
+    pub p1: P,
+    pub p2: P,
+}
+
+/// A [`QuadraticBezier`] over 2D points.
+pub type QuadraticBezier2 = QuadraticBezier<Vec2>;
+/// A [`QuadraticBezier`] over 3D points.
+pub type QuadraticBezier3 = QuadraticBezier<Vec3>;
+
+impl<P: SplinePoint> QuadraticBezier<P> {
+    #[inline(always)]
+    pub const fn new(p0: P, p1: P, p2: P) -> Self {
+        QuadraticBezier { p0, p1, p2 }
+    }
+
+    /// Returns the point at `t` in `[0, 1]`.
+    #[inline]
+    pub fn evaluate(&self, t: Scalar) -> P {
+        let u = 1.0 - t;
+        self.p0 * (u * u) + self.p1 * (2.0 * u * t) + self.p2 * (t * t)
+    }
+
+    /// Returns the derivative with respect to `t` at `t`.
+    #[inline]
+    pub fn derivative(&self, t: Scalar) -> P {
+        (self.p1 - self.p0) * (2.0 * (1.0 - t)) + (self.p2 - self.p1) * (2.0 * t)
+    }
+
+    /// Returns the same curve as a cubic Bezier.
+    #[inline]
+    pub fn to_cubic(&self) -> CubicBezier<P> {
+        let third = 1.0 / 3.0;
+        CubicBezier::new(
+            self.p0,
+            self.p0 + (self.p1 - self.p0) * (2.0 * third),
+            self.p2 + (self.p1 - self.p2) * (2.0 * third),
+            self.p2,
+        )
+    }
+
+    /// See [`CubicBezier::flatten`].
+    #[inline]
+    pub fn flatten(&self, tolerance: Scalar) -> Vec<P> {
+        self.to_cubic().flatten(tolerance)
+    }
+
+    /// See [`CubicBezier::arc_length`].
+    #[inline]
+    pub fn arc_length(&self, tolerance: Scalar) -> Scalar {
+        self.to_cubic().arc_length(tolerance)
+    }
+
+    /// See [`CubicBezier::arc_length_table`].
+    #[inline]
+    pub fn arc_length_table(&self, tolerance: Scalar) -> ArcLengthTable {
+        self.to_cubic().arc_length_table(tolerance)
+    }
+
+    /// See [`CubicBezier::t_at_distance`].
+    #[inline]
+    pub fn t_at_distance(&self, distance: Scalar, tolerance: Scalar) -> Scalar {
+        self.to_cubic().t_at_distance(distance, tolerance)
+    }
+}
+
+/// A cubic Bezier curve from `p0` to `p3` with control points `p1` and `p2`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{CubicBezier, Scalar, Vec2};
+///
+/// let road = CubicBezier::new(
+///     Vec2::ZERO,
+///     Vec2::new(30.0, 0.0),
+///     Vec2::new(70.0, 40.0),
+///     Vec2::new(100.0, 40.0),
+/// );
+/// // Walk the road at a constant 10 units per step, whatever the control point spacing.
+/// let table = road.arc_length_table(0.01);
+/// let stops: Vec<Vec2> = (0..=table.length() as u32 / 10)
+///     .map(|i| road.evaluate(table.t_at_distance(10.0 * i as Scalar)))
+///     .collect();
+/// assert!(stops.windows(2).all(|w| (w[0].distance(w[1]) - 10.0).abs() < 0.1));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier<P> {
+    pub p0: P,
+    pub p1: P,
+    pub p2: P,
+    pub p3: P,
+}
+
+/// A [`CubicBezier`] over 2D points.
+pub type CubicBezier2 = CubicBezier<Vec2>;
+/// A [`CubicBezier`] over 3D points.
+pub type CubicBezier3 = CubicBezier<Vec3>;
+
+impl<P: SplinePoint> CubicBezier<P> {
+    #[inline(always)]
+    pub const fn new(p0: P, p1: P, p2: P, p3: P) -> Self {
+        CubicBezier { p0, p1, p2, p3 }
+    }
+
+    /// Returns the point at `t` in `[0, 1]`.
+    #[inline]
+    pub fn evaluate(&self, t: Scalar) -> P {
+        let u = 1.0 - t;
+        self.p0 * (u * u * u)
+            + self.p1 * (3.0 * u * u * t)
+            + self.p2 * (3.0 * u * t * t)
+            + self.p3 * (t * t * t)
+    }
+
+    /// Returns the derivative with respect to `t` at `t`.
+    #[inline]
+    pub fn derivative(&self, t: Scalar) -> P {
+        let u = 1.0 - t;
+        (self.p1 - self.p0) * (3.0 * u * u)
+            + (self.p2 - self.p1) * (6.0 * u * t)
+            + (self.p3 - self.p2) * (3.0 * t * t)
+    }
+
+    /// Splits the curve at `t` into two curves covering `[0, t]` and `[t, 1]`.
+    pub fn split(&self, t: Scalar) -> (CubicBezier<P>, CubicBezier<P>) {
+        let lerp = |a: P, b: P| a + (b - a) * t;
+        let (a, b, c) = (
+            lerp(self.p0, self.p1),
+            lerp(self.p1, self.p2),
+            lerp(self.p2, self.p3),
+        );
+        let (d, e) = (lerp(a, b), lerp(b, c));
+        let mid = lerp(d, e);
+        (
+            CubicBezier::new(self.p0, a, d, mid),
+            CubicBezier::new(mid, e, c, self.p3),
+        )
+    }
+
+    /// Approximates the curve with a polyline no further than `tolerance` from it, starting at
+    /// `p0` and ending at `p3`.
+    ///
+    /// Flat stretches get few points and tight bends many.
+    pub fn flatten(&self, tolerance: Scalar) -> Vec<P> {
+        self.flatten_with_t(tolerance)
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    /// Returns the arc length, measured along the polyline of [`CubicBezier::flatten`].
+    pub fn arc_length(&self, tolerance: Scalar) -> Scalar {
+        self.arc_length_table(tolerance).length()
+    }
+
+    /// Builds a table mapping distance along the curve to `t`, for constant-speed traversal.
+    ///
+    /// Build it once per curve and reuse it; each lookup is a binary search.
+    pub fn arc_length_table(&self, tolerance: Scalar) -> ArcLengthTable {
+        let points = self.flatten_with_t(tolerance);
+        let mut samples = Vec::with_capacity(points.len());
+        let mut length = 0.0;
+        samples.push((0.0, 0.0));
+        for pair in points.windows(2) {
+            length += pair[0].1.distance_to(pair[1].1);
+            samples.push((length, pair[1].0));
+        }
+        ArcLengthTable { samples }
+    }
+
+    /// Returns the `t` at which the curve has travelled `distance` from `p0`.
+    ///
+    /// Builds an [`ArcLengthTable`] on every call; keep one around for repeated lookups.
+    pub fn t_at_distance(&self, distance: Scalar, tolerance: Scalar) -> Scalar {
+        self.arc_length_table(tolerance).t_at_distance(distance)
+    }
+
+    /// Flattens the curve like [`CubicBezier::flatten`], pairing each point with its `t`.
+    pub(crate) fn flatten_with_t(&self, tolerance: Scalar) -> Vec<(Scalar, P)> {
+        let mut points = vec![(0.0, self.p0)];
+        self.flatten_into(0.0, 1.0, tolerance, MAX_FLATTEN_DEPTH, &mut points);
+        points
+    }
+
+    fn flatten_into(
+        &self,
+        t0: Scalar,
+        t1: Scalar,
+        tolerance: Scalar,
+        depth: u32,
+        points: &mut Vec<(Scalar, P)>,
+    ) {
+        // Bounds how far the curve strays from its chord (Roger Willcocks' flatness test).
+        let u = (self.p1 * 3.0 - self.p0 * 2.0).distance_to(self.p3);
+        let v = (self.p2 * 3.0 - self.p3 * 2.0).distance_to(self.p0);
+        if depth == 0 || u.max(v) <= 4.0 * tolerance {
+            points.push((t1, self.p3));
+            return;
+        }
+        let mid = 0.5 * (t0 + t1);
+        let (left, right) = self.split(0.5);
+        left.flatten_into(t0, mid, tolerance, depth - 1, points);
+        right.flatten_into(mid, t1, tolerance, depth - 1, points);
+    }
+}
+
+/// Maps distance travelled along a curve to its parameter `t`, built by
+/// [`CubicBezier::arc_length_table`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArcLengthTable {
+    /// `(distance, t)` pairs, increasing in both.
+    samples: Vec<(Scalar, Scalar)>,
+}
+
+impl ArcLengthTable {
+    /// Returns the total length of the curve.
+    #[inline]
+    pub fn length(&self) -> Scalar {
+        self.samples.last().map_or(0.0, |s| s.0)
+    }
+
+    /// Returns the `t` at `distance` along the curve, clamped to the curve's ends.
+    pub fn t_at_distance(&self, distance: Scalar) -> Scalar {
+        let i = self.samples.partition_point(|s| s.0 < distance);
+        if i == 0 {
+            return 0.0;
+        }
+        let Some(&(d1, t1)) = self.samples.get(i) else {
+            return 1.0;
+        };
+        let (d0, t0) = self.samples[i - 1];
+        t0 + (t1 - t0) * (distance - d0) / (d1 - d0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Segment3;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn s_curve() -> CubicBezier2 {
+        CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(0.0, 10.0),
+            Vec2::new(10.0, 10.0),
+        )
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let cubic = s_curve();
+        let quad = QuadraticBezier::new(
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 4.0, 1.0),
+            Vec3::new(5.0, 0.0, -2.0),
+        );
+        let h = 1.0e-3 as Scalar;
+        for t in [0.1 as Scalar, 0.5, 0.85] {
+            let numeric = (cubic.evaluate(t + h) - cubic.evaluate(t - h)) * (0.5 / h);
+            assert!(numeric.distance(cubic.derivative(t)) <= 1.0e-2 as Scalar);
+            let numeric = (quad.evaluate(t + h) - quad.evaluate(t - h)) * (0.5 / h);
+            assert!(numeric.distance(quad.derivative(t)) <= 1.0e-2 as Scalar);
+            // Degree elevation traces the same curve.
+            assert!(quad.to_cubic().evaluate(t).distance(quad.evaluate(t)) <= EPS);
+        }
+    }
+
+    #[test]
+    fn split_halves_trace_the_original() {
+        let curve = s_curve();
+        let (left, right) = curve.split(0.3);
+        assert!(left.evaluate(0.5).distance(curve.evaluate(0.15)) <= EPS);
+        assert!(right.evaluate(0.5).distance(curve.evaluate(0.65)) <= EPS);
+        assert_eq!(left.p3, right.p0);
+    }
+
+    #[test]
+    fn flatten_stays_within_tolerance() {
+        let curve = s_curve();
+        let coarse = curve.flatten(1.0);
+        let fine = curve.flatten(0.01);
+        assert!(coarse.len() < fine.len());
+        assert_eq!(fine[0], curve.p0);
+        assert_eq!(*fine.last().unwrap(), curve.p3);
+        // Every polyline vertex lies on the curve, and curve samples lie near the polyline.
+        for i in 0..=100 {
+            let p = curve.evaluate(i as Scalar / 100.0);
+            let nearest = fine
+                .windows(2)
+                .map(|w| {
+                    let segment = Segment3::new(w[0].extend_z(0.0), w[1].extend_z(0.0));
+                    segment
+                        .closest_point(p.extend_z(0.0))
+                        .distance(p.extend_z(0.0))
+                })
+                .fold(Scalar::INFINITY, Scalar::min);
+            assert!(nearest <= 0.01 + EPS);
+        }
+
+        // A straight line flattens to its endpoints.
+        let line = CubicBezier::new(
+            Vec2::ZERO,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(3.0, 0.0),
+        );
+        assert_eq!(line.flatten(0.01).len(), 2);
+        assert!((line.arc_length(0.01) - 3.0).abs() <= EPS);
+    }
+
+    #[test]
+    fn t_at_distance_gives_constant_speed() {
+        let curve = s_curve();
+        let table = curve.arc_length_table(0.001);
+        let length = table.length();
+        assert!(length > curve.p0.distance(curve.p3));
+        assert_eq!(table.t_at_distance(-1.0), 0.0);
+        assert_eq!(table.t_at_distance(length + 1.0), 1.0);
+        let steps = 20;
+        let step = length / steps as Scalar;
+        for i in 0..steps {
+            let a = curve.evaluate(table.t_at_distance(step * i as Scalar));
+            let b = curve.evaluate(table.t_at_distance(step * (i + 1) as Scalar));
+            assert!((a.distance(b) - step).abs() <= 0.01);
+        }
+
+        let arc = QuadraticBezier::new(Vec2::ZERO, Vec2::new(1.0, 2.0), Vec2::new(2.0, 0.0));
+        // Symmetric, so half the length is reached at the apex.
+        let half = arc.arc_length(0.001) * 0.5;
+        assert!((arc.t_at_distance(half, 0.001) - 0.5).abs() <= 1.0e-3 as Scalar);
+    }
+}
@@ -0,0 +1,582 @@
+use crate::{Aabb3, Ray3, Scalar};
+use alloc::vec::Vec;
+
+/// Index marking a missing parent or child.
+const NULL: u32 = u32::MAX;
+
+/// A node of a [`DynamicBvh`]. Leaves have no children and hold an item.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Node<T> {
+    aabb: Aabb3,
+    parent: u32,
+    left: u32,
+    right: u32,
+    /// `0` for leaves, `-1` for nodes on the free list.
+    height: i32,
+    item: Option<T>,
+}
+
+impl<T> Node<T> {
+    #[inline]
+    fn is_leaf(&self) -> bool {
+        self.left == NULL
+    }
+}
+
+/// An incremental bounding volume hierarchy over 3D boxes, for use as a persistent broadphase.
+///
+/// Each item is stored in a leaf under a "fat" box grown by `margin`, so small movements don't
+/// touch the tree at all: [`DynamicBvh::refit`] only reinserts a leaf once its box leaves the fat
+/// box. Insertion picks the sibling with the lowest surface-area cost, and the tree is rebalanced
+/// with AVL-style rotations on the way back up.
+///
+/// Items are addressed by the `u32` proxy returned from [`DynamicBvh::insert`]. Proxies stay
+/// valid until removed and freed slots are reused. With the `serde` feature the tree can be
+/// serialized, so it can be persisted between reducer calls instead of being rebuilt.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Aabb3, DynamicBvh, Vec3};
+///
+/// let mut tree = DynamicBvh::new(0.1);
+/// let crate_box = tree.insert(Aabb3::new(Vec3::ZERO, Vec3::ONE), "crate");
+/// tree.insert(Aabb3::new(Vec3::splat(5.0), Vec3::splat(6.0)), "barrel");
+///
+/// let mut found = Vec::new();
+/// tree.query_aabb(&Aabb3::new(Vec3::splat(-1.0), Vec3::splat(0.5)), |_, item| found.push(*item));
+/// assert_eq!(found, vec!["crate"]);
+/// assert_eq!(tree.remove(crate_box), Some("crate"));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicBvh<T> {
+    nodes: Vec<Node<T>>,
+    root: u32,
+    free: Vec<u32>,
+    margin: Scalar,
+    len: usize,
+}
+
+impl<T> DynamicBvh<T> {
+    /// Creates an empty tree that fattens leaf boxes by `margin` on every side.
+    pub fn new(margin: Scalar) -> Self {
+        DynamicBvh {
+            nodes: Vec::new(),
+            root: NULL,
+            free: Vec::new(),
+            margin,
+            len: 0,
+        }
+    }
+
+    /// Returns the fattening margin.
+    #[inline]
+    pub fn margin(&self) -> Scalar {
+        self.margin
+    }
+
+    /// Returns the number of items in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tree holds no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the height of the tree: `0` for a single leaf, `-1` when empty.
+    pub fn height(&self) -> i32 {
+        if self.root == NULL {
+            -1
+        } else {
+            self.nodes[self.root as usize].height
+        }
+    }
+
+    /// Returns the item stored under `proxy`.
+    pub fn get(&self, proxy: u32) -> Option<&T> {
+        self.nodes.get(proxy as usize)?.item.as_ref()
+    }
+
+    /// Returns the fat box stored for `proxy`.
+    pub fn fat_aabb(&self, proxy: u32) -> Option<Aabb3> {
+        let node = self.nodes.get(proxy as usize)?;
+        node.item.as_ref().map(|_| node.aabb)
+    }
+
+    /// Inserts `item` with bounds `aabb`, returning its proxy.
+    pub fn insert(&mut self, aabb: Aabb3, item: T) -> u32 {
+        let leaf = self.allocate(aabb.expand_by(self.margin), Some(item));
+        self.insert_leaf(leaf);
+        self.len += 1;
+        leaf
+    }
+
+    /// Removes `proxy` from the tree, returning its item, or `None` if it is not in the tree.
+    pub fn remove(&mut self, proxy: u32) -> Option<T> {
+        let item = self.nodes.get_mut(proxy as usize)?.item.take()?;
+        self.remove_leaf(proxy);
+        self.release(proxy);
+        self.len -= 1;
+        Some(item)
+    }
+
+    /// Updates the bounds of `proxy` to `aabb`.
+    ///
+    /// Returns `true` if the leaf had to be reinserted because `aabb` escaped its fat box, and
+    /// `false` if the tree was left untouched.
+    ///
+    /// # Panics
+    /// Panics if `proxy` is not in the tree.
+    pub fn refit(&mut self, proxy: u32, aabb: Aabb3) -> bool {
+        assert!(self.get(proxy).is_some(), "proxy is not in the tree");
+        if self.nodes[proxy as usize].aabb.contains_aabb(&aabb) {
+            return false;
+        }
+        self.remove_leaf(proxy);
+        self.nodes[proxy as usize].aabb = aabb.expand_by(self.margin);
+        self.insert_leaf(proxy);
+        true
+    }
+
+    /// Calls `f` with every item whose fat box overlaps `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb3, mut f: impl FnMut(u32, &T)) {
+        self.traverse(
+            |node| node.aabb.overlaps(aabb),
+            |proxy, item| f(proxy, item),
+        );
+    }
+
+    /// Calls `f` with every item whose fat box is hit by `ray` at a parameter in `[0, max_t]`.
+    pub fn query_ray(&self, ray: &Ray3, max_t: Scalar, mut f: impl FnMut(u32, &T)) {
+        self.traverse(
+            |node| ray_hits(ray, &node.aabb, max_t),
+            |proxy, item| f(proxy, item),
+        );
+    }
+
+    /// Finds the closest item hit by `ray` within `[0, max_t]`.
+    ///
+    /// `hit` is called with each candidate whose fat box the ray reaches, and returns the exact
+    /// ray parameter of the hit (or `None` for a miss). Subtrees beyond the closest hit found so
+    /// far are skipped. Returns the proxy and parameter of the closest hit.
+    pub fn ray_cast(
+        &self,
+        ray: &Ray3,
+        max_t: Scalar,
+        mut hit: impl FnMut(u32, &T) -> Option<Scalar>,
+    ) -> Option<(u32, Scalar)> {
+        let mut best: Option<(u32, Scalar)> = None;
+        let mut stack = Vec::new();
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            let limit = best.map_or(max_t, |(_, t)| t);
+            if !ray_hits(ray, &node.aabb, limit) {
+                continue;
+            }
+            if let Some(item) = &node.item {
+                if let Some(t) = hit(index, item)
+                    && (0.0..=limit).contains(&t)
+                {
+                    best = Some((index, t));
+                }
+            } else {
+                stack.push(node.left);
+                stack.push(node.right);
+            }
+        }
+        best
+    }
+
+    /// Calls `f` once for every pair of items whose fat boxes overlap, as `(lower, higher)`
+    /// proxies.
+    pub fn for_each_overlapping_pair(&self, mut f: impl FnMut(u32, u32)) {
+        for (index, node) in self.nodes.iter().enumerate() {
+            if node.item.is_none() {
+                continue;
+            }
+            let index = index as u32;
+            self.traverse(
+                |other| other.aabb.overlaps(&node.aabb),
+                |other, _| {
+                    if other > index {
+                        f(index, other);
+                    }
+                },
+            );
+        }
+    }
+
+    /// Depth-first walk visiting subtrees whose node passes `enter`, calling `leaf` for items.
+    fn traverse(&self, mut enter: impl FnMut(&Node<T>) -> bool, mut leaf: impl FnMut(u32, &T)) {
+        let mut stack = Vec::new();
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            if !enter(node) {
+                continue;
+            }
+            if let Some(item) = &node.item {
+                leaf(index, item);
+            } else {
+                stack.push(node.left);
+                stack.push(node.right);
+            }
+        }
+    }
+
+    fn allocate(&mut self, aabb: Aabb3, item: Option<T>) -> u32 {
+        let node = Node {
+            aabb,
+            parent: NULL,
+            left: NULL,
+            right: NULL,
+            height: 0,
+            item,
+        };
+        if let Some(index) = self.free.pop() {
+            self.nodes[index as usize] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            (self.nodes.len() - 1) as u32
+        }
+    }
+
+    fn release(&mut self, index: u32) {
+        let node = &mut self.nodes[index as usize];
+        node.height = -1;
+        node.item = None;
+        self.free.push(index);
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NULL;
+            return;
+        }
+
+        // Descend towards the sibling that minimises the added surface area.
+        let leaf_aabb = self.nodes[leaf as usize].aabb;
+        let mut index = self.root;
+        while !self.nodes[index as usize].is_leaf() {
+            let node = &self.nodes[index as usize];
+            let area = half_area(&node.aabb);
+            let combined = half_area(&node.aabb.union(&leaf_aabb));
+            // Cost of making a new parent for this node and the leaf, and the cost pushed down
+            // onto every ancestor of a deeper insertion.
+            let cost = 2.0 * combined;
+            let inheritance = 2.0 * (combined - area);
+            let child_cost = |child: u32| {
+                let child = &self.nodes[child as usize];
+                let grown = half_area(&child.aabb.union(&leaf_aabb));
+                if child.is_leaf() {
+                    grown + inheritance
+                } else {
+                    grown - half_area(&child.aabb) + inheritance
+                }
+            };
+            let (left, right) = (node.left, node.right);
+            let (cost_left, cost_right) = (child_cost(left), child_cost(right));
+            if cost < cost_left && cost < cost_right {
+                break;
+            }
+            index = if cost_left < cost_right { left } else { right };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling as usize].parent;
+        let sibling_aabb = self.nodes[sibling as usize].aabb;
+        let sibling_height = self.nodes[sibling as usize].height;
+        let new_parent = self.allocate(sibling_aabb.union(&leaf_aabb), None);
+        let parent = &mut self.nodes[new_parent as usize];
+        parent.parent = old_parent;
+        parent.left = sibling;
+        parent.right = leaf;
+        parent.height = sibling_height + 1;
+        if old_parent == NULL {
+            self.root = new_parent;
+        } else {
+            self.replace_child(old_parent, sibling, new_parent);
+        }
+        self.nodes[sibling as usize].parent = new_parent;
+        self.nodes[leaf as usize].parent = new_parent;
+
+        self.fix_upwards(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+        let parent = self.nodes[leaf as usize].parent;
+        let grandparent = self.nodes[parent as usize].parent;
+        let sibling = if self.nodes[parent as usize].left == leaf {
+            self.nodes[parent as usize].right
+        } else {
+            self.nodes[parent as usize].left
+        };
+
+        self.nodes[sibling as usize].parent = grandparent;
+        self.release(parent);
+        if grandparent == NULL {
+            self.root = sibling;
+        } else {
+            self.replace_child(grandparent, parent, sibling);
+            self.fix_upwards(grandparent);
+        }
+    }
+
+    fn replace_child(&mut self, parent: u32, old: u32, new: u32) {
+        let parent = &mut self.nodes[parent as usize];
+        if parent.left == old {
+            parent.left = new;
+        } else {
+            parent.right = new;
+        }
+    }
+
+    /// Rebalances and refits every ancestor from `index` up to the root.
+    fn fix_upwards(&mut self, mut index: u32) {
+        while index != NULL {
+            index = self.balance(index);
+            let node = &self.nodes[index as usize];
+            let (left, right) = (
+                &self.nodes[node.left as usize],
+                &self.nodes[node.right as usize],
+            );
+            let height = 1 + left.height.max(right.height);
+            let aabb = left.aabb.union(&right.aabb);
+            let node = &mut self.nodes[index as usize];
+            node.height = height;
+            node.aabb = aabb;
+            index = node.parent;
+        }
+    }
+
+    /// Rotates the taller grandchild of `a` up if its children differ in height by more than one.
+    /// Returns the index of the node now at `a`'s position.
+    fn balance(&mut self, a: u32) -> u32 {
+        let node = &self.nodes[a as usize];
+        if node.is_leaf() || node.height < 2 {
+            return a;
+        }
+        let (b, c) = (node.left, node.right);
+        let balance = self.nodes[c as usize].height - self.nodes[b as usize].height;
+        if balance > 1 {
+            self.rotate_up(a, c, b, true)
+        } else if balance < -1 {
+            self.rotate_up(a, b, c, false)
+        } else {
+            a
+        }
+    }
+
+    /// Promotes child `up` of `a` into `a`'s place; `a` keeps `stay` and the shorter child of
+    /// `up`. `up_is_right` says which side of `a` `up` was on.
+    fn rotate_up(&mut self, a: u32, up: u32, stay: u32, up_is_right: bool) -> u32 {
+        let (f, g) = {
+            let node = &self.nodes[up as usize];
+            (node.left, node.right)
+        };
+        let a_parent = self.nodes[a as usize].parent;
+
+        self.nodes[up as usize].left = a;
+        self.nodes[up as usize].parent = a_parent;
+        self.nodes[a as usize].parent = up;
+        if a_parent == NULL {
+            self.root = up;
+        } else {
+            self.replace_child(a_parent, a, up);
+        }
+
+        let (taller, shorter) = if self.nodes[f as usize].height > self.nodes[g as usize].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+        self.nodes[up as usize].right = taller;
+        if up_is_right {
+            self.nodes[a as usize].right = shorter;
+        } else {
+            self.nodes[a as usize].left = shorter;
+        }
+        self.nodes[shorter as usize].parent = a;
+
+        let (stay, shorter_node, taller_node) = (
+            &self.nodes[stay as usize],
+            &self.nodes[shorter as usize],
+            &self.nodes[taller as usize],
+        );
+        let a_aabb = stay.aabb.union(&shorter_node.aabb);
+        let a_height = 1 + stay.height.max(shorter_node.height);
+        let up_aabb = a_aabb.union(&taller_node.aabb);
+        let up_height = 1 + a_height.max(taller_node.height);
+        self.nodes[a as usize].aabb = a_aabb;
+        self.nodes[a as usize].height = a_height;
+        self.nodes[up as usize].aabb = up_aabb;
+        self.nodes[up as usize].height = up_height;
+        up
+    }
+}
+
+/// Half the surface area of `aabb`, the insertion cost metric.
+#[inline]
+fn half_area(aabb: &Aabb3) -> Scalar {
+    let d = aabb.max - aabb.min;
+    d.x * d.y + d.y * d.z + d.z * d.x
+}
+
+#[inline]
+fn ray_hits(ray: &Ray3, aabb: &Aabb3, max_t: Scalar) -> bool {
+    ray.intersect_aabb(aabb)
+        .is_some_and(|hit| hit.t_enter <= max_t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec3;
+
+    /// Checks parent links, heights, enclosing boxes and the balance invariant.
+    fn validate<T>(tree: &DynamicBvh<T>) {
+        let mut leaves = 0;
+        let mut stack = vec![(tree.root, NULL)];
+        while let Some((index, parent)) = stack.pop() {
+            if index == NULL {
+                continue;
+            }
+            let node = &tree.nodes[index as usize];
+            assert_eq!(node.parent, parent);
+            if node.is_leaf() {
+                assert_eq!(node.height, 0);
+                assert!(node.item.is_some());
+                leaves += 1;
+                continue;
+            }
+            let (left, right) = (
+                &tree.nodes[node.left as usize],
+                &tree.nodes[node.right as usize],
+            );
+            assert_eq!(node.height, 1 + left.height.max(right.height));
+            assert!((left.height - right.height).abs() <= 1);
+            assert!(node.aabb.contains_aabb(&left.aabb) && node.aabb.contains_aabb(&right.aabb));
+            stack.push((node.left, index));
+            stack.push((node.right, index));
+        }
+        assert_eq!(leaves, tree.len());
+    }
+
+    fn scattered_box(i: u32) -> Aabb3 {
+        let f = i as Scalar;
+        let min = Vec3::new((f * 7.3) % 40.0, (f * 3.1) % 9.0, (f * 5.7) % 30.0);
+        Aabb3::new(min, min + Vec3::new(1.0 + (f % 3.0), 1.0, 1.5))
+    }
+
+    #[test]
+    fn insert_remove_keeps_tree_valid_and_balanced() {
+        let mut tree = DynamicBvh::new(0.0);
+        // Boxes inserted in sorted order would degenerate into a list without rotations.
+        let proxies: Vec<u32> = (0..128)
+            .map(|i| {
+                let min = Vec3::new(i as Scalar, 0.0, 0.0);
+                tree.insert(Aabb3::new(min, min + Vec3::ONE), i)
+            })
+            .collect();
+        validate(&tree);
+        assert!(tree.height() <= 10);
+
+        for &proxy in proxies.iter().step_by(2) {
+            assert!(tree.remove(proxy).is_some());
+        }
+        assert_eq!(tree.remove(proxies[0]), None);
+        validate(&tree);
+        assert_eq!(tree.len(), 64);
+        assert_eq!(tree.get(proxies[1]), Some(&1));
+
+        // Freed slots are reused.
+        let reused = tree.insert(Aabb3::default(), 1000);
+        assert!((reused as usize) < tree.nodes.len());
+        validate(&tree);
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let mut tree = DynamicBvh::new(0.0);
+        let boxes: Vec<Aabb3> = (0..80).map(scattered_box).collect();
+        for (i, aabb) in boxes.iter().enumerate() {
+            tree.insert(*aabb, i);
+        }
+
+        let query = Aabb3::new(Vec3::new(5.0, 0.0, 5.0), Vec3::new(20.0, 4.0, 15.0));
+        let mut found = Vec::new();
+        tree.query_aabb(&query, |_, &i| found.push(i));
+        found.sort();
+        let expected: Vec<usize> = (0..boxes.len())
+            .filter(|&i| boxes[i].overlaps(&query))
+            .collect();
+        assert_eq!(found, expected);
+
+        let mut pairs = Vec::new();
+        tree.for_each_overlapping_pair(|a, b| {
+            let (a, b) = (*tree.get(a).unwrap(), *tree.get(b).unwrap());
+            pairs.push((a.min(b), a.max(b)));
+        });
+        pairs.sort();
+        let mut expected = Vec::new();
+        for i in 0..boxes.len() {
+            for j in i + 1..boxes.len() {
+                if boxes[i].overlaps(&boxes[j]) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn refit_only_reinserts_outside_fat_box() {
+        let mut tree = DynamicBvh::new(0.5);
+        let proxy = tree.insert(Aabb3::new(Vec3::ZERO, Vec3::ONE), ());
+        tree.insert(Aabb3::new(Vec3::splat(10.0), Vec3::splat(11.0)), ());
+        let nudged = Aabb3::new(Vec3::splat(0.25), Vec3::splat(1.25));
+        assert!(!tree.refit(proxy, nudged));
+        let moved = Aabb3::new(Vec3::splat(20.0), Vec3::splat(21.0));
+        assert!(tree.refit(proxy, moved));
+        assert_eq!(tree.fat_aabb(proxy), Some(moved.expand_by(0.5)));
+        validate(&tree);
+    }
+
+    #[test]
+    fn ray_cast_finds_closest_hit() {
+        let mut tree = DynamicBvh::new(0.0);
+        for i in 0..10 {
+            let min = Vec3::new(2.0 * i as Scalar, 0.0, 0.0);
+            tree.insert(Aabb3::new(min, min + Vec3::ONE), i);
+        }
+        let ray = Ray3::new(Vec3::new(-5.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+        let exact = |proxy: u32, _: &i32| {
+            ray.intersect_aabb(&tree.fat_aabb(proxy).unwrap())
+                .map(|hit| hit.t_enter)
+        };
+        let (proxy, t) = tree.ray_cast(&ray, 100.0, exact).unwrap();
+        assert_eq!(tree.get(proxy), Some(&0));
+        assert_eq!(t, 5.0 as Scalar);
+        assert_eq!(tree.ray_cast(&ray, 4.0, exact), None);
+
+        let mut candidates = 0;
+        tree.query_ray(&ray, 9.5, |_, _| candidates += 1);
+        assert_eq!(candidates, 3);
+    }
+}
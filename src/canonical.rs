@@ -0,0 +1,199 @@
+//! Canonical forms of values for hashing and comparing world state.
+//!
+//! Replicas that should agree can still differ in ways that don't matter: `-0.0` against
+//! `0.0`, a subnormal against zero, `q` against `-q` for the same rotation, or noise in the
+//! last bits of a position. Hashing the raw bits then reports a desync that isn't there. The
+//! functions here map all of those to one representative, and the `snap_` variants also round
+//! to a grid so that small differences disappear too.
+//!
+//! NaN is mapped to a single quiet NaN, so two NaNs hash alike, though they still compare
+//! unequal.
+
+use crate::interp::ComponentWise;
+use crate::{Quat, Scalar, Transform, Vec2, Vec3, float};
+
+/// Returns `value` with `-0.0` and subnormals mapped to `0.0` and every NaN to [`Scalar::NAN`].
+#[inline]
+pub fn scalar(value: Scalar) -> Scalar {
+    if value.is_nan() {
+        Scalar::NAN
+    } else if value.is_subnormal() || value == 0.0 {
+        0.0
+    } else {
+        value
+    }
+}
+
+/// Rounds `value` to the nearest multiple of `step`, then canonicalizes it.
+///
+/// A `step` of zero or less, or NaN, only canonicalizes.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Scalar, canonical};
+///
+/// let a: Scalar = 0.3;
+/// // The same value after a last-bit rounding difference on another replica.
+/// let b = a + a * Scalar::EPSILON;
+/// assert_ne!(a, b);
+/// assert_eq!(canonical::snap(a, 1.0 / 1024.0), canonical::snap(b, 1.0 / 1024.0));
+/// assert_eq!(canonical::snap(-0.0001, 0.01).to_bits(), 0);
+/// ```
+#[inline]
+pub fn snap(value: Scalar, step: Scalar) -> Scalar {
+    if step > 0.0 {
+        scalar(float::round(value / step) * step)
+    } else {
+        scalar(value)
+    }
+}
+
+/// Canonicalizes every component of `v` like [`scalar`].
+#[inline]
+pub fn vec2(v: Vec2) -> Vec2 {
+    v.map(scalar)
+}
+
+/// Canonicalizes every component of `v` like [`scalar`].
+#[inline]
+pub fn vec3(v: Vec3) -> Vec3 {
+    v.map(scalar)
+}
+
+/// Snaps every component of `v` to a multiple of `step`.
+#[inline]
+pub fn snap_vec2(v: Vec2, step: Scalar) -> Vec2 {
+    v.map(|x| snap(x, step))
+}
+
+/// Snaps every component of `v` to a multiple of `step`.
+#[inline]
+pub fn snap_vec3(v: Vec3, step: Scalar) -> Vec3 {
+    v.map(|x| snap(x, step))
+}
+
+/// Canonicalizes the components of `q` and picks one of `q` and `-q`, which represent the
+/// same rotation: the one whose first non-zero component, in `w, x, y, z` order, is positive.
+#[inline]
+pub fn quat(q: Quat) -> Quat {
+    hemisphere(Quat {
+        x: scalar(q.x),
+        y: scalar(q.y),
+        z: scalar(q.z),
+        w: scalar(q.w),
+    })
+}
+
+/// Snaps every component of `q` to a multiple of `step`, then picks a hemisphere like
+/// [`quat`].
+///
+/// The result isn't renormalized, since that would undo the snapping.
+#[inline]
+pub fn snap_quat(q: Quat, step: Scalar) -> Quat {
+    // Fix the hemisphere first so `q` and `-q` snap to the same grid points.
+    let q = hemisphere(q);
+    hemisphere(Quat {
+        x: snap(q.x, step),
+        y: snap(q.y, step),
+        z: snap(q.z, step),
+        w: snap(q.w, step),
+    })
+}
+
+/// Canonicalizes every part of `transform`.
+#[inline]
+pub fn transform(transform: &Transform) -> Transform {
+    Transform::new(
+        vec3(transform.translation),
+        quat(transform.rotation),
+        vec3(transform.scale),
+    )
+}
+
+/// Snaps `transform`'s translation to multiples of `translation_step`, and its rotation and
+/// scale to multiples of `unit_step`.
+#[inline]
+pub fn snap_transform(
+    transform: &Transform,
+    translation_step: Scalar,
+    unit_step: Scalar,
+) -> Transform {
+    Transform::new(
+        snap_vec3(transform.translation, translation_step),
+        snap_quat(transform.rotation, unit_step),
+        snap_vec3(transform.scale, unit_step),
+    )
+}
+
+// Builds quaternions as literals rather than through `Quat::new`, so that NaN components
+// are canonicalized instead of tripping `validate`.
+#[inline]
+fn hemisphere(q: Quat) -> Quat {
+    let lead = [q.w, q.x, q.y, q.z]
+        .into_iter()
+        .find(|&c| c != 0.0)
+        .unwrap_or(0.0);
+    // Negation of an already-canonical zero gives -0.0, so canonicalize again.
+    if lead < 0.0 {
+        Quat {
+            x: scalar(-q.x),
+            y: scalar(-q.y),
+            z: scalar(-q.z),
+            w: scalar(-q.w),
+        }
+    } else {
+        q
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalars_have_one_representation() {
+        assert_eq!(scalar(-0.0).to_bits(), (0.0 as Scalar).to_bits());
+        assert_eq!(scalar(Scalar::MIN_POSITIVE / 2.0), 0.0);
+        assert_eq!(scalar(-Scalar::MIN_POSITIVE / 4.0).to_bits(), 0);
+        assert_eq!(scalar(-1.5), -1.5);
+        let odd_nan = Scalar::from_bits(Scalar::NAN.to_bits() | 1);
+        assert_eq!(scalar(odd_nan).to_bits(), Scalar::NAN.to_bits());
+        assert_eq!(scalar(Scalar::INFINITY), Scalar::INFINITY);
+    }
+
+    #[test]
+    fn snapping_absorbs_float_noise() {
+        let step = 1.0 / 64.0 as Scalar;
+        let a = Vec3::new(1.0, -2.0, 0.5) + Vec3::splat(1.0e-6);
+        let b = Vec3::new(1.0, -2.0, 0.5) - Vec3::splat(1.0e-6);
+        assert_eq!(snap_vec3(a, step), snap_vec3(b, step));
+        let snapped = snap_vec2(Vec2::new(0.3, -0.001), 0.1);
+        assert_eq!(snapped, Vec2::new(snap(0.3, 0.1), 0.0));
+        assert_eq!(snapped.y.to_bits(), 0);
+        assert_eq!(snap(2.5, 0.0), 2.5);
+        assert_eq!(snap(2.5, Scalar::NAN), 2.5);
+    }
+
+    #[test]
+    fn quaternions_pick_one_hemisphere() {
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 1.0);
+        assert_eq!(quat(-q), quat(q));
+        assert!(quat(-q).w > 0.0);
+        // A half turn has w == 0, so the next component decides.
+        let half = Quat::new(0.0, -1.0, 0.0, -0.0);
+        assert_eq!(quat(half), Quat::new(0.0, 1.0, 0.0, 0.0));
+        assert_eq!(quat(half).w.to_bits(), 0);
+
+        let noisy = Quat::new(q.x + 1.0e-7, q.y, q.z, q.w);
+        assert_eq!(snap_quat(-noisy, 1.0e-3), snap_quat(q, 1.0e-3));
+
+        let t = Transform::new(Vec3::new(-0.0, 1.0, 2.0), -q, Vec3::ONE);
+        let canonical = transform(&t);
+        assert_eq!(canonical.translation.x.to_bits(), 0);
+        assert_eq!(canonical.rotation, quat(q));
+        assert_eq!(
+            snap_transform(&t, 0.5, 1.0e-3).rotation,
+            snap_quat(q, 1.0e-3)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Scalar, Segment3, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A capsule: every point within `radius` of the segment from `start` to `end`.
+///
+/// The usual shape for character hitboxes.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Capsule3, Vec3};
+///
+/// let hitbox = Capsule3::new(Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.0, 1.5, 0.0), 0.5);
+/// assert!(hitbox.contains_point(Vec3::new(0.0, 1.9, 0.0)));
+/// assert!(!hitbox.contains_point(Vec3::new(0.0, 2.1, 0.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule3 {
+    /// Center of the first hemisphere cap.
+    pub start: Vec3,
+    /// Center of the second hemisphere cap.
+    pub end: Vec3,
+    /// Radius around the segment.
+    pub radius: Scalar,
+}
+
+impl Capsule3 {
+    #[inline(always)]
+    pub const fn new(start: Vec3, end: Vec3, radius: Scalar) -> Self {
+        Capsule3 { start, end, radius }
+    }
+
+    /// Returns the inner segment from `start` to `end`.
+    #[inline]
+    pub const fn segment(&self) -> Segment3 {
+        Segment3::new(self.start, self.end)
+    }
+
+    /// Returns the point on the inner segment closest to `point`.
+    #[inline]
+    pub fn closest_point_on_segment(&self, point: Vec3) -> Vec3 {
+        self.segment().closest_point(point)
+    }
+
+    /// Returns `true` if `point` is inside or on the surface of the capsule.
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.closest_point_on_segment(point).distance_squared(point) <= self.radius * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_point_covers_body_and_caps() {
+        let c = Capsule3::new(Vec3::ZERO, Vec3::new(2.0, 0.0, 0.0), 1.0);
+        assert!(c.contains_point(Vec3::new(1.0, 1.0, 0.0)));
+        assert!(c.contains_point(Vec3::new(-1.0, 0.0, 0.0)));
+        assert!(!c.contains_point(Vec3::new(-0.8, 0.8, 0.0)));
+        assert!(!c.contains_point(Vec3::new(3.1, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn degenerate_capsule_is_a_sphere() {
+        let c = Capsule3::new(Vec3::ONE, Vec3::ONE, 1.0);
+        assert_eq!(c.closest_point_on_segment(Vec3::ZERO), Vec3::ONE);
+        assert!(c.contains_point(Vec3::new(1.0, 1.0, 2.0)));
+    }
+}
@@ -0,0 +1,228 @@
+//! Stable 64-bit checksums of world state.
+//!
+//! Hash a tick's positions and rotations on each replica, or on the server before and after a
+//! change, and compare the numbers: a mismatch means the states differ. Values are first put in
+//! their [`canonical`](crate::canonical) form, so `-0.0`, subnormals and `q` against `-q` don't
+//! cause false alarms, and the mixing function is fixed, so a checksum is the same on every host
+//! and release. Snap values with the `canonical::snap_` functions first to also ignore
+//! last-bit noise.
+//!
+//! Checksums differ between the `f32` and `f64` features.
+
+use crate::rng::{GOLDEN_GAMMA, mix64};
+use crate::{Quat, Scalar, Transform, Vec2, Vec3, Vec4, canonical};
+
+/// A running checksum that values are fed into in order.
+///
+/// Unlike [`core::hash::Hasher`] implementations such as the standard library's, the result
+/// never changes between hosts, processes or releases.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::checksum::{self, Checksum};
+/// use spacetimedb_math::{Quat, Vec3};
+///
+/// let positions = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(-0.0, 5.0, 0.0)];
+/// let rotation = Quat::IDENTITY;
+///
+/// let mut server = Checksum::new();
+/// server.write(&positions[..]).write(&rotation);
+///
+/// // A replica that stored -0.0 as 0.0 and the rotation as its negation still agrees.
+/// let mut replica = Checksum::new();
+/// replica
+///     .write(&[positions[0], Vec3::new(0.0, 5.0, 0.0)][..])
+///     .write(&-rotation);
+/// assert_eq!(server.finish(), replica.finish());
+/// assert_ne!(server.finish(), checksum::of(&positions[0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Checksum {
+    state: u64,
+}
+
+impl Checksum {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Checksum { state: 0 }
+    }
+
+    /// Feeds in one 64-bit word.
+    #[inline]
+    pub fn write_u64(&mut self, word: u64) -> &mut Self {
+        self.state = mix64(self.state.wrapping_add(GOLDEN_GAMMA) ^ word);
+        self
+    }
+
+    /// Feeds in a scalar's canonical bits.
+    #[inline]
+    pub fn write_scalar(&mut self, value: Scalar) -> &mut Self {
+        let bits = canonical::scalar(value).to_bits();
+        #[cfg(feature = "f32")]
+        return self.write_u64(bits as u64);
+        #[cfg(feature = "f64")]
+        return self.write_u64(bits);
+    }
+
+    /// Feeds in anything [`Checksummable`].
+    #[inline]
+    pub fn write<T: Checksummable + ?Sized>(&mut self, value: &T) -> &mut Self {
+        value.checksum(self);
+        self
+    }
+
+    /// Returns the checksum of everything fed in so far.
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+impl Default for Checksum {
+    #[inline]
+    fn default() -> Self {
+        Checksum::new()
+    }
+}
+
+/// A value that can be fed into a [`Checksum`].
+pub trait Checksummable {
+    fn checksum(&self, checksum: &mut Checksum);
+}
+
+impl Checksummable for Scalar {
+    #[inline]
+    fn checksum(&self, checksum: &mut Checksum) {
+        checksum.write_scalar(*self);
+    }
+}
+
+impl Checksummable for Vec2 {
+    #[inline]
+    fn checksum(&self, checksum: &mut Checksum) {
+        checksum.write_scalar(self.x).write_scalar(self.y);
+    }
+}
+
+impl Checksummable for Vec3 {
+    #[inline]
+    fn checksum(&self, checksum: &mut Checksum) {
+        checksum
+            .write_scalar(self.x)
+            .write_scalar(self.y)
+            .write_scalar(self.z);
+    }
+}
+
+impl Checksummable for Vec4 {
+    #[inline]
+    fn checksum(&self, checksum: &mut Checksum) {
+        checksum
+            .write_scalar(self.x)
+            .write_scalar(self.y)
+            .write_scalar(self.z)
+            .write_scalar(self.w);
+    }
+}
+
+impl Checksummable for Quat {
+    /// `q` and `-q` are the same rotation and give the same checksum.
+    #[inline]
+    fn checksum(&self, checksum: &mut Checksum) {
+        let q = canonical::quat(*self);
+        checksum
+            .write_scalar(q.x)
+            .write_scalar(q.y)
+            .write_scalar(q.z)
+            .write_scalar(q.w);
+    }
+}
+
+impl Checksummable for Transform {
+    #[inline]
+    fn checksum(&self, checksum: &mut Checksum) {
+        checksum
+            .write(&self.translation)
+            .write(&self.rotation)
+            .write(&self.scale);
+    }
+}
+
+impl<T: Checksummable> Checksummable for [T] {
+    /// The length is included, so splitting the same values into different slices changes
+    /// the checksum.
+    fn checksum(&self, checksum: &mut Checksum) {
+        checksum.write_u64(self.len() as u64);
+        for value in self {
+            value.checksum(checksum);
+        }
+    }
+}
+
+/// Returns the checksum of a single value.
+#[inline]
+pub fn of<T: Checksummable + ?Sized>(value: &T) -> u64 {
+    Checksum::new().write(value).finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equivalent_values_agree() {
+        assert_eq!(of(&(0.0 as Scalar)), of(&(-0.0 as Scalar)));
+        assert_eq!(of(&(Scalar::MIN_POSITIVE / 2.0)), of(&(0.0 as Scalar)));
+        assert_eq!(of(&Scalar::NAN), of(&-Scalar::NAN));
+
+        let q = Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.5);
+        assert_eq!(of(&q), of(&-q));
+        let t = Transform::new(Vec3::new(1.0, -0.0, 2.0), q, Vec3::ONE);
+        let u = Transform::new(Vec3::new(1.0, 0.0, 2.0), -q, Vec3::ONE);
+        assert_eq!(of(&t), of(&u));
+
+        let nan = Quat {
+            x: Scalar::NAN,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        };
+        assert_eq!(
+            of(&nan),
+            of(&Quat {
+                x: -Scalar::NAN,
+                ..nan
+            })
+        );
+    }
+
+    #[test]
+    fn different_values_and_orders_disagree() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(3.0, 2.0, 1.0);
+        assert_ne!(of(&a), of(&b));
+        assert_ne!(of(&[a, b][..]), of(&[b, a][..]));
+        assert_ne!(of(&Vec2::new(1.0, 0.0)), of(&Vec2::new(0.0, 1.0)));
+        assert_ne!(
+            of(&Vec4::new(0.0, 0.0, 0.0, 1.0)),
+            of(&Vec4::new(0.0, 0.0, 1.0, 0.0))
+        );
+
+        // Slice lengths are part of the checksum.
+        let mut split = Checksum::new();
+        split.write(&[a][..]).write(&[b][..]);
+        assert_ne!(split.finish(), of(&[a, b][..]));
+        assert_ne!(of::<[Vec3]>(&[]), Checksum::new().finish());
+    }
+
+    #[test]
+    fn checksums_are_pinned() {
+        // Changing these breaks comparisons with checksums stored by earlier releases.
+        assert_eq!(Checksum::new().write_u64(0).finish(), 0xe220_a839_7b1d_cdaf);
+        let v = Vec3::new(1.0, -2.0, 0.5);
+        #[cfg(feature = "f32")]
+        assert_eq!(of(&v), 0x35b2_b9bb_e82d_38cf);
+        #[cfg(feature = "f64")]
+        assert_eq!(of(&v), 0x00ed_c4a7_a3bb_1cf3);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Aabb2, Aabb3, IVec2, IVec3, Scalar, Vec2, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Splits world positions into chunk coordinates and offsets inside the chunk.
+///
+/// Chunks are squares (2D) or cubes (3D) with edge length `chunk_size`, with chunk `(0, 0, 0)`
+/// spanning `[0, chunk_size)` on each axis. Negative positions floor correctly, so `-0.1` lies
+/// in chunk `-1` at local offset `chunk_size - 0.1`, and local offsets are always in
+/// `[0, chunk_size)`.
+///
+/// For integer voxel or tile coordinates, use [`IVec3::div_euclid`] and [`IVec3::rem_euclid`]
+/// with the chunk size in cells instead.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{ChunkCoords, IVec3, Vec3};
+///
+/// let chunks = ChunkCoords::new(16.0);
+/// let (chunk, local) = chunks.to_chunk_local(Vec3::new(-1.0, 20.0, 0.0));
+/// assert_eq!(chunk, IVec3::new(-1, 1, 0));
+/// assert_eq!(local, Vec3::new(15.0, 4.0, 0.0));
+/// assert_eq!(chunks.to_world(chunk, local), Vec3::new(-1.0, 20.0, 0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkCoords {
+    /// Edge length of a chunk in world units.
+    pub chunk_size: Scalar,
+}
+
+impl ChunkCoords {
+    #[inline(always)]
+    pub const fn new(chunk_size: Scalar) -> Self {
+        ChunkCoords { chunk_size }
+    }
+
+    /// Returns the chunk containing `position`.
+    #[inline]
+    pub fn chunk_of(&self, position: Vec3) -> IVec3 {
+        self.to_chunk_local(position).0
+    }
+
+    /// Splits `position` into its chunk and the offset from that chunk's origin.
+    pub fn to_chunk_local(&self, position: Vec3) -> (IVec3, Vec3) {
+        let (x, lx) = self.split_axis(position.x);
+        let (y, ly) = self.split_axis(position.y);
+        let (z, lz) = self.split_axis(position.z);
+        (IVec3::new(x, y, z), Vec3::new(lx, ly, lz))
+    }
+
+    /// Returns the world position at offset `local` inside `chunk`.
+    #[inline]
+    pub fn to_world(&self, chunk: IVec3, local: Vec3) -> Vec3 {
+        self.chunk_origin(chunk) + local
+    }
+
+    /// Returns the minimum corner of `chunk`.
+    #[inline]
+    pub fn chunk_origin(&self, chunk: IVec3) -> Vec3 {
+        chunk.as_vec3() * self.chunk_size
+    }
+
+    /// Returns the bounds of `chunk`.
+    #[inline]
+    pub fn chunk_bounds(&self, chunk: IVec3) -> Aabb3 {
+        let min = self.chunk_origin(chunk);
+        Aabb3::new(min, min + Vec3::splat(self.chunk_size))
+    }
+
+    /// Iterates over every chunk containing a point of `aabb`, X outermost and Z innermost.
+    ///
+    /// A box whose maximum lies exactly on a chunk boundary includes the chunk beyond it.
+    pub fn chunks_in_aabb(&self, aabb: &Aabb3) -> impl Iterator<Item = IVec3> + use<> {
+        let min = self.chunk_of(aabb.min);
+        let max = self.chunk_of(aabb.max);
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+        })
+    }
+
+    /// 2D variant of [`ChunkCoords::chunk_of`].
+    #[inline]
+    pub fn chunk_of_2d(&self, position: Vec2) -> IVec2 {
+        self.to_chunk_local_2d(position).0
+    }
+
+    /// 2D variant of [`ChunkCoords::to_chunk_local`].
+    pub fn to_chunk_local_2d(&self, position: Vec2) -> (IVec2, Vec2) {
+        let (x, lx) = self.split_axis(position.x);
+        let (y, ly) = self.split_axis(position.y);
+        (IVec2::new(x, y), Vec2::new(lx, ly))
+    }
+
+    /// 2D variant of [`ChunkCoords::to_world`].
+    #[inline]
+    pub fn to_world_2d(&self, chunk: IVec2, local: Vec2) -> Vec2 {
+        self.chunk_origin_2d(chunk) + local
+    }
+
+    /// 2D variant of [`ChunkCoords::chunk_origin`].
+    #[inline]
+    pub fn chunk_origin_2d(&self, chunk: IVec2) -> Vec2 {
+        chunk.as_vec2() * self.chunk_size
+    }
+
+    /// 2D variant of [`ChunkCoords::chunk_bounds`].
+    #[inline]
+    pub fn chunk_bounds_2d(&self, chunk: IVec2) -> Aabb2 {
+        let min = self.chunk_origin_2d(chunk);
+        Aabb2::new(min, min + Vec2::splat(self.chunk_size))
+    }
+
+    /// 2D variant of [`ChunkCoords::chunks_in_aabb`], X outermost.
+    pub fn chunks_in_aabb_2d(&self, aabb: &Aabb2) -> impl Iterator<Item = IVec2> + use<> {
+        let min = self.chunk_of_2d(aabb.min);
+        let max = self.chunk_of_2d(aabb.max);
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+    }
+
+    /// Splits one coordinate into its chunk index and local offset.
+    fn split_axis(&self, v: Scalar) -> (i32, Scalar) {
+        let chunk = float::floor(v / self.chunk_size);
+        let local = v - chunk * self.chunk_size;
+        // Rounding can leave a tiny negative coordinate at exactly `chunk_size` into the chunk
+        // below; it belongs at the start of the next one.
+        if local >= self.chunk_size {
+            (chunk as i32 + 1, 0.0)
+        } else {
+            (chunk as i32, local.max(0.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_coordinates_floor_into_lower_chunk() {
+        let chunks = ChunkCoords::new(16.0);
+        assert_eq!(
+            chunks.chunk_of(Vec3::new(-0.1, 0.0, 16.0)),
+            IVec3::new(-1, 0, 1)
+        );
+        assert_eq!(
+            chunks.chunk_of(Vec3::new(-16.0, -16.1, 15.9)),
+            IVec3::new(-1, -2, 0)
+        );
+
+        // A value so close to zero that `v + chunk_size` rounds to `chunk_size`.
+        let (chunk, local) = chunks.to_chunk_local_2d(Vec2::new(-1.0e-9 as Scalar, 0.0));
+        assert!(local.x >= 0.0 && local.x < 16.0);
+        assert_eq!(
+            chunks.to_world_2d(chunk, local).x,
+            chunks.chunk_origin_2d(chunk).x + local.x
+        );
+    }
+
+    #[test]
+    fn chunk_bounds_contain_their_positions() {
+        let chunks = ChunkCoords::new(8.0);
+        let p = Vec3::new(-3.0, 12.0, -20.0);
+        let (chunk, local) = chunks.to_chunk_local(p);
+        assert!(chunks.chunk_bounds(chunk).contains_point(p));
+        assert_eq!(chunks.to_world(chunk, local), p);
+        assert_eq!(
+            chunks.chunk_bounds_2d(IVec2::new(-1, 2)),
+            Aabb2::new(Vec2::new(-8.0, 16.0), Vec2::new(0.0, 24.0))
+        );
+    }
+
+    #[test]
+    fn chunks_in_aabb_covers_the_box() {
+        let chunks = ChunkCoords::new(10.0);
+        let aabb = Aabb3::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 9.0, 10.0));
+        let found: Vec<IVec3> = chunks.chunks_in_aabb(&aabb).collect();
+        assert_eq!(
+            found,
+            vec![
+                IVec3::new(-1, 0, 0),
+                IVec3::new(-1, 0, 1),
+                IVec3::new(0, 0, 0),
+                IVec3::new(0, 0, 1),
+            ]
+        );
+
+        let area = Aabb2::new(Vec2::new(-1.0, -1.0), Vec2::new(-0.5, 25.0));
+        let found: Vec<IVec2> = chunks.chunks_in_aabb_2d(&area).collect();
+        assert_eq!(
+            found,
+            vec![
+                IVec2::new(-1, -1),
+                IVec2::new(-1, 0),
+                IVec2::new(-1, 1),
+                IVec2::new(-1, 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{Scalar, Vec2};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 2D circle described by its `center` and `radius`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Circle, Vec2};
+///
+/// let c = Circle::new(Vec2::ZERO, 2.0);
+/// assert!(c.contains_point(Vec2::new(1.0, 1.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle {
+    /// Center of the circle.
+    pub center: Vec2,
+    /// Radius of the circle.
+    pub radius: Scalar,
+}
+
+impl Circle {
+    #[inline(always)]
+    pub const fn new(center: Vec2, radius: Scalar) -> Self {
+        Circle { center, radius }
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of the circle.
+    #[inline]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_point_includes_boundary() {
+        let c = Circle::new(Vec2::ZERO, 1.0 as Scalar);
+        assert!(c.contains_point(Vec2::new(0.0, 1.0)));
+        assert!(!c.contains_point(Vec2::new(0.8, 0.8)));
+    }
+}
@@ -0,0 +1,200 @@
+//! Closest-point queries against the crate's shapes.
+//!
+//! Functions are named after the pair they query, e.g. [`point_triangle`] returns the point on
+//! a triangle closest to a given point.
+
+use crate::{Aabb3, Obb3, Plane, Scalar, Segment3, Triangle3, Vec3};
+
+/// Returns the point on `segment` closest to `point`.
+#[inline]
+pub fn point_segment(point: Vec3, segment: &Segment3) -> Vec3 {
+    segment.closest_point(point)
+}
+
+/// Returns the point on `triangle` (including its interior) closest to `point`.
+///
+/// Degenerate triangles are handled as segments or points.
+pub fn point_triangle(point: Vec3, triangle: &Triangle3) -> Vec3 {
+    let Triangle3 { a, b, c } = *triangle;
+    match triangle_region(point, triangle) {
+        TriangleRegion::A => a,
+        TriangleRegion::B => b,
+        TriangleRegion::C => c,
+        TriangleRegion::Ab(t) => a + (b - a) * t,
+        TriangleRegion::Ac(t) => a + (c - a) * t,
+        TriangleRegion::Bc(t) => b + (c - b) * t,
+        TriangleRegion::Face(v, w) => a + (b - a) * v + (c - a) * w,
+    }
+}
+
+/// The feature of a triangle holding the point closest to some query point.
+///
+/// Edge regions carry the fraction of the way along the edge; the face region carries the
+/// weights of `b` and `c`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TriangleRegion {
+    A,
+    B,
+    C,
+    Ab(Scalar),
+    Ac(Scalar),
+    Bc(Scalar),
+    Face(Scalar, Scalar),
+}
+
+/// Finds the Voronoi region of `triangle` that `point` lies in.
+pub(crate) fn triangle_region(point: Vec3, triangle: &Triangle3) -> TriangleRegion {
+    let Triangle3 { a, b, c } = *triangle;
+    let ab = b - a;
+    let ac = c - a;
+
+    // Vertex region A.
+    let ap = point - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return TriangleRegion::A;
+    }
+
+    // Vertex region B.
+    let bp = point - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return TriangleRegion::B;
+    }
+
+    // Edge region AB.
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return TriangleRegion::Ab(d1 / (d1 - d3));
+    }
+
+    // Vertex region C.
+    let cp = point - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return TriangleRegion::C;
+    }
+
+    // Edge region AC.
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return TriangleRegion::Ac(d2 / (d2 - d6));
+    }
+
+    // Edge region BC.
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return TriangleRegion::Bc((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // Face region. The guard only matters for triangles that collapse under rounding.
+    let denom = va + vb + vc;
+    if denom <= 0.0 {
+        return TriangleRegion::A;
+    }
+    TriangleRegion::Face(vb / denom, vc / denom)
+}
+
+/// Returns the point inside or on `aabb` closest to `point`.
+#[inline]
+pub fn point_aabb(point: Vec3, aabb: &Aabb3) -> Vec3 {
+    aabb.closest_point(point)
+}
+
+/// Returns the point inside or on `obb` closest to `point`.
+#[inline]
+pub fn point_obb(point: Vec3, obb: &Obb3) -> Vec3 {
+    obb.closest_point(point)
+}
+
+/// Returns the point on `plane` closest to `point`.
+///
+/// The plane's normal does not need to be unit length, but must not be zero.
+#[inline]
+pub fn point_plane(point: Vec3, plane: &Plane) -> Vec3 {
+    point - plane.normal * (plane.signed_distance(point) / plane.normal.length_squared())
+}
+
+/// Returns the closest pair of points `(on_a, on_b)` between two segments.
+#[inline]
+pub fn segment_segment(a: &Segment3, b: &Segment3) -> (Vec3, Vec3) {
+    a.closest_points(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Quat, Scalar};
+
+    #[test]
+    fn point_triangle_covers_each_region() {
+        let t = Triangle3::new(
+            Vec3::ZERO,
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        );
+        // Face, vertex and edge regions.
+        assert_eq!(
+            point_triangle(Vec3::new(0.5, 0.5, 3.0), &t),
+            Vec3::new(0.5, 0.5, 0.0)
+        );
+        assert_eq!(point_triangle(Vec3::new(-1.0, -1.0, 0.0), &t), t.a);
+        assert_eq!(point_triangle(Vec3::new(3.0, -0.5, 0.0), &t), t.b);
+        assert_eq!(point_triangle(Vec3::new(-0.5, 3.0, 1.0), &t), t.c);
+        assert_eq!(
+            point_triangle(Vec3::new(1.0, -2.0, 0.0), &t),
+            Vec3::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            point_triangle(Vec3::new(-2.0, 1.0, 0.0), &t),
+            Vec3::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            point_triangle(Vec3::new(2.0, 2.0, 0.0), &t),
+            Vec3::new(1.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn point_triangle_handles_degenerate_triangles() {
+        let line = Triangle3::new(
+            Vec3::ZERO,
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+        );
+        assert_eq!(
+            point_triangle(Vec3::new(1.5, 1.0, 0.0), &line),
+            Vec3::new(1.5, 0.0, 0.0)
+        );
+        let point = Triangle3::new(Vec3::ONE, Vec3::ONE, Vec3::ONE);
+        assert_eq!(point_triangle(Vec3::ZERO, &point), Vec3::ONE);
+    }
+
+    #[test]
+    fn point_plane_projects_onto_non_unit_plane() {
+        let plane = Plane::from_point_normal(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 2.0, 0.0));
+        assert_eq!(
+            point_plane(Vec3::new(3.0, 5.0, -1.0), &plane),
+            Vec3::new(3.0, 1.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn box_and_segment_queries_delegate_to_shapes() {
+        let aabb = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        assert_eq!(point_aabb(Vec3::splat(2.0), &aabb), Vec3::ONE);
+        let obb = Obb3::new(Vec3::ZERO, Vec3::ONE, Quat::IDENTITY);
+        assert_eq!(point_obb(Vec3::splat(2.0), &obb), Vec3::ONE);
+        let s = Segment3::new(Vec3::ZERO, Vec3::new(0.0, 0.0, 4.0));
+        assert_eq!(
+            point_segment(Vec3::new(1.0, 0.0, 2.0), &s),
+            Vec3::new(0.0, 0.0, 2.0)
+        );
+        let other = Segment3::new(Vec3::new(1.0, 0.0, 5.0), Vec3::new(1.0, 0.0, 6.0));
+        let (on_s, on_other) = segment_segment(&s, &other);
+        assert_eq!(on_s.distance(on_other), (2.0 as Scalar).sqrt());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{Aabb3, Scalar, Sphere, Vec3, float, gjk3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A solid cone with a flat circular base.
+///
+/// The cone starts at `apex` and widens along `axis` until it reaches a base of `radius`
+/// at distance `height`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Cone, Vec3};
+///
+/// let cone = Cone::new(Vec3::ZERO, Vec3::new(0.0, 0.0, -1.0), 10.0, 5.0);
+/// assert!(cone.contains_point(Vec3::new(1.0, 0.0, -8.0)));
+/// assert!(!cone.contains_point(Vec3::new(1.0, 0.0, -1.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cone {
+    /// Tip of the cone.
+    pub apex: Vec3,
+    /// Unit direction from the apex towards the base.
+    pub axis: Vec3,
+    /// Distance from the apex to the base.
+    pub height: Scalar,
+    /// Radius of the base.
+    pub radius: Scalar,
+}
+
+impl Cone {
+    #[inline(always)]
+    pub const fn new(apex: Vec3, axis: Vec3, height: Scalar, radius: Scalar) -> Self {
+        Cone {
+            apex,
+            axis,
+            height,
+            radius,
+        }
+    }
+
+    /// Creates a cone from its half-angle (in radians) at the apex instead of its base radius.
+    pub fn from_half_angle(apex: Vec3, axis: Vec3, height: Scalar, half_angle: Scalar) -> Self {
+        Cone::new(apex, axis, height, height * float::tan(half_angle))
+    }
+
+    /// Returns `true` if `point` is inside or on the surface of the cone.
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        let offset = point - self.apex;
+        let h = offset.dot(self.axis);
+        if h < 0.0 || h > self.height {
+            return false;
+        }
+        let max_radius = h * self.radius / self.height;
+        (offset - self.axis * h).length_squared() <= max_radius * max_radius
+    }
+
+    /// Returns the point inside or on the surface of the cone closest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        // Work in the half-plane spanned by the axis and the point, where the cone is the
+        // triangle (0, 0), (radius, height), (0, height) in (radial, axial) coordinates.
+        let offset = point - self.apex;
+        let h = offset.dot(self.axis);
+        let radial = offset - self.axis * h;
+        let r = radial.length();
+        if h >= 0.0 && h <= self.height && r * self.height <= h * self.radius {
+            return point;
+        }
+
+        // Closest point on the slanted edge from the apex to the rim.
+        let edge_len_sq = self.radius * self.radius + self.height * self.height;
+        let t = if edge_len_sq > 0.0 {
+            ((r * self.radius + h * self.height) / edge_len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (slant_r, slant_h) = (t * self.radius, t * self.height);
+
+        // Closest point on the base disk edge.
+        let (base_r, base_h) = (r.min(self.radius), self.height);
+
+        let slant_dist_sq = (r - slant_r) * (r - slant_r) + (h - slant_h) * (h - slant_h);
+        let base_dist_sq = (r - base_r) * (r - base_r) + (h - base_h) * (h - base_h);
+        let (cr, ch) = if slant_dist_sq <= base_dist_sq {
+            (slant_r, slant_h)
+        } else {
+            (base_r, base_h)
+        };
+
+        let radial_dir = if r > 0.0 { radial / r } else { Vec3::ZERO };
+        self.apex + self.axis * ch + radial_dir * cr
+    }
+
+    /// Returns `true` if the cone and `sphere` overlap.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Returns the point of the cone furthest along `direction`: the apex or a point on the
+    /// base rim.
+    pub fn support(&self, direction: Vec3) -> Vec3 {
+        // Projected twice, as in `Cylinder::support`, so the rim point stays on the rim.
+        let radial = direction - self.axis * direction.dot(self.axis);
+        let radial = (radial - self.axis * radial.dot(self.axis)).normalize_or_zero(0.0);
+        let rim = self.apex + self.axis * self.height + radial * self.radius;
+        if rim.dot(direction) >= self.apex.dot(direction) {
+            rim
+        } else {
+            self.apex
+        }
+    }
+
+    /// Returns `true` if the cone and `aabb` overlap or are less than `tolerance` apart.
+    pub fn intersects_aabb(&self, aabb: &Aabb3, tolerance: Scalar) -> bool {
+        let center = self.apex + self.axis * (self.height * 0.5);
+        gjk3::intersects(
+            |d| self.support(d),
+            |d| aabb.support(d),
+            center - aabb.center(),
+            tolerance,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pcg32;
+    use crate::sample::{self, Source};
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn cone() -> Cone {
+        Cone::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), 4.0, 2.0)
+    }
+
+    #[test]
+    fn contains_point_respects_slant() {
+        let c = cone();
+        assert!(c.contains_point(Vec3::new(2.0, 1.0, 0.0)));
+        assert!(!c.contains_point(Vec3::new(1.0, 1.0, 0.0)));
+        assert!(!c.contains_point(Vec3::new(-0.1, 0.0, 0.0)));
+        assert!(!c.contains_point(Vec3::new(4.1, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn closest_point_handles_each_region() {
+        let c = cone();
+        // Behind the apex.
+        assert!(
+            c.closest_point(Vec3::new(-3.0, 0.0, 0.0))
+                .distance(Vec3::ZERO)
+                <= EPS
+        );
+        // Beyond the base, near the axis.
+        let p = c.closest_point(Vec3::new(6.0, 0.5, 0.0));
+        assert!(p.distance(Vec3::new(4.0, 0.5, 0.0)) <= EPS);
+        // Beside the slant: projection onto the edge from (0, 0) to (2, 4) in (radial, axial).
+        let p = c.closest_point(Vec3::new(2.0, 0.0, 3.0));
+        assert!(p.distance(Vec3::new(2.8, 0.0, 1.4)) <= EPS);
+    }
+
+    #[test]
+    fn from_half_angle_sets_base_radius() {
+        let c = Cone::from_half_angle(
+            Vec3::ZERO,
+            Vec3::new(0.0, 1.0, 0.0),
+            2.0,
+            (1.0 as Scalar).atan(),
+        );
+        assert!((c.radius - 2.0 as Scalar).abs() <= EPS);
+    }
+
+    #[test]
+    fn intersects_sphere_and_aabb() {
+        let c = cone();
+        assert!(c.intersects_sphere(&Sphere::new(Vec3::new(3.0, 2.0, 0.0), 0.6)));
+        assert!(!c.intersects_sphere(&Sphere::new(Vec3::new(1.0, 2.0, 0.0), 0.5)));
+        let near_apex = Aabb3::new(Vec3::new(-1.0, 1.0, -1.0), Vec3::new(1.0, 2.0, 1.0));
+        let at_base = Aabb3::new(Vec3::new(3.5, -0.5, -0.5), Vec3::new(5.0, 0.5, 0.5));
+        assert!(!c.intersects_aabb(&near_apex, EPS));
+        assert!(c.intersects_aabb(&at_base, EPS));
+    }
+
+    #[test]
+    fn intersects_aabb_agrees_with_sampled_points() {
+        let mut rng = Pcg32::from_seed(105);
+        let space = Aabb3::from_center_half_extents(Vec3::ZERO, Vec3::splat(2.0));
+        let (mut hits, mut misses) = (0, 0);
+        for _ in 0..500 {
+            let height = 0.2 + rng.next_unit() * 3.0;
+            let c = Cone::new(
+                sample::unit_sphere(&mut rng) * -(height * 0.5),
+                sample::unit_sphere(&mut rng),
+                height,
+                0.1 + rng.next_unit() * 1.5,
+            );
+            let half_extents = Vec3::new(
+                0.05 + rng.next_unit() * 1.5,
+                0.05 + rng.next_unit() * 1.5,
+                0.05 + rng.next_unit() * 1.5,
+            );
+            let aabb =
+                Aabb3::from_center_half_extents(sample::in_aabb3(&mut rng, &space), half_extents);
+            let hit = c.intersects_aabb(&aabb, EPS);
+            for _ in 0..400 {
+                let p = sample::in_aabb3(&mut rng, &aabb);
+                assert!(
+                    !c.contains_point(p) || hit,
+                    "{c:?} and {aabb:?} share {p:?}"
+                );
+            }
+            if hit {
+                hits += 1;
+            } else {
+                misses += 1;
+            }
+        }
+        assert!(hits > 100 && misses > 100, "{hits} {misses}");
+    }
+
+    #[test]
+    fn flat_cone_touching_a_box_face() {
+        let top = Aabb3::new(Vec3::new(-2.0, -1.0, -2.0), Vec3::new(2.0, 1.0, 2.0));
+        // A wide, shallow cone whose base faces down onto the box.
+        let cap = |base: Scalar| {
+            Cone::new(
+                Vec3::new(0.3, base + 0.01, -0.2),
+                Vec3::new(0.0, -1.0, 0.0),
+                0.01,
+                1.5,
+            )
+        };
+        assert!(cap(1.0).intersects_aabb(&top, EPS));
+        assert!(cap(1.0 + 0.5 * EPS).intersects_aabb(&top, EPS));
+        assert!(!cap(1.001).intersects_aabb(&top, EPS));
+        assert!(!cap(1.01).intersects_aabb(&top, EPS));
+    }
+}
@@ -0,0 +1,51 @@
+//! Mathematical constants as [`Scalar`], for whichever precision the crate is built with.
+//!
+//! Spelling these as `core::f32::consts::PI` breaks `f64` builds, and a literal `1.0e-6`
+//! tolerance is far looser than `f64` needs; the constants here follow the `f32`/`f64`
+//! feature instead.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{Vec3, consts};
+//!
+//! let facing = Vec3::new(0.0, 0.0, 1.0);
+//! assert!((facing.length() - 1.0).abs() <= consts::EPSILON);
+//! assert_eq!(90.0 * consts::DEG_TO_RAD, consts::FRAC_PI_2);
+//! ```
+
+use crate::Scalar;
+
+/// Archimedes' constant, π.
+pub const PI: Scalar = core::f64::consts::PI as _;
+/// The full circle constant, τ = 2π.
+pub const TAU: Scalar = core::f64::consts::TAU as _;
+/// π/2, a quarter turn.
+pub const FRAC_PI_2: Scalar = core::f64::consts::FRAC_PI_2 as _;
+/// π/4, an eighth of a turn.
+pub const FRAC_PI_4: Scalar = core::f64::consts::FRAC_PI_4 as _;
+/// √2.
+pub const SQRT_2: Scalar = core::f64::consts::SQRT_2 as _;
+/// 1/√2, the length of each component of a unit diagonal.
+pub const FRAC_1_SQRT_2: Scalar = core::f64::consts::FRAC_1_SQRT_2 as _;
+/// ln 2.
+pub const LN_2: Scalar = core::f64::consts::LN_2 as _;
+
+/// Multiply degrees by this to get radians.
+pub const DEG_TO_RAD: Scalar = (core::f64::consts::PI / 180.0) as _;
+/// Multiply radians by this to get degrees.
+pub const RAD_TO_DEG: Scalar = (180.0 / core::f64::consts::PI) as _;
+
+/// Default tolerance for lengths and comparisons of values near one, such as checking that a
+/// vector is normalized or that a direction isn't degenerate.
+///
+/// `1e-6` for `f32` and `1e-12` for `f64`: comfortably above the rounding error of a few
+/// operations, and far below any distance that matters in a game world.
+#[cfg(feature = "f32")]
+pub const EPSILON: Scalar = 1.0e-6;
+/// Default tolerance for lengths and comparisons of values near one, such as checking that a
+/// vector is normalized or that a direction isn't degenerate.
+///
+/// `1e-6` for `f32` and `1e-12` for `f64`: comfortably above the rounding error of a few
+/// operations, and far below any distance that matters in a game world.
+#[cfg(feature = "f64")]
+pub const EPSILON: Scalar = 1.0e-12;
@@ -1,9 +1,13 @@
 //! Coordinate system conventions and axis presets.
 
-use crate::{Scalar, Vec3};
+use crate::{Mat3, Quat, Scalar, Transform, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
 
 /// Orthonormal basis describing a coordinate system.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Axes {
     /// The up direction.
     pub up: Vec3,
@@ -33,6 +37,109 @@ impl Axes {
         let forward = right.cross(up);
         Some(Self { up, forward, right })
     }
+
+    /// Returns the matrix taking coordinates in this convention to coordinates in `other`, so
+    /// that this convention's up, forward and right map onto `other`'s.
+    ///
+    /// The determinant is `-1` when the two conventions differ in handedness.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{Vec3, conventions};
+    ///
+    /// let unity_to_ours = conventions::Y_UP_LEFT_HANDED_FWD_POS_Z
+    ///     .change_of_basis_to(&conventions::DEFAULT);
+    /// assert_eq!(unity_to_ours * Vec3::new(1.0, 2.0, 3.0), Vec3::new(1.0, 2.0, -3.0));
+    /// assert_eq!(unity_to_ours.determinant(), -1.0);
+    /// ```
+    pub fn change_of_basis_to(&self, other: &Axes) -> Mat3 {
+        other.basis() * self.basis().transpose()
+    }
+
+    /// Returns the matrix with columns right, up and forward, taking `(right, up, forward)`
+    /// components to coordinates in this convention.
+    pub(crate) fn basis(&self) -> Mat3 {
+        Mat3::from_cols(self.right, self.up, self.forward)
+    }
+}
+
+/// Converts a position or direction from the `from` convention to the `to` convention.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Vec3, conventions};
+///
+/// // Unreal's forward is +X; ours is -Z.
+/// let forward = conventions::convert_vec3(
+///     Vec3::new(1.0, 0.0, 0.0),
+///     &conventions::Z_UP_LEFT_HANDED_FWD_POS_X,
+///     &conventions::DEFAULT,
+/// );
+/// assert_eq!(forward, conventions::DEFAULT.forward);
+/// ```
+#[inline]
+pub fn convert_vec3(v: Vec3, from: &Axes, to: &Axes) -> Vec3 {
+    from.change_of_basis_to(to) * v
+}
+
+/// Converts a rotation from the `from` convention to the `to` convention, so that rotating a
+/// converted vector by the converted rotation matches converting the rotated vector.
+///
+/// When the handedness differs, the rotation axis is mirrored and the rotation keeps its
+/// angle, which is what makes it turn the other way on screen.
+#[inline]
+pub fn convert_quat(q: Quat, from: &Axes, to: &Axes) -> Quat {
+    conjugate_quat(&from.change_of_basis_to(to), q)
+}
+
+/// Returns the rotation `m * q * m⁻¹` for an orthonormal `m`.
+///
+/// A mirror `m` equals minus a rotation, and the two minus signs cancel, so the axis maps
+/// through `m` scaled by its determinant.
+pub(crate) fn conjugate_quat(m: &Mat3, q: Quat) -> Quat {
+    let axis = *m * Vec3::new(q.x, q.y, q.z) * m.determinant().signum();
+    Quat::new(axis.x, axis.y, axis.z, q.w)
+}
+
+/// Maps a per-axis scale through `m`, which is exact when `m` is a signed permutation like
+/// the change of basis between any two presets: the axes swap and the mirroring cancels.
+pub(crate) fn convert_scale(m: &Mat3, s: Vec3) -> Vec3 {
+    let row = |r: Vec3| r.x.abs() * s.x + r.y.abs() * s.y + r.z.abs() * s.z;
+    Vec3::new(row(m.row(0)), row(m.row(1)), row(m.row(2)))
+}
+
+/// Values that can be re-expressed in another coordinate convention.
+pub trait ConvertAxes: Sized {
+    /// Converts this value from the `from` convention to the `to` convention.
+    fn convert_axes(&self, from: &Axes, to: &Axes) -> Self;
+}
+
+impl ConvertAxes for Vec3 {
+    #[inline]
+    fn convert_axes(&self, from: &Axes, to: &Axes) -> Vec3 {
+        convert_vec3(*self, from, to)
+    }
+}
+
+impl ConvertAxes for Quat {
+    #[inline]
+    fn convert_axes(&self, from: &Axes, to: &Axes) -> Quat {
+        convert_quat(*self, from, to)
+    }
+}
+
+impl ConvertAxes for Transform {
+    /// Converts the translation and rotation, and swaps the scale's components to follow
+    /// the axes. The scale is exact between presets; for arbitrary [`Axes`] a non-uniform
+    /// scale can only be approximated, since it no longer lines up with the new axes.
+    fn convert_axes(&self, from: &Axes, to: &Axes) -> Transform {
+        let m = from.change_of_basis_to(to);
+        Transform::new(
+            m * self.translation,
+            conjugate_quat(&m, self.rotation),
+            convert_scale(&m, self.scale),
+        )
+    }
 }
 
 /// Default coordinate convention.
@@ -132,6 +239,266 @@ pub const Z_UP_LEFT_HANDED_FWD_POS_X: Axes = Axes {
     right: Vec3::new(0.0, 1.0, 0.0),
 };
 
+/// One of the preset conventions, small enough to store in a config table so clients can
+/// read the world's convention instead of assuming one.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::conventions::{self, Convention};
+///
+/// let world = Convention::default();
+/// assert_eq!(world.axes(), conventions::DEFAULT);
+/// assert_eq!(
+///     Convention::from_axes(&conventions::Z_UP_LEFT_HANDED_FWD_POS_X),
+///     Some(Convention::ZUpLeftHandedFwdPosX)
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Convention {
+    /// [`Y_UP_RIGHT_HANDED_FWD_NEG_Z`], the [`DEFAULT`]; Bevy and Godot.
+    #[default]
+    YUpRightHandedFwdNegZ,
+    /// [`Y_UP_RIGHT_HANDED_FWD_POS_Z`].
+    YUpRightHandedFwdPosZ,
+    /// [`Y_UP_LEFT_HANDED_FWD_POS_Z`]; Unity.
+    YUpLeftHandedFwdPosZ,
+    /// [`Z_UP_RIGHT_HANDED_FWD_POS_Y`]; Blender.
+    ZUpRightHandedFwdPosY,
+    /// [`Z_UP_LEFT_HANDED_FWD_POS_X`]; Unreal.
+    ZUpLeftHandedFwdPosX,
+}
+
+impl Convention {
+    /// Every preset, in declaration order.
+    pub const ALL: [Convention; 5] = [
+        Convention::YUpRightHandedFwdNegZ,
+        Convention::YUpRightHandedFwdPosZ,
+        Convention::YUpLeftHandedFwdPosZ,
+        Convention::ZUpRightHandedFwdPosY,
+        Convention::ZUpLeftHandedFwdPosX,
+    ];
+
+    /// Returns the preset's axes.
+    #[inline]
+    pub const fn axes(self) -> Axes {
+        match self {
+            Convention::YUpRightHandedFwdNegZ => Y_UP_RIGHT_HANDED_FWD_NEG_Z,
+            Convention::YUpRightHandedFwdPosZ => Y_UP_RIGHT_HANDED_FWD_POS_Z,
+            Convention::YUpLeftHandedFwdPosZ => Y_UP_LEFT_HANDED_FWD_POS_Z,
+            Convention::ZUpRightHandedFwdPosY => Z_UP_RIGHT_HANDED_FWD_POS_Y,
+            Convention::ZUpLeftHandedFwdPosX => Z_UP_LEFT_HANDED_FWD_POS_X,
+        }
+    }
+
+    /// Returns the preset with exactly these axes, or `None` for a custom convention.
+    pub fn from_axes(axes: &Axes) -> Option<Convention> {
+        Convention::ALL.into_iter().find(|c| c.axes() == *axes)
+    }
+}
+
+impl From<Convention> for Axes {
+    #[inline]
+    fn from(convention: Convention) -> Axes {
+        convention.axes()
+    }
+}
+
+/// A change of coordinate system: an axis remap, optionally mirrored, followed by a uniform
+/// change of units, applied to points, directions, rotations and transforms in one pass.
+///
+/// Rotations and the scale of transforms only see the axis part; the unit scale applies to
+/// positions and translations.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::conventions::{self, CoordinateConversion};
+/// use spacetimedb_math::Vec3;
+///
+/// // Unreal: Z-up, left-handed, forward +X, centimeters.
+/// let import = CoordinateConversion::between(
+///     &conventions::Z_UP_LEFT_HANDED_FWD_POS_X,
+///     &conventions::DEFAULT,
+/// )
+/// .units(0.01);
+/// let mut spawns = [Vec3::new(250.0, 0.0, 100.0), Vec3::new(0.0, -50.0, 0.0)];
+/// import.convert_points(&mut spawns);
+/// assert!(spawns[0].distance(Vec3::new(0.0, 1.0, -2.5)) < 1.0e-6);
+/// assert!(import.inverse().convert_point(spawns[1]).distance(Vec3::new(0.0, -50.0, 0.0)) < 1.0e-4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoordinateConversion {
+    /// Orthonormal map from source to target axes; a determinant of `-1` flips handedness.
+    pub basis: Mat3,
+    /// Target units per source unit, e.g. `0.01` from centimeters to meters.
+    pub unit_scale: Scalar,
+}
+
+impl Default for CoordinateConversion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl CoordinateConversion {
+    /// The conversion that changes nothing.
+    pub const IDENTITY: CoordinateConversion = CoordinateConversion::new(Mat3::IDENTITY, 1.0);
+
+    #[inline(always)]
+    pub const fn new(basis: Mat3, unit_scale: Scalar) -> Self {
+        CoordinateConversion { basis, unit_scale }
+    }
+
+    /// Creates the conversion from the `from` convention to the `to` convention, keeping
+    /// units.
+    #[inline]
+    pub fn between(from: &Axes, to: &Axes) -> Self {
+        CoordinateConversion::new(from.change_of_basis_to(to), 1.0)
+    }
+
+    /// Returns this conversion followed by multiplying lengths by `factor`.
+    #[inline]
+    pub fn units(self, factor: Scalar) -> Self {
+        CoordinateConversion::new(self.basis, self.unit_scale * factor)
+    }
+
+    /// Returns this conversion followed by a mirror across the plane through the origin with
+    /// unit `normal`, which flips handedness without moving the other two axes.
+    pub fn mirror(self, normal: Vec3) -> Self {
+        let reflect = |v: Vec3| v - normal * (2.0 * v.dot(normal));
+        let mirror = Mat3::from_cols(
+            reflect(Vec3::new(1.0, 0.0, 0.0)),
+            reflect(Vec3::new(0.0, 1.0, 0.0)),
+            reflect(Vec3::new(0.0, 0.0, 1.0)),
+        );
+        CoordinateConversion::new(mirror * self.basis, self.unit_scale)
+    }
+
+    /// Returns this conversion followed by `next`.
+    #[inline]
+    pub fn then(self, next: CoordinateConversion) -> Self {
+        CoordinateConversion::new(next.basis * self.basis, self.unit_scale * next.unit_scale)
+    }
+
+    /// Returns the conversion back from the target system to the source.
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        CoordinateConversion::new(self.basis.transpose(), 1.0 / self.unit_scale)
+    }
+
+    /// Converts a position: remaps the axes and changes the units.
+    #[inline]
+    pub fn convert_point(&self, point: Vec3) -> Vec3 {
+        self.basis * point * self.unit_scale
+    }
+
+    /// Converts a unit direction, which only has its axes remapped.
+    #[inline]
+    pub fn convert_direction(&self, direction: Vec3) -> Vec3 {
+        self.basis * direction
+    }
+
+    /// Converts a rotation; see [`convert_quat`].
+    #[inline]
+    pub fn convert_quat(&self, rotation: Quat) -> Quat {
+        conjugate_quat(&self.basis, rotation)
+    }
+
+    /// Converts a transform: the translation as a point, the rotation with
+    /// [`CoordinateConversion::convert_quat`] and the scale as in [`ConvertAxes`]. The scale
+    /// stays a ratio, so it isn't affected by the change of units.
+    pub fn convert_transform(&self, transform: &Transform) -> Transform {
+        Transform::new(
+            self.convert_point(transform.translation),
+            self.convert_quat(transform.rotation),
+            convert_scale(&self.basis, transform.scale),
+        )
+    }
+
+    /// Converts every position in `points` in place.
+    pub fn convert_points(&self, points: &mut [Vec3]) {
+        for p in points {
+            *p = self.convert_point(*p);
+        }
+    }
+
+    /// Converts every direction in `directions` in place.
+    pub fn convert_directions(&self, directions: &mut [Vec3]) {
+        for d in directions {
+            *d = self.convert_direction(*d);
+        }
+    }
+
+    /// Converts every rotation in `rotations` in place.
+    pub fn convert_quats(&self, rotations: &mut [Quat]) {
+        for q in rotations {
+            *q = self.convert_quat(*q);
+        }
+    }
+
+    /// Converts every transform in `transforms` in place.
+    pub fn convert_transforms(&self, transforms: &mut [Transform]) {
+        for t in transforms {
+            *t = self.convert_transform(t);
+        }
+    }
+}
+
+/// Converts a value from Unity's convention (left-handed, Y-up, forward +Z) to [`DEFAULT`].
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Quat, Transform, Vec3, conventions};
+///
+/// // A Unity object 5 units ahead, yawed a quarter turn towards its right.
+/// let yaw = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), std::f64::consts::FRAC_PI_2 as _);
+/// let unity = Transform::from_translation_rotation(Vec3::new(0.0, 0.0, 5.0), yaw);
+/// let ours = conventions::from_unity(unity);
+/// assert_eq!(ours.translation, Vec3::new(0.0, 0.0, -5.0));
+/// let facing = ours.rotation * conventions::DEFAULT.forward;
+/// assert!(facing.distance(conventions::DEFAULT.right) < 1.0e-6);
+/// ```
+#[inline]
+pub fn from_unity<T: ConvertAxes>(value: T) -> T {
+    value.convert_axes(&Y_UP_LEFT_HANDED_FWD_POS_Z, &DEFAULT)
+}
+
+/// Converts a value from [`DEFAULT`] to Unity's convention (left-handed, Y-up, forward +Z).
+#[inline]
+pub fn to_unity<T: ConvertAxes>(value: T) -> T {
+    value.convert_axes(&DEFAULT, &Y_UP_LEFT_HANDED_FWD_POS_Z)
+}
+
+/// Converts a value from Unreal's convention (left-handed, Z-up, forward +X) to [`DEFAULT`].
+///
+/// Only the axes change; Unreal's centimeters stay centimeters. Use a
+/// [`CoordinateConversion`] to convert the units as well.
+#[inline]
+pub fn from_unreal<T: ConvertAxes>(value: T) -> T {
+    value.convert_axes(&Z_UP_LEFT_HANDED_FWD_POS_X, &DEFAULT)
+}
+
+/// Converts a value from [`DEFAULT`] to Unreal's convention (left-handed, Z-up, forward +X).
+#[inline]
+pub fn to_unreal<T: ConvertAxes>(value: T) -> T {
+    value.convert_axes(&DEFAULT, &Z_UP_LEFT_HANDED_FWD_POS_X)
+}
+
+/// Converts a value from Blender's convention (right-handed, Z-up, forward +Y) to [`DEFAULT`].
+#[inline]
+pub fn from_blender<T: ConvertAxes>(value: T) -> T {
+    value.convert_axes(&Z_UP_RIGHT_HANDED_FWD_POS_Y, &DEFAULT)
+}
+
+/// Converts a value from [`DEFAULT`] to Blender's convention (right-handed, Z-up, forward +Y).
+#[inline]
+pub fn to_blender<T: ConvertAxes>(value: T) -> T {
+    value.convert_axes(&DEFAULT, &Z_UP_RIGHT_HANDED_FWD_POS_Y)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,4 +542,124 @@ mod tests {
         let axes = Axes::try_right_handed(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0), EPS);
         assert!(axes.is_none());
     }
+
+    #[test]
+    fn change_of_basis_maps_axes_onto_each_other() {
+        let presets = [
+            Y_UP_RIGHT_HANDED_FWD_NEG_Z,
+            Y_UP_RIGHT_HANDED_FWD_POS_Z,
+            Y_UP_LEFT_HANDED_FWD_POS_Z,
+            Z_UP_RIGHT_HANDED_FWD_POS_Y,
+            Z_UP_LEFT_HANDED_FWD_POS_X,
+        ];
+        for from in presets {
+            for to in presets {
+                let m = from.change_of_basis_to(&to);
+                assert_eq!(m * from.up, to.up);
+                assert_eq!(m * from.forward, to.forward);
+                assert_eq!(m * from.right, to.right);
+                assert_eq!(m * to.change_of_basis_to(&from), Mat3::IDENTITY);
+            }
+        }
+    }
+
+    #[test]
+    fn converted_rotations_commute_with_converted_vectors() {
+        let q = Quat::from_axis_angle(Vec3::new(2.0, 3.0, 6.0) * (1.0 / 7.0), 0.9);
+        let v = Vec3::new(0.5, -1.0, 2.0);
+        for (from, to) in [
+            (Y_UP_LEFT_HANDED_FWD_POS_Z, DEFAULT),
+            (Z_UP_LEFT_HANDED_FWD_POS_X, DEFAULT),
+            (Z_UP_RIGHT_HANDED_FWD_POS_Y, Z_UP_LEFT_HANDED_FWD_POS_X),
+        ] {
+            let expected = convert_vec3(q * v, &from, &to);
+            let actual = convert_quat(q, &from, &to) * convert_vec3(v, &from, &to);
+            assert!(actual.distance(expected) <= 1.0e-5 as Scalar);
+        }
+        // A yaw that turns forward towards right keeps doing so in the other convention.
+        let unity = Y_UP_LEFT_HANDED_FWD_POS_Z;
+        let yaw = Quat::from_axis_angle(unity.up, crate::consts::FRAC_PI_2);
+        assert!((yaw * unity.forward).distance(unity.right) <= 1.0e-5 as Scalar);
+        let ours = convert_quat(yaw, &unity, &DEFAULT);
+        assert!((ours * DEFAULT.forward).distance(DEFAULT.right) <= 1.0e-5 as Scalar);
+    }
+
+    #[test]
+    fn engine_converters_round_trip_and_match_presets() {
+        let t = Transform::new(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.6, 0.8), 1.2),
+            Vec3::new(1.0, 2.0, 4.0),
+        );
+        type Converter = fn(Transform) -> Transform;
+        let engines: [(Converter, Converter, Axes); 3] = [
+            (from_unity, to_unity, Y_UP_LEFT_HANDED_FWD_POS_Z),
+            (from_unreal, to_unreal, Z_UP_LEFT_HANDED_FWD_POS_X),
+            (from_blender, to_blender, Z_UP_RIGHT_HANDED_FWD_POS_Y),
+        ];
+        for (from, to, axes) in engines {
+            let back = to(from(t));
+            assert!(back.translation.distance(t.translation) <= 1.0e-5 as Scalar);
+            assert!(back.rotation.dot(t.rotation).abs() >= 1.0 - 1.0e-5 as Scalar);
+            assert_eq!(back.scale, t.scale);
+            let p = Vec3::new(-0.5, 0.25, 2.0);
+            let expected = convert_vec3(t.transform_point(p), &axes, &DEFAULT);
+            let actual = from(t).transform_point(convert_vec3(p, &axes, &DEFAULT));
+            assert!(actual.distance(expected) <= 1.0e-5 as Scalar);
+        }
+        // Unreal's forward and up land on ours; its scale along forward lands on our Z.
+        assert_eq!(from_unreal(Vec3::new(1.0, 0.0, 0.0)), DEFAULT.forward);
+        assert_eq!(from_unreal(Vec3::new(0.0, 0.0, 1.0)), DEFAULT.up);
+        let stretched = Transform::new(Vec3::ZERO, Quat::IDENTITY, Vec3::new(3.0, 1.0, 1.0));
+        assert_eq!(from_unreal(stretched).scale, Vec3::new(1.0, 1.0, 3.0));
+        assert_eq!(to_blender(from_blender(Quat::IDENTITY)), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn convention_enum_round_trips_through_axes() {
+        for convention in Convention::ALL {
+            assert_eq!(Convention::from_axes(&convention.axes()), Some(convention));
+        }
+        assert_eq!(Axes::from(Convention::default()), DEFAULT);
+        let tilted =
+            Axes::try_right_handed(Vec3::new(0.0, 1.0, 0.1), Vec3::new(0.0, 0.0, -1.0), EPS)
+                .expect("expected axes");
+        assert_eq!(Convention::from_axes(&tilted), None);
+    }
+
+    #[test]
+    fn coordinate_conversion_composes_axes_mirror_and_units() {
+        let unreal =
+            CoordinateConversion::between(&Z_UP_LEFT_HANDED_FWD_POS_X, &DEFAULT).units(0.01);
+        let t = Transform::new(
+            Vec3::new(100.0, 200.0, 300.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 0.4),
+            Vec3::new(2.0, 1.0, 1.0),
+        );
+        let mut batch = [t, Transform::IDENTITY];
+        unreal.convert_transforms(&mut batch);
+        assert_eq!(batch[0].translation, from_unreal(t.translation) * 0.01);
+        assert_eq!(batch[0].rotation, from_unreal(t.rotation));
+        assert_eq!(batch[0].scale, Vec3::new(1.0, 1.0, 2.0));
+        assert_eq!(batch[1], Transform::IDENTITY);
+        let back = unreal.then(unreal.inverse());
+        assert!((back.unit_scale - 1.0).abs() <= EPS);
+        assert_eq!(back.basis, Mat3::IDENTITY);
+
+        // Mirroring Unity's Z turns it into our convention.
+        let mirrored = CoordinateConversion::IDENTITY.mirror(Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(
+            mirrored.basis,
+            CoordinateConversion::between(&Y_UP_LEFT_HANDED_FWD_POS_Z, &DEFAULT).basis
+        );
+        let mut directions = [Vec3::new(0.0, 0.0, 1.0)];
+        mirrored.convert_directions(&mut directions);
+        assert_eq!(directions[0], DEFAULT.forward);
+        let mut rotations = [Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5)];
+        mirrored.convert_quats(&mut rotations);
+        assert_eq!(
+            rotations[0],
+            from_unity(Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5))
+        );
+    }
 }
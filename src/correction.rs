@@ -0,0 +1,224 @@
+use crate::interp::{decay_from_half_life, exp_decay};
+use crate::{Quat, Scalar, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Hides a position misprediction by spreading it out over time.
+///
+/// When an authoritative state corrects a predicted one, jumping straight to it looks like a
+/// teleport. Instead, [`PositionCorrection::correct`] records the visual error as an offset
+/// that keeps the displayed position where it was, and [`PositionCorrection::update`] decays
+/// that offset towards zero. Simulate with the corrected state and display
+/// [`PositionCorrection::apply`] of it.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{PositionCorrection, Vec3};
+///
+/// let mut smoothing = PositionCorrection::new(0.1, 5.0);
+/// let predicted = Vec3::new(10.0, 0.0, 0.0);
+/// let corrected = Vec3::new(9.0, 0.0, 0.0);
+/// smoothing.correct(predicted, corrected);
+/// // Nothing moves on screen at the moment of the correction...
+/// assert_eq!(smoothing.apply(corrected), predicted);
+/// // ...and the error halves every 0.1 seconds afterwards.
+/// smoothing.update(0.1);
+/// assert!(smoothing.apply(corrected).distance(Vec3::new(9.5, 0.0, 0.0)) < 1.0e-5);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionCorrection {
+    /// Displayed position minus simulated position.
+    pub offset: Vec3,
+    /// Seconds for the remaining error to halve.
+    pub half_life: Scalar,
+    /// Errors longer than this snap instead of smoothing, since gliding across a large
+    /// distance looks worse than a jump.
+    pub max_offset: Scalar,
+}
+
+impl PositionCorrection {
+    /// Creates a correction with no pending error.
+    #[inline]
+    pub const fn new(half_life: Scalar, max_offset: Scalar) -> Self {
+        PositionCorrection {
+            offset: Vec3::ZERO,
+            half_life,
+            max_offset,
+        }
+    }
+
+    /// Records that the simulation moved from `predicted` to `corrected`, keeping the
+    /// displayed position unchanged. Errors pending from earlier corrections carry over.
+    pub fn correct(&mut self, predicted: Vec3, corrected: Vec3) {
+        self.offset = predicted + self.offset - corrected;
+        let length = self.offset.length();
+        if length.is_nan() || length > self.max_offset {
+            self.offset = Vec3::ZERO;
+        }
+    }
+
+    /// Decays the offset by `dt` seconds.
+    #[inline]
+    pub fn update(&mut self, dt: Scalar) {
+        self.offset = exp_decay(
+            self.offset,
+            Vec3::ZERO,
+            decay_from_half_life(self.half_life),
+            dt,
+        );
+    }
+
+    /// Returns where to display an object simulated at `position`.
+    #[inline]
+    pub fn apply(&self, position: Vec3) -> Vec3 {
+        position + self.offset
+    }
+
+    /// Drops any pending error, snapping the display to the simulation.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.offset = Vec3::ZERO;
+    }
+}
+
+/// Hides a rotation misprediction by spreading it out over time. See [`PositionCorrection`].
+///
+/// The offset is a world-space rotation applied on top of the simulated one, and always
+/// takes the short way round.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotationCorrection {
+    /// Rotation from the simulated orientation to the displayed one.
+    pub offset: Quat,
+    /// Seconds for the remaining error to halve.
+    pub half_life: Scalar,
+    /// Errors larger than this many radians snap instead of smoothing.
+    pub max_angle: Scalar,
+}
+
+impl RotationCorrection {
+    /// Creates a correction with no pending error.
+    #[inline]
+    pub const fn new(half_life: Scalar, max_angle: Scalar) -> Self {
+        RotationCorrection {
+            offset: Quat::IDENTITY,
+            half_life,
+            max_angle,
+        }
+    }
+
+    /// Records that the simulation turned from unit quaternion `predicted` to `corrected`,
+    /// keeping the displayed orientation unchanged.
+    pub fn correct(&mut self, predicted: Quat, corrected: Quat) {
+        let offset = (self.offset * predicted * corrected.conjugate()).normalize_or_identity(0.0);
+        let offset = if offset.w < 0.0 { -offset } else { offset };
+        let angle = 2.0 * float::acos(offset.w.min(1.0));
+        self.offset = if angle <= self.max_angle {
+            offset
+        } else {
+            Quat::IDENTITY
+        };
+    }
+
+    /// Decays the offset by `dt` seconds.
+    #[inline]
+    pub fn update(&mut self, dt: Scalar) {
+        self.offset = exp_decay(
+            self.offset,
+            Quat::IDENTITY,
+            decay_from_half_life(self.half_life),
+            dt,
+        );
+    }
+
+    /// Returns the orientation to display an object simulated at `rotation`.
+    #[inline]
+    pub fn apply(&self, rotation: Quat) -> Quat {
+        (self.offset * rotation).normalize_or_identity(0.0)
+    }
+
+    /// Drops any pending error, snapping the display to the simulation.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.offset = Quat::IDENTITY;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn position_errors_accumulate_and_decay() {
+        let mut smoothing = PositionCorrection::new(0.2, 10.0);
+        smoothing.correct(Vec3::new(1.0, 0.0, 0.0), Vec3::ZERO);
+        smoothing.correct(Vec3::new(0.0, 2.0, 0.0), Vec3::ZERO);
+        // The display never jumps: both errors are still pending.
+        assert_eq!(smoothing.offset, Vec3::new(1.0, 2.0, 0.0));
+        for _ in 0..10 {
+            smoothing.update(0.02);
+        }
+        assert!(smoothing.offset.distance(Vec3::new(0.5, 1.0, 0.0)) <= EPS);
+        for _ in 0..100 {
+            smoothing.update(0.1);
+        }
+        assert!(smoothing.offset.length() <= EPS);
+    }
+
+    #[test]
+    fn large_errors_snap() {
+        let mut smoothing = PositionCorrection::new(0.2, 1.0);
+        smoothing.correct(Vec3::new(5.0, 0.0, 0.0), Vec3::ZERO);
+        assert_eq!(smoothing.offset, Vec3::ZERO);
+        smoothing.correct(Vec3::new(0.5, 0.0, 0.0), Vec3::ZERO);
+        smoothing.reset();
+        assert_eq!(smoothing.apply(Vec3::ONE), Vec3::ONE);
+    }
+
+    #[test]
+    #[cfg_attr(
+        feature = "validate",
+        ignore = "`correct` subtracts the NaN, which `validate` asserts on"
+    )]
+    fn nan_errors_snap() {
+        let mut smoothing = PositionCorrection::new(0.2, 1.0);
+        let nan = Vec3 {
+            x: Scalar::NAN,
+            y: 0.0,
+            z: 0.0,
+        };
+        smoothing.correct(nan, Vec3::ZERO);
+        assert_eq!(smoothing.offset, Vec3::ZERO);
+    }
+
+    #[test]
+    fn rotation_errors_keep_the_display_still_then_decay() {
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let predicted = Quat::from_axis_angle(up, 0.5);
+        let corrected = Quat::from_axis_angle(up, 0.3);
+        let mut smoothing = RotationCorrection::new(0.1, 1.0);
+        smoothing.correct(predicted, corrected);
+        assert!(smoothing.apply(corrected).dot(predicted) >= 1.0 - EPS);
+
+        smoothing.update(0.1);
+        let halfway = Quat::from_axis_angle(up, 0.4);
+        assert!(smoothing.apply(corrected).dot(halfway) >= 1.0 - EPS);
+
+        // Across the ±π seam the offset still takes the short way round.
+        let mut seam = RotationCorrection::new(0.1, 1.0);
+        seam.correct(
+            Quat::from_axis_angle(up, 3.1),
+            Quat::from_axis_angle(up, -3.1),
+        );
+        assert!(seam.offset.w > 0.99);
+
+        let mut snap = RotationCorrection::new(0.1, 0.1);
+        snap.correct(predicted, corrected);
+        assert_eq!(snap.offset, Quat::IDENTITY);
+    }
+}
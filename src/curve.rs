@@ -0,0 +1,293 @@
+use crate::{Interpolate, Scalar, float};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// How a [`Curve`] moves from one key to the next.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyInterpolation {
+    /// Holds the key's value until the next key.
+    Constant,
+    /// Blends straight to the next key.
+    #[default]
+    Linear,
+    /// Blends along a Catmull-Rom curve through the neighbouring keys, so the rate of change
+    /// is continuous across the key.
+    Cubic,
+}
+
+/// A value at a point in time on a [`Curve`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe<T> {
+    pub time: Scalar,
+    pub value: T,
+    /// Interpolation from this key to the next one.
+    pub interpolation: KeyInterpolation,
+}
+
+impl<T> Keyframe<T> {
+    #[inline(always)]
+    pub const fn new(time: Scalar, value: T, interpolation: KeyInterpolation) -> Self {
+        Keyframe {
+            time,
+            value,
+            interpolation,
+        }
+    }
+}
+
+/// A value animated by keyframes, such as a door angle or a light's intensity over a day.
+///
+/// Keys are kept sorted by time. Before the first key and after the last one, an open curve
+/// holds the end values; a looped curve repeats with a period from the first key's time to the
+/// last key's, so its last key should usually repeat the first key's value.
+///
+/// Cubic keys are evaluated with the Barry-Goldman pyramid, which only blends pairs of values
+/// with [`Interpolate`], so rotations stay on the sphere and keys may be unevenly spaced.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Curve, KeyInterpolation, Keyframe, Scalar};
+///
+/// // Sunlight over a 24 hour day.
+/// let sunlight: Curve<Scalar> = Curve::new(
+///     vec![
+///         Keyframe::new(0.0, 0.0, KeyInterpolation::Constant),
+///         Keyframe::new(6.0, 0.0, KeyInterpolation::Cubic),
+///         Keyframe::new(12.0, 1.0, KeyInterpolation::Cubic),
+///         Keyframe::new(18.0, 0.0, KeyInterpolation::Constant),
+///         Keyframe::new(24.0, 0.0, KeyInterpolation::Constant),
+///     ],
+///     true,
+/// );
+/// assert_eq!(sunlight.sample(3.0), 0.0);
+/// assert_eq!(sunlight.sample(12.0), 1.0);
+/// assert_eq!(sunlight.sample(24.0 + 12.0), 1.0);
+/// assert!(sunlight.sample(9.0) > 0.4 && sunlight.sample(9.0) < 0.6);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Curve<T> {
+    keys: Vec<Keyframe<T>>,
+    looped: bool,
+}
+
+impl<T: Interpolate> Curve<T> {
+    /// Creates a curve from `keys` in any order.
+    ///
+    /// # Panics
+    /// Panics if `keys` is empty.
+    pub fn new(mut keys: Vec<Keyframe<T>>, looped: bool) -> Self {
+        assert!(!keys.is_empty(), "curve needs at least one key");
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Curve { keys, looped }
+    }
+
+    /// Returns the keys, sorted by time.
+    #[inline]
+    pub fn keys(&self) -> &[Keyframe<T>] {
+        &self.keys
+    }
+
+    /// Returns `true` if the curve repeats.
+    #[inline]
+    pub fn is_looped(&self) -> bool {
+        self.looped
+    }
+
+    /// Adds `key`, after any existing keys at the same time.
+    pub fn insert(&mut self, key: Keyframe<T>) {
+        let index = self.keys.partition_point(|k| k.time <= key.time);
+        self.keys.insert(index, key);
+    }
+
+    /// Returns the time of the first key.
+    #[inline]
+    pub fn start_time(&self) -> Scalar {
+        self.keys[0].time
+    }
+
+    /// Returns the time of the last key.
+    #[inline]
+    pub fn end_time(&self) -> Scalar {
+        self.keys[self.keys.len() - 1].time
+    }
+
+    /// Returns the time from the first key to the last, which is the period of a looped curve.
+    #[inline]
+    pub fn duration(&self) -> Scalar {
+        self.end_time() - self.start_time()
+    }
+
+    /// Returns the value at `time`.
+    pub fn sample(&self, time: Scalar) -> T {
+        let (start, duration) = (self.start_time(), self.duration());
+        let time = if self.looped && duration > 0.0 {
+            start + float::rem_euclid(time - start, duration)
+        } else {
+            time.clamp(start, self.end_time())
+        };
+        let next = self.keys.partition_point(|k| k.time <= time);
+        if next == 0 {
+            return self.keys[0].value;
+        }
+        if next == self.keys.len() {
+            return self.keys[next - 1].value;
+        }
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        match a.interpolation {
+            KeyInterpolation::Constant => a.value,
+            KeyInterpolation::Linear => a.value.interpolate(&b.value, ratio(a.time, b.time, time)),
+            KeyInterpolation::Cubic => {
+                let (before, after) = self.neighbors(next - 1);
+                barry_goldman([before, (a.time, a.value), (b.time, b.value), after], time)
+            }
+        }
+    }
+
+    /// Returns the keys before `index` and after `index + 1` as `(time, value)` pairs, wrapping
+    /// on a looped curve and mirroring the segment at the ends of an open one.
+    fn neighbors(&self, index: usize) -> ((Scalar, T), (Scalar, T)) {
+        let (a, b) = (&self.keys[index], &self.keys[index + 1]);
+        let last = self.keys.len() - 1;
+        // A looped curve's last key stands in for its first, so skip it when wrapping.
+        let wraps = self.looped && last >= 2;
+        let before = if index > 0 {
+            let k = &self.keys[index - 1];
+            (k.time, k.value)
+        } else if wraps {
+            let k = &self.keys[last - 1];
+            (k.time - self.duration(), k.value)
+        } else {
+            (2.0 * a.time - b.time, b.value.interpolate(&a.value, 2.0))
+        };
+        let after = if index + 2 <= last {
+            let k = &self.keys[index + 2];
+            (k.time, k.value)
+        } else if wraps {
+            let k = &self.keys[1];
+            (k.time + self.duration(), k.value)
+        } else {
+            (2.0 * b.time - a.time, a.value.interpolate(&b.value, 2.0))
+        };
+        (before, after)
+    }
+}
+
+/// Evaluates the Catmull-Rom curve through four timed values between the middle two.
+fn barry_goldman<T: Interpolate>(keys: [(Scalar, T); 4], time: Scalar) -> T {
+    let [(t0, p0), (t1, p1), (t2, p2), (t3, p3)] = keys;
+    let blend = |a: T, b: T, ta: Scalar, tb: Scalar| a.interpolate(&b, ratio(ta, tb, time));
+    let a1 = blend(p0, p1, t0, t1);
+    let a2 = blend(p1, p2, t1, t2);
+    let a3 = blend(p2, p3, t2, t3);
+    let b1 = blend(a1, a2, t0, t2);
+    let b2 = blend(a2, a3, t1, t3);
+    blend(b1, b2, t1, t2)
+}
+
+/// Returns where `time` lies between `a` and `b`, or `0` when they coincide.
+#[inline]
+fn ratio(a: Scalar, b: Scalar, time: Scalar) -> Scalar {
+    if b > a { (time - a) / (b - a) } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Quat, Vec3};
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn keys(interpolation: KeyInterpolation) -> Vec<Keyframe<Scalar>> {
+        vec![
+            Keyframe::new(2.0, 10.0, interpolation),
+            Keyframe::new(0.0, 0.0, interpolation),
+            Keyframe::new(3.0, 0.0, interpolation),
+        ]
+    }
+
+    #[test]
+    fn interpolation_modes() {
+        let constant = Curve::new(keys(KeyInterpolation::Constant), false);
+        assert_eq!(constant.keys()[1].time, 2.0);
+        assert_eq!(constant.sample(1.9), 0.0);
+        assert_eq!(constant.sample(2.0), 10.0);
+
+        let linear = Curve::new(keys(KeyInterpolation::Linear), false);
+        assert_eq!(linear.sample(1.0), 5.0);
+        assert_eq!(linear.sample(2.5), 5.0);
+        assert_eq!(linear.sample(-4.0), 0.0);
+        assert_eq!(linear.sample(9.0), 0.0);
+
+        let cubic = Curve::new(keys(KeyInterpolation::Cubic), false);
+        for key in cubic.keys() {
+            assert!((cubic.sample(key.time) - key.value).abs() <= EPS);
+        }
+        // The cubic overshoots the linear blend approaching the peak.
+        assert!(cubic.sample(1.5) > linear.sample(1.5));
+    }
+
+    #[test]
+    fn cubic_slope_is_continuous_across_keys() {
+        let cubic = Curve::new(keys(KeyInterpolation::Cubic), false);
+        let h = 1.0e-3 as Scalar;
+        let left = (cubic.sample(2.0) - cubic.sample(2.0 - h)) / h;
+        let right = (cubic.sample(2.0 + h) - cubic.sample(2.0)) / h;
+        assert!((left - right).abs() <= 0.1);
+    }
+
+    #[test]
+    fn looped_curves_wrap() {
+        let mut curve = Curve::new(
+            vec![
+                Keyframe::new(0.0, 0.0, KeyInterpolation::Cubic),
+                Keyframe::new(4.0, 0.0, KeyInterpolation::Cubic),
+            ],
+            true,
+        );
+        curve.insert(Keyframe::new(2.0, 8.0, KeyInterpolation::Cubic));
+        assert_eq!(curve.duration(), 4.0);
+        assert!((curve.sample(6.0) - 8.0).abs() <= EPS);
+        assert!((curve.sample(-1.0) - curve.sample(3.0)).abs() <= EPS);
+        // Smooth through the seam: the slope is zero where the curve bottoms out.
+        let h = 1.0e-3 as Scalar;
+        assert!((curve.sample(4.0 + h) - curve.sample(4.0 - h)).abs() <= EPS);
+    }
+
+    #[test]
+    fn rotations_stay_unit() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let curve = Curve::new(
+            vec![
+                Keyframe::new(0.0, Quat::IDENTITY, KeyInterpolation::Cubic),
+                Keyframe::new(
+                    1.0,
+                    Quat::from_axis_angle(axis, 1.0),
+                    KeyInterpolation::Cubic,
+                ),
+                Keyframe::new(
+                    3.0,
+                    Quat::from_axis_angle(axis, 0.2),
+                    KeyInterpolation::Cubic,
+                ),
+            ],
+            false,
+        );
+        for i in 0..=30 {
+            let q = curve.sample(i as Scalar * 0.1);
+            assert!((q.length() - 1.0).abs() <= EPS);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one key")]
+    fn new_needs_a_key() {
+        Curve::<Scalar>::new(Vec::new(), false);
+    }
+}
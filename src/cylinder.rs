@@ -0,0 +1,212 @@
+use crate::{Aabb3, Scalar, Sphere, Vec3, float, gjk3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A solid, capped cylinder around an arbitrary axis.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Cylinder, Vec3};
+///
+/// // A 4 unit tall, upright AoE zone with a radius of 3.
+/// let zone = Cylinder::new(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0), 2.0, 3.0);
+/// assert!(zone.contains_point(Vec3::new(2.0, 1.5, 0.0)));
+/// assert!(!zone.contains_point(Vec3::new(0.0, 2.5, 0.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cylinder {
+    /// Center of the cylinder (halfway between the caps).
+    pub center: Vec3,
+    /// Unit direction of the cylinder axis.
+    pub axis: Vec3,
+    /// Half the distance between the caps.
+    pub half_height: Scalar,
+    /// Radius of the caps.
+    pub radius: Scalar,
+}
+
+impl Cylinder {
+    #[inline(always)]
+    pub const fn new(center: Vec3, axis: Vec3, half_height: Scalar, radius: Scalar) -> Self {
+        Cylinder {
+            center,
+            axis,
+            half_height,
+            radius,
+        }
+    }
+
+    /// Returns `true` if `point` is inside or on the surface of the cylinder.
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        let offset = point - self.center;
+        let h = offset.dot(self.axis);
+        if h.abs() > self.half_height {
+            return false;
+        }
+        let radial = offset - self.axis * h;
+        radial.length_squared() <= self.radius * self.radius
+    }
+
+    /// Returns the point inside or on the surface of the cylinder closest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let offset = point - self.center;
+        let h = offset.dot(self.axis);
+        let radial = offset - self.axis * h;
+        let h = h.clamp(-self.half_height, self.half_height);
+        let len_sq = radial.length_squared();
+        let radial = if len_sq > self.radius * self.radius {
+            radial * (self.radius / float::sqrt(len_sq))
+        } else {
+            radial
+        };
+        self.center + self.axis * h + radial
+    }
+
+    /// Returns `true` if the cylinder and `sphere` overlap.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Returns the point of the cylinder furthest along `direction`.
+    pub fn support(&self, direction: Vec3) -> Vec3 {
+        let along = direction.dot(self.axis);
+        let cap = if along >= 0.0 {
+            self.half_height
+        } else {
+            -self.half_height
+        };
+        // Projecting twice keeps the radial direction perpendicular to the axis even when
+        // `direction` is nearly parallel to it and the first projection is mostly rounding.
+        let radial = direction - self.axis * along;
+        let radial = (radial - self.axis * radial.dot(self.axis)).normalize_or_zero(0.0);
+        self.center + self.axis * cap + radial * self.radius
+    }
+
+    /// Returns `true` if the cylinder and `aabb` overlap or are less than `tolerance` apart.
+    pub fn intersects_aabb(&self, aabb: &Aabb3, tolerance: Scalar) -> bool {
+        gjk3::intersects(
+            |d| self.support(d),
+            |d| aabb.support(d),
+            self.center - aabb.center(),
+            tolerance,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pcg32;
+    use crate::sample::{self, Source};
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn upright() -> Cylinder {
+        Cylinder::new(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0), 1.0, 2.0)
+    }
+
+    #[test]
+    fn contains_point_checks_height_and_radius() {
+        let c = upright();
+        assert!(c.contains_point(Vec3::new(2.0, 1.0, 0.0)));
+        assert!(!c.contains_point(Vec3::new(0.0, 1.5, 0.0)));
+        assert!(!c.contains_point(Vec3::new(1.5, 0.0, 1.5)));
+    }
+
+    #[test]
+    fn closest_point_clamps_to_rim() {
+        let c = upright();
+        let p = c.closest_point(Vec3::new(4.0, 3.0, 0.0));
+        assert!(p.distance(Vec3::new(2.0, 1.0, 0.0)) <= EPS);
+        let inside = Vec3::new(0.5, 0.5, 0.5);
+        assert_eq!(c.closest_point(inside), inside);
+    }
+
+    #[test]
+    fn intersects_sphere_uses_surface_distance() {
+        let c = upright();
+        assert!(c.intersects_sphere(&Sphere::new(Vec3::new(3.0, 0.0, 0.0), 1.1)));
+        assert!(!c.intersects_sphere(&Sphere::new(Vec3::new(3.0, 0.0, 0.0), 0.9)));
+    }
+
+    #[test]
+    fn intersects_aabb_detects_overlap_and_separation() {
+        let c = Cylinder::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), 1.0, 1.0);
+        let touching = Aabb3::new(Vec3::new(0.5, 0.5, 0.5), Vec3::new(2.0, 2.0, 2.0));
+        let corner_gap = Aabb3::new(Vec3::new(-0.5, 0.8, 0.8), Vec3::new(0.5, 2.0, 2.0));
+        assert!(c.intersects_aabb(&touching, EPS));
+        assert!(!c.intersects_aabb(&corner_gap, EPS));
+    }
+
+    #[test]
+    fn intersects_aabb_finds_shallow_overlap() {
+        let c = Cylinder::new(
+            Vec3::ZERO,
+            Vec3::new(0.2820892, 0.585622, 0.7599162),
+            1.3719082,
+            0.5491808,
+        );
+        let aabb = Aabb3::new(
+            Vec3::new(0.104, -1.176, -2.441),
+            Vec3::new(2.754, 1.332, -1.114),
+        );
+        let inside_both = Vec3::new(0.104, -0.716, -1.114);
+        assert!(c.contains_point(inside_both) && aabb.contains_point(inside_both));
+        assert!(c.intersects_aabb(&aabb, EPS));
+    }
+
+    #[test]
+    fn intersects_aabb_agrees_with_sampled_points() {
+        let mut rng = Pcg32::from_seed(831);
+        let space = Aabb3::from_center_half_extents(Vec3::ZERO, Vec3::splat(2.5));
+        let (mut hits, mut misses) = (0, 0);
+        for _ in 0..500 {
+            let c = Cylinder::new(
+                Vec3::ZERO,
+                sample::unit_sphere(&mut rng),
+                0.1 + rng.next_unit() * 1.5,
+                0.1 + rng.next_unit(),
+            );
+            let half_extents = Vec3::new(
+                0.05 + rng.next_unit() * 1.5,
+                0.05 + rng.next_unit() * 1.5,
+                0.05 + rng.next_unit() * 1.5,
+            );
+            let aabb =
+                Aabb3::from_center_half_extents(sample::in_aabb3(&mut rng, &space), half_extents);
+            let hit = c.intersects_aabb(&aabb, EPS);
+            for _ in 0..400 {
+                let p = sample::in_aabb3(&mut rng, &aabb);
+                assert!(
+                    !c.contains_point(p) || hit,
+                    "{c:?} and {aabb:?} share {p:?}"
+                );
+            }
+            if hit {
+                hits += 1;
+            } else {
+                misses += 1;
+            }
+        }
+        assert!(hits > 100 && misses > 100, "{hits} {misses}");
+    }
+
+    #[test]
+    fn flat_cylinder_touching_a_box_face() {
+        let top = Aabb3::new(Vec3::new(-2.0, -1.0, -2.0), Vec3::new(2.0, 1.0, 2.0));
+        let disk = |height: Scalar| {
+            Cylinder::new(
+                Vec3::new(0.3, height, -0.2),
+                Vec3::new(0.0, 1.0, 0.0),
+                0.0,
+                1.0,
+            )
+        };
+        assert!(disk(1.0).intersects_aabb(&top, EPS));
+        assert!(disk(1.0 + 0.5 * EPS).intersects_aabb(&top, EPS));
+        assert!(!disk(1.001).intersects_aabb(&top, EPS));
+        assert!(!disk(1.01).intersects_aabb(&top, EPS));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{IVec2, IVec3, Ray2, Ray3, Scalar, float};
+
+/// Amanatides & Woo stepping state shared by the 2D and 3D traversals.
+#[derive(Debug, Clone)]
+struct Dda<const N: usize> {
+    cell: [i32; N],
+    step: [i32; N],
+    t_next: [Scalar; N],
+    t_delta: [Scalar; N],
+    t: Scalar,
+    max_t: Scalar,
+    done: bool,
+}
+
+impl<const N: usize> Dda<N> {
+    fn new(origin: [Scalar; N], direction: [Scalar; N], cell_size: Scalar, max_t: Scalar) -> Self {
+        let mut dda = Dda {
+            cell: [0; N],
+            step: [0; N],
+            t_next: [Scalar::INFINITY; N],
+            t_delta: [Scalar::INFINITY; N],
+            t: 0.0,
+            max_t,
+            done: max_t < 0.0,
+        };
+        for axis in 0..N {
+            let o = origin[axis] / cell_size;
+            let d = direction[axis] / cell_size;
+            let cell = float::floor(o);
+            dda.cell[axis] = cell as i32;
+            // Axes the ray doesn't move along are never crossed.
+            if d > 0.0 {
+                dda.step[axis] = 1;
+                dda.t_next[axis] = (cell + 1.0 - o) / d;
+                dda.t_delta[axis] = 1.0 / d;
+            } else if d < 0.0 {
+                dda.step[axis] = -1;
+                dda.t_next[axis] = (cell - o) / d;
+                dda.t_delta[axis] = -1.0 / d;
+            }
+        }
+        dda
+    }
+
+    fn next(&mut self) -> Option<([i32; N], Scalar)> {
+        if self.done {
+            return None;
+        }
+        let current = (self.cell, self.t);
+        // Ties between axes (passing exactly through a corner) step the lowest axis first.
+        let mut axis = 0;
+        for other in 1..N {
+            if self.t_next[other] < self.t_next[axis] {
+                axis = other;
+            }
+        }
+        let t = self.t_next[axis];
+        if t > self.max_t || !t.is_finite() {
+            self.done = true;
+        } else {
+            self.t = t;
+            self.cell[axis] += self.step[axis];
+            self.t_next[axis] += self.t_delta[axis];
+        }
+        Some(current)
+    }
+}
+
+/// Iterator over the grid cells a [`Ray2`] passes through, in order.
+///
+/// Created by [`Ray2::grid_cells`]. Yields each cell with the ray parameter at which the ray
+/// enters it (`0.0` for the starting cell).
+#[derive(Debug, Clone)]
+pub struct GridTraversal2 {
+    dda: Dda<2>,
+}
+
+impl Iterator for GridTraversal2 {
+    type Item = (IVec2, Scalar);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ([x, y], t) = self.dda.next()?;
+        Some((IVec2::new(x, y), t))
+    }
+}
+
+/// Iterator over the grid cells a [`Ray3`] passes through, in order.
+///
+/// Created by [`Ray3::grid_cells`]. Yields each cell with the ray parameter at which the ray
+/// enters it (`0.0` for the starting cell).
+#[derive(Debug, Clone)]
+pub struct GridTraversal3 {
+    dda: Dda<3>,
+}
+
+impl Iterator for GridTraversal3 {
+    type Item = (IVec3, Scalar);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ([x, y, z], t) = self.dda.next()?;
+        Some((IVec3::new(x, y, z), t))
+    }
+}
+
+impl Ray2 {
+    /// Walks the square grid of `cell_size` along the ray, yielding every cell it passes
+    /// through up to parameter `max_t`.
+    ///
+    /// Cell `(i, j)` spans `[i, i + 1) * cell_size` on each axis. Pass `Scalar::INFINITY` for an
+    /// unbounded walk. When the ray crosses exactly through a corner, only one of the two
+    /// diagonal neighbours is visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{IVec2, Ray2, Vec2};
+    ///
+    /// let ray = Ray2::new(Vec2::new(0.5, 0.5), Vec2::new(1.0, 0.5));
+    /// let cells: Vec<IVec2> = ray.grid_cells(1.0, 2.4).map(|(cell, _)| cell).collect();
+    /// let expected = [(0, 0), (1, 0), (1, 1), (2, 1)].map(|(x, y)| IVec2::new(x, y));
+    /// assert_eq!(cells, expected);
+    /// ```
+    pub fn grid_cells(&self, cell_size: Scalar, max_t: Scalar) -> GridTraversal2 {
+        GridTraversal2 {
+            dda: Dda::new(
+                [self.origin.x, self.origin.y],
+                [self.direction.x, self.direction.y],
+                cell_size,
+                max_t,
+            ),
+        }
+    }
+}
+
+impl Ray3 {
+    /// Walks the cubic grid of `cell_size` along the ray, yielding every voxel it passes
+    /// through up to parameter `max_t`.
+    ///
+    /// See [`Ray2::grid_cells`] for the cell layout and corner handling.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{IVec3, Ray3, Vec3};
+    ///
+    /// let ray = Ray3::new(Vec3::new(0.5, 0.5, 0.5), Vec3::new(0.0, -1.0, 0.0));
+    /// let cells: Vec<IVec3> = ray.grid_cells(1.0, 2.0).map(|(cell, _)| cell).collect();
+    /// assert_eq!(cells, vec![IVec3::new(0, 0, 0), IVec3::new(0, -1, 0), IVec3::new(0, -2, 0)]);
+    /// ```
+    pub fn grid_cells(&self, cell_size: Scalar, max_t: Scalar) -> GridTraversal3 {
+        GridTraversal3 {
+            dda: Dda::new(
+                self.origin.to_array(),
+                self.direction.to_array(),
+                cell_size,
+                max_t,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vec2, Vec3};
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn traversal2_steps_are_edge_neighbours_and_cover_the_path() {
+        let ray = Ray2::new(Vec2::new(-2.3, 4.1), Vec2::new(3.0, -1.7));
+        let cells: Vec<(IVec2, Scalar)> = ray.grid_cells(0.5, 2.0).collect();
+        for pair in cells.windows(2) {
+            let delta = pair[1].0 - pair[0].0;
+            assert_eq!(delta.x.abs() + delta.y.abs(), 1);
+            assert!(pair[1].1 >= pair[0].1);
+        }
+        // Sampled points along the ray all fall in visited cells.
+        for i in 0..=200 {
+            let p = ray.at(2.0 * i as Scalar / 200.0);
+            let cell = IVec2::floor(p / 0.5);
+            assert!(cells.iter().any(|&(c, _)| c == cell), "missed {cell:?}");
+        }
+        assert_eq!(cells.last().unwrap().0, IVec2::floor(ray.at(2.0) / 0.5));
+    }
+
+    #[test]
+    fn traversal3_reports_entry_parameters() {
+        let ray = Ray3::new(Vec3::new(0.25, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+        let cells: Vec<(IVec3, Scalar)> = ray.grid_cells(1.0, 2.0).collect();
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0], (IVec3::new(0, 0, 0), 0.0));
+        assert!((cells[1].1 - 0.75).abs() <= EPS);
+        assert!((cells[2].1 - 1.75).abs() <= EPS);
+
+        // Negative directions floor into negative cells.
+        let down = Ray3::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(-1.0, -1.0, -1.0));
+        let first: Vec<IVec3> = down.grid_cells(1.0, 0.5).map(|(c, _)| c).collect();
+        assert_eq!(first[0], IVec3::ZERO);
+        assert_eq!(*first.last().unwrap(), IVec3::splat(-1));
+    }
+
+    #[test]
+    fn traversal_edge_cases() {
+        let still = Ray3::new(Vec3::new(1.5, 2.5, 3.5), Vec3::ZERO);
+        let cells: Vec<_> = still.grid_cells(1.0, Scalar::INFINITY).collect();
+        assert_eq!(cells, vec![(IVec3::new(1, 2, 3), 0.0)]);
+
+        let ray = Ray2::new(Vec2::ZERO, Vec2::new(1.0, 0.0));
+        assert_eq!(ray.grid_cells(1.0, -1.0).count(), 0);
+        assert_eq!(ray.grid_cells(1.0, Scalar::INFINITY).take(100).count(), 100);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Quat, Scalar, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Position, rotation and their rates of change at a point in time.
+///
+/// `angular_velocity` is in world space, in radians per second around its direction.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MotionState {
+    /// Time in seconds the state was sampled at.
+    pub time: Scalar,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub acceleration: Vec3,
+    pub rotation: Quat,
+    pub angular_velocity: Vec3,
+}
+
+impl MotionState {
+    /// Creates a state moving at constant velocity without rotating.
+    #[inline]
+    pub fn new(time: Scalar, position: Vec3, velocity: Vec3) -> Self {
+        MotionState {
+            time,
+            position,
+            velocity,
+            ..MotionState::default()
+        }
+    }
+
+    /// Predicts the state at `time`, extrapolating by at most `max_extrapolation` seconds.
+    ///
+    /// Times before `self.time` return `self`. Past the limit the prediction freezes, which
+    /// keeps a lost connection from flinging the object away; the returned state's `time`
+    /// is the time actually predicted for.
+    pub fn extrapolate(&self, time: Scalar, max_extrapolation: Scalar) -> MotionState {
+        let dt = (time - self.time).clamp(0.0, max_extrapolation.max(0.0));
+        MotionState {
+            time: self.time + dt,
+            position: self.position + self.velocity * dt + self.acceleration * (0.5 * dt * dt),
+            velocity: self.velocity + self.acceleration * dt,
+            acceleration: self.acceleration,
+            rotation: (Quat::from_scaled_axis(self.angular_velocity * dt) * self.rotation)
+                .normalize_or_identity(0.0),
+            angular_velocity: self.angular_velocity,
+        }
+    }
+}
+
+/// Dead reckoning for a remote object: extrapolates from the last known state and blends
+/// smoothly into each new state instead of snapping.
+///
+/// When a state arrives, the current prediction keeps being extrapolated alongside the new
+/// one and the output slides from the old to the new over `blend_duration` seconds, so a
+/// correction shows up as a short curve rather than a jump.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{DeadReckoning, MotionState, Vec3};
+///
+/// let mut arrow = DeadReckoning::new(
+///     MotionState::new(0.0, Vec3::ZERO, Vec3::new(10.0, 0.0, 0.0)),
+///     1.0,
+///     0.25,
+/// );
+/// assert_eq!(arrow.predict(0.5).position, Vec3::new(5.0, 0.0, 0.0));
+/// // Extrapolation stops a second after the last update.
+/// assert_eq!(arrow.predict(3.0).position, Vec3::new(10.0, 0.0, 0.0));
+///
+/// // A correction arrives: the arrow was actually a bit higher.
+/// let corrected = MotionState::new(0.5, Vec3::new(5.0, 1.0, 0.0), Vec3::new(10.0, 0.0, 0.0));
+/// arrow.receive(corrected, 0.5);
+/// assert_eq!(arrow.predict(0.5).position, Vec3::new(5.0, 0.0, 0.0));
+/// assert_eq!(arrow.predict(0.75).position, Vec3::new(7.5, 1.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeadReckoning {
+    /// The most recent authoritative state.
+    pub state: MotionState,
+    /// Longest time in seconds to extrapolate past `state.time`.
+    pub max_extrapolation: Scalar,
+    /// Seconds to blend from the old prediction to the new one after a state arrives.
+    pub blend_duration: Scalar,
+    /// The prediction at the moment the last state arrived.
+    pub blend_from: MotionState,
+    /// Local time the last state arrived at.
+    pub blend_start: Scalar,
+}
+
+impl DeadReckoning {
+    /// Starts from `state` without blending.
+    #[inline]
+    pub fn new(state: MotionState, max_extrapolation: Scalar, blend_duration: Scalar) -> Self {
+        DeadReckoning {
+            state,
+            max_extrapolation,
+            blend_duration,
+            blend_from: state,
+            blend_start: Scalar::NEG_INFINITY,
+        }
+    }
+
+    /// Accepts a new authoritative `state` received at local time `now`.
+    ///
+    /// States older than the current one are ignored, so out-of-order packets are harmless.
+    pub fn receive(&mut self, state: MotionState, now: Scalar) {
+        if state.time < self.state.time {
+            return;
+        }
+        self.blend_from = self.predict(now);
+        self.blend_start = now;
+        self.state = state;
+    }
+
+    /// Returns the predicted state at `now`.
+    pub fn predict(&self, now: Scalar) -> MotionState {
+        let target = self.state.extrapolate(now, self.max_extrapolation);
+        let elapsed = now - self.blend_start;
+        if elapsed >= self.blend_duration {
+            return target;
+        }
+        let t = (elapsed / self.blend_duration).clamp(0.0, 1.0);
+        let from = self.blend_from.extrapolate(now, self.max_extrapolation);
+        MotionState {
+            position: from.position.lerp(target.position, t),
+            rotation: from.rotation.slerp(target.rotation, t),
+            ..target
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn extrapolates_acceleration_and_spin() {
+        let state = MotionState {
+            time: 1.0,
+            position: Vec3::new(0.0, 10.0, 0.0),
+            velocity: Vec3::new(2.0, 0.0, 0.0),
+            acceleration: Vec3::new(0.0, -10.0, 0.0),
+            rotation: Quat::IDENTITY,
+            angular_velocity: Vec3::new(0.0, 0.0, 1.0),
+        };
+        let later = state.extrapolate(2.0, 5.0);
+        assert_eq!(later.time, 2.0);
+        assert_eq!(later.position, Vec3::new(2.0, 5.0, 0.0));
+        assert_eq!(later.velocity, Vec3::new(2.0, -10.0, 0.0));
+        let spun = Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 1.0);
+        assert!(later.rotation.dot(spun) >= 1.0 - EPS);
+
+        let capped = state.extrapolate(100.0, 0.5);
+        assert_eq!(capped.time, 1.5);
+        assert_eq!(state.extrapolate(0.0, 0.5), state);
+    }
+
+    #[test]
+    fn corrections_blend_in_over_time() {
+        let velocity = Vec3::new(1.0, 0.0, 0.0);
+        let mut remote = DeadReckoning::new(MotionState::new(0.0, Vec3::ZERO, velocity), 1.0, 0.5);
+        remote.receive(
+            MotionState::new(1.0, Vec3::new(1.0, 2.0, 0.0), velocity),
+            1.0,
+        );
+        let mut previous = remote.predict(1.0).position;
+        assert!(previous.distance(Vec3::new(1.0, 0.0, 0.0)) <= EPS);
+        for i in 1..=10 {
+            let now = 1.0 + i as Scalar * 0.05;
+            let position = remote.predict(now).position;
+            assert!(position.y >= previous.y);
+            assert!((position.x - now).abs() <= EPS);
+            previous = position;
+        }
+        assert!((previous.y - 2.0).abs() <= EPS);
+    }
+
+    #[test]
+    fn stale_states_are_ignored() {
+        let mut remote = DeadReckoning::new(
+            MotionState::new(5.0, Vec3::new(5.0, 0.0, 0.0), Vec3::ZERO),
+            1.0,
+            0.2,
+        );
+        let before = remote;
+        remote.receive(MotionState::new(4.0, Vec3::ZERO, Vec3::ZERO), 5.1);
+        assert_eq!(remote, before);
+    }
+}
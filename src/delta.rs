@@ -0,0 +1,263 @@
+use crate::{Quat, Scalar, Vec3, float};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// The change between two positions, quantized to `i16` steps per axis.
+///
+/// With `steps_per_unit` steps per world unit a delta spans ±32767 steps, e.g. ±32 m at
+/// millimeter precision, in 6 bytes. Encoding a sequence with [`PosDelta::encode_path`]
+/// measures each delta from the previous *decoded* position, so rounding errors don't
+/// accumulate along the path.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{PosDelta, Vec3};
+///
+/// let path = [Vec3::new(10.0, 0.0, 5.0), Vec3::new(10.5, 0.0, 5.25), Vec3::new(11.0, 0.1, 5.5)];
+/// let (start, deltas) = PosDelta::encode_path(&path, 1000.0).unwrap();
+/// let decoded = PosDelta::decode_path(start, &deltas, 1000.0);
+/// for (a, b) in path.iter().zip(&decoded) {
+///     assert!(a.distance(*b) < 1.0e-3);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PosDelta {
+    pub x: i16,
+    pub y: i16,
+    pub z: i16,
+}
+
+impl PosDelta {
+    #[inline(always)]
+    pub const fn new(x: i16, y: i16, z: i16) -> Self {
+        PosDelta { x, y, z }
+    }
+
+    /// Quantizes `to - from`, or returns `None` if an axis doesn't fit in an `i16`.
+    pub fn encode(from: Vec3, to: Vec3, steps_per_unit: Scalar) -> Option<Self> {
+        let d = (to - from) * steps_per_unit;
+        Some(PosDelta::new(
+            quantize_i16(d.x)?,
+            quantize_i16(d.y)?,
+            quantize_i16(d.z)?,
+        ))
+    }
+
+    /// Returns `from` moved by this delta.
+    #[inline]
+    pub fn apply(&self, from: Vec3, steps_per_unit: Scalar) -> Vec3 {
+        from + Vec3::new(self.x as Scalar, self.y as Scalar, self.z as Scalar)
+            * (1.0 / steps_per_unit)
+    }
+
+    /// Encodes `positions` as the first position and one delta per following position, or
+    /// returns `None` if the path is empty or any step is out of range.
+    pub fn encode_path(positions: &[Vec3], steps_per_unit: Scalar) -> Option<(Vec3, Vec<Self>)> {
+        let (&start, rest) = positions.split_first()?;
+        let mut current = start;
+        let mut deltas = Vec::with_capacity(rest.len());
+        for &position in rest {
+            let delta = PosDelta::encode(current, position, steps_per_unit)?;
+            current = delta.apply(current, steps_per_unit);
+            deltas.push(delta);
+        }
+        Some((start, deltas))
+    }
+
+    /// Reverses [`PosDelta::encode_path`].
+    pub fn decode_path(start: Vec3, deltas: &[Self], steps_per_unit: Scalar) -> Vec<Vec3> {
+        let mut current = start;
+        let mut positions = Vec::with_capacity(deltas.len() + 1);
+        positions.push(start);
+        for delta in deltas {
+            current = delta.apply(current, steps_per_unit);
+            positions.push(current);
+        }
+        positions
+    }
+}
+
+/// The rotation between two orientations, quantized to three `i16`s.
+///
+/// Stores the relative rotation `to * from⁻¹` as modified Rodrigues parameters, the vector
+/// part divided by `1 + w`, after flipping it into the hemisphere with a non-negative real
+/// part so it always takes the short way round. Each parameter is `tan(angle / 4)` times an
+/// axis component, so any rotation fits in `[-1, 1]`, and unlike recovering the real part
+/// from unit length the decoding stays accurate near half turns: the error is at most about
+/// 1e-4 radians, and half that for small turns.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Quat, RotDelta, Vec3};
+///
+/// let up = Vec3::new(0.0, 1.0, 0.0);
+/// let from = Quat::from_axis_angle(up, 0.5);
+/// let to = Quat::from_axis_angle(up, 0.75);
+/// let turned = RotDelta::encode(from, to).apply(from);
+/// assert!(turned.dot(to).abs() > 1.0 - 1.0e-6);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotDelta {
+    pub x: i16,
+    pub y: i16,
+    pub z: i16,
+}
+
+impl RotDelta {
+    #[inline(always)]
+    pub const fn new(x: i16, y: i16, z: i16) -> Self {
+        RotDelta { x, y, z }
+    }
+
+    /// Quantizes the rotation from unit quaternion `from` to unit quaternion `to`.
+    pub fn encode(from: Quat, to: Quat) -> Self {
+        let d = to * from.conjugate();
+        let d = if d.w < 0.0 { -d } else { d };
+        let inv = 1.0 / (1.0 + d.w);
+        let q = |v: Scalar| float::round((v * inv).clamp(-1.0, 1.0) * UNIT_STEPS) as i16;
+        RotDelta::new(q(d.x), q(d.y), q(d.z))
+    }
+
+    /// Returns the relative rotation this delta stores.
+    pub fn to_quat(&self) -> Quat {
+        let (x, y, z) = (
+            self.x as Scalar / UNIT_STEPS,
+            self.y as Scalar / UNIT_STEPS,
+            self.z as Scalar / UNIT_STEPS,
+        );
+        let s = x * x + y * y + z * z;
+        let inv = 1.0 / (1.0 + s);
+        Quat::new(2.0 * x * inv, 2.0 * y * inv, 2.0 * z * inv, (1.0 - s) * inv)
+            .normalize_or_identity(0.0)
+    }
+
+    /// Returns `from` rotated by this delta.
+    #[inline]
+    pub fn apply(&self, from: Quat) -> Quat {
+        (self.to_quat() * from).normalize_or_identity(0.0)
+    }
+
+    /// Encodes `rotations` as the first rotation and one delta per following rotation,
+    /// measuring each delta from the previous decoded rotation so errors don't accumulate.
+    /// Returns `None` if `rotations` is empty.
+    pub fn encode_path(rotations: &[Quat]) -> Option<(Quat, Vec<Self>)> {
+        let (&start, rest) = rotations.split_first()?;
+        let mut current = start;
+        let mut deltas = Vec::with_capacity(rest.len());
+        for &rotation in rest {
+            let delta = RotDelta::encode(current, rotation);
+            current = delta.apply(current);
+            deltas.push(delta);
+        }
+        Some((start, deltas))
+    }
+
+    /// Reverses [`RotDelta::encode_path`].
+    pub fn decode_path(start: Quat, deltas: &[Self]) -> Vec<Quat> {
+        let mut current = start;
+        let mut rotations = Vec::with_capacity(deltas.len() + 1);
+        rotations.push(start);
+        for delta in deltas {
+            current = delta.apply(current);
+            rotations.push(current);
+        }
+        rotations
+    }
+}
+
+/// Steps per unit for quaternion components in `[-1, 1]`.
+const UNIT_STEPS: Scalar = i16::MAX as Scalar;
+
+#[inline]
+fn quantize_i16(value: Scalar) -> Option<i16> {
+    let rounded = float::round(value);
+    (rounded >= i16::MIN as Scalar && rounded <= i16::MAX as Scalar).then_some(rounded as i16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_deltas_round_and_reject_overflow() {
+        let from = Vec3::new(1.0, 2.0, 3.0);
+        let delta = PosDelta::encode(from, Vec3::new(1.0104, 1.9996, 3.0), 100.0).unwrap();
+        assert_eq!(delta, PosDelta::new(1, 0, 0));
+        assert_eq!(delta.apply(from, 100.0), Vec3::new(1.01, 2.0, 3.0));
+        assert_eq!(
+            PosDelta::encode(from, Vec3::new(40.0, 2.0, 3.0), 1000.0),
+            None
+        );
+        assert_eq!(PosDelta::encode_path(&[], 1000.0), None);
+    }
+
+    #[test]
+    fn paths_do_not_drift() {
+        // Every step rounds by 0.4 mm; encoding against the decoded position keeps the total
+        // error under one step instead of growing with the path length.
+        let positions: Vec<Vec3> = (0..1000)
+            .map(|i| Vec3::new(i as Scalar * 0.0104, 0.0, -(i as Scalar) * 0.0021))
+            .collect();
+        let (start, deltas) = PosDelta::encode_path(&positions, 1000.0).unwrap();
+        let decoded = PosDelta::decode_path(start, &deltas, 1000.0);
+        assert_eq!(decoded.len(), positions.len());
+        for (a, b) in positions.iter().zip(&decoded) {
+            assert!(a.distance(*b) <= 1.0e-3 as Scalar);
+        }
+    }
+
+    #[test]
+    fn rotation_deltas_take_the_short_way_round() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let from = Quat::from_axis_angle(axis, 3.0);
+        // Crossing the -180°/180° seam flips the quaternion's sign; the delta stays small.
+        let to = Quat::from_axis_angle(axis, 3.3);
+        let delta = RotDelta::encode(from, to);
+        assert!(delta.to_quat().w > 0.98);
+        assert!(delta.apply(from).dot(to).abs() >= 1.0 - 1.0e-6 as Scalar);
+        assert_eq!(RotDelta::encode(to, to), RotDelta::default());
+    }
+
+    #[test]
+    fn rotation_paths_do_not_drift() {
+        let axis = Vec3::new(1.0, 2.0, 2.0) * (1.0 / 3.0);
+        let rotations: Vec<Quat> = (0..500)
+            .map(|i| Quat::from_axis_angle(axis, i as Scalar * 0.0123))
+            .collect();
+        let (start, deltas) = RotDelta::encode_path(&rotations).unwrap();
+        let decoded = RotDelta::decode_path(start, &deltas);
+        for (a, b) in rotations.iter().zip(&decoded) {
+            assert!(a.dot(*b).abs() >= 1.0 - 1.0e-6 as Scalar);
+        }
+    }
+
+    #[test]
+    fn rotation_error_stays_bounded_near_half_turns() {
+        use crate::consts;
+        use crate::sample::Source;
+
+        let mut rng = crate::Pcg32::from_seed(885);
+        let mut worst: Scalar = 0.0;
+        for i in 0..2000 {
+            let axis = crate::sample::unit_sphere(&mut rng);
+            let angle = if i == 0 {
+                consts::PI
+            } else {
+                3.0 + (consts::PI - 3.0) * rng.next_unit()
+            };
+            let from = Quat::random_uniform(&mut rng);
+            let to = Quat::from_axis_angle(axis, angle) * from;
+            // The vector part of the residual is sin(error / 2), which stays precise for
+            // small errors where an acos of the dot product wouldn't.
+            let residual = RotDelta::encode(from, to).apply(from) * to.conjugate();
+            let error = 2.0 * Vec3::new(residual.x, residual.y, residual.z).length();
+            worst = worst.max(error);
+        }
+        assert!(worst <= 1.2e-4 as Scalar, "{worst}");
+    }
+}
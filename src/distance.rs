@@ -0,0 +1,154 @@
+//! Distance queries between points and primitives.
+//!
+//! Every query has a `_squared` variant that skips the square root, which is all that is
+//! needed to compare distances against a threshold.
+
+use crate::segment::closest_parameters;
+use crate::{Plane, Ray3, Scalar, Segment3, Vec3, float};
+
+/// Returns the squared distance from `point` to the infinite line through `origin` along
+/// `direction`.
+///
+/// A zero `direction` degenerates to the distance to `origin`.
+pub fn point_line_squared(point: Vec3, origin: Vec3, direction: Vec3) -> Scalar {
+    let offset = point - origin;
+    let len_sq = direction.length_squared();
+    if len_sq <= 0.0 {
+        return offset.length_squared();
+    }
+    let along = offset.dot(direction);
+    (offset.length_squared() - along * along / len_sq).max(0.0)
+}
+
+/// Returns the distance from `point` to the infinite line through `origin` along `direction`.
+#[inline]
+pub fn point_line(point: Vec3, origin: Vec3, direction: Vec3) -> Scalar {
+    float::sqrt(point_line_squared(point, origin, direction))
+}
+
+/// Returns the squared distance from `point` to `plane`.
+///
+/// The plane's normal does not need to be unit length, but must not be zero.
+#[inline]
+pub fn point_plane_squared(point: Vec3, plane: &Plane) -> Scalar {
+    let distance = plane.signed_distance(point);
+    distance * distance / plane.normal.length_squared()
+}
+
+/// Returns the distance from `point` to `plane`.
+///
+/// Use [`Plane::signed_distance`] on a normalized plane to also learn which side the point is on.
+#[inline]
+pub fn point_plane(point: Vec3, plane: &Plane) -> Scalar {
+    float::sqrt(point_plane_squared(point, plane))
+}
+
+/// Returns the squared distance from `point` to `ray`.
+pub fn point_ray_squared(point: Vec3, ray: &Ray3) -> Scalar {
+    let offset = point - ray.origin;
+    let along = offset.dot(ray.direction);
+    if along <= 0.0 {
+        return offset.length_squared();
+    }
+    (offset.length_squared() - along * along / ray.direction.length_squared()).max(0.0)
+}
+
+/// Returns the distance from `point` to `ray`.
+#[inline]
+pub fn point_ray(point: Vec3, ray: &Ray3) -> Scalar {
+    float::sqrt(point_ray_squared(point, ray))
+}
+
+/// Returns the squared distance from `point` to `segment`.
+#[inline]
+pub fn point_segment_squared(point: Vec3, segment: &Segment3) -> Scalar {
+    segment.closest_point(point).distance_squared(point)
+}
+
+/// Returns the distance from `point` to `segment`.
+#[inline]
+pub fn point_segment(point: Vec3, segment: &Segment3) -> Scalar {
+    float::sqrt(point_segment_squared(point, segment))
+}
+
+/// Returns the squared distance between two segments.
+#[inline]
+pub fn segment_segment_squared(a: &Segment3, b: &Segment3) -> Scalar {
+    let (on_a, on_b) = a.closest_points(b);
+    on_a.distance_squared(on_b)
+}
+
+/// Returns the distance between two segments.
+#[inline]
+pub fn segment_segment(a: &Segment3, b: &Segment3) -> Scalar {
+    float::sqrt(segment_segment_squared(a, b))
+}
+
+/// Returns the squared distance between `ray` and `segment`.
+pub fn ray_segment_squared(ray: &Ray3, segment: &Segment3) -> Scalar {
+    let (s, t) = closest_parameters(
+        ray.origin,
+        ray.direction,
+        Scalar::INFINITY,
+        segment.start,
+        segment.end - segment.start,
+    );
+    ray.at(s).distance_squared(segment.at(t))
+}
+
+/// Returns the distance between `ray` and `segment`.
+#[inline]
+pub fn ray_segment(ray: &Ray3, segment: &Segment3) -> Scalar {
+    float::sqrt(ray_segment_squared(ray, segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn point_line_ignores_extent() {
+        let origin = Vec3::ZERO;
+        let direction = Vec3::new(2.0, 0.0, 0.0);
+        assert_eq!(
+            point_line_squared(Vec3::new(-5.0, 3.0, 4.0), origin, direction),
+            25.0 as Scalar
+        );
+        assert_eq!(
+            point_line(Vec3::new(1.0, 2.0, 0.0), origin, Vec3::ZERO),
+            (5.0 as Scalar).sqrt()
+        );
+    }
+
+    #[test]
+    fn point_plane_and_ray() {
+        let plane = Plane::from_point_normal(Vec3::ZERO, Vec3::new(0.0, 0.0, 2.0));
+        assert_eq!(
+            point_plane(Vec3::new(1.0, 1.0, -3.0), &plane),
+            3.0 as Scalar
+        );
+        let ray = Ray3::new(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0));
+        // Behind the origin the ray acts like a point.
+        assert_eq!(
+            point_ray_squared(Vec3::new(3.0, -4.0, 0.0), &ray),
+            25.0 as Scalar
+        );
+        assert_eq!(point_ray(Vec3::new(3.0, 4.0, 0.0), &ray), 3.0 as Scalar);
+    }
+
+    #[test]
+    fn segment_and_ray_segment() {
+        let a = Segment3::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0));
+        let b = Segment3::new(Vec3::new(3.0, 1.0, 0.0), Vec3::new(3.0, 2.0, 0.0));
+        assert_eq!(segment_segment_squared(&a, &b), 5.0 as Scalar);
+        assert_eq!(point_segment(Vec3::new(0.5, 0.0, 2.0), &a), 2.0 as Scalar);
+
+        // The same segment is close to a ray that keeps going past `a`'s end.
+        let ray = Ray3::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0));
+        assert!((ray_segment(&ray, &b) - 1.0 as Scalar).abs() <= EPS);
+        let backwards = Ray3::new(Vec3::ZERO, Vec3::new(-1.0, 0.0, 0.0));
+        assert!((ray_segment_squared(&backwards, &b) - 10.0 as Scalar).abs() <= EPS);
+    }
+}
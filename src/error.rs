@@ -0,0 +1,55 @@
+//! The error type of the crate's fallible `checked_` operations.
+
+use crate::Scalar;
+use core::fmt;
+
+/// Why a `checked_` operation couldn't produce a meaningful result.
+///
+/// The unchecked versions of these operations clamp, fall back to a default or return NaN;
+/// the checked ones report the problem instead, so a reducer can reject the request and tell
+/// the client exactly what was wrong with it.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{MathError, Vec3, float};
+///
+/// assert_eq!(Vec3::ZERO.checked_normalize(1.0e-6), Err(MathError::ZeroLength));
+/// assert_eq!(
+///     float::checked_acos(1.5),
+///     Err(MathError::OutOfDomain { value: 1.5 })
+/// );
+/// let reason = Vec3::ZERO.checked_normalize(1.0e-6).unwrap_err().to_string();
+/// assert_eq!(reason, "vector has zero length");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MathError {
+    /// A vector or quaternion was too short to have a direction.
+    ZeroLength,
+    /// An input was NaN or infinite.
+    NotFinite,
+    /// A quaternion or axis that must have unit length didn't.
+    NotNormalized {
+        /// The actual length.
+        length: Scalar,
+    },
+    /// An input was outside the function's domain, such as `acos(2.0)` or `sqrt(-1.0)`.
+    OutOfDomain {
+        /// The offending input.
+        value: Scalar,
+    },
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::ZeroLength => f.write_str("vector has zero length"),
+            MathError::NotFinite => f.write_str("value is NaN or infinite"),
+            MathError::NotNormalized { length } => {
+                write!(f, "value is not normalized (length {length})")
+            }
+            MathError::OutOfDomain { value } => write!(f, "{value} is outside the domain"),
+        }
+    }
+}
+
+impl core::error::Error for MathError {}
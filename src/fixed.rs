@@ -0,0 +1,625 @@
+use crate::{Quat, Scalar, Vec2, Vec3, float};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+const FRAC_BITS: u32 = 32;
+
+/// A Q32.32 fixed-point number: a signed 64-bit integer counting units of `2^-32`.
+///
+/// Every operation is plain integer arithmetic, so results are the same on every platform
+/// and in every build, which lockstep simulations need and floats can't promise. The range
+/// is about ±2.1 billion with a resolution of about 2.3e-10.
+///
+/// Addition, subtraction and multiplication wrap on overflow instead of panicking, so debug
+/// and release builds agree. Products and quotients round towards negative infinity.
+///
+/// The rest of the crate keeps computing in [`Scalar`]; convert at the boundary with
+/// [`Fixed::from_scalar`] and [`Fixed::to_scalar`], or the `From` impls on the vector types.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::Fixed;
+///
+/// let two = Fixed::from_int(2);
+/// assert_eq!(Fixed::from_int(9).sqrt(), Fixed::from_int(3));
+/// assert_eq!((Fixed::from_int(7) / two).to_scalar(), 3.5);
+/// assert_eq!(Fixed::from_scalar(0.75) * two, Fixed::from_scalar(1.5));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixed {
+    /// The raw value in units of `2^-32`.
+    pub bits: i64,
+}
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed::from_bits(0);
+    pub const ONE: Fixed = Fixed::from_bits(1 << FRAC_BITS);
+    pub const MIN: Fixed = Fixed::from_bits(i64::MIN);
+    pub const MAX: Fixed = Fixed::from_bits(i64::MAX);
+    /// The smallest positive value, `2^-32`.
+    pub const EPSILON: Fixed = Fixed::from_bits(1);
+    pub const PI: Fixed = Fixed::from_bits(13_493_037_705);
+    pub const FRAC_PI_2: Fixed = Fixed::from_bits(6_746_518_852);
+    pub const TAU: Fixed = Fixed::from_bits(26_986_075_409);
+
+    #[inline(always)]
+    pub const fn from_bits(bits: i64) -> Self {
+        Fixed { bits }
+    }
+
+    #[inline(always)]
+    pub const fn from_int(n: i32) -> Self {
+        Fixed::from_bits((n as i64) << FRAC_BITS)
+    }
+
+    /// Converts from a float, rounding to the nearest representable value.
+    ///
+    /// Out-of-range values saturate and NaN becomes zero.
+    #[inline]
+    pub fn from_scalar(v: Scalar) -> Self {
+        const SCALE: Scalar = (1u64 << FRAC_BITS) as _;
+        Fixed::from_bits(float::round(v * SCALE) as i64)
+    }
+
+    #[inline]
+    pub fn to_scalar(self) -> Scalar {
+        const SCALE: Scalar = (1u64 << FRAC_BITS) as _;
+        self.bits as Scalar / SCALE
+    }
+
+    /// Returns the integer part, rounded towards negative infinity.
+    #[inline]
+    pub const fn floor_to_int(self) -> i64 {
+        self.bits >> FRAC_BITS
+    }
+
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Fixed::from_bits(self.bits.wrapping_abs())
+    }
+
+    #[inline]
+    pub fn min(self, other: Fixed) -> Self {
+        Ord::min(self, other)
+    }
+
+    #[inline]
+    pub fn max(self, other: Fixed) -> Self {
+        Ord::max(self, other)
+    }
+
+    #[inline]
+    pub fn clamp(self, min: Fixed, max: Fixed) -> Self {
+        Ord::clamp(self, min, max)
+    }
+
+    /// Returns the square root, rounded down, or zero for negative values.
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        if self.bits <= 0 {
+            return Fixed::ZERO;
+        }
+        Fixed::from_bits(((self.bits as u128) << FRAC_BITS).isqrt() as i64)
+    }
+
+    /// Returns the sine of an angle in radians.
+    #[inline]
+    pub fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    /// Returns the cosine of an angle in radians.
+    #[inline]
+    pub fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    /// Returns the sine and cosine of an angle in radians, accurate to a few units of
+    /// [`Fixed::EPSILON`] for angles within a few turns of zero.
+    pub fn sin_cos(self) -> (Fixed, Fixed) {
+        // Reduce to r in [-pi/4, pi/4] plus a quadrant, where short Taylor series converge
+        // well below the fixed-point resolution.
+        let half_pi = Fixed::FRAC_PI_2.bits as i128;
+        let quotient = ((self.bits as i128) << FRAC_BITS) / half_pi;
+        let quadrant = (quotient + (1 << (FRAC_BITS - 1))) >> FRAC_BITS;
+        let r = Fixed::from_bits((self.bits as i128 - quadrant * half_pi) as i64);
+        let r2 = r * r;
+        let term =
+            |acc: Fixed, divisor: i64| Fixed::ONE - Fixed::from_bits((r2 * acc).bits / divisor);
+        let sin = r * [110, 72, 42, 20, 6].into_iter().fold(Fixed::ONE, term);
+        let cos = [132, 90, 56, 30, 12, 2].into_iter().fold(Fixed::ONE, term);
+        match quadrant.rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+}
+
+impl From<i32> for Fixed {
+    #[inline]
+    fn from(n: i32) -> Self {
+        Fixed::from_int(n)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn add(self, rhs: Fixed) -> Fixed {
+        Fixed::from_bits(self.bits.wrapping_add(rhs.bits))
+    }
+}
+
+impl AddAssign for Fixed {
+    #[inline]
+    fn add_assign(&mut self, rhs: Fixed) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn sub(self, rhs: Fixed) -> Fixed {
+        Fixed::from_bits(self.bits.wrapping_sub(rhs.bits))
+    }
+}
+
+impl SubAssign for Fixed {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Fixed) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn mul(self, rhs: Fixed) -> Fixed {
+        Fixed::from_bits(((self.bits as i128 * rhs.bits as i128) >> FRAC_BITS) as i64)
+    }
+}
+
+impl MulAssign for Fixed {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Fixed) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    /// # Panics
+    /// Panics if `rhs` is zero.
+    #[inline]
+    fn div(self, rhs: Fixed) -> Fixed {
+        let (a, b) = ((self.bits as i128) << FRAC_BITS, rhs.bits as i128);
+        let quotient = a / b;
+        // Integer division truncates; step down for inexact negative quotients.
+        let floor = if a % b != 0 && (a < 0) != (b < 0) {
+            quotient - 1
+        } else {
+            quotient
+        };
+        Fixed::from_bits(floor as i64)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn neg(self) -> Fixed {
+        Fixed::from_bits(self.bits.wrapping_neg())
+    }
+}
+
+/// A 2D vector of [`Fixed`] components.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedVec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    pub const ZERO: FixedVec2 = FixedVec2::new(Fixed::ZERO, Fixed::ZERO);
+
+    #[inline(always)]
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        FixedVec2 { x, y }
+    }
+
+    #[inline]
+    pub fn dot(&self, other: FixedVec2) -> Fixed {
+        self.x * other.x + self.y * other.y
+    }
+
+    #[inline]
+    pub fn length_squared(&self) -> Fixed {
+        self.dot(*self)
+    }
+
+    #[inline]
+    pub fn length(&self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    /// Returns the unit vector in the same direction, or zero for a zero vector.
+    #[inline]
+    pub fn normalize_or_zero(&self) -> FixedVec2 {
+        let len = self.length();
+        if len == Fixed::ZERO {
+            FixedVec2::ZERO
+        } else {
+            FixedVec2::new(self.x / len, self.y / len)
+        }
+    }
+
+    #[inline]
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.x.to_scalar(), self.y.to_scalar())
+    }
+}
+
+impl From<Vec2> for FixedVec2 {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        FixedVec2::new(Fixed::from_scalar(v.x), Fixed::from_scalar(v.y))
+    }
+}
+
+impl From<FixedVec2> for Vec2 {
+    #[inline]
+    fn from(v: FixedVec2) -> Self {
+        v.to_vec2()
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = FixedVec2;
+
+    #[inline]
+    fn add(self, rhs: FixedVec2) -> FixedVec2 {
+        FixedVec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = FixedVec2;
+
+    #[inline]
+    fn sub(self, rhs: FixedVec2) -> FixedVec2 {
+        FixedVec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = FixedVec2;
+
+    #[inline]
+    fn mul(self, rhs: Fixed) -> FixedVec2 {
+        FixedVec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for FixedVec2 {
+    type Output = FixedVec2;
+
+    #[inline]
+    fn neg(self) -> FixedVec2 {
+        FixedVec2::new(-self.x, -self.y)
+    }
+}
+
+/// A 3D vector of [`Fixed`] components.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Fixed, FixedVec3, Vec3};
+///
+/// let v = FixedVec3::from(Vec3::new(3.0, 0.0, 4.0));
+/// assert_eq!(v.length(), Fixed::from_int(5));
+/// assert_eq!(Vec3::from(v * Fixed::from_int(2)), Vec3::new(6.0, 0.0, 8.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedVec3 {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub z: Fixed,
+}
+
+impl FixedVec3 {
+    pub const ZERO: FixedVec3 = FixedVec3::new(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO);
+
+    #[inline(always)]
+    pub const fn new(x: Fixed, y: Fixed, z: Fixed) -> Self {
+        FixedVec3 { x, y, z }
+    }
+
+    #[inline]
+    pub fn dot(&self, other: FixedVec3) -> Fixed {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    #[inline]
+    pub fn cross(&self, other: FixedVec3) -> FixedVec3 {
+        FixedVec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    #[inline]
+    pub fn length_squared(&self) -> Fixed {
+        self.dot(*self)
+    }
+
+    #[inline]
+    pub fn length(&self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    /// Returns the unit vector in the same direction, or zero for a zero vector.
+    #[inline]
+    pub fn normalize_or_zero(&self) -> FixedVec3 {
+        let len = self.length();
+        if len == Fixed::ZERO {
+            FixedVec3::ZERO
+        } else {
+            FixedVec3::new(self.x / len, self.y / len, self.z / len)
+        }
+    }
+
+    #[inline]
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(self.x.to_scalar(), self.y.to_scalar(), self.z.to_scalar())
+    }
+}
+
+impl From<Vec3> for FixedVec3 {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        FixedVec3::new(
+            Fixed::from_scalar(v.x),
+            Fixed::from_scalar(v.y),
+            Fixed::from_scalar(v.z),
+        )
+    }
+}
+
+impl From<FixedVec3> for Vec3 {
+    #[inline]
+    fn from(v: FixedVec3) -> Self {
+        v.to_vec3()
+    }
+}
+
+impl Add for FixedVec3 {
+    type Output = FixedVec3;
+
+    #[inline]
+    fn add(self, rhs: FixedVec3) -> FixedVec3 {
+        FixedVec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for FixedVec3 {
+    type Output = FixedVec3;
+
+    #[inline]
+    fn sub(self, rhs: FixedVec3) -> FixedVec3 {
+        FixedVec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<Fixed> for FixedVec3 {
+    type Output = FixedVec3;
+
+    #[inline]
+    fn mul(self, rhs: Fixed) -> FixedVec3 {
+        FixedVec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for FixedVec3 {
+    type Output = FixedVec3;
+
+    #[inline]
+    fn neg(self) -> FixedVec3 {
+        FixedVec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// A rotation quaternion of [`Fixed`] components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedQuat {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub z: Fixed,
+    pub w: Fixed,
+}
+
+impl Default for FixedQuat {
+    #[inline]
+    fn default() -> Self {
+        FixedQuat::IDENTITY
+    }
+}
+
+impl FixedQuat {
+    pub const IDENTITY: FixedQuat =
+        FixedQuat::new(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO, Fixed::ONE);
+
+    #[inline(always)]
+    pub const fn new(x: Fixed, y: Fixed, z: Fixed, w: Fixed) -> Self {
+        FixedQuat { x, y, z, w }
+    }
+
+    /// Creates a rotation of `angle` radians around the unit vector `axis`.
+    #[inline]
+    pub fn from_axis_angle(axis: FixedVec3, angle: Fixed) -> Self {
+        let (s, c) = Fixed::from_bits(angle.bits >> 1).sin_cos();
+        FixedQuat::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+
+    #[inline]
+    pub fn conjugate(&self) -> FixedQuat {
+        FixedQuat::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    #[inline]
+    pub fn dot(&self, other: FixedQuat) -> Fixed {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Returns the quaternion scaled to unit length, or the identity if it has zero length.
+    ///
+    /// Repeatedly composed rotations drift by a few units of [`Fixed::EPSILON`] per step;
+    /// renormalize periodically.
+    #[inline]
+    pub fn normalize_or_identity(&self) -> FixedQuat {
+        let len = self.dot(*self).sqrt();
+        if len == Fixed::ZERO {
+            return FixedQuat::IDENTITY;
+        }
+        FixedQuat::new(self.x / len, self.y / len, self.z / len, self.w / len)
+    }
+
+    /// Rotates the vector `v`.
+    #[inline]
+    pub fn mul_vec3(&self, v: FixedVec3) -> FixedVec3 {
+        let u = FixedVec3::new(self.x, self.y, self.z);
+        let t = u.cross(v) * Fixed::from_int(2);
+        v + t * self.w + u.cross(t)
+    }
+
+    #[inline]
+    pub fn to_quat(&self) -> Quat {
+        Quat::new(
+            self.x.to_scalar(),
+            self.y.to_scalar(),
+            self.z.to_scalar(),
+            self.w.to_scalar(),
+        )
+    }
+}
+
+impl From<Quat> for FixedQuat {
+    #[inline]
+    fn from(q: Quat) -> Self {
+        FixedQuat::new(
+            Fixed::from_scalar(q.x),
+            Fixed::from_scalar(q.y),
+            Fixed::from_scalar(q.z),
+            Fixed::from_scalar(q.w),
+        )
+    }
+}
+
+impl From<FixedQuat> for Quat {
+    #[inline]
+    fn from(q: FixedQuat) -> Self {
+        q.to_quat()
+    }
+}
+
+impl Mul for FixedQuat {
+    type Output = FixedQuat;
+
+    /// Composes two rotations: `a * b` applies `b` first.
+    #[inline]
+    fn mul(self, rhs: FixedQuat) -> FixedQuat {
+        let (a, b) = (self, rhs);
+        FixedQuat::new(
+            a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+            a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Fixed, b: Fixed, ulps: i64) -> bool {
+        (a.bits - b.bits).abs() <= ulps
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        let a = Fixed::from_scalar(1.5);
+        let b = Fixed::from_int(-3);
+        assert_eq!(a + b, Fixed::from_scalar(-1.5));
+        assert_eq!(a * b, Fixed::from_scalar(-4.5));
+        assert_eq!(b / a, Fixed::from_int(-2));
+        assert_eq!((-a).floor_to_int(), -2);
+        assert_eq!(Fixed::MAX + Fixed::EPSILON, Fixed::MIN);
+        assert_eq!(Fixed::from_scalar(Scalar::NAN), Fixed::ZERO);
+        // One third rounds down, so three thirds fall one unit short of one.
+        let third = Fixed::ONE / Fixed::from_int(3);
+        assert_eq!(third * Fixed::from_int(3), Fixed::ONE - Fixed::EPSILON);
+    }
+
+    #[test]
+    fn sqrt_is_exact_for_squares() {
+        assert_eq!(Fixed::from_int(144).sqrt(), Fixed::from_int(12));
+        assert_eq!(Fixed::from_scalar(0.25).sqrt(), Fixed::from_scalar(0.5));
+        assert_eq!(Fixed::from_int(-4).sqrt(), Fixed::ZERO);
+        let root2 = Fixed::from_int(2).sqrt();
+        assert!(close(root2, Fixed::from_scalar(Scalar::sqrt(2.0)), 1 << 10));
+    }
+
+    #[test]
+    fn sin_cos_match_floats() {
+        for i in -40..=40 {
+            let angle = i as Scalar * 0.37;
+            let (s, c) = Fixed::from_scalar(angle).sin_cos();
+            assert!(
+                (s.to_scalar() - angle.sin()).abs() <= 1.0e-5 as Scalar,
+                "{angle}"
+            );
+            assert!(
+                (c.to_scalar() - angle.cos()).abs() <= 1.0e-5 as Scalar,
+                "{angle}"
+            );
+        }
+        assert_eq!(Fixed::ZERO.sin_cos(), (Fixed::ZERO, Fixed::ONE));
+        assert!(close(Fixed::FRAC_PI_2.sin(), Fixed::ONE, 4));
+        assert!(close(Fixed::PI.cos(), -Fixed::ONE, 4));
+    }
+
+    #[test]
+    fn rotations_agree_with_float_quats() {
+        let axis = FixedVec3::from(Vec3::new(0.0, 1.0, 0.0));
+        let q = FixedQuat::from_axis_angle(axis, Fixed::FRAC_PI_2);
+        let v = q.mul_vec3(FixedVec3::from(Vec3::new(1.0, 0.0, 0.0)));
+        assert!(v.to_vec3().distance(Vec3::new(0.0, 0.0, -1.0)) <= 1.0e-6 as Scalar);
+
+        let twice = (q * q).normalize_or_identity();
+        let expected = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), core::f64::consts::PI as _);
+        assert!((twice.to_quat().dot(expected).abs() - 1.0).abs() <= 1.0e-6 as Scalar);
+        assert_eq!(q * q.conjugate() * FixedQuat::IDENTITY, (q * q.conjugate()));
+        assert_eq!(
+            FixedVec2::from(Vec2::new(0.0, 2.0)).normalize_or_zero(),
+            FixedVec2::new(Fixed::ZERO, Fixed::ONE)
+        );
+    }
+}
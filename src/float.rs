@@ -0,0 +1,172 @@
+//! Floating-point functions used by the rest of the crate.
+//!
+//! Basic arithmetic and `sqrt` are exactly specified by IEEE 754 and give the same bits
+//! everywhere, but the standard library's trigonometric and exponential functions call into
+//! the host's math library, whose last-bit rounding varies between platforms. A module
+//! replayed on another host, or a client predicting the server, could then drift apart.
+//!
+//! With the `deterministic` feature every function here uses the pure-Rust [`libm`] port
+//! instead, so results are bit-identical on every target, WASM included. Without it they
+//! forward to the standard library, unless the `std` feature is off, in which case `libm`
+//! fills in for the functions `core` lacks. Rust never enables fast-math contractions such as
+//! fusing a multiply and add, so nothing else in the crate depends on the host.
+//!
+//! [`libm`]: https://docs.rs/libm
+
+use crate::{MathError, Scalar};
+
+macro_rules! forward {
+    ($(#[$doc:meta] $name:ident($($arg:ident),*) -> $ret:ty => $libm:ident;)*) => {
+        $(
+            #[$doc]
+            #[cfg(any(feature = "deterministic", not(feature = "std")))]
+            #[inline]
+            pub fn $name(x: Scalar $(, $arg: Scalar)*) -> $ret {
+                libm::Libm::<Scalar>::$libm(x $(, $arg)*)
+            }
+
+            #[$doc]
+            #[cfg(all(not(feature = "deterministic"), feature = "std"))]
+            #[inline]
+            pub fn $name(x: Scalar $(, $arg: Scalar)*) -> $ret {
+                x.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+forward! {
+    /// Square root; NaN for negative inputs.
+    sqrt() -> Scalar => sqrt;
+    /// Cube root.
+    cbrt() -> Scalar => cbrt;
+    /// Sine of an angle in radians.
+    sin() -> Scalar => sin;
+    /// Cosine of an angle in radians.
+    cos() -> Scalar => cos;
+    /// Sine and cosine of an angle in radians.
+    sin_cos() -> (Scalar, Scalar) => sincos;
+    /// Tangent of an angle in radians.
+    tan() -> Scalar => tan;
+    /// Arcsine in radians, in `[-pi/2, pi/2]`.
+    asin() -> Scalar => asin;
+    /// Arccosine in radians, in `[0, pi]`.
+    acos() -> Scalar => acos;
+    /// Arctangent in radians, in `[-pi/2, pi/2]`.
+    atan() -> Scalar => atan;
+    /// Four-quadrant arctangent of `x / y` in radians, in `[-pi, pi]`.
+    atan2(y) -> Scalar => atan2;
+    /// `e` raised to the power `x`.
+    exp() -> Scalar => exp;
+    /// Natural logarithm.
+    ln() -> Scalar => log;
+    /// `ln(1 + x)`, accurate even when `x` is close to zero.
+    ln_1p() -> Scalar => log1p;
+    /// `x` raised to the power `y`.
+    powf(y) -> Scalar => pow;
+    /// Hyperbolic cosine.
+    cosh() -> Scalar => cosh;
+    /// Largest integer less than or equal to `x`.
+    floor() -> Scalar => floor;
+    /// Smallest integer greater than or equal to `x`.
+    ceil() -> Scalar => ceil;
+    /// Nearest integer, rounding half-way cases away from zero.
+    round() -> Scalar => round;
+    /// Integer part of `x`, rounding towards zero.
+    trunc() -> Scalar => trunc;
+}
+
+/// [`round`] for `f64`, whatever [`Scalar`] is. Rounding is exact, so `std` is kept when
+/// available.
+#[inline]
+pub(crate) fn round_f64(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    return x.round();
+    #[cfg(not(feature = "std"))]
+    return libm::round(x);
+}
+
+/// Least non-negative remainder of `x` divided by `y`, matching the standard library's
+/// `rem_euclid`.
+#[inline]
+pub fn rem_euclid(x: Scalar, y: Scalar) -> Scalar {
+    let r = x % y;
+    if r < 0.0 { r + y.abs() } else { r }
+}
+
+/// Like [`sqrt`], but rejects negative and non-finite inputs instead of returning NaN.
+#[inline]
+pub fn checked_sqrt(x: Scalar) -> Result<Scalar, MathError> {
+    if !x.is_finite() {
+        return Err(MathError::NotFinite);
+    }
+    if x < 0.0 {
+        return Err(MathError::OutOfDomain { value: x });
+    }
+    Ok(sqrt(x))
+}
+
+/// Like [`acos`], but rejects inputs outside `[-1, 1]` instead of returning NaN.
+///
+/// A dot product of unit vectors can land a hair outside that range through rounding;
+/// clamp it first if that should be accepted.
+#[inline]
+pub fn checked_acos(x: Scalar) -> Result<Scalar, MathError> {
+    if !x.is_finite() {
+        return Err(MathError::NotFinite);
+    }
+    if x.abs() > 1.0 {
+        return Err(MathError::OutOfDomain { value: x });
+    }
+    Ok(acos(x))
+}
+
+/// Like [`asin`], but rejects inputs outside `[-1, 1]` instead of returning NaN.
+#[inline]
+pub fn checked_asin(x: Scalar) -> Result<Scalar, MathError> {
+    if !x.is_finite() {
+        return Err(MathError::NotFinite);
+    }
+    if x.abs() > 1.0 {
+        return Err(MathError::OutOfDomain { value: x });
+    }
+    Ok(asin(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-6 as Scalar;
+
+    #[test]
+    fn matches_the_standard_library() {
+        for x in [0.0, 0.25, 0.5, 1.0, 2.0, -3.0, 10.0] {
+            let x = x as Scalar;
+            assert!((sin(x) - x.sin()).abs() <= EPS, "{x}");
+            assert!((cos(x) - x.cos()).abs() <= EPS, "{x}");
+            assert_eq!(sin_cos(x), (sin(x), cos(x)), "{x}");
+            assert!((atan2(x, 1.5) - x.atan2(1.5)).abs() <= EPS, "{x}");
+            assert!((exp(x) - x.exp()).abs() <= x.exp() * EPS, "{x}");
+            assert_eq!(sqrt(x.abs()), x.abs().sqrt(), "{x}");
+        }
+        assert!((powf(2.0, 0.5) - Scalar::sqrt(2.0)).abs() <= EPS);
+        assert!(sqrt(-1.0).is_nan());
+    }
+
+    #[test]
+    fn checked_functions_reject_out_of_domain_input() {
+        assert_eq!(checked_acos(1.0), Ok(0.0));
+        assert_eq!(
+            checked_acos(-1.5),
+            Err(MathError::OutOfDomain { value: -1.5 })
+        );
+        assert_eq!(checked_asin(Scalar::NAN), Err(MathError::NotFinite));
+        assert_eq!(checked_sqrt(4.0), Ok(2.0));
+        assert_eq!(
+            checked_sqrt(-0.25),
+            Err(MathError::OutOfDomain { value: -0.25 })
+        );
+        assert_eq!(checked_sqrt(Scalar::INFINITY), Err(MathError::NotFinite));
+    }
+}
@@ -0,0 +1,114 @@
+//! Tolerant comparisons of [`Scalar`] values.
+//!
+//! No single tolerance suits every value: an absolute one is too strict for large
+//! coordinates and too loose near zero, and a relative one fails for results that should be
+//! exactly zero. Pick the check that matches the quantity, or use [`abs_or_rel_eq`], which
+//! passes if either does. All of them return `false` when either value is NaN.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{Scalar, float_cmp};
+//!
+//! let far: Scalar = 100_000.0;
+//! let moved = far + 0.005;
+//! // An absolute tolerance suited to small values rejects rounding at this magnitude...
+//! assert!(!float_cmp::abs_eq(far, moved, 1.0e-3));
+//! // ...where a relative one accepts it.
+//! assert!(float_cmp::rel_eq(far, moved, 1.0e-6));
+//! // Near zero, only the absolute part can pass.
+//! assert!(float_cmp::abs_or_rel_eq(1.0e-9, 0.0, 1.0e-6, 1.0e-6));
+//! assert!(float_cmp::ulps_eq(0.1 + 0.2, 0.3, 4));
+//! ```
+
+use crate::{Scalar, consts};
+
+/// Returns `true` if `a` and `b` differ by at most `max_diff`.
+#[inline]
+pub fn abs_eq(a: Scalar, b: Scalar, max_diff: Scalar) -> bool {
+    (a - b).abs() <= max_diff
+}
+
+/// Returns `true` if `a` and `b` differ by at most `max_relative` times the larger of their
+/// magnitudes. Infinities equal only themselves.
+#[inline]
+pub fn rel_eq(a: Scalar, b: Scalar, max_relative: Scalar) -> bool {
+    let difference = (a - b).abs();
+    a == b || (difference.is_finite() && difference <= max_relative * a.abs().max(b.abs()))
+}
+
+/// Returns `true` if [`abs_eq`] or [`rel_eq`] does: the absolute tolerance covers values
+/// near zero and the relative one large values.
+#[inline]
+pub fn abs_or_rel_eq(a: Scalar, b: Scalar, max_diff: Scalar, max_relative: Scalar) -> bool {
+    abs_eq(a, b, max_diff) || rel_eq(a, b, max_relative)
+}
+
+/// Returns [`abs_or_rel_eq`] with both tolerances set to [`consts::EPSILON`], a default for
+/// values computed from a handful of operations.
+#[inline]
+pub fn approx_eq(a: Scalar, b: Scalar) -> bool {
+    abs_or_rel_eq(a, b, consts::EPSILON, consts::EPSILON)
+}
+
+/// Returns how many representable values apart `a` and `b` are, counting `0.0` and `-0.0`
+/// as the same value, or `None` if either is NaN.
+#[inline]
+pub fn ulps_between(a: Scalar, b: Scalar) -> Option<u64> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    // Map sign-and-magnitude bits onto integers that order like the values do.
+    #[cfg(feature = "f32")]
+    let ordered = |x: Scalar| {
+        let bits = x.to_bits() as i32;
+        i64::from(if bits < 0 { i32::MIN - bits } else { bits })
+    };
+    #[cfg(feature = "f64")]
+    let ordered = |x: Scalar| {
+        let bits = x.to_bits() as i64;
+        if bits < 0 { i64::MIN - bits } else { bits }
+    };
+    Some(ordered(a).abs_diff(ordered(b)))
+}
+
+/// Returns `true` if `a` and `b` are at most `max_ulps` representable values apart.
+///
+/// This scales with the values' magnitude automatically, but the steps shrink towards zero:
+/// `1e-20` is hundreds of millions of steps from `0.0`, so compare against zero with
+/// [`abs_eq`] instead.
+#[inline]
+pub fn ulps_eq(a: Scalar, b: Scalar, max_ulps: u32) -> bool {
+    ulps_between(a, b).is_some_and(|ulps| ulps <= max_ulps as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ulps_count_representable_steps() {
+        let one = 1.0 as Scalar;
+        let next = Scalar::from_bits(one.to_bits() + 1);
+        assert_eq!(ulps_between(one, next), Some(1));
+        assert_eq!(ulps_between(0.0, -0.0), Some(0));
+        let tiny = Scalar::from_bits(1);
+        assert_eq!(ulps_between(tiny, -tiny), Some(2));
+        assert_eq!(ulps_between(Scalar::NAN, 1.0), None);
+        assert!(ulps_eq(one, next, 1) && !ulps_eq(one, next, 0));
+        assert!(!ulps_eq(Scalar::NAN, Scalar::NAN, u32::MAX));
+    }
+
+    #[test]
+    fn tolerances_behave_at_every_scale() {
+        assert!(abs_eq(1.0, 1.0005, 1.0e-3));
+        assert!(!abs_eq(1.0, 1.01, 1.0e-3));
+        assert!(rel_eq(1.0e6, 1.0e6 + 0.5, 1.0e-6));
+        assert!(!rel_eq(1.0e-9, 0.0, 0.5));
+        assert!(rel_eq(Scalar::INFINITY, Scalar::INFINITY, 0.0));
+        assert!(!rel_eq(Scalar::INFINITY, Scalar::MAX, 1.0e-3));
+        assert!(abs_or_rel_eq(1.0e-9, 0.0, 1.0e-6, 0.0));
+        assert!(approx_eq(0.1 + 0.2, 0.3));
+        assert!(!approx_eq(Scalar::NAN, Scalar::NAN));
+        assert!(!abs_eq(Scalar::NAN, 0.0, Scalar::INFINITY));
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{IVec2, Scalar, Vec2};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Which cells count as adjacent when walking a grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GridNeighborhood {
+    /// The four edge neighbours.
+    #[default]
+    Four,
+    /// The four edge neighbours and the four diagonals. A diagonal step is only allowed when
+    /// both edge neighbours it cuts between are passable, so agents never squeeze through
+    /// the corner between two walls.
+    Eight,
+}
+
+impl GridNeighborhood {
+    const OFFSETS: [IVec2; 8] = [
+        IVec2::new(1, 0),
+        IVec2::new(0, 1),
+        IVec2::new(-1, 0),
+        IVec2::new(0, -1),
+        IVec2::new(1, 1),
+        IVec2::new(-1, 1),
+        IVec2::new(-1, -1),
+        IVec2::new(1, -1),
+    ];
+
+    /// Returns the neighbour offsets, edge neighbours first.
+    #[inline]
+    pub fn offsets(&self) -> &'static [IVec2] {
+        match self {
+            GridNeighborhood::Four => &Self::OFFSETS[..4],
+            GridNeighborhood::Eight => &Self::OFFSETS,
+        }
+    }
+}
+
+/// Distances from every reachable cell to the nearest goal, for steering many agents towards
+/// the same goals at once.
+///
+/// The grid is unbounded and stored sparsely, so the search is limited by a maximum distance
+/// and by the `passable` callback. Cells that are impassable or out of range are absent.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{FlowField, GridNeighborhood, IVec2};
+///
+/// // A wall along x = 1 with a gap at y = 3.
+/// let passable = |c: IVec2| c.x != 1 || c.y == 3;
+/// let field = FlowField::bfs([IVec2::new(2, 0)], GridNeighborhood::Four, 20, passable);
+///
+/// assert_eq!(field.distance(IVec2::new(0, 0)), Some(8.0));
+/// assert_eq!(field.next_step(IVec2::new(0, 0)), Some(IVec2::new(0, 1)));
+/// assert_eq!(field.distance(IVec2::new(1, 0)), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlowField {
+    neighborhood: GridNeighborhood,
+    distances: HashMap<IVec2, Scalar>,
+}
+
+impl FlowField {
+    /// Builds a field where every step costs `1`, with a breadth-first search from `goals`.
+    ///
+    /// Cells more than `max_steps` steps from every goal are left out.
+    pub fn bfs(
+        goals: impl IntoIterator<Item = IVec2>,
+        neighborhood: GridNeighborhood,
+        max_steps: u32,
+        mut passable: impl FnMut(IVec2) -> bool,
+    ) -> Self {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        for goal in goals {
+            if passable(goal) && !distances.contains_key(&goal) {
+                distances.insert(goal, 0.0);
+                queue.push_back((goal, 0u32));
+            }
+        }
+        while let Some((cell, steps)) = queue.pop_front() {
+            if steps == max_steps {
+                continue;
+            }
+            for &offset in neighborhood.offsets() {
+                let next = cell + offset;
+                if distances.contains_key(&next) || !can_step(cell, offset, &mut passable) {
+                    continue;
+                }
+                distances.insert(next, (steps + 1) as Scalar);
+                queue.push_back((next, steps + 1));
+            }
+        }
+        FlowField {
+            neighborhood,
+            distances,
+        }
+    }
+
+    /// Builds a field with per-step costs, with Dijkstra's algorithm from `goals`.
+    ///
+    /// `cost(from, to)` is the cost for an agent to step from `from` into its neighbour `to`
+    /// and must be positive; return [`Scalar::INFINITY`] to forbid the step. Cells costing more
+    /// than `max_cost` to reach a goal are left out.
+    pub fn dijkstra(
+        goals: impl IntoIterator<Item = IVec2>,
+        neighborhood: GridNeighborhood,
+        max_cost: Scalar,
+        mut passable: impl FnMut(IVec2) -> bool,
+        mut cost: impl FnMut(IVec2, IVec2) -> Scalar,
+    ) -> Self {
+        let mut distances: HashMap<IVec2, Scalar> = HashMap::new();
+        let mut open = BinaryHeap::new();
+        for goal in goals {
+            if passable(goal) {
+                distances.insert(goal, 0.0);
+                open.push(Open {
+                    cost: 0.0,
+                    cell: goal,
+                });
+            }
+        }
+        while let Some(Open {
+            cost: current,
+            cell,
+        }) = open.pop()
+        {
+            if distances.get(&cell).is_some_and(|&best| current > best) {
+                continue;
+            }
+            for &offset in neighborhood.offsets() {
+                let next = cell + offset;
+                if !can_step(cell, offset, &mut passable) {
+                    continue;
+                }
+                // The search runs outwards from the goals, against the direction agents move.
+                let step_cost = cost(next, cell);
+                let total = current + step_cost;
+                if !step_cost.is_finite()
+                    || !total.is_finite()
+                    || total > max_cost
+                    || distances.get(&next).is_some_and(|&best| total >= best)
+                {
+                    continue;
+                }
+                distances.insert(next, total);
+                open.push(Open {
+                    cost: total,
+                    cell: next,
+                });
+            }
+        }
+        FlowField {
+            neighborhood,
+            distances,
+        }
+    }
+
+    /// Returns the neighbourhood the field was built with.
+    #[inline]
+    pub fn neighborhood(&self) -> GridNeighborhood {
+        self.neighborhood
+    }
+
+    /// Returns the number of reachable cells.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Returns `true` if no cell is reachable.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Returns the distance from `cell` to the nearest goal, or `None` if it is unreachable.
+    #[inline]
+    pub fn distance(&self, cell: IVec2) -> Option<Scalar> {
+        self.distances.get(&cell).copied()
+    }
+
+    /// Iterates over every reachable cell and its distance, in an unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, Scalar)> + '_ {
+        self.distances.iter().map(|(&cell, &d)| (cell, d))
+    }
+
+    /// Returns the neighbour an agent in `cell` should move to next: the one with the lowest
+    /// distance, if it is lower than `cell`'s own.
+    ///
+    /// Returns `None` at a goal and in unreachable cells. Ties go to the first neighbour in
+    /// [`GridNeighborhood::offsets`] order.
+    pub fn next_step(&self, cell: IVec2) -> Option<IVec2> {
+        let mut best = (*self.distances.get(&cell)?, None);
+        for &offset in self.neighborhood.offsets() {
+            let next = cell + offset;
+            let Some(&d) = self.distances.get(&next) else {
+                continue;
+            };
+            let diagonal = offset.x != 0 && offset.y != 0;
+            if diagonal
+                && !(self
+                    .distances
+                    .contains_key(&(cell + IVec2::new(offset.x, 0)))
+                    && self
+                        .distances
+                        .contains_key(&(cell + IVec2::new(0, offset.y))))
+            {
+                continue;
+            }
+            if d < best.0 {
+                best = (d, Some(next));
+            }
+        }
+        best.1
+    }
+
+    /// Returns the unit direction from `cell` towards [`FlowField::next_step`], or
+    /// [`Vec2::ZERO`] where there is no step to take.
+    pub fn direction(&self, cell: IVec2) -> Vec2 {
+        match self.next_step(cell) {
+            Some(next) => (next - cell).as_vec2().normalize_or_zero(0.0),
+            None => Vec2::ZERO,
+        }
+    }
+}
+
+/// Returns `true` if a step by `offset` out of `cell` is allowed: the target is passable and,
+/// for diagonals, so are both cells the step cuts between.
+fn can_step(cell: IVec2, offset: IVec2, passable: &mut impl FnMut(IVec2) -> bool) -> bool {
+    if !passable(cell + offset) {
+        return false;
+    }
+    if offset.x != 0 && offset.y != 0 {
+        passable(cell + IVec2::new(offset.x, 0)) && passable(cell + IVec2::new(0, offset.y))
+    } else {
+        true
+    }
+}
+
+/// A Dijkstra frontier entry, ordered so the cheapest is on top of the max-heap.
+#[derive(Debug, Clone, Copy)]
+struct Open {
+    cost: Scalar,
+    cell: IVec2,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_room(c: IVec2) -> bool {
+        c.x.abs() <= 5 && c.y.abs() <= 5
+    }
+
+    #[test]
+    fn bfs_distances_are_grid_metrics() {
+        let four = FlowField::bfs([IVec2::ZERO], GridNeighborhood::Four, 100, in_room);
+        let eight = FlowField::bfs([IVec2::ZERO], GridNeighborhood::Eight, 100, in_room);
+        assert_eq!(four.len(), 121);
+        for (cell, d) in four.iter() {
+            assert_eq!(d, (cell.x.abs() + cell.y.abs()) as Scalar);
+            assert_eq!(
+                eight.distance(cell),
+                Some(cell.x.abs().max(cell.y.abs()) as Scalar)
+            );
+        }
+        let limited = FlowField::bfs([IVec2::ZERO], GridNeighborhood::Four, 2, in_room);
+        assert_eq!(limited.len(), 13);
+    }
+
+    #[test]
+    fn following_next_step_reaches_a_goal() {
+        // A U-shaped wall the agent has to walk around.
+        let passable = |c: IVec2| {
+            in_room(c)
+                && !((c.y == 2 && c.x.abs() <= 3) || (c.x.abs() == 3 && (0..=2).contains(&c.y)))
+        };
+        let goals = [IVec2::new(0, 1)];
+        let field = FlowField::bfs(goals, GridNeighborhood::Eight, 100, passable);
+        let mut cell = IVec2::new(0, 5);
+        let mut steps = 0;
+        while let Some(next) = field.next_step(cell) {
+            assert!(passable(next));
+            assert!(field.distance(next) < field.distance(cell));
+            cell = next;
+            steps += 1;
+        }
+        assert_eq!(cell, goals[0]);
+        assert_eq!(steps as Scalar, field.distance(IVec2::new(0, 5)).unwrap());
+        assert_eq!(field.direction(goals[0]), Vec2::ZERO);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_cells() {
+        // Mud along x = 0 costs 10 to step into, so going around it is cheaper.
+        let cost = |_from: IVec2, to: IVec2| {
+            if to.x == 0 && (-2..=2).contains(&to.y) {
+                10.0
+            } else {
+                1.0
+            }
+        };
+        let field = FlowField::dijkstra(
+            [IVec2::new(0, -3)],
+            GridNeighborhood::Four,
+            50.0,
+            in_room,
+            cost,
+        );
+        assert_eq!(field.distance(IVec2::new(0, -3)), Some(0.0));
+        assert_eq!(field.distance(IVec2::new(1, -3)), Some(1.0));
+        assert_eq!(field.distance(IVec2::new(0, -1)), Some(4.0));
+        assert_eq!(field.distance(IVec2::new(0, 0)), Some(5.0));
+        assert_eq!(field.next_step(IVec2::new(0, 0)), Some(IVec2::new(1, 0)));
+
+        let goals = [IVec2::new(3, 3)];
+        let capped = FlowField::dijkstra(goals, GridNeighborhood::Four, 1.5, in_room, cost);
+        assert_eq!(capped.len(), 5);
+    }
+
+    #[test]
+    fn dijkstra_never_takes_forbidden_steps() {
+        // A row of cells where the step between x = 1 and x = 2 is forbidden both ways.
+        let row = |c: IVec2| c.y == 0 && (0..=4).contains(&c.x);
+        let cost = |from: IVec2, to: IVec2| {
+            if from.x.min(to.x) == 1 && from.x.max(to.x) == 2 {
+                Scalar::INFINITY
+            } else {
+                1.0
+            }
+        };
+        let field = FlowField::dijkstra(
+            [IVec2::ZERO],
+            GridNeighborhood::Four,
+            Scalar::INFINITY,
+            row,
+            cost,
+        );
+        assert_eq!(field.distance(IVec2::new(1, 0)), Some(1.0));
+        assert_eq!(field.distance(IVec2::new(2, 0)), None);
+        assert_eq!(field.next_step(IVec2::new(2, 0)), None);
+        assert_eq!(field.len(), 2);
+    }
+}
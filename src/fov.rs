@@ -0,0 +1,137 @@
+use crate::{Scalar, Vec2, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A field of view: a view cone with a half-angle and a maximum range.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Fov, Vec3};
+///
+/// let guard = Fov::new(std::f32::consts::FRAC_PI_4 as _, 20.0);
+/// let facing = Vec3::new(0.0, 0.0, -1.0);
+/// assert!(guard.contains(Vec3::ZERO, facing, Vec3::new(1.0, 0.0, -5.0)));
+/// assert!(!guard.contains(Vec3::ZERO, facing, Vec3::new(5.0, 0.0, -1.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fov {
+    /// Half of the full cone angle, in radians.
+    pub half_angle: Scalar,
+    /// Maximum distance at which targets are visible.
+    pub max_range: Scalar,
+}
+
+impl Fov {
+    /// Creates a field of view from its half-angle (in radians) and maximum range.
+    pub const fn new(half_angle: Scalar, max_range: Scalar) -> Self {
+        Fov {
+            half_angle,
+            max_range,
+        }
+    }
+
+    /// Returns `true` if `target` is within the view cone of an observer at `observer`
+    /// looking along `facing`.
+    ///
+    /// `facing` does not need to be unit length. A target at the observer's position is
+    /// always visible.
+    pub fn contains(&self, observer: Vec3, facing: Vec3, target: Vec3) -> bool {
+        let to_target = target - observer;
+        let dist_sq = to_target.length_squared();
+        if dist_sq > self.max_range * self.max_range {
+            return false;
+        }
+        within_cone(
+            to_target.dot(facing),
+            dist_sq * facing.length_squared(),
+            float::cos(self.half_angle),
+        )
+    }
+
+    /// 2D variant of [`Fov::contains`].
+    pub fn contains_2d(&self, observer: Vec2, facing: Vec2, target: Vec2) -> bool {
+        let to_target = target - observer;
+        let dist_sq = to_target.length_squared();
+        if dist_sq > self.max_range * self.max_range {
+            return false;
+        }
+        within_cone(
+            to_target.dot(facing),
+            dist_sq * facing.length_squared(),
+            float::cos(self.half_angle),
+        )
+    }
+}
+
+/// Returns `true` if `target` is within `max_range` of `observer` and no more than
+/// `half_angle` radians away from `facing`.
+///
+/// See [`Fov`] to store the angle and range together.
+#[inline]
+pub fn in_fov(
+    observer: Vec3,
+    facing: Vec3,
+    half_angle: Scalar,
+    max_range: Scalar,
+    target: Vec3,
+) -> bool {
+    Fov::new(half_angle, max_range).contains(observer, facing, target)
+}
+
+/// 2D variant of [`in_fov`].
+#[inline]
+pub fn in_fov_2d(
+    observer: Vec2,
+    facing: Vec2,
+    half_angle: Scalar,
+    max_range: Scalar,
+    target: Vec2,
+) -> bool {
+    Fov::new(half_angle, max_range).contains_2d(observer, facing, target)
+}
+
+/// Compares `dot / sqrt(len_sq_product)` against `cos_half_angle` without a square root.
+///
+/// Both sides are squared with their sign kept (`x * |x|`), so half-angles above 90° (where the
+/// cosine is negative) stay correct.
+fn within_cone(dot: Scalar, len_sq_product: Scalar, cos_half_angle: Scalar) -> bool {
+    if len_sq_product <= 0.0 {
+        return true;
+    }
+    let lhs = dot * dot.abs();
+    let rhs = cos_half_angle * cos_half_angle.abs() * len_sq_product;
+    lhs >= rhs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUARTER: Scalar = core::f64::consts::FRAC_PI_4 as Scalar;
+    const THREE_QUARTERS: Scalar = (3.0 * core::f64::consts::FRAC_PI_4) as Scalar;
+
+    #[test]
+    fn in_fov_checks_angle_and_range() {
+        let forward = Vec3::new(0.0, 0.0, -2.0);
+        let sees = |target: Vec3| in_fov(Vec3::ZERO, forward, QUARTER, 10.0, target);
+        assert!(sees(Vec3::new(0.9, 0.0, -1.0)));
+        assert!(!sees(Vec3::new(1.1, 0.0, -1.0)));
+        assert!(!sees(Vec3::new(0.0, 0.0, -11.0)));
+        assert!(!sees(Vec3::new(0.0, 0.0, 1.0)));
+        assert!(sees(Vec3::ZERO));
+    }
+
+    #[test]
+    fn wide_fov_sees_behind_the_side() {
+        let mut fov = Fov::new(QUARTER, 10.0);
+        let facing = Vec2::new(1.0, 0.0);
+        assert!(!fov.contains_2d(Vec2::ZERO, facing, Vec2::new(-1.0, 1.1)));
+        fov.half_angle = THREE_QUARTERS;
+        assert!(fov.contains_2d(Vec2::ZERO, facing, Vec2::new(-1.0, 1.1)));
+        assert!(!fov.contains_2d(Vec2::ZERO, facing, Vec2::new(-1.0, 0.9)));
+        let target = Vec2::new(3.0, 2.0);
+        assert!(in_fov_2d(Vec2::ZERO, facing, QUARTER, 5.0, target));
+    }
+}
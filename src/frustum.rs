@@ -0,0 +1,211 @@
+use crate::{Aabb3, Mat4, Plane, Scalar, Sphere, Vec3};
+use alloc::vec::Vec;
+
+/// A view frustum bounded by six inward-facing planes.
+///
+/// Planes are stored in the order left, right, bottom, top, near, far.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Frustum, Vec3};
+///
+/// let frustum = Frustum::from_camera(
+///     Vec3::ZERO,
+///     Vec3::new(0.0, 0.0, -1.0),
+///     Vec3::new(0.0, 1.0, 0.0),
+///     1.2,
+///     16.0 / 9.0,
+///     0.1,
+///     100.0,
+/// );
+/// assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -10.0)));
+/// assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 10.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    /// The bounding planes, with normals pointing into the frustum.
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    pub const LEFT: usize = 0;
+    pub const RIGHT: usize = 1;
+    pub const BOTTOM: usize = 2;
+    pub const TOP: usize = 3;
+    pub const NEAR: usize = 4;
+    pub const FAR: usize = 5;
+
+    #[inline(always)]
+    pub const fn new(planes: [Plane; 6]) -> Self {
+        Frustum { planes }
+    }
+
+    /// Extracts the frustum planes from a combined view-projection matrix.
+    ///
+    /// Expects clip space depth in `[0, 1]`, as produced by [`Mat4::perspective_rh`] and `glam`.
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let r0 = view_projection.row(0);
+        let r1 = view_projection.row(1);
+        let r2 = view_projection.row(2);
+        let r3 = view_projection.row(3);
+        let plane = |x: Scalar, y: Scalar, z: Scalar, w: Scalar| {
+            let p = Plane::new(Vec3::new(x, y, z), w);
+            p.try_normalize(Scalar::EPSILON).unwrap_or(p)
+        };
+        Frustum::new([
+            plane(r3.x + r0.x, r3.y + r0.y, r3.z + r0.z, r3.w + r0.w),
+            plane(r3.x - r0.x, r3.y - r0.y, r3.z - r0.z, r3.w - r0.w),
+            plane(r3.x + r1.x, r3.y + r1.y, r3.z + r1.z, r3.w + r1.w),
+            plane(r3.x - r1.x, r3.y - r1.y, r3.z - r1.z, r3.w - r1.w),
+            plane(r2.x, r2.y, r2.z, r2.w),
+            plane(r3.x - r2.x, r3.y - r2.y, r3.z - r2.z, r3.w - r2.w),
+        ])
+    }
+
+    /// Builds the frustum of a perspective camera at `position` looking along `forward`.
+    ///
+    /// `fov_y_radians` is the full vertical field of view; `forward` and `up` are expected to be normalized.
+    pub fn from_camera(
+        position: Vec3,
+        forward: Vec3,
+        up: Vec3,
+        fov_y_radians: Scalar,
+        aspect_ratio: Scalar,
+        z_near: Scalar,
+        z_far: Scalar,
+    ) -> Self {
+        let view = Mat4::look_to_rh(position, forward, up);
+        let projection = Mat4::perspective_rh(fov_y_radians, aspect_ratio, z_near, z_far);
+        Frustum::from_view_projection(&(projection * view))
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of the frustum.
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|p| p.signed_distance(point) >= 0.0)
+    }
+
+    /// Returns `true` if `sphere` is at least partially inside the frustum.
+    ///
+    /// Conservative: spheres near frustum corners may be reported as intersecting.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|p| p.signed_distance(sphere.center) >= -sphere.radius)
+    }
+
+    /// Returns `true` if `aabb` is at least partially inside the frustum.
+    ///
+    /// Conservative: boxes near frustum corners may be reported as intersecting.
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        // Test the corner furthest along each plane normal.
+        self.planes
+            .iter()
+            .all(|p| p.signed_distance(aabb.support(p.normal)) >= 0.0)
+    }
+
+    /// Tests every box in `aabbs` against the frustum; `true` marks a potentially visible box.
+    ///
+    /// Allocates the result; use [`Frustum::cull_aabbs_into`] to reuse a buffer across ticks.
+    pub fn cull_aabbs(&self, aabbs: &[Aabb3]) -> Vec<bool> {
+        let mut visible = Vec::with_capacity(aabbs.len());
+        self.cull_aabbs_into(aabbs, &mut visible);
+        visible
+    }
+
+    /// Like [`Frustum::cull_aabbs`], but writes into `visible`, replacing its contents.
+    pub fn cull_aabbs_into(&self, aabbs: &[Aabb3], visible: &mut Vec<bool>) {
+        visible.clear();
+        visible.extend(aabbs.iter().map(|aabb| self.intersects_aabb(aabb)));
+    }
+
+    /// Tests every sphere in `spheres` against the frustum; `true` marks a potentially visible
+    /// sphere.
+    ///
+    /// Allocates the result; use [`Frustum::cull_spheres_into`] to reuse a buffer across ticks.
+    pub fn cull_spheres(&self, spheres: &[Sphere]) -> Vec<bool> {
+        let mut visible = Vec::with_capacity(spheres.len());
+        self.cull_spheres_into(spheres, &mut visible);
+        visible
+    }
+
+    /// Like [`Frustum::cull_spheres`], but writes into `visible`, replacing its contents.
+    pub fn cull_spheres_into(&self, spheres: &[Sphere], visible: &mut Vec<bool>) {
+        visible.clear();
+        visible.extend(spheres.iter().map(|sphere| self.intersects_sphere(sphere)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Frustum {
+        Frustum::from_camera(
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            1.5 as Scalar,
+            1.0 as Scalar,
+            1.0 as Scalar,
+            50.0 as Scalar,
+        )
+    }
+
+    #[test]
+    fn contains_point_respects_near_and_far() {
+        let f = camera();
+        assert!(f.contains_point(Vec3::new(0.0, 0.0, -2.0)));
+        assert!(!f.contains_point(Vec3::new(0.0, 0.0, -0.5)));
+        assert!(!f.contains_point(Vec3::new(0.0, 0.0, -60.0)));
+    }
+
+    #[test]
+    fn planes_are_normalized() {
+        for plane in camera().planes {
+            assert!((plane.normal.length() - 1.0 as Scalar).abs() <= 1.0e-4 as Scalar);
+        }
+    }
+
+    #[test]
+    fn near_plane_is_at_z_near() {
+        let near = camera().planes[Frustum::NEAR];
+        assert!((near.signed_distance(Vec3::new(0.0, 0.0, -1.0))).abs() <= 1.0e-4 as Scalar);
+    }
+
+    #[test]
+    fn intersects_sphere_includes_partial_overlap() {
+        let f = camera();
+        assert!(f.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, 1.0), 2.5)));
+        assert!(!f.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, 5.0), 1.0)));
+        assert!(!f.intersects_sphere(&Sphere::new(Vec3::new(100.0, 0.0, -10.0), 1.0)));
+    }
+
+    #[test]
+    fn intersects_aabb_includes_partial_overlap() {
+        let f = camera();
+        let straddling = Aabb3::new(Vec3::new(-1.0, -1.0, -60.0), Vec3::new(1.0, 1.0, -40.0));
+        let behind = Aabb3::new(Vec3::new(-1.0, -1.0, 1.0), Vec3::new(1.0, 1.0, 2.0));
+        assert!(f.intersects_aabb(&straddling));
+        assert!(!f.intersects_aabb(&behind));
+    }
+
+    #[test]
+    fn batch_culling_matches_single_tests() {
+        let f = camera();
+        let aabbs = [
+            Aabb3::new(Vec3::new(-1.0, -1.0, -5.0), Vec3::new(1.0, 1.0, -4.0)),
+            Aabb3::new(Vec3::new(-1.0, -1.0, 1.0), Vec3::new(1.0, 1.0, 2.0)),
+        ];
+        assert_eq!(f.cull_aabbs(&aabbs), vec![true, false]);
+
+        let spheres = [
+            Sphere::new(Vec3::new(100.0, 0.0, -10.0), 1.0),
+            Sphere::new(Vec3::new(0.0, 0.0, -10.0), 1.0),
+            Sphere::new(Vec3::new(0.0, 0.0, 5.0), 1.0),
+        ];
+        let mut visible = vec![true; 8];
+        f.cull_spheres_into(&spheres, &mut visible);
+        assert_eq!(visible, vec![false, true, false]);
+        assert_eq!(f.cull_spheres(&[]), Vec::<bool>::new());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::conventions::Axes;
+use crate::{Scalar, Vec3, consts, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A geographic position: latitude and longitude in degrees, altitude above the surface in
+/// world units.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Geodetic {
+    /// Degrees north of the equator, in `[-90, 90]`.
+    pub latitude: Scalar,
+    /// Degrees east of the prime meridian, in `(-180, 180]`.
+    pub longitude: Scalar,
+    /// Height above the planet's surface.
+    pub altitude: Scalar,
+}
+
+impl Geodetic {
+    #[inline(always)]
+    pub const fn new(latitude: Scalar, longitude: Scalar, altitude: Scalar) -> Self {
+        Geodetic {
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+}
+
+/// A spherical planet for converting between geographic and Cartesian coordinates.
+///
+/// Cartesian positions are Earth-centered, Earth-fixed (ECEF): the origin at the planet's
+/// center, +Z through the north pole and +X through latitude and longitude zero. This is the
+/// standard geodesy layout rather than the crate's Y-up default, so ECEF vectors can be
+/// exchanged with other tools. Local work is easier in an east-north-up (ENU) frame around a
+/// reference point, where X is east, Y is north and Z is up.
+///
+/// The model is a sphere, which is accurate to about 0.5% of distances on Earth. At Earth's
+/// scale `f32` resolves ECEF coordinates to about half a meter; enable the `f64` feature for
+/// anything finer.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Geodetic, GeoSphere, Vec3};
+///
+/// let moon = GeoSphere::new(1737.4);
+/// let base = Geodetic::new(0.0, 90.0, 0.0);
+/// assert!(moon.to_ecef(&base).distance(Vec3::new(0.0, 1737.4, 0.0)) < 1.0e-3);
+/// // A rover 2 km east of the base, in the base's local frame.
+/// let rover = moon.enu_to_ecef(&base, Vec3::new(2.0, 0.0, 0.0));
+/// assert!(moon.ecef_to_enu(&base, rover).distance(Vec3::new(2.0, 0.0, 0.0)) < 1.0e-3);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeoSphere {
+    /// Radius of the planet's surface.
+    pub radius: Scalar,
+}
+
+impl GeoSphere {
+    /// Earth's mean radius in meters.
+    pub const EARTH: GeoSphere = GeoSphere::new(6_371_008.8);
+
+    #[inline(always)]
+    pub const fn new(radius: Scalar) -> Self {
+        GeoSphere { radius }
+    }
+
+    /// Converts a geographic position to ECEF coordinates.
+    pub fn to_ecef(&self, geo: &Geodetic) -> Vec3 {
+        let (sin_lat, cos_lat) = float::sin_cos(geo.latitude * consts::DEG_TO_RAD);
+        let (sin_lon, cos_lon) = float::sin_cos(geo.longitude * consts::DEG_TO_RAD);
+        let r = self.radius + geo.altitude;
+        Vec3::new(r * cos_lat * cos_lon, r * cos_lat * sin_lon, r * sin_lat)
+    }
+
+    /// Converts ECEF coordinates to a geographic position.
+    ///
+    /// The poles have longitude `0`, and the center has latitude `0` and an altitude of
+    /// `-radius`.
+    pub fn from_ecef(&self, ecef: Vec3) -> Geodetic {
+        let horizontal = float::sqrt(ecef.x * ecef.x + ecef.y * ecef.y);
+        Geodetic::new(
+            float::atan2(ecef.z, horizontal) * consts::RAD_TO_DEG,
+            float::atan2(ecef.y, ecef.x) * consts::RAD_TO_DEG,
+            ecef.length() - self.radius,
+        )
+    }
+
+    /// Returns the east, north and up directions at `geo` in ECEF coordinates, as `right`,
+    /// `forward` and `up`.
+    ///
+    /// At the poles, north is taken along the meridian of `geo.longitude`.
+    pub fn enu_axes(&self, geo: &Geodetic) -> Axes {
+        let (sin_lat, cos_lat) = float::sin_cos(geo.latitude * consts::DEG_TO_RAD);
+        let (sin_lon, cos_lon) = float::sin_cos(geo.longitude * consts::DEG_TO_RAD);
+        Axes {
+            up: Vec3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat),
+            forward: Vec3::new(-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat),
+            right: Vec3::new(-sin_lon, cos_lon, 0.0),
+        }
+    }
+
+    /// Converts ECEF coordinates to the ENU frame at `origin`, whose zero is `origin` itself,
+    /// altitude included.
+    pub fn ecef_to_enu(&self, origin: &Geodetic, ecef: Vec3) -> Vec3 {
+        let axes = self.enu_axes(origin);
+        let offset = ecef - self.to_ecef(origin);
+        Vec3::new(
+            offset.dot(axes.right),
+            offset.dot(axes.forward),
+            offset.dot(axes.up),
+        )
+    }
+
+    /// Converts a position in the ENU frame at `origin` to ECEF coordinates.
+    pub fn enu_to_ecef(&self, origin: &Geodetic, enu: Vec3) -> Vec3 {
+        let axes = self.enu_axes(origin);
+        self.to_ecef(origin) + axes.right * enu.x + axes.forward * enu.y + axes.up * enu.z
+    }
+
+    /// Converts a geographic position to the ENU frame at `origin`.
+    #[inline]
+    pub fn geodetic_to_enu(&self, origin: &Geodetic, geo: &Geodetic) -> Vec3 {
+        self.ecef_to_enu(origin, self.to_ecef(geo))
+    }
+
+    /// Converts a position in the ENU frame at `origin` to a geographic position.
+    #[inline]
+    pub fn enu_to_geodetic(&self, origin: &Geodetic, enu: Vec3) -> Geodetic {
+        self.from_ecef(self.enu_to_ecef(origin, enu))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn ecef_axes_follow_the_geodesy_convention() {
+        let unit = GeoSphere::new(1.0);
+        let cases = [
+            (Geodetic::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)),
+            (Geodetic::new(0.0, 90.0, 0.0), Vec3::new(0.0, 1.0, 0.0)),
+            (Geodetic::new(90.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 2.0)),
+            (
+                Geodetic::new(-45.0, 180.0, 0.0),
+                Vec3::new(-0.5, 0.0, -0.5) * Scalar::sqrt(2.0),
+            ),
+        ];
+        for (geo, ecef) in cases {
+            assert!(unit.to_ecef(&geo).distance(ecef) <= EPS, "{geo:?}");
+        }
+    }
+
+    #[test]
+    fn geodetic_round_trips() {
+        let planet = GeoSphere::new(1000.0);
+        for geo in [
+            Geodetic::new(12.5, -33.25, 4.0),
+            Geodetic::new(-89.0, 179.0, 0.0),
+            Geodetic::new(60.0, 0.0, -10.0),
+        ] {
+            let back = planet.from_ecef(planet.to_ecef(&geo));
+            assert!(
+                (back.latitude - geo.latitude).abs() <= 1.0e-3 as Scalar,
+                "{geo:?}"
+            );
+            assert!(
+                (back.longitude - geo.longitude).abs() <= 1.0e-3 as Scalar,
+                "{geo:?}"
+            );
+            assert!(
+                (back.altitude - geo.altitude).abs() <= 1.0e-2 as Scalar,
+                "{geo:?}"
+            );
+        }
+        let center = planet.from_ecef(Vec3::ZERO);
+        assert_eq!(center, Geodetic::new(0.0, 0.0, -1000.0));
+    }
+
+    #[test]
+    fn enu_frames_are_orthonormal_and_local() {
+        let planet = GeoSphere::new(100.0);
+        let origin = Geodetic::new(30.0, 45.0, 2.0);
+        let axes = planet.enu_axes(&origin);
+        assert!((axes.right.cross(axes.forward) - axes.up).length() <= EPS);
+        assert!((axes.up.length() - 1.0).abs() <= EPS);
+
+        assert!(
+            planet
+                .ecef_to_enu(&origin, planet.to_ecef(&origin))
+                .length()
+                <= EPS
+        );
+        let above = Geodetic::new(30.0, 45.0, 12.0);
+        assert!(
+            planet
+                .geodetic_to_enu(&origin, &above)
+                .distance(Vec3::new(0.0, 0.0, 10.0))
+                <= EPS
+        );
+        // Moving north raises the latitude; moving east raises the longitude.
+        let north = planet.enu_to_geodetic(&origin, Vec3::new(0.0, 1.0, 0.0));
+        assert!(north.latitude > origin.latitude);
+        assert!((north.longitude - origin.longitude).abs() <= 1.0e-3 as Scalar);
+        let east = planet.enu_to_geodetic(&origin, Vec3::new(1.0, 0.0, 0.0));
+        assert!(east.longitude > origin.longitude);
+    }
+}
@@ -0,0 +1,304 @@
+//! Distance between convex 2D shapes with the GJK algorithm.
+//!
+//! Any shape implementing [`SupportMap`] can be queried against any other with [`distance`].
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::gjk;
+//! use spacetimedb_math::{Circle, Segment2, Vec2};
+//!
+//! let wall = Segment2::new(Vec2::new(2.0, -1.0), Vec2::new(2.0, 1.0));
+//! let player = Circle::new(Vec2::ZERO, 0.5);
+//! let result = gjk::distance(&player, &wall, 1.0e-4).unwrap();
+//! assert!((result.distance - 1.5).abs() < 1.0e-4);
+//! ```
+
+use crate::{Circle, ConvexPolygon2, Scalar, Segment2, Vec2, float};
+
+const MAX_ITERATIONS: usize = 32;
+
+/// A convex shape described by its support function.
+///
+/// Shapes are a convex core inflated by [`SupportMap::margin`], so rounded shapes such as
+/// circles don't need an infinite number of support points to converge.
+pub trait SupportMap {
+    /// Returns the point of the core furthest along `direction`.
+    fn support(&self, direction: Vec2) -> Vec2;
+
+    /// Returns the radius the core is inflated by.
+    fn margin(&self) -> Scalar {
+        0.0
+    }
+}
+
+impl SupportMap for Vec2 {
+    #[inline]
+    fn support(&self, _direction: Vec2) -> Vec2 {
+        *self
+    }
+}
+
+impl SupportMap for Circle {
+    #[inline]
+    fn support(&self, _direction: Vec2) -> Vec2 {
+        self.center
+    }
+
+    #[inline]
+    fn margin(&self) -> Scalar {
+        self.radius
+    }
+}
+
+impl SupportMap for Segment2 {
+    #[inline]
+    fn support(&self, direction: Vec2) -> Vec2 {
+        if self.end.dot(direction) > self.start.dot(direction) {
+            self.end
+        } else {
+            self.start
+        }
+    }
+}
+
+impl SupportMap for ConvexPolygon2 {
+    #[inline]
+    fn support(&self, direction: Vec2) -> Vec2 {
+        ConvexPolygon2::support(self, direction)
+    }
+}
+
+/// Result of [`distance`] for two separated shapes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Proximity {
+    /// Distance between the shapes.
+    pub distance: Scalar,
+    /// Point on the first shape closest to the second.
+    pub point_a: Vec2,
+    /// Point on the second shape closest to the first.
+    pub point_b: Vec2,
+}
+
+/// A vertex of the Minkowski difference `a - b`, with the support points that produced it.
+#[derive(Clone, Copy)]
+struct SimplexVertex {
+    w: Vec2,
+    a: Vec2,
+    b: Vec2,
+}
+
+/// Returns the distance and closest points between `a` and `b`, or `None` if they overlap.
+///
+/// `tolerance` is the absolute accuracy of the distance; iteration stops once the distance
+/// is known to within it.
+pub fn distance<A, B>(a: &A, b: &B, tolerance: Scalar) -> Option<Proximity>
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    let support = |direction: Vec2| {
+        let a = a.support(direction);
+        let b = b.support(-direction);
+        SimplexVertex { w: a - b, a, b }
+    };
+
+    let mut simplex = [support(Vec2::new(1.0, 0.0)); 3];
+    let mut len = 1;
+    let mut weights = [1.0, 0.0, 0.0];
+    for _ in 0..MAX_ITERATIONS {
+        let closest = reduce_simplex(&mut simplex, &mut len, &mut weights)?;
+        let dist_sq = closest.length_squared();
+        if dist_sq <= 0.0 {
+            return None;
+        }
+        let next = support(-closest);
+        // `closest.dot(next.w) / |closest|` is a lower bound on the distance.
+        let dist = float::sqrt(dist_sq);
+        if dist - closest.dot(next.w) / dist <= tolerance {
+            break;
+        }
+        simplex[len] = next;
+        len += 1;
+    }
+
+    let mut point_a = Vec2::ZERO;
+    let mut point_b = Vec2::ZERO;
+    for i in 0..len {
+        point_a += simplex[i].a * weights[i];
+        point_b += simplex[i].b * weights[i];
+    }
+    let core_distance = point_a.distance(point_b);
+    let margins = a.margin() + b.margin();
+    if core_distance <= margins {
+        return None;
+    }
+    let direction = (point_b - point_a) / core_distance;
+    Some(Proximity {
+        distance: core_distance - margins,
+        point_a: point_a + direction * a.margin(),
+        point_b: point_b - direction * b.margin(),
+    })
+}
+
+/// Returns `true` if `a` and `b` overlap (touching counts, to within `tolerance`).
+#[inline]
+pub fn intersects<A, B>(a: &A, b: &B, tolerance: Scalar) -> bool
+where
+    A: SupportMap + ?Sized,
+    B: SupportMap + ?Sized,
+{
+    distance(a, b, tolerance).is_none_or(|p| p.distance <= tolerance)
+}
+
+/// Shrinks the simplex to the feature closest to the origin and returns the closest point on it.
+///
+/// Updates `weights` with the barycentric weights of that point. Returns `None` if the simplex
+/// is a triangle containing the origin.
+fn reduce_simplex(
+    simplex: &mut [SimplexVertex; 3],
+    len: &mut usize,
+    weights: &mut [Scalar; 3],
+) -> Option<Vec2> {
+    let keep = |simplex: &mut [SimplexVertex; 3], len: &mut usize, picks: &[usize]| {
+        let old = *simplex;
+        for (i, &p) in picks.iter().enumerate() {
+            simplex[i] = old[p];
+        }
+        *len = picks.len();
+    };
+    match *len {
+        1 => {
+            *weights = [1.0, 0.0, 0.0];
+            Some(simplex[0].w)
+        }
+        2 => {
+            let (a, b) = (simplex[0].w, simplex[1].w);
+            let ab = b - a;
+            let len_sq = ab.length_squared();
+            let t = if len_sq > 0.0 {
+                (-a.dot(ab) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            if t <= 0.0 {
+                keep(simplex, len, &[0]);
+                *weights = [1.0, 0.0, 0.0];
+            } else if t >= 1.0 {
+                keep(simplex, len, &[1]);
+                *weights = [1.0, 0.0, 0.0];
+            } else {
+                *weights = [1.0 - t, t, 0.0];
+            }
+            Some(a + ab * t)
+        }
+        _ => {
+            // Voronoi regions of the triangle, tested against the origin.
+            let (a, b, c) = (simplex[0].w, simplex[1].w, simplex[2].w);
+            let ab = b - a;
+            let ac = c - a;
+            let d1 = ab.dot(-a);
+            let d2 = ac.dot(-a);
+            if d1 <= 0.0 && d2 <= 0.0 {
+                keep(simplex, len, &[0]);
+                *weights = [1.0, 0.0, 0.0];
+                return Some(a);
+            }
+            let d3 = ab.dot(-b);
+            let d4 = ac.dot(-b);
+            if d3 >= 0.0 && d4 <= d3 {
+                keep(simplex, len, &[1]);
+                *weights = [1.0, 0.0, 0.0];
+                return Some(b);
+            }
+            let vc = d1 * d4 - d3 * d2;
+            if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+                let t = d1 / (d1 - d3);
+                keep(simplex, len, &[0, 1]);
+                *weights = [1.0 - t, t, 0.0];
+                return Some(a + ab * t);
+            }
+            let d5 = ab.dot(-c);
+            let d6 = ac.dot(-c);
+            if d6 >= 0.0 && d5 <= d6 {
+                keep(simplex, len, &[2]);
+                *weights = [1.0, 0.0, 0.0];
+                return Some(c);
+            }
+            let vb = d5 * d2 - d1 * d6;
+            if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+                let t = d2 / (d2 - d6);
+                keep(simplex, len, &[0, 2]);
+                *weights = [1.0 - t, t, 0.0];
+                return Some(a + ac * t);
+            }
+            let va = d3 * d6 - d5 * d4;
+            if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+                let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+                keep(simplex, len, &[1, 2]);
+                *weights = [1.0 - t, t, 0.0];
+                return Some(b + (c - b) * t);
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn square_at(offset: Vec2) -> ConvexPolygon2 {
+        ConvexPolygon2::new(vec![
+            offset,
+            offset + Vec2::new(1.0, 0.0),
+            offset + Vec2::ONE,
+            offset + Vec2::new(0.0, 1.0),
+        ])
+    }
+
+    #[test]
+    fn polygon_polygon_distance_and_points() {
+        let a = square_at(Vec2::ZERO);
+        let b = square_at(Vec2::new(3.0, 0.5));
+        let result = distance(&a, &b, EPS).unwrap();
+        assert!((result.distance - 2.0 as Scalar).abs() <= EPS);
+        assert!((result.point_a.x - 1.0 as Scalar).abs() <= EPS);
+        assert!((result.point_b.x - 3.0 as Scalar).abs() <= EPS);
+        assert!((result.point_a.y - result.point_b.y).abs() <= EPS);
+
+        // Corner to corner, along the diagonal.
+        let c = square_at(Vec2::splat(2.0));
+        let result = distance(&a, &c, EPS).unwrap();
+        assert!((result.distance - (2.0 as Scalar).sqrt()).abs() <= EPS);
+        assert!(result.point_a.distance(Vec2::ONE) <= EPS);
+        assert!(result.point_b.distance(Vec2::splat(2.0)) <= EPS);
+    }
+
+    #[test]
+    fn margins_round_the_shapes() {
+        let circle = Circle::new(Vec2::new(0.0, 3.0), 1.0);
+        let square = square_at(Vec2::ZERO);
+        let result = distance(&circle, &square, EPS).unwrap();
+        assert!((result.distance - 1.0 as Scalar).abs() <= EPS);
+        assert!(result.point_a.distance(Vec2::new(0.0, 2.0)) <= EPS);
+        assert!((result.point_b.y - 1.0 as Scalar).abs() <= EPS);
+
+        let point = Vec2::new(5.0, 0.0);
+        let result = distance(&point, &circle, EPS).unwrap();
+        assert!((result.distance - ((34.0 as Scalar).sqrt() - 1.0)).abs() <= EPS);
+    }
+
+    #[test]
+    fn overlapping_shapes_have_no_distance() {
+        let a = square_at(Vec2::ZERO);
+        assert_eq!(distance(&a, &square_at(Vec2::splat(0.5)), EPS), None);
+        assert_eq!(distance(&a, &Vec2::splat(0.5), EPS), None);
+        let crossing = Segment2::new(Vec2::new(-1.0, 0.5), Vec2::new(2.0, 0.5));
+        assert!(intersects(&a, &crossing, EPS));
+        // Core distance is 0.5, fully absorbed by the circle's radius.
+        assert!(intersects(&Circle::new(Vec2::new(1.5, 0.5), 0.6), &a, EPS));
+        assert!(!intersects(&Circle::new(Vec2::new(1.5, 0.5), 0.4), &a, EPS));
+    }
+}
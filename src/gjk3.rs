@@ -0,0 +1,178 @@
+//! Overlap tests between convex 3D solids with the GJK algorithm.
+//!
+//! The 3D counterpart of [`crate::gjk`] for the solids that only need a yes or no answer, such
+//! as [`crate::Cylinder::intersects_aabb`] and [`crate::Cone::intersects_aabb`].
+
+use crate::closest_point::{TriangleRegion, triangle_region};
+use crate::{Scalar, Triangle3, Vec3, float};
+
+const MAX_ITERATIONS: usize = 64;
+
+/// Tests two convex solids for overlap given their support functions.
+///
+/// `direction` is the first direction to search along; the offset between the centers of the
+/// solids is a good choice. Returns `false` only once a plane separates the solids by more than
+/// `tolerance`, or once the distance between them is known to exceed it.
+pub(crate) fn intersects<A, B>(
+    support_a: A,
+    support_b: B,
+    direction: Vec3,
+    tolerance: Scalar,
+) -> bool
+where
+    A: Fn(Vec3) -> Vec3,
+    B: Fn(Vec3) -> Vec3,
+{
+    let support = |d: Vec3| support_a(d) - support_b(-d);
+    let mut simplex = [support(direction); 4];
+    let mut len = 1;
+    let mut last_dist_sq = Scalar::INFINITY;
+    for _ in 0..MAX_ITERATIONS {
+        let Some(closest) = reduce_simplex(&mut simplex, &mut len) else {
+            return true;
+        };
+        let dist_sq = closest.length_squared();
+        // The simplex only stops getting closer to the origin once rounding takes over, and
+        // without a separating plane the solids are then treated as touching.
+        if dist_sq <= tolerance * tolerance || dist_sq >= last_dist_sq {
+            return true;
+        }
+        last_dist_sq = dist_sq;
+        let next = support(-closest);
+        // `bound / |closest|` is a lower bound on the distance between the solids. Once it is
+        // within rounding of `|closest|` the distance is known, and it exceeds `tolerance`.
+        let bound = closest.dot(next);
+        if bound > tolerance * float::sqrt(dist_sq)
+            || dist_sq - bound <= dist_sq * (Scalar::EPSILON * 64.0)
+        {
+            return false;
+        }
+        simplex[len] = next;
+        len += 1;
+    }
+    true
+}
+
+/// Shrinks the simplex to the feature closest to the origin and returns the closest point on
+/// it, or `None` if the simplex is a tetrahedron containing the origin.
+fn reduce_simplex(simplex: &mut [Vec3; 4], len: &mut usize) -> Option<Vec3> {
+    let (closest, picks, count) = match *len {
+        1 => (simplex[0], [0, 0, 0], 1),
+        2 => closest_on_segment(simplex),
+        3 => closest_on_triangle(simplex, [0, 1, 2]),
+        _ => closest_on_tetrahedron(simplex)?,
+    };
+    let old = *simplex;
+    for (i, &p) in picks[..count].iter().enumerate() {
+        simplex[i] = old[p];
+    }
+    *len = count;
+    Some(closest)
+}
+
+/// The point closest to the origin, the indices of the simplex vertices it depends on and
+/// how many of them there are.
+type Feature = (Vec3, [usize; 3], usize);
+
+/// Closest point to the origin on the segment between the first two simplex vertices.
+fn closest_on_segment(simplex: &[Vec3; 4]) -> Feature {
+    let (a, b) = (simplex[0], simplex[1]);
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    let t = if len_sq > 0.0 {
+        (-a.dot(ab) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    if t <= 0.0 {
+        (a, [0, 0, 0], 1)
+    } else if t >= 1.0 {
+        (b, [1, 0, 0], 1)
+    } else {
+        (a + ab * t, [0, 1, 0], 2)
+    }
+}
+
+/// Closest point to the origin on a triangle of simplex vertices.
+fn closest_on_triangle(simplex: &[Vec3; 4], [i, j, k]: [usize; 3]) -> Feature {
+    let (a, b, c) = (simplex[i], simplex[j], simplex[k]);
+    match triangle_region(Vec3::ZERO, &Triangle3::new(a, b, c)) {
+        TriangleRegion::A => (a, [i, 0, 0], 1),
+        TriangleRegion::B => (b, [j, 0, 0], 1),
+        TriangleRegion::C => (c, [k, 0, 0], 1),
+        TriangleRegion::Ab(t) => (a + (b - a) * t, [i, j, 0], 2),
+        TriangleRegion::Ac(t) => (a + (c - a) * t, [i, k, 0], 2),
+        TriangleRegion::Bc(t) => (b + (c - b) * t, [j, k, 0], 2),
+        TriangleRegion::Face(v, w) => (a + (b - a) * v + (c - a) * w, [i, j, k], 3),
+    }
+}
+
+/// Closest point to the origin on the tetrahedron formed by the whole simplex, or `None` if
+/// the origin is inside it.
+///
+/// A tetrahedron flattened by rounding has no inside, so it is reduced to its closest face.
+fn closest_on_tetrahedron(simplex: &[Vec3; 4]) -> Option<Feature> {
+    let [a, b, c, d] = *simplex;
+    let (ab, ac, ad) = (b - a, c - a, d - a);
+    let scale = ab.length() * ac.length() * ad.length();
+    let flat = ab.cross(ac).dot(ad).abs() <= scale * (Scalar::EPSILON * 64.0);
+
+    let faces = [
+        ([0, 1, 2], 3),
+        ([0, 2, 3], 1),
+        ([0, 3, 1], 2),
+        ([1, 3, 2], 0),
+    ];
+    let mut best: Option<Feature> = None;
+    for (face, opposite) in faces {
+        let [p, q, r] = face.map(|i| simplex[i]);
+        let normal = (q - p).cross(r - p);
+        // Only faces with the origin and the opposite vertex on different sides can hold
+        // the closest point.
+        if !flat && normal.dot(-p) * normal.dot(simplex[opposite] - p) >= 0.0 {
+            continue;
+        }
+        let candidate = closest_on_triangle(simplex, face);
+        if best.is_none_or(|(p, _, _)| candidate.0.length_squared() < p.length_squared()) {
+            best = Some(candidate);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Aabb3;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn flat_tetrahedron_reduces_to_its_closest_face() {
+        let simplex = [
+            Vec3::new(-1.0, 0.5, -1.0),
+            Vec3::new(1.0, 0.5, -1.0),
+            Vec3::new(0.0, 0.5, 1.0),
+            Vec3::new(0.2, 0.5, 0.1),
+        ];
+        let (closest, _, _) = closest_on_tetrahedron(&simplex).unwrap();
+        assert!(closest.distance(Vec3::new(0.0, 0.5, 0.0)) <= EPS);
+    }
+
+    #[test]
+    fn boxes_touch_only_within_tolerance() {
+        let unit = Aabb3::from_center_half_extents(Vec3::ZERO, Vec3::splat(0.5));
+        let at = |x: Scalar| {
+            let other = Aabb3::from_center_half_extents(Vec3::new(x, 0.2, -0.1), Vec3::splat(0.5));
+            intersects(
+                |d| unit.support(d),
+                |d| other.support(d),
+                Vec3::new(-x, -0.2, 0.1),
+                EPS,
+            )
+        };
+        assert!(at(0.9));
+        assert!(at(1.0));
+        assert!(!at(1.01));
+    }
+}
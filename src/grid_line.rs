@@ -0,0 +1,220 @@
+//! Integer line rasterization between grid cells.
+//!
+//! Both iterators are symmetric: walking from `b` to `a` visits exactly the cells of walking
+//! from `a` to `b`, in reverse order. Two players checking line of sight to each other always
+//! agree, whichever of them the server happens to evaluate first.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{grid_line, IVec2};
+//!
+//! let a = IVec2::new(0, 0);
+//! let b = IVec2::new(4, 1);
+//! let there: Vec<IVec2> = grid_line::bresenham(a, b).collect();
+//! let mut back: Vec<IVec2> = grid_line::bresenham(b, a).collect();
+//! back.reverse();
+//! assert_eq!(there, back);
+//! assert_eq!(there.len(), 5);
+//! ```
+
+use crate::IVec2;
+
+/// Returns an iterator over the 8-connected Bresenham line from `a` to `b`, both included.
+///
+/// Exactly one cell is visited per step along the major axis.
+pub fn bresenham(a: IVec2, b: IVec2) -> Bresenham {
+    let (dx, dy) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let x_major = dx.abs() >= dy.abs();
+    let (major, minor) = if x_major { (dx, dy) } else { (dy, dx) };
+    Bresenham {
+        start: a,
+        x_major,
+        major_step: major.signum(),
+        minor_delta: minor,
+        steps: major.abs(),
+        index: 0,
+    }
+}
+
+/// Returns an iterator over the 4-connected supercover line from `a` to `b`, both included.
+///
+/// Every cell the segment between the two cell centers passes through is visited. Where the
+/// segment passes exactly through a cell corner, both cells beside the corner are visited too,
+/// the one along X first.
+pub fn supercover(a: IVec2, b: IVec2) -> Supercover {
+    let (dx, dy) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    Supercover {
+        current: a,
+        step: IVec2::new(dx.signum() as i32, dy.signum() as i32),
+        n: (dx.abs(), dy.abs()),
+        i: (0, 0),
+        started: false,
+        queued: [a; 2],
+        queued_len: 0,
+    }
+}
+
+/// Iterator returned by [`bresenham`].
+#[derive(Debug, Clone)]
+pub struct Bresenham {
+    start: IVec2,
+    x_major: bool,
+    major_step: i64,
+    minor_delta: i64,
+    steps: i64,
+    index: i64,
+}
+
+impl Iterator for Bresenham {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if self.index > self.steps {
+            return None;
+        }
+        let i = self.index;
+        self.index += 1;
+        // The minor offset is `round(i * minor / steps)` with ties rounded up. Evaluating it
+        // exactly per step, rather than with an accumulated error term, is what makes the line
+        // identical in both directions.
+        let minor = if self.steps == 0 {
+            0
+        } else {
+            (2 * i * self.minor_delta + self.steps).div_euclid(2 * self.steps)
+        };
+        let major = i * self.major_step;
+        let (dx, dy) = if self.x_major {
+            (major, minor)
+        } else {
+            (minor, major)
+        };
+        Some(IVec2::new(
+            (self.start.x as i64 + dx) as i32,
+            (self.start.y as i64 + dy) as i32,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.steps + 1 - self.index).max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Bresenham {}
+
+/// Iterator returned by [`supercover`].
+#[derive(Debug, Clone)]
+pub struct Supercover {
+    current: IVec2,
+    step: IVec2,
+    n: (i64, i64),
+    i: (i64, i64),
+    started: bool,
+    queued: [IVec2; 2],
+    queued_len: usize,
+}
+
+impl Iterator for Supercover {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if self.queued_len > 0 {
+            let cell = self.queued[2 - self.queued_len];
+            self.queued_len -= 1;
+            return Some(cell);
+        }
+        if !self.started {
+            self.started = true;
+            return Some(self.current);
+        }
+        let ((nx, ny), (ix, iy)) = (self.n, self.i);
+        if ix == nx && iy == ny {
+            return None;
+        }
+        // Compares `(ix + 0.5) / nx` with `(iy + 0.5) / ny`: which grid line the segment
+        // crosses next.
+        let decision = (1 + 2 * ix) * ny - (1 + 2 * iy) * nx;
+        if decision == 0 {
+            let side_x = self.current + IVec2::new(self.step.x, 0);
+            let side_y = self.current + IVec2::new(0, self.step.y);
+            self.current += self.step;
+            self.i = (ix + 1, iy + 1);
+            self.queued = [side_y, self.current];
+            self.queued_len = 2;
+            return Some(side_x);
+        }
+        if decision < 0 {
+            self.current.x += self.step.x;
+            self.i.0 += 1;
+        } else {
+            self.current.y += self.step.y;
+            self.i.1 += 1;
+        }
+        Some(self.current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints() -> Vec<(IVec2, IVec2)> {
+        let mut pairs = Vec::new();
+        for &(x, y) in &[
+            (0, 0),
+            (5, 2),
+            (-3, 7),
+            (4, -4),
+            (-6, -1),
+            (2, 9),
+            (0, 3),
+            (-2, -2),
+        ] {
+            pairs.push((IVec2::new(1, -1), IVec2::new(x, y)));
+            pairs.push((IVec2::new(x, y), IVec2::new(-4, 3)));
+        }
+        pairs
+    }
+
+    #[test]
+    fn bresenham_is_symmetric_and_8_connected() {
+        for (a, b) in endpoints() {
+            let there: Vec<IVec2> = bresenham(a, b).collect();
+            let mut back: Vec<IVec2> = bresenham(b, a).collect();
+            back.reverse();
+            assert_eq!(there, back, "{a:?} -> {b:?}");
+            assert_eq!(there.first(), Some(&a));
+            assert_eq!(there.last(), Some(&b));
+            assert_eq!(there.len(), bresenham(a, b).len());
+            for pair in there.windows(2) {
+                let d = pair[1] - pair[0];
+                assert!(d.x.abs() <= 1 && d.y.abs() <= 1 && d != IVec2::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn supercover_is_symmetric_and_4_connected() {
+        for (a, b) in endpoints() {
+            let there: Vec<IVec2> = supercover(a, b).collect();
+            let mut back: Vec<IVec2> = supercover(b, a).collect();
+            back.reverse();
+            assert_eq!(there, back, "{a:?} -> {b:?}");
+            assert_eq!(there.first(), Some(&a));
+            assert_eq!(there.last(), Some(&b));
+        }
+        // Corner crossings visit both neighbours of the corner.
+        let diagonal: Vec<IVec2> = supercover(IVec2::ZERO, IVec2::new(2, 2)).collect();
+        let expected = [(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)];
+        assert_eq!(diagonal, expected.map(|(x, y)| IVec2::new(x, y)));
+
+        // Without corner crossings every step is to an edge neighbour.
+        let straight: Vec<IVec2> = supercover(IVec2::new(0, 0), IVec2::new(4, 1)).collect();
+        assert_eq!(straight.len(), 6);
+        for pair in straight.windows(2) {
+            let d = pair[1] - pair[0];
+            assert_eq!(d.x.abs() + d.y.abs(), 1);
+        }
+        assert_eq!(supercover(IVec2::ONE, IVec2::ONE).count(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{Scalar, Vec2, Vec3, float};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A regular grid of terrain heights over the XZ plane.
+///
+/// Sample `(i, j)` sits at `origin + (i, j) * cell_size` in XZ, with its height along Y stored at
+/// `heights()[j * columns + i]`. Between samples the surface is bilinearly interpolated.
+///
+/// The grid size and the samples are private so they always agree; edit heights in place
+/// with [`Heightfield::heights_mut`].
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Heightfield, Vec2};
+///
+/// // A 2x2 cell ramp rising along X.
+/// let ramp = Heightfield::new(Vec2::ZERO, 1.0, 3, 3, [0.0, 1.0, 2.0].repeat(3));
+/// assert_eq!(ramp.height_at(Vec2::new(1.5, 0.5)), Some(1.5));
+/// assert_eq!(ramp.height_at(Vec2::new(3.0, 0.0)), None);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Heightfield {
+    /// World XZ position of sample `(0, 0)`.
+    pub origin: Vec2,
+    /// Distance between neighbouring samples.
+    pub cell_size: Scalar,
+    columns: u32,
+    rows: u32,
+    heights: Vec<Scalar>,
+}
+
+const SAMPLE_COUNT_MISMATCH: &str = "heightfield needs columns * rows samples";
+
+impl Heightfield {
+    /// Creates a heightfield.
+    ///
+    /// # Panics
+    /// Panics if `heights` does not hold exactly `columns * rows` samples.
+    pub fn new(
+        origin: Vec2,
+        cell_size: Scalar,
+        columns: u32,
+        rows: u32,
+        heights: Vec<Scalar>,
+    ) -> Self {
+        assert_eq!(
+            heights.len(),
+            columns as usize * rows as usize,
+            "{SAMPLE_COUNT_MISMATCH}"
+        );
+        Heightfield {
+            origin,
+            cell_size,
+            columns,
+            rows,
+            heights,
+        }
+    }
+
+    /// Returns the number of samples along X.
+    #[inline]
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// Returns the number of samples along Z.
+    #[inline]
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Returns the heights in row-major order (X varies fastest).
+    #[inline]
+    pub fn heights(&self) -> &[Scalar] {
+        &self.heights
+    }
+
+    /// Returns the heights in row-major order for editing in place.
+    #[inline]
+    pub fn heights_mut(&mut self) -> &mut [Scalar] {
+        &mut self.heights
+    }
+
+    /// Returns the height of sample `(i, j)`.
+    ///
+    /// # Panics
+    /// Panics if the sample is out of range.
+    #[inline]
+    pub fn height(&self, i: u32, j: u32) -> Scalar {
+        assert!(i < self.columns && j < self.rows, "sample out of range");
+        self.heights[j as usize * self.columns as usize + i as usize]
+    }
+
+    /// Returns the number of cells along X and Z.
+    #[inline]
+    pub fn cells(&self) -> (u32, u32) {
+        (self.columns.saturating_sub(1), self.rows.saturating_sub(1))
+    }
+
+    /// Returns the world XZ extent covered by the cells, as `(min, max)`.
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let (cx, cz) = self.cells();
+        let size = Vec2::new(cx as Scalar, cz as Scalar) * self.cell_size;
+        (self.origin, self.origin + size)
+    }
+
+    /// Returns the interpolated height at world position `xz`, or `None` outside the grid.
+    pub fn height_at(&self, xz: Vec2) -> Option<Scalar> {
+        let (i, j, u, v) = self.locate(xz)?;
+        let [h00, b, c, d] = self.cell_coefficients(i, j);
+        Some(h00 + b * u + c * v + d * u * v)
+    }
+
+    /// Returns the unit surface normal at world position `xz`, or `None` outside the grid.
+    pub fn normal_at(&self, xz: Vec2) -> Option<Vec3> {
+        let (i, j, u, v) = self.locate(xz)?;
+        Some(self.cell_normal(i, j, u, v))
+    }
+
+    /// Returns the cell containing `xz` and the local `(u, v)` coordinates in `[0, 1]` within it.
+    fn locate(&self, xz: Vec2) -> Option<(u32, u32, Scalar, Scalar)> {
+        let (cx, cz) = self.cells();
+        if cx == 0 || cz == 0 {
+            return None;
+        }
+        let local = (xz - self.origin) / self.cell_size;
+        if local.x < 0.0 || local.y < 0.0 || local.x > cx as Scalar || local.y > cz as Scalar {
+            return None;
+        }
+        // Points on the far edges belong to the last cell.
+        let i = (float::floor(local.x) as u32).min(cx - 1);
+        let j = (float::floor(local.y) as u32).min(cz - 1);
+        Some((i, j, local.x - i as Scalar, local.y - j as Scalar))
+    }
+
+    /// Returns `[h00, b, c, d]` such that the height inside cell `(i, j)` is
+    /// `h00 + b * u + c * v + d * u * v` for local coordinates `(u, v)`.
+    pub(crate) fn cell_coefficients(&self, i: u32, j: u32) -> [Scalar; 4] {
+        let h00 = self.height(i, j);
+        let h10 = self.height(i + 1, j);
+        let h01 = self.height(i, j + 1);
+        let h11 = self.height(i + 1, j + 1);
+        [h00, h10 - h00, h01 - h00, h00 - h10 - h01 + h11]
+    }
+
+    /// Returns the unit surface normal inside cell `(i, j)` at local coordinates `(u, v)`.
+    pub(crate) fn cell_normal(&self, i: u32, j: u32, u: Scalar, v: Scalar) -> Vec3 {
+        let [_, b, c, d] = self.cell_coefficients(i, j);
+        let dh_dx = (b + d * v) / self.cell_size;
+        let dh_dz = (c + d * u) / self.cell_size;
+        let n = Vec3::new(-dh_dx, 1.0, -dh_dz);
+        n / n.length()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Heightfield {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Heightfield")]
+        struct Fields {
+            origin: Vec2,
+            cell_size: Scalar,
+            columns: u32,
+            rows: u32,
+            heights: Vec<Scalar>,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        if fields.heights.len() != fields.columns as usize * fields.rows as usize {
+            return Err(serde::de::Error::custom(SAMPLE_COUNT_MISMATCH));
+        }
+        Ok(Heightfield {
+            origin: fields.origin,
+            cell_size: fields.cell_size,
+            columns: fields.columns,
+            rows: fields.rows,
+            heights: fields.heights,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    fn saddle() -> Heightfield {
+        Heightfield::new(Vec2::new(10.0, 20.0), 2.0, 2, 2, vec![0.0, 1.0, 1.0, 0.0])
+    }
+
+    #[test]
+    fn height_at_interpolates_bilinearly() {
+        let hf = saddle();
+        assert_eq!(hf.height_at(Vec2::new(10.0, 20.0)), Some(0.0));
+        assert_eq!(hf.height_at(Vec2::new(11.0, 21.0)), Some(0.5));
+        assert_eq!(hf.height_at(Vec2::new(12.0, 20.0)), Some(1.0));
+        assert_eq!(hf.height_at(Vec2::new(9.0, 21.0)), None);
+        assert_eq!(hf.bounds(), (Vec2::new(10.0, 20.0), Vec2::new(12.0, 22.0)));
+    }
+
+    #[test]
+    fn heights_edit_in_place() {
+        let mut hf = saddle();
+        hf.heights_mut()[3] = 2.0;
+        assert_eq!(hf.height(1, 1), 2.0);
+        assert_eq!(hf.heights(), [0.0, 1.0, 1.0, 2.0]);
+        assert_eq!((hf.columns(), hf.rows()), (2, 2));
+    }
+
+    #[test]
+    fn normal_at_follows_slope() {
+        let ramp = Heightfield::new(Vec2::ZERO, 1.0, 2, 2, vec![0.0, 1.0, 0.0, 1.0]);
+        let n = ramp.normal_at(Vec2::new(0.5, 0.5)).unwrap();
+        let expected = Vec3::new(-1.0, 1.0, 0.0) / (2.0 as Scalar).sqrt();
+        assert!(n.distance(expected) <= EPS);
+    }
+
+    #[test]
+    #[should_panic(expected = "columns * rows")]
+    fn new_checks_sample_count() {
+        Heightfield::new(Vec2::ZERO, 1.0, 3, 3, vec![0.0; 4]);
+    }
+}
@@ -0,0 +1,364 @@
+use crate::float::round_f64;
+use crate::{Scalar, Vec2, float};
+use alloc::{vec, vec::Vec};
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+const SQRT_3: Scalar = 1.732_050_807_568_877_2_f64 as _;
+
+/// A hexagon in axial coordinates.
+///
+/// The implicit third cube coordinate is `s = -q - r`. Axial coordinates are what gets stored;
+/// [`HexLayout`] converts them to and from world positions.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::Hex;
+///
+/// let a = Hex::new(0, 0);
+/// let b = Hex::new(3, -1);
+/// assert_eq!(a.distance(b), 3);
+/// assert_eq!(a.ring(1).len(), 6);
+/// assert_eq!(a.line_to(b).len(), 4);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub const ZERO: Hex = Hex::new(0, 0);
+
+    /// The six neighbour offsets, starting at `+q` and turning towards `-r`.
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+        Hex::new(0, 1),
+    ];
+
+    #[inline(always)]
+    pub const fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// Creates a hex from cube coordinates, which must satisfy `q + r + s == 0`.
+    #[inline]
+    pub const fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        debug_assert!(q + r + s == 0);
+        Hex::new(q, r)
+    }
+
+    /// Returns the third cube coordinate, `-q - r`.
+    #[inline(always)]
+    pub const fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// Rounds fractional axial coordinates to the hex containing them.
+    pub fn round(q: Scalar, r: Scalar) -> Hex {
+        // Rounded in f64 so `line_to` can share it without losing its nudge.
+        cube_round(q as _, r as _)
+    }
+
+    /// Returns the number of steps from the origin.
+    #[inline]
+    pub const fn length(&self) -> i32 {
+        (self.q.abs() + self.r.abs() + self.s().abs()) / 2
+    }
+
+    /// Returns the number of steps between `self` and `other`.
+    #[inline]
+    pub fn distance(&self, other: Hex) -> i32 {
+        (*self - other).length()
+    }
+
+    /// Returns the neighbour in `direction`, an index into [`Hex::DIRECTIONS`] taken modulo 6.
+    #[inline]
+    pub fn neighbor(&self, direction: usize) -> Hex {
+        *self + Hex::DIRECTIONS[direction % 6]
+    }
+
+    /// Returns all six neighbours in [`Hex::DIRECTIONS`] order.
+    #[inline]
+    pub fn neighbors(&self) -> [Hex; 6] {
+        Hex::DIRECTIONS.map(|d| *self + d)
+    }
+
+    /// Returns the hexes exactly `radius` steps away, walking once around the ring.
+    ///
+    /// A radius of `0` returns just `self`.
+    pub fn ring(&self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let radius = radius as i32;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        let mut hex = *self + Hex::DIRECTIONS[4] * radius;
+        for direction in 0..6 {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.neighbor(direction);
+            }
+        }
+        ring
+    }
+
+    /// Returns every hex within `radius` steps, ring by ring outwards from `self`.
+    pub fn spiral(&self, radius: u32) -> Vec<Hex> {
+        let mut hexes = Vec::with_capacity(1 + 3 * radius as usize * (radius as usize + 1));
+        for r in 0..=radius {
+            hexes.extend(self.ring(r));
+        }
+        hexes
+    }
+
+    /// Returns the hexes on the straight line from `self` to `other`, both included.
+    pub fn line_to(&self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![*self];
+        }
+        // Nudging the start keeps samples that land exactly on an edge from flip-flopping.
+        let (aq, ar) = (self.q as f64 + 1.0e-6, self.r as f64 + 2.0e-6);
+        let (bq, br) = (other.q as f64 + 1.0e-6, other.r as f64 + 2.0e-6);
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                cube_round(aq + (bq - aq) * t, ar + (br - ar) * t)
+            })
+            .collect()
+    }
+}
+
+/// Rounds fractional cube coordinates, fixing up the component with the largest rounding error
+/// so the result stays on the `q + r + s == 0` plane.
+fn cube_round(q: f64, r: f64) -> Hex {
+    let s = -q - r;
+    let (rq, rr, rs) = (round_f64(q), round_f64(r), round_f64(s));
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        Hex::new((-rr - rs) as i32, rr as i32)
+    } else if dr > ds {
+        Hex::new(rq as i32, (-rq - rs) as i32)
+    } else {
+        Hex::new(rq as i32, rr as i32)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn add(self, rhs: Hex) -> Hex {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    #[inline]
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn sub(self, rhs: Hex) -> Hex {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Hex) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> Hex {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+/// Which way the hexagons of a [`HexLayout`] point.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexOrientation {
+    /// A vertex points along +Y; rows of hexes run along X.
+    #[default]
+    Pointy,
+    /// An edge faces +Y; columns of hexes run along Y.
+    Flat,
+}
+
+/// Maps between [`Hex`] coordinates and world positions.
+///
+/// `size` is the distance from a hex's center to its corners along each axis; pass equal
+/// components for regular hexagons. Hex `(0, 0)` is centered on `origin`, and `+r` moves
+/// towards `+y`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Hex, HexLayout, HexOrientation, Vec2};
+///
+/// let layout = HexLayout::new(HexOrientation::Flat, Vec2::splat(10.0), Vec2::ZERO);
+/// let hex = Hex::new(2, -1);
+/// assert_eq!(layout.world_to_hex(layout.hex_to_world(hex)), hex);
+/// assert_eq!(layout.world_to_hex(Vec2::new(4.0, -3.0)), Hex::ZERO);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexLayout {
+    pub orientation: HexOrientation,
+    pub size: Vec2,
+    pub origin: Vec2,
+}
+
+impl HexLayout {
+    #[inline(always)]
+    pub const fn new(orientation: HexOrientation, size: Vec2, origin: Vec2) -> Self {
+        HexLayout {
+            orientation,
+            size,
+            origin,
+        }
+    }
+
+    /// Returns the world position of the center of `hex`.
+    pub fn hex_to_world(&self, hex: Hex) -> Vec2 {
+        let (q, r) = (hex.q as Scalar, hex.r as Scalar);
+        let (x, y) = match self.orientation {
+            HexOrientation::Pointy => (SQRT_3 * q + SQRT_3 / 2.0 * r, 1.5 * r),
+            HexOrientation::Flat => (1.5 * q, SQRT_3 / 2.0 * q + SQRT_3 * r),
+        };
+        Vec2::new(
+            self.origin.x + x * self.size.x,
+            self.origin.y + y * self.size.y,
+        )
+    }
+
+    /// Returns the fractional axial coordinates `(q, r)` of a world position.
+    pub fn world_to_fractional(&self, position: Vec2) -> (Scalar, Scalar) {
+        let x = (position.x - self.origin.x) / self.size.x;
+        let y = (position.y - self.origin.y) / self.size.y;
+        match self.orientation {
+            HexOrientation::Pointy => (SQRT_3 / 3.0 * x - y / 3.0, 2.0 / 3.0 * y),
+            HexOrientation::Flat => (2.0 / 3.0 * x, -x / 3.0 + SQRT_3 / 3.0 * y),
+        }
+    }
+
+    /// Returns the hex containing a world position.
+    #[inline]
+    pub fn world_to_hex(&self, position: Vec2) -> Hex {
+        let (q, r) = self.world_to_fractional(position);
+        Hex::round(q, r)
+    }
+
+    /// Returns the six corners of `hex`, counter-clockwise.
+    pub fn corners(&self, hex: Hex) -> [Vec2; 6] {
+        let center = self.hex_to_world(hex);
+        let offset = match self.orientation {
+            HexOrientation::Pointy => -30.0,
+            HexOrientation::Flat => 0.0,
+        };
+        core::array::from_fn(|i| {
+            let angle = ((60.0 * i as Scalar) + offset).to_radians();
+            Vec2::new(
+                center.x + self.size.x * float::cos(angle),
+                center.y + self.size.y * float::sin(angle),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn distance_and_neighbors() {
+        let h = Hex::new(2, -3);
+        assert_eq!(h.s(), 1);
+        for n in h.neighbors() {
+            assert_eq!(h.distance(n), 1);
+        }
+        assert_eq!(h.neighbor(6), h.neighbor(0));
+        assert_eq!(Hex::ZERO.distance(Hex::new(3, -1)), 3);
+        assert_eq!(Hex::from_cube(-2, 5, -3).length(), 5);
+    }
+
+    #[test]
+    fn rings_and_spirals_have_expected_sizes() {
+        let center = Hex::new(-1, 4);
+        for radius in 1..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|h| h.distance(center) == radius as i32));
+            // Consecutive ring entries are neighbours, including the wrap-around.
+            for i in 0..ring.len() {
+                assert_eq!(ring[i].distance(ring[(i + 1) % ring.len()]), 1);
+            }
+        }
+        let spiral = center.spiral(3);
+        assert_eq!(spiral.len(), 37);
+        assert_eq!(spiral[0], center);
+    }
+
+    #[test]
+    fn line_steps_between_neighbours() {
+        let a = Hex::new(-3, 1);
+        let b = Hex::new(4, -5);
+        let line = a.line_to(b);
+        assert_eq!(line.len() as i32, a.distance(b) + 1);
+        assert_eq!((line[0], *line.last().unwrap()), (a, b));
+        for pair in line.windows(2) {
+            assert_eq!(pair[0].distance(pair[1]), 1);
+        }
+        assert_eq!(a.line_to(a), vec![a]);
+    }
+
+    #[test]
+    fn layout_round_trips_for_both_orientations() {
+        for orientation in [HexOrientation::Pointy, HexOrientation::Flat] {
+            let layout = HexLayout::new(orientation, Vec2::new(4.0, 3.0), Vec2::new(10.0, -2.0));
+            for hex in Hex::new(1, 1).spiral(3) {
+                let center = layout.hex_to_world(hex);
+                assert_eq!(layout.world_to_hex(center), hex);
+                // Points just inside each corner still belong to the hex.
+                for corner in layout.corners(hex) {
+                    let inside = center + (corner - center) * 0.9;
+                    assert_eq!(layout.world_to_hex(inside), hex);
+                }
+            }
+            let corners = layout.corners(Hex::ZERO);
+            let edge = (corners[1] - corners[0]).length();
+            assert!(edge > EPS);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Hilbert curve indexing for 2D grid coordinates.
+//!
+//! Like a [Morton code](crate::morton), a Hilbert index turns a 2D cell into a single integer
+//! that can be stored in an indexed table column. The Hilbert curve never jumps: consecutive
+//! indices are always neighbouring cells, so a query rectangle breaks into fewer, longer index
+//! ranges than with Z-order.
+//!
+//! A curve of `order` `n` covers a `2^n x 2^n` grid with indices in `[0, 4^n)`, for `n` in
+//! `1..=32`.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::hilbert;
+//!
+//! // The order-1 curve visits (0, 0), (0, 1), (1, 1), (1, 0).
+//! assert_eq!(hilbert::encode(1, 0, 1), 1);
+//! assert_eq!(hilbert::decode(1, 3), (1, 0));
+//!
+//! // The 2x2 block in the corner of an order-4 grid is one contiguous range.
+//! assert_eq!(hilbert::cover_rect(4, (0, 0), (1, 1), 4), vec![(0, 3)]);
+//! ```
+
+use crate::{Aabb2, Vec2, morton};
+use alloc::vec::Vec;
+
+/// Reflects and transposes `(x, y)` into the orientation of the sub-square `(rx, ry)`.
+#[inline]
+fn rotate(side: u64, x: &mut u64, y: &mut u64, rx: u64, ry: u64) {
+    if ry == 0 {
+        if rx == 1 {
+            *x = side - 1 - *x;
+            *y = side - 1 - *y;
+        }
+        core::mem::swap(x, y);
+    }
+}
+
+/// Returns the index of cell `(x, y)` along the Hilbert curve of `order`.
+///
+/// Coordinate bits above `order` are ignored.
+pub fn encode(order: u32, x: u32, y: u32) -> u64 {
+    debug_assert!((1..=32).contains(&order), "order must be in 1..=32");
+    let side = 1u64 << order;
+    let (mut x, mut y) = (x as u64 & (side - 1), y as u64 & (side - 1));
+    let mut index = 0u64;
+    let mut s = side >> 1;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        index += s * s * ((3 * rx) ^ ry);
+        rotate(side, &mut x, &mut y, rx, ry);
+        s >>= 1;
+    }
+    index
+}
+
+/// Returns the cell `(x, y)` at `index` along the Hilbert curve of `order`.
+pub fn decode(order: u32, index: u64) -> (u32, u32) {
+    debug_assert!((1..=32).contains(&order), "order must be in 1..=32");
+    let side = 1u64 << order;
+    let (mut x, mut y) = (0u64, 0u64);
+    let mut t = index;
+    let mut s = 1u64;
+    while s < side {
+        let rx = 1 & (t / 2);
+        let ry = 1 & (t ^ rx);
+        rotate(s, &mut x, &mut y, rx, ry);
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s <<= 1;
+    }
+    (x as u32, y as u32)
+}
+
+/// Returns sorted, non-overlapping inclusive index ranges `(start, end)` covering every cell in
+/// the inclusive rectangle from `min` to `max`.
+///
+/// The curve is subdivided at most `max_depth` levels. Blocks still only partly inside the
+/// rectangle at that depth are included whole, so a smaller depth trades a few extra cells for
+/// fewer ranges; a depth of `order` covers the rectangle exactly. Adjacent ranges are merged.
+pub fn cover_rect(order: u32, min: (u32, u32), max: (u32, u32), max_depth: u32) -> Vec<(u64, u64)> {
+    debug_assert!((1..=32).contains(&order), "order must be in 1..=32");
+    let mut ranges = Vec::new();
+    if min.0 > max.0 || min.1 > max.1 {
+        return ranges;
+    }
+    let rect = ((min.0 as u64, min.1 as u64), (max.0 as u64, max.1 as u64));
+    cover_block(order, rect, 0, order, max_depth, &mut ranges);
+    ranges
+}
+
+/// Recursive step of [`cover_rect`] for the aligned block of `4^level` indices starting at
+/// `first`.
+fn cover_block(
+    order: u32,
+    rect: ((u64, u64), (u64, u64)),
+    first: u64,
+    level: u32,
+    depth_left: u32,
+    ranges: &mut Vec<(u64, u64)>,
+) {
+    // Every aligned index block maps to an aligned square, found from its first cell.
+    let side = 1u64 << level;
+    let (x, y) = decode(order, first);
+    let x0 = x as u64 & !(side - 1);
+    let y0 = y as u64 & !(side - 1);
+    let (x1, y1) = (x0 + side - 1, y0 + side - 1);
+    let ((min_x, min_y), (max_x, max_y)) = rect;
+    if x1 < min_x || x0 > max_x || y1 < min_y || y0 > max_y {
+        return;
+    }
+    let inside = x0 >= min_x && x1 <= max_x && y0 >= min_y && y1 <= max_y;
+    if inside || level == 0 || depth_left == 0 {
+        // `4^level - 1`, written to stay in range for `level == 32`.
+        let span = if level == 0 {
+            0
+        } else {
+            u64::MAX >> (64 - 2 * level)
+        };
+        let last = first + span;
+        match ranges.last_mut() {
+            Some(previous) if previous.1.wrapping_add(1) == first => previous.1 = last,
+            _ => ranges.push((first, last)),
+        }
+        return;
+    }
+    let child_len = 1u64 << (2 * (level - 1));
+    for k in 0..4 {
+        cover_block(
+            order,
+            rect,
+            first + k * child_len,
+            level - 1,
+            depth_left - 1,
+            ranges,
+        );
+    }
+}
+
+/// Quantizes `position` inside `bounds` onto the grid of `order` and returns its index.
+pub fn encode_position(position: Vec2, bounds: &Aabb2, order: u32) -> u64 {
+    let (x, y) = quantize(position, bounds, order);
+    encode(order, x, y)
+}
+
+/// Returns the center of the cell at `index`, the inverse of [`encode_position`].
+pub fn decode_position(index: u64, bounds: &Aabb2, order: u32) -> Vec2 {
+    let (x, y) = decode(order, index);
+    Vec2::new(
+        morton::dequantize(x, bounds.min.x, bounds.max.x, order),
+        morton::dequantize(y, bounds.min.y, bounds.max.y, order),
+    )
+}
+
+/// Returns index ranges covering every cell that overlaps `query` on the grid of `order`
+/// spanning `bounds`. See [`cover_rect`] for `max_depth`.
+pub fn cover_aabb(bounds: &Aabb2, order: u32, query: &Aabb2, max_depth: u32) -> Vec<(u64, u64)> {
+    if query.min.x > query.max.x || query.min.y > query.max.y {
+        return Vec::new();
+    }
+    let min = quantize(query.min, bounds, order);
+    let max = quantize(query.max, bounds, order);
+    cover_rect(order, min, max, max_depth)
+}
+
+#[inline]
+fn quantize(position: Vec2, bounds: &Aabb2, order: u32) -> (u32, u32) {
+    (
+        morton::quantize(position.x, bounds.min.x, bounds.max.x, order),
+        morton::quantize(position.y, bounds.min.y, bounds.max.y, order),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_is_continuous_and_bijective() {
+        for order in 1..=4 {
+            let side = 1u32 << order;
+            let mut seen = vec![false; (side * side) as usize];
+            let mut previous = decode(order, 0);
+            for index in 0..(side * side) as u64 {
+                let (x, y) = decode(order, index);
+                assert_eq!(encode(order, x, y), index);
+                seen[(y * side + x) as usize] = true;
+                // Consecutive cells are edge neighbours.
+                assert!(index == 0 || previous.0.abs_diff(x) + previous.1.abs_diff(y) == 1);
+                previous = (x, y);
+            }
+            assert!(seen.iter().all(|&s| s));
+        }
+    }
+
+    #[test]
+    fn large_orders_round_trip() {
+        for &(x, y) in &[(0u32, 0u32), (u32::MAX, 0), (123_456_789, 3_000_000_000)] {
+            assert_eq!(decode(32, encode(32, x, y)), (x, y));
+        }
+        assert_eq!(decode(16, encode(16, 65535, 1)), (65535, 1));
+    }
+
+    #[test]
+    fn cover_rect_matches_brute_force() {
+        let order = 5;
+        let (min, max) = ((3, 7), (20, 12));
+        let ranges = cover_rect(order, min, max, order);
+        let mut expected: Vec<u64> = Vec::new();
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                expected.push(encode(order, x, y));
+            }
+        }
+        expected.sort();
+        let covered: Vec<u64> = ranges.iter().flat_map(|&(a, b)| a..=b).collect();
+        assert_eq!(covered, expected);
+        // Ranges are sorted and separated by gaps.
+        assert!(ranges.windows(2).all(|w| w[0].1 + 1 < w[1].0));
+
+        // A shallow depth over-covers with fewer ranges.
+        let coarse = cover_rect(order, min, max, 2);
+        assert!(coarse.len() <= ranges.len());
+        for index in expected {
+            assert!(coarse.iter().any(|&(a, b)| (a..=b).contains(&index)));
+        }
+    }
+
+    #[test]
+    fn cover_whole_grid_is_one_range() {
+        assert_eq!(
+            cover_rect(32, (0, 0), (u32::MAX, u32::MAX), 32),
+            vec![(0, u64::MAX)]
+        );
+        assert_eq!(cover_rect(3, (5, 5), (5, 5), 3).len(), 1);
+        assert!(cover_rect(3, (5, 5), (4, 5), 3).is_empty());
+    }
+
+    #[test]
+    fn positions_use_the_bounds_grid() {
+        let bounds = Aabb2::new(Vec2::ZERO, Vec2::new(16.0, 16.0));
+        let index = encode_position(Vec2::new(0.5, 1.5), &bounds, 4);
+        assert_eq!(index, encode(4, 0, 1));
+        assert_eq!(decode_position(index, &bounds, 4), Vec2::new(0.5, 1.5));
+        let query = Aabb2::new(Vec2::new(0.2, 0.2), Vec2::new(1.9, 1.9));
+        assert_eq!(cover_aabb(&bounds, 4, &query, 4), vec![(0, 3)]);
+    }
+}
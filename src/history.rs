@@ -0,0 +1,306 @@
+use crate::{Interpolate, Scalar};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A value recorded at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryEntry<T> {
+    /// Time in seconds on the server's clock.
+    pub time: Scalar,
+    pub value: T,
+}
+
+/// A fixed-capacity ring buffer of timestamped values, for lag compensation.
+///
+/// Record each entity's [`Transform`](crate::Transform) (or position) once per tick; when a
+/// shot arrives, rewind its hitbox to the time the shooter saw with
+/// [`HistoryBuffer::sample_at`]. Once full, each new entry overwrites the oldest, so memory
+/// stays constant and the covered time span is `capacity` ticks.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{HistoryBuffer, Scalar, Vec3};
+///
+/// let mut history = HistoryBuffer::new(4);
+/// for tick in 0..6 {
+///     history.push(tick as Scalar * 0.5, Vec3::new(tick as Scalar, 0.0, 0.0));
+/// }
+/// assert_eq!(history.len(), 4);
+/// assert_eq!(history.oldest().unwrap().time, 1.0);
+/// assert_eq!(history.sample_at(1.75), Some(Vec3::new(3.5, 0.0, 0.0)));
+/// assert_eq!(history.sample_at(0.5), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HistoryBuffer<T> {
+    capacity: u32,
+    /// Index of the oldest entry once the buffer has wrapped.
+    start: u32,
+    entries: Vec<HistoryEntry<T>>,
+}
+
+impl<T: Interpolate> HistoryBuffer<T> {
+    /// Creates an empty buffer holding up to `capacity` entries.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: u32) -> Self {
+        assert!(capacity > 0, "history buffer needs a non-zero capacity");
+        HistoryBuffer {
+            capacity,
+            start: 0,
+            entries: Vec::with_capacity(capacity as usize),
+        }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every entry.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.start = 0;
+    }
+
+    /// Returns the entry `index` places after the oldest.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&HistoryEntry<T>> {
+        let len = self.entries.len();
+        (index < len).then(|| &self.entries[(self.start as usize + index) % len])
+    }
+
+    #[inline]
+    pub fn oldest(&self) -> Option<&HistoryEntry<T>> {
+        self.get(0)
+    }
+
+    #[inline]
+    pub fn latest(&self) -> Option<&HistoryEntry<T>> {
+        self.get(self.entries.len().wrapping_sub(1))
+    }
+
+    /// Iterates over the entries, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &HistoryEntry<T>> {
+        let (newer, older) = self.entries.split_at(self.start as usize);
+        older.iter().chain(newer)
+    }
+
+    /// Records `value` at `time`, overwriting the oldest entry when full.
+    ///
+    /// Time must move forward: an entry at the latest time replaces it, and older times are
+    /// ignored.
+    pub fn push(&mut self, time: Scalar, value: T) {
+        let entry = HistoryEntry { time, value };
+        if let Some(latest) = self.latest() {
+            if time < latest.time {
+                return;
+            }
+            if time == latest.time {
+                let last = (self.start as usize + self.entries.len() - 1) % self.entries.len();
+                self.entries[last] = entry;
+                return;
+            }
+        }
+        if self.entries.len() < self.capacity as usize {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.start as usize] = entry;
+            self.start = (self.start + 1) % self.capacity;
+        }
+    }
+
+    /// Returns the value at `time`, blending linearly between the surrounding entries, or
+    /// `None` if `time` is outside the recorded history.
+    ///
+    /// A time too old to rewind to should usually fail validation rather than be clamped.
+    pub fn sample_at(&self, time: Scalar) -> Option<T> {
+        let (oldest, latest) = (self.oldest()?, self.latest()?);
+        if !(time >= oldest.time && time <= latest.time) {
+            return None;
+        }
+        let next = self.partition_point(|e| e.time <= time);
+        let Some(b) = self.get(next) else {
+            return Some(latest.value);
+        };
+        let a = self.get(next - 1)?;
+        Some(
+            a.value
+                .interpolate(&b.value, (time - a.time) / (b.time - a.time)),
+        )
+    }
+
+    /// Iterates over the entries no more than `duration` seconds older than the latest, oldest
+    /// first.
+    pub fn rewind_window(&self, duration: Scalar) -> impl Iterator<Item = &HistoryEntry<T>> {
+        let first = match self.latest() {
+            Some(latest) => {
+                let cutoff = latest.time - duration;
+                self.partition_point(|e| e.time < cutoff)
+            }
+            None => 0,
+        };
+        self.iter().skip(first)
+    }
+
+    /// Returns the number of leading entries for which `pred` holds, which must be a prefix.
+    fn partition_point(&self, pred: impl Fn(&HistoryEntry<T>) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.entries.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if pred(&self.entries[(self.start as usize + mid) % self.entries.len()]) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+/// Rejects a zero capacity, more entries than the capacity, and a `start` that isn't an
+/// index into a full buffer, none of which [`HistoryBuffer::push`] can produce.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for HistoryBuffer<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "HistoryBuffer")]
+        struct Fields<T> {
+            capacity: u32,
+            start: u32,
+            entries: Vec<HistoryEntry<T>>,
+        }
+
+        let Fields {
+            capacity,
+            start,
+            entries,
+        } = Fields::<T>::deserialize(deserializer)?;
+        if capacity == 0 {
+            return Err(D::Error::custom("history buffer needs a non-zero capacity"));
+        }
+        if entries.len() > capacity as usize {
+            return Err(D::Error::custom(
+                "history buffer holds more entries than its capacity",
+            ));
+        }
+        if start != 0 && (start >= capacity || entries.len() < capacity as usize) {
+            return Err(D::Error::custom("history buffer start is out of range"));
+        }
+        Ok(HistoryBuffer {
+            capacity,
+            start,
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Quat, Transform, Vec3};
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    fn times(history: &HistoryBuffer<Scalar>) -> Vec<Scalar> {
+        history.iter().map(|e| e.time).collect()
+    }
+
+    #[test]
+    fn overwrites_the_oldest_entry_when_full() {
+        let mut history = HistoryBuffer::new(3);
+        assert_eq!(history.latest(), None);
+        for i in 0..5 {
+            history.push(i as Scalar, i as Scalar * 10.0);
+        }
+        assert_eq!(times(&history), vec![2.0, 3.0, 4.0]);
+        assert_eq!(history.get(1).unwrap().value, 30.0);
+        assert_eq!(history.get(3), None);
+        assert_eq!(history.latest().unwrap().value, 40.0);
+
+        // Older times are ignored; the latest time is replaced.
+        history.push(1.0, -1.0);
+        history.push(4.0, 45.0);
+        assert_eq!(times(&history), vec![2.0, 3.0, 4.0]);
+        assert_eq!(history.latest().unwrap().value, 45.0);
+
+        history.clear();
+        assert!(history.is_empty());
+        history.push(7.0, 1.0);
+        assert_eq!(times(&history), vec![7.0]);
+    }
+
+    #[test]
+    fn samples_between_entries_across_the_wrap() {
+        let mut history = HistoryBuffer::new(4);
+        for i in 0..7 {
+            history.push(i as Scalar * 0.1, i as Scalar);
+        }
+        for i in 31..=59 {
+            let time = i as Scalar * 0.01;
+            let value = history.sample_at(time).unwrap();
+            assert!((value - time * 10.0).abs() <= 1.0e-4 as Scalar, "{time}");
+        }
+        assert_eq!(history.sample_at(0.29), None);
+        assert_eq!(history.sample_at(0.61), None);
+        assert_eq!(history.sample_at(Scalar::NAN), None);
+    }
+
+    #[test]
+    fn rewinds_transforms() {
+        let mut history = HistoryBuffer::new(8);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        history.push(
+            1.0,
+            Transform::from_translation_rotation(Vec3::ZERO, Quat::IDENTITY),
+        );
+        history.push(
+            2.0,
+            Transform::from_translation_rotation(
+                Vec3::new(4.0, 0.0, 0.0),
+                Quat::from_axis_angle(up, 1.0),
+            ),
+        );
+        let rewound = history.sample_at(1.25).unwrap();
+        assert!(rewound.translation.distance(Vec3::new(1.0, 0.0, 0.0)) <= EPS);
+        let expected = Quat::from_axis_angle(up, 0.25);
+        assert!(rewound.rotation.dot(expected) >= 1.0 - EPS);
+    }
+
+    #[test]
+    fn rewind_window_covers_the_requested_duration() {
+        let mut history = HistoryBuffer::new(10);
+        assert_eq!(history.rewind_window(1.0).count(), 0);
+        for i in 0..15 {
+            history.push(i as Scalar * 0.25, i as Scalar);
+        }
+        let window: Vec<Scalar> = history.rewind_window(0.5).map(|e| e.value).collect();
+        assert_eq!(window, vec![12.0, 13.0, 14.0]);
+        assert_eq!(history.rewind_window(100.0).count(), 10);
+        assert_eq!(history.rewind_window(0.0).count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero capacity")]
+    fn zero_capacity_panics() {
+        HistoryBuffer::<Scalar>::new(0);
+    }
+}
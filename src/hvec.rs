@@ -0,0 +1,231 @@
+use crate::{Scalar, Vec2, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 2D vector stored as IEEE 754 half-precision floats.
+///
+/// Each component keeps 11 significant bits (about 3 decimal digits) over a range of ±65504,
+/// so it suits small offsets and deltas rather than world positions. Converting rounds to
+/// the nearest half float; values beyond the range become infinite.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{HVec2, Vec2};
+///
+/// let offset = HVec2::from_vec2(Vec2::new(0.5, -1.25));
+/// assert_eq!(offset.to_vec2(), Vec2::new(0.5, -1.25));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HVec2 {
+    /// X component as half-float bits.
+    pub x: u16,
+    /// Y component as half-float bits.
+    pub y: u16,
+}
+
+/// A 3D vector stored as IEEE 754 half-precision floats. See [`HVec2`] for the precision.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{HVec3, Vec3};
+///
+/// let delta = Vec3::new(0.1, 2.0, -300.0);
+/// let packed = HVec3::from_vec3(delta);
+/// assert!(packed.to_vec3().distance(delta) < 0.1);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HVec3 {
+    /// X component as half-float bits.
+    pub x: u16,
+    /// Y component as half-float bits.
+    pub y: u16,
+    /// Z component as half-float bits.
+    pub z: u16,
+}
+
+impl HVec2 {
+    /// Rounds each component of `v` to a half float.
+    #[inline]
+    pub fn from_vec2(v: Vec2) -> Self {
+        HVec2 {
+            x: half_from_scalar(v.x),
+            y: half_from_scalar(v.y),
+        }
+    }
+
+    /// Widens back to full precision, exactly.
+    #[inline]
+    pub fn to_vec2(&self) -> Vec2 {
+        Vec2::new(scalar_from_half(self.x), scalar_from_half(self.y))
+    }
+}
+
+impl HVec3 {
+    /// Rounds each component of `v` to a half float.
+    #[inline]
+    pub fn from_vec3(v: Vec3) -> Self {
+        HVec3 {
+            x: half_from_scalar(v.x),
+            y: half_from_scalar(v.y),
+            z: half_from_scalar(v.z),
+        }
+    }
+
+    /// Widens back to full precision, exactly.
+    #[inline]
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(
+            scalar_from_half(self.x),
+            scalar_from_half(self.y),
+            scalar_from_half(self.z),
+        )
+    }
+}
+
+impl From<Vec2> for HVec2 {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        HVec2::from_vec2(v)
+    }
+}
+
+impl From<HVec2> for Vec2 {
+    #[inline]
+    fn from(h: HVec2) -> Self {
+        h.to_vec2()
+    }
+}
+
+impl From<Vec3> for HVec3 {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        HVec3::from_vec3(v)
+    }
+}
+
+impl From<HVec3> for Vec3 {
+    #[inline]
+    fn from(h: HVec3) -> Self {
+        h.to_vec3()
+    }
+}
+
+/// Rounds `value` to the nearest half float, ties to even.
+///
+/// Under `f64` the value is first rounded to `f32`, which can differ from direct rounding
+/// only for values within a hair of a tie.
+fn half_from_scalar(value: Scalar) -> u16 {
+    let single: f32 = value as _;
+    let bits = single.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Infinity stays infinite; NaN stays NaN with its payload's top bits, kept quiet.
+        let nan = if mantissa != 0 {
+            0x200 | (mantissa >> 13) as u16
+        } else {
+            0
+        };
+        return sign | 0x7c00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exponent <= 0 {
+        // Subnormal half, or zero once the value is below half the smallest subnormal.
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        return sign | round_shift(mantissa, shift) as u16;
+    }
+    // A carry out of the mantissa correctly bumps the exponent, up to infinity.
+    let rounded = round_shift(((exponent as u32) << 23) | mantissa, 13);
+    sign | rounded as u16
+}
+
+/// Shifts `value` right by `shift` bits, rounding to nearest with ties to even.
+#[inline]
+fn round_shift(value: u32, shift: u32) -> u32 {
+    let truncated = value >> shift;
+    let rest = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if rest > half || (rest == half && truncated & 1 == 1) {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
+
+/// Widens half-float bits to a [`Scalar`], exactly.
+fn scalar_from_half(half: u16) -> Scalar {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x3ff) as u32;
+    let bits = match exponent {
+        0 => {
+            // Subnormal: mantissa * 2^-24.
+            let magnitude = mantissa as Scalar / 16_777_216.0;
+            return if sign != 0 { -magnitude } else { magnitude };
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits) as _
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_half_float_round_trips() {
+        for bits in 0..=u16::MAX {
+            let value = scalar_from_half(bits);
+            if value.is_nan() {
+                assert_eq!(half_from_scalar(value) & 0x7c00, 0x7c00);
+                continue;
+            }
+            assert_eq!(half_from_scalar(value), bits, "{bits:#06x}");
+        }
+    }
+
+    #[test]
+    fn rounding_and_special_values() {
+        let h = |v: Scalar| scalar_from_half(half_from_scalar(v));
+        assert_eq!(h(1.0), 1.0);
+        assert_eq!(h(-0.0).to_bits(), (-0.0 as Scalar).to_bits());
+        assert_eq!(h(65504.0), 65504.0);
+        assert_eq!(h(65520.0), Scalar::INFINITY);
+        assert_eq!(h(-1.0e6), Scalar::NEG_INFINITY);
+        assert!(h(Scalar::NAN).is_nan());
+        // 2049 lies halfway between 2048 and 2050 and rounds to the even mantissa.
+        assert_eq!(h(2049.0), 2048.0);
+        assert_eq!(h(2051.0), 2052.0);
+        // Subnormals down to 2^-24, below half of which values flush to zero.
+        let tiny = 1.0 / 16_777_216.0 as Scalar;
+        assert_eq!(h(tiny), tiny);
+        assert_eq!(h(3.0 * tiny), 3.0 * tiny);
+        assert_eq!(h(0.4 * tiny), 0.0);
+    }
+
+    #[test]
+    fn vectors_convert_per_component() {
+        let v = Vec3::new(0.333, -7.5, 1024.0);
+        let packed = HVec3::from(v);
+        assert_eq!(Vec3::from(packed).y, -7.5);
+        assert!((packed.to_vec3().x - 0.333).abs() <= 2.0e-4 as Scalar);
+        assert_eq!(
+            HVec2::from(Vec2::new(-2.0, 0.25)).to_vec2(),
+            Vec2::new(-2.0, 0.25)
+        );
+    }
+}
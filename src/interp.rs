@@ -0,0 +1,374 @@
+//! Interpolation and falloff curves.
+//!
+//! The curve functions work on [`Scalar`] and component-wise on [`Vec2`], [`Vec3`] and
+//! [`Vec4`] through the [`ComponentWise`] trait. [`exp_decay`] works on any
+//! [`Interpolate`] type, including [`crate::Quat`].
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{interp, Scalar};
+//!
+//! // Full damage up to 2 units away, fading smoothly to none at 10 units.
+//! let falloff = |distance: Scalar| 1.0 - interp::smoothstep(2.0, 10.0, distance);
+//! assert_eq!(falloff(1.0), 1.0);
+//! assert_eq!(falloff(6.0), 0.5);
+//! assert_eq!(falloff(12.0), 0.0);
+//!
+//! // Aggro weight falling from 1.0 at full health to 0.0 at 100 damage taken.
+//! assert_eq!(interp::remap(75.0, 0.0..100.0, 1.0..0.0), 0.25);
+//! ```
+
+use crate::{Interpolate, Scalar, Vec2, Vec3, Vec4, consts, float};
+use core::ops::Range;
+
+/// Types made of [`Scalar`] lanes that the functions in [`crate::interp`] apply to one lane at
+/// a time.
+pub trait ComponentWise: Copy {
+    /// Applies `f` to each lane of `self`.
+    fn map(self, f: impl Fn(Scalar) -> Scalar) -> Self;
+
+    /// Applies `f` to matching lanes of `self` and `b`.
+    fn map2(self, b: Self, f: impl Fn(Scalar, Scalar) -> Scalar) -> Self;
+
+    /// Applies `f` to matching lanes of `self`, `b` and `c`.
+    fn map3(self, b: Self, c: Self, f: impl Fn(Scalar, Scalar, Scalar) -> Scalar) -> Self;
+}
+
+impl ComponentWise for Scalar {
+    #[inline]
+    fn map(self, f: impl Fn(Scalar) -> Scalar) -> Self {
+        f(self)
+    }
+
+    #[inline]
+    fn map2(self, b: Self, f: impl Fn(Scalar, Scalar) -> Scalar) -> Self {
+        f(self, b)
+    }
+
+    #[inline]
+    fn map3(self, b: Self, c: Self, f: impl Fn(Scalar, Scalar, Scalar) -> Scalar) -> Self {
+        f(self, b, c)
+    }
+}
+
+impl ComponentWise for Vec2 {
+    #[inline]
+    fn map(self, f: impl Fn(Scalar) -> Scalar) -> Self {
+        Vec2::new(f(self.x), f(self.y))
+    }
+
+    #[inline]
+    fn map2(self, b: Self, f: impl Fn(Scalar, Scalar) -> Scalar) -> Self {
+        Vec2::new(f(self.x, b.x), f(self.y, b.y))
+    }
+
+    #[inline]
+    fn map3(self, b: Self, c: Self, f: impl Fn(Scalar, Scalar, Scalar) -> Scalar) -> Self {
+        Vec2::new(f(self.x, b.x, c.x), f(self.y, b.y, c.y))
+    }
+}
+
+impl ComponentWise for Vec3 {
+    #[inline]
+    fn map(self, f: impl Fn(Scalar) -> Scalar) -> Self {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
+
+    #[inline]
+    fn map2(self, b: Self, f: impl Fn(Scalar, Scalar) -> Scalar) -> Self {
+        Vec3::new(f(self.x, b.x), f(self.y, b.y), f(self.z, b.z))
+    }
+
+    #[inline]
+    fn map3(self, b: Self, c: Self, f: impl Fn(Scalar, Scalar, Scalar) -> Scalar) -> Self {
+        Vec3::new(
+            f(self.x, b.x, c.x),
+            f(self.y, b.y, c.y),
+            f(self.z, b.z, c.z),
+        )
+    }
+}
+
+impl ComponentWise for Vec4 {
+    #[inline]
+    fn map(self, f: impl Fn(Scalar) -> Scalar) -> Self {
+        Vec4::new(f(self.x), f(self.y), f(self.z), f(self.w))
+    }
+
+    #[inline]
+    fn map2(self, b: Self, f: impl Fn(Scalar, Scalar) -> Scalar) -> Self {
+        Vec4::new(
+            f(self.x, b.x),
+            f(self.y, b.y),
+            f(self.z, b.z),
+            f(self.w, b.w),
+        )
+    }
+
+    #[inline]
+    fn map3(self, b: Self, c: Self, f: impl Fn(Scalar, Scalar, Scalar) -> Scalar) -> Self {
+        Vec4::new(
+            f(self.x, b.x, c.x),
+            f(self.y, b.y, c.y),
+            f(self.z, b.z, c.z),
+            f(self.w, b.w, c.w),
+        )
+    }
+}
+
+/// Linearly interpolates from `a` (at `t = 0`) to `b` (at `t = 1`), without clamping `t`.
+#[inline]
+pub fn lerp<T: ComponentWise>(a: T, b: T, t: T) -> T {
+    a.map3(b, t, |a, b, t| a + (b - a) * t)
+}
+
+/// Returns where `value` lies between `a` and `b`: `0` at `a`, `1` at `b`, unclamped.
+///
+/// Lanes where `a == b` return `0`.
+#[inline]
+pub fn inverse_lerp<T: ComponentWise>(a: T, b: T, value: T) -> T {
+    a.map3(b, value, inverse_lerp_lane)
+}
+
+/// Maps `value` from `from` onto `to`, so `from.start` becomes `to.start` and `from.end`
+/// becomes `to.end`. Values outside `from` extrapolate.
+#[inline]
+pub fn remap<T: ComponentWise>(value: T, from: Range<T>, to: Range<T>) -> T {
+    lerp(to.start, to.end, inverse_lerp(from.start, from.end, value))
+}
+
+/// Like [`remap`], but clamps the result to `to`.
+#[inline]
+pub fn remap_clamped<T: ComponentWise>(value: T, from: Range<T>, to: Range<T>) -> T {
+    let t = inverse_lerp(from.start, from.end, value);
+    let t = t.map(|t| t.clamp(0.0, 1.0));
+    lerp(to.start, to.end, t)
+}
+
+/// Hermite interpolation `3t² - 2t³` of `x` between `edge0` and `edge1`, clamped to `[0, 1]`.
+///
+/// Lanes where the edges are equal step from `0` to `1` at the edge.
+#[inline]
+pub fn smoothstep<T: ComponentWise>(edge0: T, edge1: T, x: T) -> T {
+    edge0.map3(edge1, x, |e0, e1, x| {
+        let t = clamped_t(e0, e1, x);
+        t * t * (3.0 - 2.0 * t)
+    })
+}
+
+/// Ken Perlin's `6t⁵ - 15t⁴ + 10t³` of `x` between `edge0` and `edge1`, clamped to `[0, 1]`.
+///
+/// Unlike [`smoothstep`], its second derivative is also zero at both edges.
+#[inline]
+pub fn smootherstep<T: ComponentWise>(edge0: T, edge1: T, x: T) -> T {
+    edge0.map3(edge1, x, |e0, e1, x| {
+        let t = clamped_t(e0, e1, x);
+        t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+    })
+}
+
+/// Polynomial smooth minimum of `a` and `b`, blending over a band of width `k`.
+///
+/// Equals the plain minimum once the inputs are more than `k` apart, and dips at most
+/// `k / 4` below it where they meet. A `k` of zero or less is a plain minimum. Besides
+/// blending [`crate::sdf`] shapes, it softens hard caps in gameplay formulas, such as damage
+/// that levels off smoothly instead of at a kink.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{interp, Scalar};
+///
+/// // Speed capped at 10, easing into the cap over the last 4 units.
+/// let speed = |boost: Scalar| interp::smin(boost, 10.0, 4.0);
+/// assert_eq!(speed(5.0), 5.0);
+/// assert!(speed(9.0) < 9.0 && speed(11.0) < 10.0);
+/// assert_eq!(speed(20.0), 10.0);
+/// ```
+#[inline]
+pub fn smin<T: ComponentWise>(a: T, b: T, k: Scalar) -> T {
+    a.map2(b, |a, b| {
+        if k <= 0.0 {
+            return a.min(b);
+        }
+        let h = (k - (a - b).abs()).max(0.0) / k;
+        a.min(b) - h * h * k * 0.25
+    })
+}
+
+/// Polynomial smooth maximum; the mirror image of [`smin`].
+#[inline]
+pub fn smax<T: ComponentWise>(a: T, b: T, k: Scalar) -> T {
+    a.map2(b, |a, b| -smin(-a, -b, k))
+}
+
+/// Exponential smooth minimum of `a` and `b`, with a blend sharpness of `k`.
+///
+/// Unlike [`smin`] it never quite reaches the plain minimum: it lies below it by `k · ln 2`
+/// where the inputs meet and approaches it exponentially as they separate. It is smooth to
+/// every order, and blending three or more values with it gives the same result in any
+/// order. A `k` of zero or less is a plain minimum.
+#[inline]
+pub fn smin_exp<T: ComponentWise>(a: T, b: T, k: Scalar) -> T {
+    a.map2(b, |a, b| {
+        if k <= 0.0 {
+            return a.min(b);
+        }
+        // `-k ln(e^(-a/k) + e^(-b/k))`, rearranged so the exponent can't overflow.
+        a.min(b) - k * float::ln_1p(float::exp(-(a - b).abs() / k))
+    })
+}
+
+/// Exponential smooth maximum; the mirror image of [`smin_exp`].
+#[inline]
+pub fn smax_exp<T: ComponentWise>(a: T, b: T, k: Scalar) -> T {
+    a.map2(b, |a, b| -smin_exp(-a, -b, k))
+}
+
+/// Moves `current` towards `target`, closing the fraction `1 - e^(-decay * dt)` of the gap.
+///
+/// Unlike `lerp(current, target, 0.1)` each tick, the result after a given time doesn't depend
+/// on how that time was split into ticks: two steps of `dt` land where one step of `2 * dt`
+/// does. `decay` is the rate in 1/seconds; use [`decay_from_half_life`] to pick it by feel.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{interp, Vec3};
+///
+/// let target = Vec3::new(8.0, 0.0, 0.0);
+/// let decay = interp::decay_from_half_life(0.25);
+/// let mut fast_ticks = Vec3::ZERO;
+/// for _ in 0..10 {
+///     fast_ticks = interp::exp_decay(fast_ticks, target, decay, 0.025);
+/// }
+/// let one_tick = interp::exp_decay(Vec3::ZERO, target, decay, 0.25);
+/// assert!(fast_ticks.distance(one_tick) < 1.0e-4);
+/// assert!(one_tick.distance(Vec3::new(4.0, 0.0, 0.0)) < 1.0e-4);
+/// ```
+#[inline]
+pub fn exp_decay<T: Interpolate>(current: T, target: T, decay: Scalar, dt: Scalar) -> T {
+    current.interpolate(&target, 1.0 - float::exp(-decay * dt))
+}
+
+/// Returns the [`exp_decay`] rate that halves the remaining distance every `half_life`
+/// seconds.
+#[inline]
+pub fn decay_from_half_life(half_life: Scalar) -> Scalar {
+    consts::LN_2 / half_life
+}
+
+#[inline]
+fn inverse_lerp_lane(a: Scalar, b: Scalar, value: Scalar) -> Scalar {
+    if a == b { 0.0 } else { (value - a) / (b - a) }
+}
+
+#[inline]
+fn clamped_t(edge0: Scalar, edge1: Scalar, x: Scalar) -> Scalar {
+    if edge0 == edge1 {
+        if x < edge0 { 0.0 } else { 1.0 }
+    } else {
+        ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quat;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn steps_hit_their_anchor_points() {
+        for f in [smoothstep::<Scalar>, smootherstep::<Scalar>] {
+            assert_eq!(f(1.0, 3.0, 0.0), 0.0);
+            assert_eq!(f(1.0, 3.0, 1.0), 0.0);
+            assert_eq!(f(1.0, 3.0, 2.0), 0.5);
+            assert_eq!(f(1.0, 3.0, 3.0), 1.0);
+            assert_eq!(f(1.0, 3.0, 9.0), 1.0);
+            // Reversed edges fall instead of rising.
+            assert_eq!(f(3.0, 1.0, 1.0), 1.0);
+            assert_eq!(f(2.0, 2.0, 1.9), 0.0);
+            assert_eq!(f(2.0, 2.0, 2.0), 1.0);
+        }
+        assert!((smoothstep(0.0, 1.0, 0.25 as Scalar) - 0.15625).abs() <= EPS);
+        assert!((smootherstep(0.0, 1.0, 0.25 as Scalar) - 0.103515625).abs() <= EPS);
+    }
+
+    #[test]
+    fn remap_and_inverse_lerp() {
+        assert_eq!(inverse_lerp(2.0, 6.0, 3.0 as Scalar), 0.25);
+        assert_eq!(inverse_lerp(2.0, 2.0, 3.0 as Scalar), 0.0);
+        assert_eq!(remap(5.0 as Scalar, 0.0..10.0, 100.0..200.0), 150.0);
+        assert_eq!(remap(15.0 as Scalar, 0.0..10.0, 100.0..200.0), 250.0);
+        assert_eq!(
+            remap_clamped(15.0 as Scalar, 0.0..10.0, 100.0..200.0),
+            200.0
+        );
+        assert_eq!(remap_clamped(-5.0 as Scalar, 0.0..10.0, 1.0..0.0), 1.0);
+    }
+
+    #[test]
+    fn exp_decay_is_frame_rate_independent() {
+        let decay = decay_from_half_life(0.5);
+        let one_step = exp_decay(0.0 as Scalar, 10.0, decay, 0.5);
+        assert!((one_step - 5.0).abs() <= EPS);
+        let mut many_steps = 0.0 as Scalar;
+        for dt in [0.1 as Scalar, 0.05, 0.2, 0.15] {
+            many_steps = exp_decay(many_steps, 10.0, decay, dt);
+        }
+        assert!((many_steps - one_step).abs() <= EPS);
+        assert_eq!(exp_decay(3.0 as Scalar, 10.0, decay, 0.0), 3.0);
+
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let turned = exp_decay(Quat::IDENTITY, Quat::from_axis_angle(axis, 2.0), decay, 0.5);
+        assert!(turned.dot(Quat::from_axis_angle(axis, 1.0)) >= 1.0 - EPS);
+    }
+
+    #[test]
+    fn vectors_are_component_wise() {
+        let v = remap(
+            Vec3::new(0.0, 5.0, 10.0),
+            Vec3::ZERO..Vec3::splat(10.0),
+            Vec3::ZERO..Vec3::new(1.0, 2.0, 3.0),
+        );
+        assert_eq!(v, Vec3::new(0.0, 1.0, 3.0));
+        assert_eq!(
+            smoothstep(Vec2::ZERO, Vec2::ONE, Vec2::new(0.5, 2.0)),
+            Vec2::new(0.5, 1.0)
+        );
+        assert_eq!(
+            lerp(
+                Vec4::ZERO,
+                Vec4::new(2.0, 2.0, 2.0, 2.0),
+                Vec4::new(0.0, 0.5, 1.0, 1.5)
+            ),
+            Vec4::new(0.0, 1.0, 2.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn smooth_extrema_blend_only_near_the_seam() {
+        assert_eq!(smin(1.0, 5.0, 2.0 as Scalar), 1.0);
+        assert!((smin(1.0, 1.0, 2.0 as Scalar) - 0.5).abs() <= EPS);
+        assert!((smax(1.0, 1.0, 2.0 as Scalar) - 1.5).abs() <= EPS);
+        assert_eq!(smax(1.0, 5.0, 0.0 as Scalar), 5.0);
+
+        let ln_2 = consts::LN_2;
+        assert!((smin_exp(3.0, 3.0, 1.0 as Scalar) - (3.0 - ln_2)).abs() <= EPS);
+        assert!((smax_exp(3.0, 3.0, 1.0 as Scalar) - (3.0 + ln_2)).abs() <= EPS);
+        // Far apart, the exponential form is within rounding of the sharp one.
+        assert!((smin_exp(0.0, 1000.0, 1.0 as Scalar)).abs() <= EPS);
+        assert!(smin_exp(-1.0e30, 1.0e30, 1.0 as Scalar).is_finite());
+        // Blends are continuous across the seam.
+        for i in 0..100 {
+            let b = 1.0 + i as Scalar * 0.001;
+            assert!((smin(1.0, b, 0.5) - smin(1.0, b + 0.001, 0.5)).abs() <= 0.002);
+        }
+
+        let (a, b) = (Vec3::new(0.0, 4.0, -2.0), Vec3::new(10.0, 4.0, -2.5));
+        let blended = smin(a, b, 1.0);
+        assert_eq!(blended.x, 0.0);
+        assert!((blended.y - 3.75).abs() <= EPS);
+        assert!((smax_exp(a, b, 1.0).z - smax_exp(-2.0, -2.5, 1.0 as Scalar)).abs() <= EPS);
+    }
+}
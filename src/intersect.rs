@@ -0,0 +1,678 @@
+//! Ray intersection queries against the crate's shapes.
+//!
+//! Queries are inherent methods on the ray types, e.g. [`Ray3::intersect_sphere`].
+
+use crate::{
+    Aabb3, Capsule3, Cylinder, Heightfield, Plane, Ray3, Scalar, Sphere, Triangle3, Vec2, Vec3,
+    float,
+};
+
+/// Result of [`Ray3::intersect_aabb`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AabbHit {
+    /// Parameter where the ray enters the box (`0.0` if the origin is inside).
+    pub t_enter: Scalar,
+    /// Parameter where the ray leaves the box.
+    pub t_exit: Scalar,
+    /// Outward face normal at the entry point, or at the exit point if the origin is inside.
+    pub normal: Vec3,
+}
+
+/// Result of [`Ray3::intersect_plane`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaneHit {
+    /// Ray parameter of the hit.
+    pub t: Scalar,
+    /// `true` if the ray hit the side the plane normal points towards.
+    pub front_face: bool,
+}
+
+/// Result of [`Ray3::intersect_triangle`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleHit {
+    /// Ray parameter of the hit.
+    pub t: Scalar,
+    /// Barycentric weights of the hit point for the triangle's `(a, b, c)` vertices.
+    pub barycentric: Vec3,
+    /// `true` if the ray hit the counter-clockwise (front) face.
+    pub front_face: bool,
+}
+
+/// A ray hit against a solid's surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Ray parameter of the hit.
+    pub t: Scalar,
+    /// Outward unit surface normal at the hit point.
+    pub normal: Vec3,
+}
+
+impl Ray3 {
+    /// Returns the `(t_near, t_far)` interval where the ray is inside `sphere`, or `None` on a miss.
+    ///
+    /// If the origin is inside the sphere, `t_near` is `0.0`. Spheres entirely behind the origin
+    /// are misses. Uses the numerically stable quadratic formulation, so grazing hits on large or
+    /// distant spheres stay accurate.
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<(Scalar, Scalar)> {
+        let (t_near, t_far) = sphere_roots(self, sphere.center, sphere.radius)?;
+        if t_far < 0.0 {
+            return None;
+        }
+        Some((t_near.max(0.0), t_far))
+    }
+
+    /// Intersects the ray with `aabb` using the slab method.
+    ///
+    /// Rays parallel to an axis hit only if the origin lies within that slab; origins exactly on a
+    /// face count as inside. Returns `None` if the box is missed or lies entirely behind the origin.
+    pub fn intersect_aabb(&self, aabb: &Aabb3) -> Option<AabbHit> {
+        let origin = self.origin.to_array();
+        let direction = self.direction.to_array();
+        let min = aabb.min.to_array();
+        let max = aabb.max.to_array();
+        // With a zero direction component the slab bounds below become `inf` or NaN, so an
+        // origin outside that slab has to be rejected up front.
+        let outside_parallel_slab = (0..3).any(|axis| {
+            direction[axis] == 0.0 && (origin[axis] < min[axis] || origin[axis] > max[axis])
+        });
+        if outside_parallel_slab {
+            return None;
+        }
+
+        // Division by zero yields signed infinities, which makes parallel slabs either unbounded
+        // or empty. `0 * inf` is NaN: a parallel ray lying on a face, which is inside the slab
+        // for every `t`. `max(-inf)` and `min(inf)` turn that NaN into the open bound and leave
+        // every other value alone.
+        let slab = |axis: usize| {
+            let inv = 1.0 / direction[axis];
+            let t1 = (min[axis] - origin[axis]) * inv;
+            let t2 = (max[axis] - origin[axis]) * inv;
+            let near = t1
+                .max(Scalar::NEG_INFINITY)
+                .min(t2.max(Scalar::NEG_INFINITY));
+            let far = t1.min(Scalar::INFINITY).max(t2.min(Scalar::INFINITY));
+            (near, far)
+        };
+        let slabs = [slab(0), slab(1), slab(2)];
+        let t_enter = slabs[0].0.max(slabs[1].0).max(slabs[2].0);
+        let t_exit = slabs[0].1.min(slabs[1].1).min(slabs[2].1);
+
+        if t_enter > t_exit || t_exit < 0.0 {
+            return None;
+        }
+        let (t_enter, axis, sign) = if t_enter >= 0.0 {
+            let axis = slabs.iter().position(|s| s.0 == t_enter).unwrap_or(0);
+            (t_enter, axis, -direction[axis].signum())
+        } else {
+            let axis = slabs.iter().position(|s| s.1 == t_exit).unwrap_or(0);
+            (0.0, axis, direction[axis].signum())
+        };
+        let mut normal = [0.0; 3];
+        normal[axis] = sign;
+        Some(AabbHit {
+            t_enter,
+            t_exit,
+            normal: Vec3::from_array(normal),
+        })
+    }
+
+    /// Intersects the ray with `plane`, returning `None` if it is parallel or the plane is behind.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<PlaneHit> {
+        let denom = plane.normal.dot(self.direction);
+        if denom == 0.0 {
+            return None;
+        }
+        let t = -plane.signed_distance(self.origin) / denom;
+        if t < 0.0 {
+            return None;
+        }
+        Some(PlaneHit {
+            t,
+            front_face: denom < 0.0,
+        })
+    }
+
+    /// Intersects the ray with `triangle` using the Möller–Trumbore algorithm.
+    ///
+    /// Both faces are hit; check [`TriangleHit::front_face`] to cull back faces. Rays parallel to
+    /// the triangle (within `epsilon` of the determinant) and hits behind the origin are misses.
+    pub fn intersect_triangle(&self, triangle: &Triangle3, epsilon: Scalar) -> Option<TriangleHit> {
+        let edge1 = triangle.b - triangle.a;
+        let edge2 = triangle.c - triangle.a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det.abs() <= epsilon {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let s = self.origin - triangle.a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = edge2.dot(q) * inv_det;
+        if t < 0.0 {
+            return None;
+        }
+        Some(TriangleHit {
+            t,
+            barycentric: Vec3::new(1.0 - u - v, u, v),
+            front_face: det > 0.0,
+        })
+    }
+
+    /// Returns the first surface crossing of the ray with `capsule` at or after the origin.
+    ///
+    /// Rays starting inside the capsule report where they exit it.
+    pub fn intersect_capsule(&self, capsule: &Capsule3) -> Option<RayHit> {
+        let mut best: Option<RayHit> = None;
+        let axis = capsule.end - capsule.start;
+        let length = axis.length();
+        if let Some(axis) = axis.try_normalize(0.0) {
+            let body = Cylinder::new(
+                (capsule.start + capsule.end) * 0.5,
+                axis,
+                length * 0.5,
+                capsule.radius,
+            );
+            best = nearest(best, cylinder_body_hit(self, &body));
+        }
+        for (center, other) in [(capsule.start, capsule.end), (capsule.end, capsule.start)] {
+            let Some(roots) = sphere_roots(self, center, capsule.radius) else {
+                continue;
+            };
+            for t in [roots.0, roots.1] {
+                let normal = (self.at(t) - center) / capsule.radius;
+                // Only the hemisphere facing away from the other cap is exposed.
+                if t >= 0.0 && normal.dot(other - center) <= 0.0 {
+                    best = nearest(best, Some(RayHit { t, normal }));
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the first surface crossing of the ray with the capped `cylinder` at or after the
+    /// origin.
+    ///
+    /// Rays starting inside the cylinder report where they exit it.
+    pub fn intersect_cylinder(&self, cylinder: &Cylinder) -> Option<RayHit> {
+        let mut best = cylinder_body_hit(self, cylinder);
+        let denom = self.direction.dot(cylinder.axis);
+        if denom != 0.0 {
+            for sign in [1.0, -1.0] {
+                let normal = cylinder.axis * sign;
+                let cap_center = cylinder.center + normal * cylinder.half_height;
+                let t = (cap_center - self.origin).dot(cylinder.axis) / denom;
+                let radial = self.at(t) - cap_center;
+                if t >= 0.0 && radial.length_squared() <= cylinder.radius * cylinder.radius {
+                    best = nearest(best, Some(RayHit { t, normal }));
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the first crossing of the ray with the bilinearly interpolated `heightfield`
+    /// surface at or after the origin.
+    ///
+    /// The ray is marched cell by cell across the grid, solving exactly for the crossing
+    /// inside each cell. Rays starting below the surface hit immediately. The hit point is
+    /// `self.at(hit.t)`.
+    pub fn intersect_heightfield(&self, heightfield: &Heightfield) -> Option<RayHit> {
+        let (cells_x, cells_z) = heightfield.cells();
+        if cells_x == 0 || cells_z == 0 {
+            return None;
+        }
+        let (min, max) = heightfield.bounds();
+        // Unbounded in Y; built from literals so the `validate` feature doesn't object.
+        let bounds = Aabb3::new(
+            Vec3 {
+                x: min.x,
+                y: Scalar::NEG_INFINITY,
+                z: min.y,
+            },
+            Vec3 {
+                x: max.x,
+                y: Scalar::INFINITY,
+                z: max.y,
+            },
+        );
+        let span = self.intersect_aabb(&bounds)?;
+        let size = heightfield.cell_size;
+
+        // Local grid coordinates of the ray, in cells.
+        let o = (self.origin.xz() - heightfield.origin) / size;
+        let d = self.direction.xz() / size;
+        let mut t = span.t_enter;
+        let entry = o + d * t;
+        let mut i = (float::floor(entry.x).max(0.0) as u32).min(cells_x - 1);
+        let mut j = (float::floor(entry.y).max(0.0) as u32).min(cells_z - 1);
+
+        let step = |d: Scalar| if d > 0.0 { 1 } else { -1 };
+        let boundary = |cell: u32, o: Scalar, d: Scalar| {
+            if d == 0.0 {
+                Scalar::INFINITY
+            } else {
+                let edge = if d > 0.0 { cell + 1 } else { cell } as Scalar;
+                (edge - o) / d
+            }
+        };
+
+        loop {
+            let t_next_x = boundary(i, o.x, d.x);
+            let t_next_z = boundary(j, o.y, d.y);
+            let t_cell_exit = t_next_x.min(t_next_z).min(span.t_exit);
+            if let Some(hit) =
+                heightfield_cell_hit(self, heightfield, (i, j), o, d, (t, t_cell_exit))
+            {
+                return Some(hit);
+            }
+            if t_cell_exit >= span.t_exit {
+                return None;
+            }
+            let next_i = if t_next_x <= t_next_z {
+                i as i64 + step(d.x)
+            } else {
+                i as i64
+            };
+            let next_j = if t_next_z <= t_next_x {
+                j as i64 + step(d.y)
+            } else {
+                j as i64
+            };
+            if next_i < 0 || next_j < 0 || next_i >= cells_x as i64 || next_j >= cells_z as i64 {
+                return None;
+            }
+            i = next_i as u32;
+            j = next_j as u32;
+            t = t_cell_exit;
+        }
+    }
+}
+
+/// Returns the first crossing of the ray with the surface of cell `(i, j)` for `t` in
+/// `[t_start, t_end]`. `o` and `d` are the ray's XZ origin and direction in cell units.
+fn heightfield_cell_hit(
+    ray: &Ray3,
+    heightfield: &Heightfield,
+    (i, j): (u32, u32),
+    o: Vec2,
+    d: Vec2,
+    (t_start, t_end): (Scalar, Scalar),
+) -> Option<RayHit> {
+    // With u = u0 + du * t and v = v0 + dv * t, the gap between the ray and the bilinear
+    // surface, f(t) = y(t) - h(u, v), is a quadratic in t.
+    let [h00, b, c, dd] = heightfield.cell_coefficients(i, j);
+    let (u0, du) = (o.x - i as Scalar, d.x);
+    let (v0, dv) = (o.y - j as Scalar, d.y);
+    let qa = -dd * du * dv;
+    let qb = ray.direction.y - b * du - c * dv - dd * (u0 * dv + v0 * du);
+    let qc = ray.origin.y - (h00 + b * u0 + c * v0 + dd * u0 * v0);
+    let f = |t: Scalar| (qa * t + qb) * t + qc;
+
+    let hit_at = |t: Scalar| {
+        let (u, v) = (u0 + du * t, v0 + dv * t);
+        RayHit {
+            t,
+            normal: heightfield.cell_normal(i, j, u.clamp(0.0, 1.0), v.clamp(0.0, 1.0)),
+        }
+    };
+    if f(t_start) <= 0.0 {
+        return Some(hit_at(t_start));
+    }
+    let roots: [Scalar; 2] = if qa == 0.0 {
+        if qb == 0.0 {
+            return None;
+        }
+        [-qc / qb, Scalar::NAN]
+    } else {
+        let discriminant = qb * qb - 4.0 * qa * qc;
+        if discriminant < 0.0 {
+            return None;
+        }
+        // Stable quadratic roots.
+        let q = -0.5 * (qb + qb.signum() * float::sqrt(discriminant));
+        let (r0, r1) = (q / qa, if q != 0.0 { qc / q } else { Scalar::NAN });
+        if r0 <= r1 { [r0, r1] } else { [r1, r0] }
+    };
+    roots
+        .into_iter()
+        .find(|&t| t >= t_start && t <= t_end)
+        .map(hit_at)
+}
+
+/// Returns both parameters where the ray's line crosses a sphere surface, sorted, or `None`.
+fn sphere_roots(ray: &Ray3, center: Vec3, radius: Scalar) -> Option<(Scalar, Scalar)> {
+    let a = ray.direction.length_squared();
+    if a <= 0.0 {
+        return None;
+    }
+    let f = ray.origin - center;
+    let b = -f.dot(ray.direction);
+    // Discriminant from the perpendicular distance to the center, avoiding cancellation.
+    let perpendicular = f + ray.direction * (b / a);
+    let radius_sq = radius * radius;
+    let discriminant = radius_sq - perpendicular.length_squared();
+    if discriminant < 0.0 {
+        return None;
+    }
+    let c = f.length_squared() - radius_sq;
+    let q = b + b.signum() * float::sqrt(a * discriminant);
+    let (t0, t1) = if q == 0.0 {
+        (b / a, b / a)
+    } else {
+        (c / q, q / a)
+    };
+    Some(if t0 <= t1 { (t0, t1) } else { (t1, t0) })
+}
+
+/// Returns the first crossing of the cylinder's curved side (excluding caps) at or after the origin.
+fn cylinder_body_hit(ray: &Ray3, cylinder: &Cylinder) -> Option<RayHit> {
+    let axis = cylinder.axis;
+    let offset = ray.origin - cylinder.center;
+    // Solve in the plane perpendicular to the axis, where the side is a circle.
+    let d = ray.direction - axis * ray.direction.dot(axis);
+    let o = offset - axis * offset.dot(axis);
+    let projected = Ray3::new(o, d);
+    let (t0, t1) = sphere_roots(&projected, Vec3::ZERO, cylinder.radius)?;
+    [t0, t1].into_iter().find_map(|t| {
+        let h = (offset + ray.direction * t).dot(axis);
+        if t >= 0.0 && h.abs() <= cylinder.half_height {
+            let normal = (o + d * t) / cylinder.radius;
+            Some(RayHit { t, normal })
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns whichever hit is closer to the ray origin.
+fn nearest(a: Option<RayHit>, b: Option<RayHit>) -> Option<RayHit> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.t < a.t { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec3;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn assert_interval(actual: Option<(Scalar, Scalar)>, near: Scalar, far: Scalar) {
+        let (t_near, t_far) = actual.expect("expected hit");
+        assert!((t_near - near).abs() <= EPS, "t_near {t_near} != {near}");
+        assert!((t_far - far).abs() <= EPS, "t_far {t_far} != {far}");
+    }
+
+    #[test]
+    fn ray_sphere_hits_front_and_back() {
+        let ray = Ray3::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let sphere = Sphere::new(Vec3::ZERO, 1.0);
+        assert_interval(ray.intersect_sphere(&sphere), 4.0, 6.0);
+    }
+
+    #[test]
+    fn ray_sphere_from_inside_starts_at_zero() {
+        let ray = Ray3::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0));
+        let sphere = Sphere::new(Vec3::new(0.5, 0.0, 0.0), 2.0);
+        assert_interval(ray.intersect_sphere(&sphere), 0.0, 2.5);
+    }
+
+    #[test]
+    fn ray_sphere_misses_behind_and_beside() {
+        let ray = Ray3::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0));
+        assert!(
+            ray.intersect_sphere(&Sphere::new(Vec3::new(-5.0, 0.0, 0.0), 1.0))
+                .is_none()
+        );
+        assert!(
+            ray.intersect_sphere(&Sphere::new(Vec3::new(5.0, 1.5, 0.0), 1.0))
+                .is_none()
+        );
+        let still = Ray3::new(Vec3::ZERO, Vec3::ZERO);
+        assert!(
+            still
+                .intersect_sphere(&Sphere::new(Vec3::ZERO, 1.0))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn ray_sphere_scales_with_direction_length() {
+        let ray = Ray3::new(Vec3::new(-10.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0));
+        let sphere = Sphere::new(Vec3::ZERO, 2.0);
+        assert_interval(ray.intersect_sphere(&sphere), 4.0, 6.0);
+    }
+
+    fn unit_box() -> Aabb3 {
+        Aabb3::new(Vec3::splat(-1.0), Vec3::ONE)
+    }
+
+    #[test]
+    fn ray_aabb_reports_entry_exit_and_normal() {
+        let ray = Ray3::new(Vec3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit = ray.intersect_aabb(&unit_box()).expect("expected hit");
+        assert_eq!(hit.t_enter, 4.0 as Scalar);
+        assert_eq!(hit.t_exit, 6.0 as Scalar);
+        assert_eq!(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn ray_aabb_from_inside_reports_exit_face() {
+        let ray = Ray3::new(Vec3::ZERO, Vec3::new(0.0, -2.0, 0.0));
+        let hit = ray.intersect_aabb(&unit_box()).expect("expected hit");
+        assert_eq!(hit.t_enter, 0.0 as Scalar);
+        assert_eq!(hit.t_exit, 0.5 as Scalar);
+        assert_eq!(hit.normal, Vec3::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn ray_aabb_handles_axis_parallel_rays() {
+        let inside_slab = Ray3::new(Vec3::new(0.5, 0.5, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        let outside_slab = Ray3::new(Vec3::new(1.5, 0.5, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        let on_face = Ray3::new(Vec3::new(1.0, 0.5, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        assert!(inside_slab.intersect_aabb(&unit_box()).is_some());
+        assert!(outside_slab.intersect_aabb(&unit_box()).is_none());
+        let hit = on_face.intersect_aabb(&unit_box()).expect("expected hit");
+        assert_eq!(hit.t_enter, 4.0 as Scalar);
+        assert_eq!(hit.normal, Vec3::new(0.0, 0.0, -1.0));
+        // On the opposite face and travelling the other way along a negative-zero component.
+        let on_min_face = Ray3::new(Vec3::new(-1.0, -1.0, 5.0), Vec3::new(-0.0, 0.0, -1.0));
+        let hit = on_min_face
+            .intersect_aabb(&unit_box())
+            .expect("expected hit");
+        assert_eq!((hit.t_enter, hit.t_exit), (4.0, 6.0));
+        assert_eq!(hit.normal, Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn ray_aabb_with_zero_direction_hits_only_from_inside() {
+        let outside = Ray3::new(Vec3::new(3.0, 0.0, 0.0), Vec3::ZERO);
+        assert!(outside.intersect_aabb(&unit_box()).is_none());
+        let inside = Ray3::new(Vec3::new(0.5, 0.0, -0.5), Vec3::ZERO);
+        let hit = inside.intersect_aabb(&unit_box()).expect("expected hit");
+        assert_eq!(hit.t_enter, 0.0);
+    }
+
+    #[test]
+    fn ray_aabb_misses_boxes_behind() {
+        let ray = Ray3::new(Vec3::new(5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(ray.intersect_aabb(&unit_box()).is_none());
+    }
+
+    #[test]
+    fn ray_plane_reports_side() {
+        let ground = Plane::from_point_normal(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0));
+        let down = Ray3::new(Vec3::new(1.0, 3.0, 2.0), Vec3::new(0.0, -1.0, 0.0));
+        assert_eq!(
+            down.intersect_plane(&ground),
+            Some(PlaneHit {
+                t: 3.0,
+                front_face: true
+            })
+        );
+        let up = Ray3::new(Vec3::new(0.0, -2.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(
+            up.intersect_plane(&ground).map(|h| h.front_face),
+            Some(false)
+        );
+        let away = Ray3::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(away.intersect_plane(&ground).is_none());
+        let parallel = Ray3::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(parallel.intersect_plane(&ground).is_none());
+    }
+
+    fn floor_triangle() -> Triangle3 {
+        // Counter-clockwise seen from +Y.
+        Triangle3::new(
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(2.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn ray_triangle_reports_barycentric_and_face() {
+        let tri = floor_triangle();
+        let down = Ray3::new(Vec3::new(0.5, 4.0, 1.0), Vec3::new(0.0, -1.0, 0.0));
+        let hit = down.intersect_triangle(&tri, EPS).expect("expected hit");
+        assert!((hit.t - 4.0 as Scalar).abs() <= EPS);
+        assert!(hit.front_face);
+        let point = tri.point_from_barycentric(hit.barycentric);
+        assert!(point.distance(Vec3::new(0.5, 0.0, 1.0)) <= EPS);
+
+        let up = Ray3::new(Vec3::new(0.5, -4.0, 1.0), Vec3::new(0.0, 1.0, 0.0));
+        let hit = up.intersect_triangle(&tri, EPS).expect("expected hit");
+        assert!(!hit.front_face);
+    }
+
+    #[test]
+    fn ray_triangle_misses_outside_and_parallel() {
+        let tri = floor_triangle();
+        let outside = Ray3::new(Vec3::new(1.5, 4.0, 1.5), Vec3::new(0.0, -1.0, 0.0));
+        assert!(outside.intersect_triangle(&tri, EPS).is_none());
+        let parallel = Ray3::new(Vec3::new(-1.0, 0.0, 0.5), Vec3::new(1.0, 0.0, 0.0));
+        assert!(parallel.intersect_triangle(&tri, EPS).is_none());
+        let behind = Ray3::new(Vec3::new(0.5, 4.0, 1.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(behind.intersect_triangle(&tri, EPS).is_none());
+    }
+
+    fn assert_hit(hit: Option<RayHit>, t: Scalar, normal: Vec3) {
+        let hit = hit.expect("expected hit");
+        assert!((hit.t - t).abs() <= EPS, "t {} != {t}", hit.t);
+        assert!(
+            hit.normal.distance(normal) <= EPS,
+            "normal {:?} != {normal:?}",
+            hit.normal
+        );
+    }
+
+    fn standing_capsule() -> Capsule3 {
+        Capsule3::new(Vec3::ZERO, Vec3::new(0.0, 2.0, 0.0), 0.5)
+    }
+
+    #[test]
+    fn ray_capsule_hits_body_and_caps() {
+        let c = standing_capsule();
+        let side = Ray3::new(Vec3::new(-5.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_hit(side.intersect_capsule(&c), 4.5, Vec3::new(-1.0, 0.0, 0.0));
+        let top = Ray3::new(Vec3::new(0.0, 10.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        assert_hit(top.intersect_capsule(&c), 7.5, Vec3::new(0.0, 1.0, 0.0));
+        let bottom = Ray3::new(Vec3::new(0.0, -3.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_hit(bottom.intersect_capsule(&c), 2.5, Vec3::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn ray_capsule_misses_and_exits() {
+        let c = standing_capsule();
+        let over = Ray3::new(Vec3::new(-5.0, 2.6, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(over.intersect_capsule(&c).is_none());
+        let inside = Ray3::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_hit(inside.intersect_capsule(&c), 0.5, Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn ray_cylinder_hits_side_and_caps() {
+        let c = Cylinder::new(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0), 1.0, 0.5);
+        let side = Ray3::new(Vec3::new(0.0, 0.5, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_hit(side.intersect_cylinder(&c), 4.5, Vec3::new(0.0, 0.0, -1.0));
+        let top = Ray3::new(Vec3::new(0.2, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        assert_hit(top.intersect_cylinder(&c), 4.0, Vec3::new(0.0, 1.0, 0.0));
+        let over_rim = Ray3::new(Vec3::new(-5.0, 1.1, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(over_rim.intersect_cylinder(&c).is_none());
+        let diagonal = Ray3::new(Vec3::new(0.0, 3.0, -2.4), Vec3::new(0.0, -1.0, 1.0));
+        assert_hit(
+            diagonal.intersect_cylinder(&c),
+            2.0,
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn ray_sphere_stays_accurate_far_away() {
+        let ray = Ray3::new(Vec3::new(0.0, 0.0, 10_000.0), Vec3::new(0.0, 0.0, -1.0));
+        let sphere = Sphere::new(Vec3::new(0.0, 0.99, 0.0), 1.0);
+        assert!(ray.intersect_sphere(&sphere).is_some());
+    }
+
+    fn terrain() -> Heightfield {
+        // 3x3 cells of 2 units: flat at height 0 except a ridge of height 4 along x = 4.
+        #[rustfmt::skip]
+        let heights = vec![
+            0.0, 0.0, 4.0, 0.0,
+            0.0, 0.0, 4.0, 0.0,
+            0.0, 0.0, 4.0, 0.0,
+            0.0, 0.0, 4.0, 0.0,
+        ];
+        Heightfield::new(crate::Vec2::ZERO, 2.0, 4, 4, heights)
+    }
+
+    #[test]
+    fn ray_heightfield_hits_interpolated_slope() {
+        let hf = terrain();
+        // Horizontal shot at height 2 hits the rising slope halfway between x = 2 and x = 4.
+        let shot = Ray3::new(Vec3::new(-1.0, 2.0, 3.0), Vec3::new(1.0, 0.0, 0.0));
+        let expected_normal = Vec3::new(-2.0, 1.0, 0.0) / (5.0 as Scalar).sqrt();
+        assert_hit(shot.intersect_heightfield(&hf), 4.0, expected_normal);
+
+        // Straight down onto the flat part.
+        let drop = Ray3::new(Vec3::new(1.0, 10.0, 1.0), Vec3::new(0.0, -2.0, 0.0));
+        assert_hit(
+            drop.intersect_heightfield(&hf),
+            5.0,
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn ray_heightfield_misses() {
+        let hf = terrain();
+        let over = Ray3::new(Vec3::new(-1.0, 4.5, 3.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(over.intersect_heightfield(&hf).is_none());
+        let outside = Ray3::new(Vec3::new(-1.0, 1.0, 7.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(outside.intersect_heightfield(&hf).is_none());
+        let away = Ray3::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(away.intersect_heightfield(&hf).is_none());
+    }
+
+    #[test]
+    fn ray_heightfield_crosses_cells_diagonally() {
+        let hf = terrain();
+        // Descending diagonally across several cells before reaching the far side of the ridge.
+        let ray = Ray3::new(Vec3::new(5.5, 3.0, 0.5), Vec3::new(0.1, -1.0, 1.5));
+        let hit = ray.intersect_heightfield(&hf).expect("expected hit");
+        assert!((hit.t - 2.5 as Scalar).abs() <= EPS);
+        let point = ray.at(hit.t);
+        let surface = hf.height_at(point.xz()).unwrap();
+        assert!((point.y - surface).abs() <= EPS);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{IVec2, Scalar, Vec2};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// How the tiles of an [`IsoProjection`] are numbered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IsoLayout {
+    /// Tile axes run diagonally: `+x` moves right and down, `+y` moves left and down, and the
+    /// map is drawn as one big diamond.
+    #[default]
+    Diamond,
+    /// Rows of tiles run horizontally, each half a tile below the previous one, with odd rows
+    /// shifted half a tile to the right. The map is drawn as a rectangle.
+    Staggered,
+}
+
+/// Converts between isometric (or dimetric) tile coordinates and 2D world positions.
+///
+/// Tiles are diamonds `tile_width` wide and `tile_height` tall; a classic 2:1 isometric view
+/// uses a height of half the width. Tile `(0, 0)` is centered on `origin`, and world `+y`
+/// points down the screen.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{IVec2, IsoLayout, IsoProjection, Vec2};
+///
+/// let iso = IsoProjection::new(IsoLayout::Diamond, 64.0, 32.0, Vec2::ZERO);
+/// assert_eq!(iso.tile_to_world(IVec2::new(1, 0)), Vec2::new(32.0, 16.0));
+/// // A click just inside the right corner of tile (0, 0).
+/// assert_eq!(iso.world_to_tile(Vec2::new(30.0, 0.0)), IVec2::new(0, 0));
+/// assert_eq!(iso.world_to_tile(Vec2::new(34.0, 0.0)), IVec2::new(1, -1));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IsoProjection {
+    pub layout: IsoLayout,
+    pub tile_width: Scalar,
+    pub tile_height: Scalar,
+    pub origin: Vec2,
+}
+
+impl IsoProjection {
+    #[inline(always)]
+    pub const fn new(
+        layout: IsoLayout,
+        tile_width: Scalar,
+        tile_height: Scalar,
+        origin: Vec2,
+    ) -> Self {
+        IsoProjection {
+            layout,
+            tile_width,
+            tile_height,
+            origin,
+        }
+    }
+
+    /// Returns the world position of the center of `tile`.
+    pub fn tile_to_world(&self, tile: IVec2) -> Vec2 {
+        let (half_w, half_h) = (self.tile_width * 0.5, self.tile_height * 0.5);
+        let offset = match self.layout {
+            IsoLayout::Diamond => Vec2::new(
+                (tile.x - tile.y) as Scalar * half_w,
+                (tile.x + tile.y) as Scalar * half_h,
+            ),
+            IsoLayout::Staggered => Vec2::new(
+                tile.x as Scalar * self.tile_width + (tile.y & 1) as Scalar * half_w,
+                tile.y as Scalar * half_h,
+            ),
+        };
+        self.origin + offset
+    }
+
+    /// Returns the tile under a world position, such as a mouse click.
+    ///
+    /// Points exactly on an edge between tiles go to one of them consistently.
+    pub fn world_to_tile(&self, position: Vec2) -> IVec2 {
+        let grid = self.world_to_diamond(position);
+        let diamond = IVec2::floor(grid + Vec2::splat(0.5));
+        match self.layout {
+            IsoLayout::Diamond => diamond,
+            // Staggered tile centers are exactly the diamond centers with an even `x - y`
+            // column offset, so the diamond pick maps across directly.
+            IsoLayout::Staggered => {
+                IVec2::new((diamond.x - diamond.y).div_euclid(2), diamond.x + diamond.y)
+            }
+        }
+    }
+
+    /// Converts continuous diamond grid coordinates to a world position, where `(0.0, 0.0)`
+    /// is the center of tile `(0, 0)`.
+    ///
+    /// Useful for placing entities that move smoothly between tiles. The grid is always the
+    /// diamond one, whatever the tile numbering.
+    pub fn grid_to_world(&self, grid: Vec2) -> Vec2 {
+        self.origin
+            + Vec2::new(
+                (grid.x - grid.y) * self.tile_width * 0.5,
+                (grid.x + grid.y) * self.tile_height * 0.5,
+            )
+    }
+
+    /// Inverse of [`IsoProjection::grid_to_world`].
+    #[inline]
+    pub fn world_to_grid(&self, position: Vec2) -> Vec2 {
+        self.world_to_diamond(position)
+    }
+
+    /// Returns the four corners of `tile`: top, right, bottom, left.
+    pub fn tile_corners(&self, tile: IVec2) -> [Vec2; 4] {
+        let center = self.tile_to_world(tile);
+        let (half_w, half_h) = (self.tile_width * 0.5, self.tile_height * 0.5);
+        [
+            center + Vec2::new(0.0, -half_h),
+            center + Vec2::new(half_w, 0.0),
+            center + Vec2::new(0.0, half_h),
+            center + Vec2::new(-half_w, 0.0),
+        ]
+    }
+
+    fn world_to_diamond(&self, position: Vec2) -> Vec2 {
+        let local = position - self.origin;
+        let u = local.x / (self.tile_width * 0.5);
+        let v = local.y / (self.tile_height * 0.5);
+        Vec2::new((v + u) * 0.5, (v - u) * 0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projections() -> [IsoProjection; 3] {
+        [
+            IsoProjection::new(IsoLayout::Diamond, 64.0, 32.0, Vec2::new(100.0, -20.0)),
+            IsoProjection::new(IsoLayout::Diamond, 48.0, 36.0, Vec2::ZERO),
+            IsoProjection::new(IsoLayout::Staggered, 64.0, 32.0, Vec2::new(-5.0, 7.0)),
+        ]
+    }
+
+    #[test]
+    fn picking_round_trips_tile_centers_and_corners() {
+        for iso in projections() {
+            for x in -4..5 {
+                for y in -4..5 {
+                    let tile = IVec2::new(x, y);
+                    let center = iso.tile_to_world(tile);
+                    assert_eq!(iso.world_to_tile(center), tile);
+                    // Points most of the way towards each corner still pick the same tile.
+                    for corner in iso.tile_corners(tile) {
+                        let inside = center + (corner - center) * 0.95;
+                        assert_eq!(iso.world_to_tile(inside), tile, "{:?}", iso.layout);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn staggered_rows_shift_half_a_tile() {
+        let iso = IsoProjection::new(IsoLayout::Staggered, 64.0, 32.0, Vec2::ZERO);
+        assert_eq!(iso.tile_to_world(IVec2::new(0, 1)), Vec2::new(32.0, 16.0));
+        assert_eq!(iso.tile_to_world(IVec2::new(0, -1)), Vec2::new(32.0, -16.0));
+        assert_eq!(iso.tile_to_world(IVec2::new(2, 2)), Vec2::new(128.0, 32.0));
+        // The gap left of row 1's first tile belongs to row 1's tile -1.
+        assert_eq!(iso.world_to_tile(Vec2::new(-2.0, 16.0)), IVec2::new(-1, 1));
+    }
+
+    #[test]
+    fn grid_coordinates_match_diamond_tiles() {
+        let iso = IsoProjection::new(IsoLayout::Diamond, 64.0, 32.0, Vec2::new(3.0, 4.0));
+        let grid = Vec2::new(2.25, -1.5);
+        let world = iso.grid_to_world(grid);
+        assert_eq!(iso.world_to_grid(world), grid);
+        assert_eq!(
+            iso.grid_to_world(Vec2::new(2.0, -1.0)),
+            iso.tile_to_world(IVec2::new(2, -1))
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{Scalar, Vec2, float};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 2D integer vector, typically a grid cell, tile or chunk coordinate.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{IVec2, Vec2};
+///
+/// let cell = IVec2::floor(Vec2::new(-0.5, 2.5));
+/// assert_eq!(cell, IVec2::new(-1, 2));
+/// assert_eq!(cell.div_euclid(2), IVec2::new(-1, 1));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IVec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl IVec2 {
+    pub const ZERO: IVec2 = IVec2::new(0, 0);
+    pub const ONE: IVec2 = IVec2::new(1, 1);
+
+    #[inline(always)]
+    pub const fn new(x: i32, y: i32) -> Self {
+        IVec2 { x, y }
+    }
+
+    /// Creates a vector with all components set to `v`.
+    #[inline(always)]
+    pub const fn splat(v: i32) -> Self {
+        IVec2 { x: v, y: v }
+    }
+
+    /// Returns the cell containing `v`, rounding each component towards negative infinity.
+    #[inline]
+    pub fn floor(v: Vec2) -> IVec2 {
+        IVec2::new(float::floor(v.x) as i32, float::floor(v.y) as i32)
+    }
+
+    /// Converts to a floating-point vector.
+    #[inline]
+    pub fn as_vec2(&self) -> Vec2 {
+        Vec2::new(self.x as Scalar, self.y as Scalar)
+    }
+
+    /// Returns the component-wise minimum of `self` and `other`.
+    #[inline]
+    pub fn min(&self, other: IVec2) -> IVec2 {
+        IVec2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`.
+    #[inline]
+    pub fn max(&self, other: IVec2) -> IVec2 {
+        IVec2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Component-wise Euclidean division, which rounds towards negative infinity for positive
+    /// `rhs`. This is the chunk containing a cell when `rhs` is the chunk size.
+    #[inline]
+    pub const fn div_euclid(&self, rhs: i32) -> IVec2 {
+        IVec2::new(self.x.div_euclid(rhs), self.y.div_euclid(rhs))
+    }
+
+    /// Component-wise Euclidean remainder, always in `0..rhs` for positive `rhs`. This is a
+    /// cell's offset inside its chunk when `rhs` is the chunk size.
+    #[inline]
+    pub const fn rem_euclid(&self, rhs: i32) -> IVec2 {
+        IVec2::new(self.x.rem_euclid(rhs), self.y.rem_euclid(rhs))
+    }
+}
+
+impl Add for IVec2 {
+    type Output = IVec2;
+
+    #[inline]
+    fn add(self, rhs: IVec2) -> IVec2 {
+        IVec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for IVec2 {
+    #[inline]
+    fn add_assign(&mut self, rhs: IVec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for IVec2 {
+    type Output = IVec2;
+
+    #[inline]
+    fn sub(self, rhs: IVec2) -> IVec2 {
+        IVec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for IVec2 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: IVec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for IVec2 {
+    type Output = IVec2;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> IVec2 {
+        IVec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl MulAssign<i32> for IVec2 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: i32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Neg for IVec2 {
+    type Output = IVec2;
+
+    #[inline]
+    fn neg(self) -> IVec2 {
+        IVec2::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Vector2<i32>> for IVec2 {
+        #[inline(always)]
+        fn from(v: nalgebra::Vector2<i32>) -> Self {
+            IVec2::new(v.x, v.y)
+        }
+    }
+
+    impl From<IVec2> for nalgebra::Vector2<i32> {
+        #[inline(always)]
+        fn from(v: IVec2) -> Self {
+            nalgebra::Vector2::new(v.x, v.y)
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    impl From<glam::IVec2> for IVec2 {
+        #[inline(always)]
+        fn from(v: glam::IVec2) -> Self {
+            IVec2::new(v.x, v.y)
+        }
+    }
+
+    impl From<IVec2> for glam::IVec2 {
+        #[inline(always)]
+        fn from(v: IVec2) -> Self {
+            glam::IVec2::new(v.x, v.y)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ivec2_floor_rounds_towards_negative_infinity() {
+        assert_eq!(
+            IVec2::floor(Vec2::new(-0.5 as Scalar, 1.5 as Scalar)),
+            IVec2::new(-1, 1)
+        );
+        assert_eq!(IVec2::new(-1, 1).as_vec2(), Vec2::new(-1.0, 1.0));
+    }
+
+    #[test]
+    fn ivec2_euclid_splits_negative_cells() {
+        let cell = IVec2::new(-1, -16);
+        assert_eq!(cell.div_euclid(16), IVec2::new(-1, -1));
+        assert_eq!(cell.rem_euclid(16), IVec2::new(15, 0));
+    }
+
+    #[test]
+    fn ivec2_arithmetic_operators_are_component_wise() {
+        let a = IVec2::new(1, 2);
+        let b = IVec2::new(4, 6);
+        assert_eq!(a + b, IVec2::new(5, 8));
+        assert_eq!(b - a, IVec2::new(3, 4));
+        assert_eq!(a * 3, IVec2::new(3, 6));
+        assert_eq!(-a, IVec2::new(-1, -2));
+        assert_eq!(a.min(IVec2::new(0, 5)), IVec2::new(0, 2));
+        assert_eq!(a.max(IVec2::new(0, 5)), IVec2::new(1, 5));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn ivec2_glam_round_trip() {
+        let v = IVec2::new(-3, 4);
+        let g: glam::IVec2 = v.into();
+        assert_eq!(g, glam::IVec2::new(-3, 4));
+        assert_eq!(IVec2::from(g), v);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{Scalar, Vec3, float};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 3D integer vector, typically a voxel, cell or chunk coordinate.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{IVec3, Vec3};
+///
+/// let voxel = IVec3::floor(Vec3::new(-0.5, 2.5, 17.0));
+/// assert_eq!(voxel, IVec3::new(-1, 2, 17));
+/// assert_eq!(voxel.rem_euclid(16), IVec3::new(15, 2, 1));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IVec3 {
+    /// X component.
+    pub x: i32,
+    /// Y component.
+    pub y: i32,
+    /// Z component.
+    pub z: i32,
+}
+
+impl IVec3 {
+    pub const ZERO: IVec3 = IVec3::new(0, 0, 0);
+    pub const ONE: IVec3 = IVec3::new(1, 1, 1);
+
+    #[inline(always)]
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        IVec3 { x, y, z }
+    }
+
+    /// Creates a vector with all components set to `v`.
+    #[inline(always)]
+    pub const fn splat(v: i32) -> Self {
+        IVec3 { x: v, y: v, z: v }
+    }
+
+    /// Returns the cell containing `v`, rounding each component towards negative infinity.
+    #[inline]
+    pub fn floor(v: Vec3) -> IVec3 {
+        IVec3::new(
+            float::floor(v.x) as i32,
+            float::floor(v.y) as i32,
+            float::floor(v.z) as i32,
+        )
+    }
+
+    /// Converts to a floating-point vector.
+    #[inline]
+    pub fn as_vec3(&self) -> Vec3 {
+        Vec3::new(self.x as Scalar, self.y as Scalar, self.z as Scalar)
+    }
+
+    /// Returns the component-wise minimum of `self` and `other`.
+    #[inline]
+    pub fn min(&self, other: IVec3) -> IVec3 {
+        IVec3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`.
+    #[inline]
+    pub fn max(&self, other: IVec3) -> IVec3 {
+        IVec3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Component-wise Euclidean division, which rounds towards negative infinity for positive
+    /// `rhs`. This is the chunk containing a voxel when `rhs` is the chunk size.
+    #[inline]
+    pub const fn div_euclid(&self, rhs: i32) -> IVec3 {
+        IVec3::new(
+            self.x.div_euclid(rhs),
+            self.y.div_euclid(rhs),
+            self.z.div_euclid(rhs),
+        )
+    }
+
+    /// Component-wise Euclidean remainder, always in `0..rhs` for positive `rhs`. This is a
+    /// voxel's offset inside its chunk when `rhs` is the chunk size.
+    #[inline]
+    pub const fn rem_euclid(&self, rhs: i32) -> IVec3 {
+        IVec3::new(
+            self.x.rem_euclid(rhs),
+            self.y.rem_euclid(rhs),
+            self.z.rem_euclid(rhs),
+        )
+    }
+}
+
+impl Add for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn add(self, rhs: IVec3) -> IVec3 {
+        IVec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for IVec3 {
+    #[inline]
+    fn add_assign(&mut self, rhs: IVec3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn sub(self, rhs: IVec3) -> IVec3 {
+        IVec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for IVec3 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: IVec3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> IVec3 {
+        IVec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl MulAssign<i32> for IVec3 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: i32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Neg for IVec3 {
+    type Output = IVec3;
+
+    #[inline]
+    fn neg(self) -> IVec3 {
+        IVec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Vector3<i32>> for IVec3 {
+        #[inline(always)]
+        fn from(v: nalgebra::Vector3<i32>) -> Self {
+            IVec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<IVec3> for nalgebra::Vector3<i32> {
+        #[inline(always)]
+        fn from(v: IVec3) -> Self {
+            nalgebra::Vector3::new(v.x, v.y, v.z)
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    impl From<glam::IVec3> for IVec3 {
+        #[inline(always)]
+        fn from(v: glam::IVec3) -> Self {
+            IVec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<IVec3> for glam::IVec3 {
+        #[inline(always)]
+        fn from(v: IVec3) -> Self {
+            glam::IVec3::new(v.x, v.y, v.z)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ivec3_floor_rounds_towards_negative_infinity() {
+        let v = Vec3::new(-0.5 as Scalar, 1.5 as Scalar, -2.0 as Scalar);
+        assert_eq!(IVec3::floor(v), IVec3::new(-1, 1, -2));
+        assert_eq!(IVec3::new(-1, 1, -2).as_vec3(), Vec3::new(-1.0, 1.0, -2.0));
+    }
+
+    #[test]
+    fn ivec3_euclid_splits_negative_voxels() {
+        let voxel = IVec3::new(-1, -16, 17);
+        assert_eq!(voxel.div_euclid(16), IVec3::new(-1, -1, 1));
+        assert_eq!(voxel.rem_euclid(16), IVec3::new(15, 0, 1));
+    }
+
+    #[test]
+    fn ivec3_arithmetic_operators_are_component_wise() {
+        let a = IVec3::new(1, 2, 3);
+        let b = IVec3::new(4, 6, 8);
+        assert_eq!(a + b, IVec3::new(5, 8, 11));
+        assert_eq!(b - a, IVec3::new(3, 4, 5));
+        assert_eq!(a * 2, IVec3::new(2, 4, 6));
+        assert_eq!(-a, IVec3::new(-1, -2, -3));
+        assert_eq!(a.min(IVec3::splat(2)), IVec3::new(1, 2, 2));
+        assert_eq!(a.max(IVec3::splat(2)), IVec3::new(2, 2, 3));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn ivec3_glam_round_trip() {
+        let v = IVec3::new(-3, 4, 5);
+        let g: glam::IVec3 = v.into();
+        assert_eq!(g, glam::IVec3::new(-3, 4, 5));
+        assert_eq!(IVec3::from(g), v);
+    }
+}
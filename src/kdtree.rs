@@ -0,0 +1,337 @@
+use crate::{Scalar, Vec2, Vec3};
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// A point type a [`KdTree`] can be built over.
+pub trait KdPoint: Copy {
+    /// Number of axes.
+    const DIM: usize;
+
+    /// Returns the coordinate along `axis` (`0` is X).
+    fn axis(&self, axis: usize) -> Scalar;
+
+    /// Returns the squared distance to `other`.
+    fn distance_squared_to(&self, other: &Self) -> Scalar;
+}
+
+impl KdPoint for Vec2 {
+    const DIM: usize = 2;
+
+    #[inline]
+    fn axis(&self, axis: usize) -> Scalar {
+        if axis == 0 { self.x } else { self.y }
+    }
+
+    #[inline]
+    fn distance_squared_to(&self, other: &Self) -> Scalar {
+        self.distance_squared(*other)
+    }
+}
+
+impl KdPoint for Vec3 {
+    const DIM: usize = 3;
+
+    #[inline]
+    fn axis(&self, axis: usize) -> Scalar {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    #[inline]
+    fn distance_squared_to(&self, other: &Self) -> Scalar {
+        self.distance_squared(*other)
+    }
+}
+
+/// A static KD-tree over points, for nearest-neighbour and radius queries over data that rarely
+/// changes (resource nodes, spawn points, waypoints).
+///
+/// The tree is built once from a list of `(point, item)` pairs and stored implicitly in a single
+/// vector, so there are no per-node allocations. Rebuild it when the data changes.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{KdTree3, Vec3};
+///
+/// let nodes = KdTree3::new(vec![
+///     (Vec3::new(0.0, 0.0, 0.0), "copper"),
+///     (Vec3::new(10.0, 0.0, 0.0), "iron"),
+///     (Vec3::new(3.0, 0.0, 4.0), "tin"),
+/// ]);
+/// let closest: Vec<_> = nodes.k_nearest(Vec3::new(4.0, 0.0, 4.0), 2)
+///     .into_iter()
+///     .map(|(_, item)| *item)
+///     .collect();
+/// assert_eq!(closest, vec!["tin", "copper"]);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KdTree<P, T> {
+    entries: Vec<(P, T)>,
+}
+
+/// A [`KdTree`] over 2D points.
+pub type KdTree2<T> = KdTree<Vec2, T>;
+/// A [`KdTree`] over 3D points.
+pub type KdTree3<T> = KdTree<Vec3, T>;
+
+impl<P: KdPoint, T> KdTree<P, T> {
+    /// Builds a tree from `(point, item)` pairs.
+    pub fn new(mut entries: Vec<(P, T)>) -> Self {
+        build(&mut entries, 0);
+        KdTree { entries }
+    }
+
+    /// Returns the number of points in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the tree holds no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over every `(point, item)` pair in an unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (P, &T)> {
+        self.entries.iter().map(|(p, item)| (*p, item))
+    }
+
+    /// Returns the point nearest to `query` and its item.
+    pub fn nearest(&self, query: P) -> Option<(P, &T)> {
+        self.k_nearest(query, 1).into_iter().next()
+    }
+
+    /// Returns up to `k` points nearest to `query`, nearest first.
+    pub fn k_nearest(&self, query: P, k: usize) -> Vec<(P, &T)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        self.search_nearest(0, self.entries.len(), 0, query, k, &mut heap);
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|candidate| {
+                let (p, item) = &self.entries[candidate.index];
+                (*p, item)
+            })
+            .collect()
+    }
+
+    /// Calls `f` with every point within `radius` of `center` (inclusive), in an unspecified order.
+    pub fn for_each_within_radius(&self, center: P, radius: Scalar, mut f: impl FnMut(P, &T)) {
+        self.search_radius(0, self.entries.len(), 0, center, radius, &mut f);
+    }
+
+    /// Returns every point within `radius` of `center` (inclusive), in an unspecified order.
+    pub fn within_radius(&self, center: P, radius: Scalar) -> Vec<(P, &T)> {
+        let mut found = Vec::new();
+        self.search_radius(0, self.entries.len(), 0, center, radius, &mut |p, item| {
+            found.push((p, item))
+        });
+        found
+    }
+
+    /// Searches the subtree stored in `entries[lo..hi]`, keeping the `k` best in a max-heap.
+    fn search_nearest(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        query: P,
+        k: usize,
+        heap: &mut BinaryHeap<Candidate>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let point = self.entries[mid].0;
+        let distance_sq = point.distance_squared_to(&query);
+        if heap.len() < k {
+            heap.push(Candidate::new(distance_sq, mid));
+        } else if heap
+            .peek()
+            .is_some_and(|worst| distance_sq < worst.distance_sq)
+        {
+            heap.pop();
+            heap.push(Candidate::new(distance_sq, mid));
+        }
+
+        let axis = depth % P::DIM;
+        let diff = query.axis(axis) - point.axis(axis);
+        let (near, far) = if diff < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search_nearest(near.0, near.1, depth + 1, query, k, heap);
+        // The far side can only help if the splitting plane is closer than the current worst.
+        if heap.len() < k
+            || heap
+                .peek()
+                .is_some_and(|worst| diff * diff < worst.distance_sq)
+        {
+            self.search_nearest(far.0, far.1, depth + 1, query, k, heap);
+        }
+    }
+
+    fn search_radius<'a>(
+        &'a self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        center: P,
+        radius: Scalar,
+        f: &mut impl FnMut(P, &'a T),
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (point, item) = &self.entries[mid];
+        if point.distance_squared_to(&center) <= radius * radius {
+            f(*point, item);
+        }
+        let axis = depth % P::DIM;
+        let diff = center.axis(axis) - point.axis(axis);
+        if diff <= radius {
+            self.search_radius(lo, mid, depth + 1, center, radius, f);
+        }
+        if diff >= -radius {
+            self.search_radius(mid + 1, hi, depth + 1, center, radius, f);
+        }
+    }
+}
+
+/// Arranges `entries` so the median along the depth's axis sits in the middle, with smaller
+/// coordinates before it and larger after, recursively.
+fn build<P: KdPoint, T>(entries: &mut [(P, T)], depth: usize) {
+    if entries.len() <= 1 {
+        return;
+    }
+    let mid = entries.len() / 2;
+    let axis = depth % P::DIM;
+    entries.select_nth_unstable_by(mid, |a, b| a.0.axis(axis).total_cmp(&b.0.axis(axis)));
+    let (left, rest) = entries.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut rest[1..], depth + 1);
+}
+
+/// A k-nearest candidate, ordered by distance so the heap's top is the worst kept so far.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance_sq: Scalar,
+    index: usize,
+}
+
+impl Candidate {
+    #[inline]
+    fn new(distance_sq: Scalar, index: usize) -> Self {
+        Candidate { distance_sq, index }
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_sq
+            .total_cmp(&other.distance_sq)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scattered(n: usize) -> Vec<(Vec3, usize)> {
+        (0..n)
+            .map(|i| {
+                let f = i as Scalar;
+                let p = Vec3::new((f * 7.3) % 50.0, (f * 3.1) % 17.0 - 8.0, (f * 5.7) % 31.0);
+                (p, i)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn k_nearest_matches_brute_force() {
+        let points = scattered(200);
+        let tree = KdTree3::new(points.clone());
+        let query = Vec3::new(20.0, 1.0, 12.0);
+
+        let mut expected: Vec<(Scalar, usize)> = points
+            .iter()
+            .map(|&(p, i)| (p.distance_squared(query), i))
+            .collect();
+        expected.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let expected: Vec<Scalar> = expected.iter().take(5).map(|e| e.0).collect();
+
+        let found: Vec<Scalar> = tree
+            .k_nearest(query, 5)
+            .iter()
+            .map(|(p, _)| p.distance_squared(query))
+            .collect();
+        assert_eq!(found, expected);
+        assert_eq!(tree.k_nearest(query, 500).len(), 200);
+        assert!(tree.k_nearest(query, 0).is_empty());
+    }
+
+    #[test]
+    fn within_radius_matches_brute_force() {
+        let points = scattered(150);
+        let tree = KdTree::new(points.clone());
+        let center = Vec3::new(25.0, 0.0, 15.0);
+        let mut found: Vec<usize> = tree
+            .within_radius(center, 9.0)
+            .iter()
+            .map(|&(_, &i)| i)
+            .collect();
+        found.sort();
+        let expected: Vec<usize> = points
+            .iter()
+            .filter(|(p, _)| p.distance(center) <= 9.0)
+            .map(|&(_, i)| i)
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn tree2_nearest_and_empty() {
+        let tree = KdTree2::new(vec![
+            (Vec2::new(0.0, 0.0), 'a'),
+            (Vec2::new(5.0, 5.0), 'b'),
+            (Vec2::new(-3.0, 1.0), 'c'),
+            (Vec2::new(5.0, 5.0), 'd'),
+        ]);
+        assert_eq!(
+            tree.nearest(Vec2::new(-2.0, 0.0)),
+            Some((Vec2::new(-3.0, 1.0), &'c'))
+        );
+        assert_eq!(tree.within_radius(Vec2::new(5.0, 5.0), 0.0).len(), 2);
+
+        let empty: KdTree2<()> = KdTree::new(Vec::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(Vec2::ZERO), None);
+    }
+}
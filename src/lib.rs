@@ -3,9 +3,30 @@
 //! # Features
 //! - `f32` (default): use `f32` as `Scalar`.
 //! - `f64`: use `f64` as `Scalar`.
+//! - `std` (default): link the standard library. Without it the crate is `no_std` and needs
+//!   `alloc` plus the `libm` feature; the hash-map based grids, [`flow_field`] and
+//!   [`shadowcast`] are unavailable.
+//! - `spacetimedb` (default): derive `SpacetimeType` on every type. Requires `std`.
+//! - `timing` (default): delta-time helpers for SpacetimeDB timestamps.
+//! - `libm`: compute float functions with `libm` instead of `std`.
 //! - `serde`: enable `Serialize`/`Deserialize` derives.
 //! - `glam`: enable `From` conversions with `glam` types.
 //! - `nalgebra`: enable `From` conversions with `nalgebra` types.
+//! - `rand`: enable `rand` distributions for vectors and quaternions, and `RngCore` for the
+//!   crate's generators; see [`random`].
+//! - `bytemuck`: make the vector and quaternion types `repr(C)` and `Pod`, with zero-copy
+//!   slice casts in [`pod`].
+//! - `approx`: implement `approx`'s `AbsDiffEq`, `RelativeEq` and `UlpsEq` for the vector,
+//!   quaternion, matrix, transform and shape types, for `assert_relative_eq!` in tests.
+//! - `bevy`: enable `From` conversions with `bevy_math` vectors and quaternions and, with
+//!   `f32`, `bevy_transform`'s `Transform` and `GlobalTransform`.
+//! - `deterministic`: compute float functions with `libm` for bit-identical results on every
+//!   host; see [`float`].
+//! - `validate`: debug-assert that vector and quaternion constructors and arithmetic produce
+//!   finite values, naming the operation, so NaN and infinity are caught where they appear
+//!   rather than after they've been stored.
+//! - `fixed`: enable the Q32.32 fixed-point [`Fixed`] number and its vector and quaternion
+//!   types, for lockstep simulations that must not depend on floats at all.
 //!
 //! # Examples
 //! ```ignore
@@ -21,20 +42,199 @@
 //!     pub rotation: Quat,
 //! }
 //! ```
+// Tests spell literals as `1.0 as Scalar` so they compile under both `f32` and `f64`.
+#![cfg_attr(test, allow(clippy::unnecessary_cast))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 #[cfg(all(feature = "f32", feature = "f64"))]
 compile_error!("Features 'f32' and 'f64' are mutually exclusive.");
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("Without the 'std' feature, enable 'libm' for float functions.");
+
+/// Evaluates to `$value`, first debug-asserting that it is finite when the `validate` feature
+/// is on. `$op` names the operation in the panic message.
+macro_rules! check_finite {
+    ($op:literal, $value:expr) => {{
+        let value = $value;
+        #[cfg(feature = "validate")]
+        debug_assert!(
+            value.is_finite(),
+            concat!($op, " produced a non-finite value")
+        );
+        value
+    }};
+}
+
+pub mod aabb;
+pub mod angle;
+#[cfg(feature = "approx")]
+mod approx_impls;
+pub mod bezier;
+pub mod bvh;
+pub mod canonical;
+pub mod capsule;
+pub mod checksum;
+pub mod chunk;
+pub mod circle;
+pub mod closest_point;
+pub mod cone;
+pub mod consts;
 pub mod conventions;
+pub mod correction;
+pub mod curve;
+pub mod cylinder;
+pub mod dda;
+pub mod dead_reckoning;
+pub mod delta;
+pub mod distance;
+pub mod error;
+#[cfg(feature = "fixed")]
+pub mod fixed;
+pub mod float;
+pub mod float_cmp;
+#[cfg(feature = "std")]
+pub mod flow_field;
+pub mod fov;
+pub mod frustum;
+pub mod geo;
+pub mod gjk;
+mod gjk3;
+pub mod grid_line;
+pub mod heightfield;
+pub mod hex;
+pub mod hilbert;
+pub mod history;
+pub mod hvec;
+pub mod interp;
+pub mod intersect;
+pub mod iso;
+pub mod ivec2;
+pub mod ivec3;
+pub mod kdtree;
+#[cfg(feature = "std")]
+pub mod loose_grid;
+pub mod low_discrepancy;
+pub mod mat3;
+pub mod mat4;
+pub mod morton;
+pub mod noise;
+pub mod obb;
+pub mod origin;
+pub mod overlap;
+pub mod packed_dir;
+pub mod pid;
+pub mod plane;
+#[cfg(feature = "bytemuck")]
+pub mod pod;
+pub mod polygon;
+pub mod polyline;
+pub mod qpos;
 pub mod quat;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod ray;
+pub mod region;
+pub mod rng;
+pub mod sample;
+pub mod sanitize;
 pub mod scalar;
+pub mod sdf;
+pub mod second_order;
+pub mod segment;
+#[cfg(feature = "std")]
+pub mod shadowcast;
+pub mod slide;
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod spatial_hash;
+pub mod sphere;
+pub mod spline;
+pub mod spline_path;
+pub mod spring;
+pub mod surface;
+pub mod sweep;
+pub mod timestep;
 #[cfg(feature = "timing")]
 pub mod timing;
+pub mod transform;
+pub mod triangle;
+pub mod tween;
+pub mod validate;
 pub mod vec2;
 pub mod vec3;
+pub mod vec4;
+pub mod world_pos;
+pub mod wrap;
+pub mod zone;
 
+pub use aabb::*;
+pub use bezier::*;
+pub use bvh::*;
+pub use capsule::*;
+pub use chunk::*;
+pub use circle::*;
+pub use cone::*;
+pub use correction::*;
+pub use curve::*;
+pub use cylinder::*;
+pub use dda::*;
+pub use dead_reckoning::*;
+pub use delta::*;
+pub use error::*;
+#[cfg(feature = "fixed")]
+pub use fixed::*;
+#[cfg(feature = "std")]
+pub use flow_field::*;
+pub use fov::*;
+pub use frustum::*;
+pub use geo::*;
+pub use heightfield::*;
+pub use hex::*;
+pub use history::*;
+pub use hvec::*;
+pub use iso::*;
+pub use ivec2::*;
+pub use ivec3::*;
+pub use kdtree::*;
+#[cfg(feature = "std")]
+pub use loose_grid::*;
+pub use low_discrepancy::*;
+pub use mat3::*;
+pub use mat4::*;
+pub use noise::*;
+pub use obb::*;
+pub use origin::*;
+pub use packed_dir::*;
+pub use pid::*;
+pub use plane::*;
+pub use polygon::*;
+pub use polyline::*;
+pub use qpos::*;
 pub use quat::*;
+pub use ray::*;
+pub use rng::*;
 pub use scalar::*;
+pub use second_order::*;
+pub use segment::*;
+pub use snapshot::*;
+#[cfg(feature = "std")]
+pub use spatial_hash::*;
+pub use sphere::*;
+pub use spline::*;
+pub use spline_path::*;
+pub use spring::*;
+pub use timestep::*;
 #[cfg(feature = "timing")]
 pub use timing::*;
+pub use transform::*;
+pub use triangle::*;
+pub use tween::*;
 pub use vec2::*;
 pub use vec3::*;
+pub use vec4::*;
+pub use world_pos::*;
+pub use wrap::*;
+pub use zone::*;
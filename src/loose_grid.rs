@@ -0,0 +1,455 @@
+use crate::{Aabb2, Aabb3, Circle, Scalar, Sphere, Vec2, Vec3};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A loose grid broadphase over 2D circles.
+///
+/// Each entry lives in a single "home" cell, and every cell is treated as `looseness` larger on
+/// each side than its tight bounds. An entry stays in its home cell for as long as its circle
+/// fits inside those loose bounds, so most movements are a plain field update with no cell
+/// change. Queries pay for this by scanning `looseness` further out.
+///
+/// A `looseness` of at least the typical entry radius lets large or fast entries (bosses,
+/// vehicles) live in the grid without reinserting every tick, unlike [`SpatialHashGrid2`] where
+/// entries are points. Entries too big to fit any cell are kept in a short overflow list that
+/// every query checks.
+///
+/// [`SpatialHashGrid2`]: crate::SpatialHashGrid2
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Circle, LooseGrid2, Vec2};
+///
+/// let mut grid = LooseGrid2::new(10.0, 5.0);
+/// grid.insert(1u64, Circle::new(Vec2::new(5.0, 5.0), 4.0));
+/// // A small step stays within the loose bounds: no cell change.
+/// assert!(!grid.update(1u64, Circle::new(Vec2::new(6.0, 5.0), 4.0)));
+/// assert_eq!(grid.query_circle(&Circle::new(Vec2::new(13.0, 5.0), 4.0)), vec![1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LooseGrid2<T> {
+    cell_size: Scalar,
+    looseness: Scalar,
+    cells: HashMap<[i32; 2], Vec<T>>,
+    oversized: Vec<T>,
+    entries: HashMap<T, (Circle, Option<[i32; 2]>)>,
+}
+
+impl<T: Copy + Eq + Hash> LooseGrid2<T> {
+    /// Creates an empty grid with square cells of `cell_size`, each extended by `looseness` on
+    /// every side.
+    pub fn new(cell_size: Scalar, looseness: Scalar) -> Self {
+        LooseGrid2 {
+            cell_size,
+            looseness,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of entries in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the grid holds no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the bounds of `id`, if it is in the grid.
+    #[inline]
+    pub fn get(&self, id: T) -> Option<Circle> {
+        self.entries.get(&id).map(|&(bounds, _)| bounds)
+    }
+
+    /// Returns the cell containing `position`.
+    #[inline]
+    pub fn cell_of(&self, position: Vec2) -> [i32; 2] {
+        [
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Returns the loose bounds of `cell`: its tight bounds grown by `looseness`.
+    pub fn loose_bounds(&self, cell: [i32; 2]) -> Aabb2 {
+        let min = Vec2::new(cell[0] as Scalar, cell[1] as Scalar) * self.cell_size;
+        Aabb2::new(min, min + Vec2::splat(self.cell_size)).expand_by(self.looseness)
+    }
+
+    /// Inserts `id` with `bounds`, moving it if it is already present.
+    pub fn insert(&mut self, id: T, bounds: Circle) {
+        self.update(id, bounds);
+    }
+
+    /// Removes `id`, returning its bounds if it was present.
+    pub fn remove(&mut self, id: T) -> Option<Circle> {
+        let (bounds, home) = self.entries.remove(&id)?;
+        self.unlink(id, home);
+        Some(bounds)
+    }
+
+    /// Updates the bounds of `id`, inserting it if it is not present.
+    ///
+    /// Returns `true` if the entry changed cells, and `false` if it still fits its home cell.
+    pub fn update(&mut self, id: T, bounds: Circle) -> bool {
+        if let Some(&(_, home)) = self.entries.get(&id) {
+            if self.fits(home, &bounds) {
+                self.entries.insert(id, (bounds, home));
+                return false;
+            }
+            self.unlink(id, home);
+        }
+        let cell = self.cell_of(bounds.center);
+        let home = self.fits(Some(cell), &bounds).then_some(cell);
+        match home {
+            Some(cell) => self.cells.entry(cell).or_default().push(id),
+            None => self.oversized.push(id),
+        }
+        self.entries.insert(id, (bounds, home));
+        true
+    }
+
+    /// Removes every entry, keeping the cell size and looseness.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.oversized.clear();
+        self.entries.clear();
+    }
+
+    /// Calls `f` for every entry whose circle overlaps `aabb`.
+    pub fn for_each_in_aabb(&self, aabb: &Aabb2, mut f: impl FnMut(T, Circle)) {
+        let mut visit = |id: T| {
+            let bounds = self.entries[&id].0;
+            if aabb.distance_squared_to_point(bounds.center) <= bounds.radius * bounds.radius {
+                f(id, bounds);
+            }
+        };
+        let [x0, y0] = self.cell_of(aabb.min - Vec2::splat(self.looseness));
+        let [x1, y1] = self.cell_of(aabb.max + Vec2::splat(self.looseness));
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                for &id in self.cells.get(&[x, y]).into_iter().flatten() {
+                    visit(id);
+                }
+            }
+        }
+        self.oversized.iter().for_each(|&id| visit(id));
+    }
+
+    /// Returns every entry whose circle overlaps `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb2) -> Vec<T> {
+        let mut found = Vec::new();
+        self.for_each_in_aabb(aabb, |id, _| found.push(id));
+        found
+    }
+
+    /// Returns every entry whose circle overlaps `circle`.
+    pub fn query_circle(&self, circle: &Circle) -> Vec<T> {
+        let bounds = Aabb2::from_center_half_extents(circle.center, Vec2::splat(circle.radius));
+        let mut found = Vec::new();
+        self.for_each_in_aabb(&bounds, |id, other| {
+            let reach = circle.radius + other.radius;
+            if circle.center.distance_squared(other.center) <= reach * reach {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    /// Calls `f` once for every pair of entries whose circles overlap, as `(lower, higher)`.
+    pub fn for_each_overlapping_pair(&self, mut f: impl FnMut(T, T))
+    where
+        T: Ord,
+    {
+        for (&a, &(bounds, _)) in &self.entries {
+            for b in self.query_circle(&bounds) {
+                if a < b {
+                    f(a, b);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if `bounds` lies inside the loose bounds of `home`.
+    fn fits(&self, home: Option<[i32; 2]>, bounds: &Circle) -> bool {
+        home.is_some_and(|cell| {
+            let loose = self.loose_bounds(cell);
+            let r = Vec2::splat(bounds.radius);
+            loose.contains_aabb(&Aabb2::new(bounds.center - r, bounds.center + r))
+        })
+    }
+
+    fn unlink(&mut self, id: T, home: Option<[i32; 2]>) {
+        match home {
+            Some(cell) => {
+                if let Some(items) = self.cells.get_mut(&cell) {
+                    items.retain(|&other| other != id);
+                    if items.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }
+            None => self.oversized.retain(|&other| other != id),
+        }
+    }
+}
+
+/// A loose grid broadphase over 3D spheres.
+///
+/// The 3D counterpart of [`LooseGrid2`].
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{LooseGrid3, Sphere, Vec3};
+///
+/// let mut grid = LooseGrid3::new(16.0, 8.0);
+/// grid.insert(7u32, Sphere::new(Vec3::new(8.0, 8.0, 8.0), 6.0));
+/// grid.insert(9u32, Sphere::new(Vec3::new(20.0, 8.0, 8.0), 6.0));
+/// let mut pairs = Vec::new();
+/// grid.for_each_overlapping_pair(|a, b| pairs.push((a, b)));
+/// assert_eq!(pairs, vec![(7, 9)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LooseGrid3<T> {
+    cell_size: Scalar,
+    looseness: Scalar,
+    cells: HashMap<[i32; 3], Vec<T>>,
+    oversized: Vec<T>,
+    entries: HashMap<T, (Sphere, Option<[i32; 3]>)>,
+}
+
+impl<T: Copy + Eq + Hash> LooseGrid3<T> {
+    /// Creates an empty grid with cubic cells of `cell_size`, each extended by `looseness` on
+    /// every side.
+    pub fn new(cell_size: Scalar, looseness: Scalar) -> Self {
+        LooseGrid3 {
+            cell_size,
+            looseness,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of entries in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the grid holds no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the bounds of `id`, if it is in the grid.
+    #[inline]
+    pub fn get(&self, id: T) -> Option<Sphere> {
+        self.entries.get(&id).map(|&(bounds, _)| bounds)
+    }
+
+    /// Returns the cell containing `position`.
+    #[inline]
+    pub fn cell_of(&self, position: Vec3) -> [i32; 3] {
+        [
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+            (position.z / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Returns the loose bounds of `cell`: its tight bounds grown by `looseness`.
+    pub fn loose_bounds(&self, cell: [i32; 3]) -> Aabb3 {
+        let min = Vec3::new(cell[0] as Scalar, cell[1] as Scalar, cell[2] as Scalar);
+        let min = min * self.cell_size;
+        Aabb3::new(min, min + Vec3::splat(self.cell_size)).expand_by(self.looseness)
+    }
+
+    /// Inserts `id` with `bounds`, moving it if it is already present.
+    pub fn insert(&mut self, id: T, bounds: Sphere) {
+        self.update(id, bounds);
+    }
+
+    /// Removes `id`, returning its bounds if it was present.
+    pub fn remove(&mut self, id: T) -> Option<Sphere> {
+        let (bounds, home) = self.entries.remove(&id)?;
+        self.unlink(id, home);
+        Some(bounds)
+    }
+
+    /// Updates the bounds of `id`, inserting it if it is not present.
+    ///
+    /// Returns `true` if the entry changed cells, and `false` if it still fits its home cell.
+    pub fn update(&mut self, id: T, bounds: Sphere) -> bool {
+        if let Some(&(_, home)) = self.entries.get(&id) {
+            if self.fits(home, &bounds) {
+                self.entries.insert(id, (bounds, home));
+                return false;
+            }
+            self.unlink(id, home);
+        }
+        let cell = self.cell_of(bounds.center);
+        let home = self.fits(Some(cell), &bounds).then_some(cell);
+        match home {
+            Some(cell) => self.cells.entry(cell).or_default().push(id),
+            None => self.oversized.push(id),
+        }
+        self.entries.insert(id, (bounds, home));
+        true
+    }
+
+    /// Removes every entry, keeping the cell size and looseness.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.oversized.clear();
+        self.entries.clear();
+    }
+
+    /// Calls `f` for every entry whose sphere overlaps `aabb`.
+    pub fn for_each_in_aabb(&self, aabb: &Aabb3, mut f: impl FnMut(T, Sphere)) {
+        let mut visit = |id: T| {
+            let bounds = self.entries[&id].0;
+            if aabb.distance_squared_to_point(bounds.center) <= bounds.radius * bounds.radius {
+                f(id, bounds);
+            }
+        };
+        let [x0, y0, z0] = self.cell_of(aabb.min - Vec3::splat(self.looseness));
+        let [x1, y1, z1] = self.cell_of(aabb.max + Vec3::splat(self.looseness));
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                for z in z0..=z1 {
+                    for &id in self.cells.get(&[x, y, z]).into_iter().flatten() {
+                        visit(id);
+                    }
+                }
+            }
+        }
+        self.oversized.iter().for_each(|&id| visit(id));
+    }
+
+    /// Returns every entry whose sphere overlaps `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb3) -> Vec<T> {
+        let mut found = Vec::new();
+        self.for_each_in_aabb(aabb, |id, _| found.push(id));
+        found
+    }
+
+    /// Returns every entry whose sphere overlaps `sphere`.
+    pub fn query_sphere(&self, sphere: &Sphere) -> Vec<T> {
+        let bounds = Aabb3::from_center_half_extents(sphere.center, Vec3::splat(sphere.radius));
+        let mut found = Vec::new();
+        self.for_each_in_aabb(&bounds, |id, other| {
+            let reach = sphere.radius + other.radius;
+            if sphere.center.distance_squared(other.center) <= reach * reach {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    /// Calls `f` once for every pair of entries whose spheres overlap, as `(lower, higher)`.
+    pub fn for_each_overlapping_pair(&self, mut f: impl FnMut(T, T))
+    where
+        T: Ord,
+    {
+        for (&a, &(bounds, _)) in &self.entries {
+            for b in self.query_sphere(&bounds) {
+                if a < b {
+                    f(a, b);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if `bounds` lies inside the loose bounds of `home`.
+    fn fits(&self, home: Option<[i32; 3]>, bounds: &Sphere) -> bool {
+        home.is_some_and(|cell| {
+            let loose = self.loose_bounds(cell);
+            let r = Vec3::splat(bounds.radius);
+            loose.contains_aabb(&Aabb3::new(bounds.center - r, bounds.center + r))
+        })
+    }
+
+    fn unlink(&mut self, id: T, home: Option<[i32; 3]>) {
+        match home {
+            Some(cell) => {
+                if let Some(items) = self.cells.get_mut(&cell) {
+                    items.retain(|&other| other != id);
+                    if items.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }
+            None => self.oversized.retain(|&other| other != id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid2_small_moves_stay_in_home_cell() {
+        let mut grid = LooseGrid2::new(10.0, 2.0);
+        assert!(grid.update(1, Circle::new(Vec2::new(5.0, 5.0), 1.0)));
+        assert!(!grid.update(1, Circle::new(Vec2::new(10.5, 5.0), 1.0)));
+        assert!(grid.update(1, Circle::new(Vec2::new(11.5, 5.0), 1.0)));
+        assert_eq!(grid.remove(1), Some(Circle::new(Vec2::new(11.5, 5.0), 1.0)));
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn grid2_queries_find_entries_outside_home_cell() {
+        let mut grid = LooseGrid2::new(10.0, 3.0);
+        // Home cell (0, 0), but the circle reaches into cell (1, 0).
+        grid.insert('a', Circle::new(Vec2::new(9.0, 5.0), 3.0));
+        // Too big for any cell: kept in the overflow list.
+        grid.insert('b', Circle::new(Vec2::new(-40.0, 0.0), 20.0));
+        let probe = Aabb2::new(Vec2::new(11.0, 4.0), Vec2::new(12.0, 6.0));
+        assert_eq!(grid.query_aabb(&probe), vec!['a']);
+        assert_eq!(
+            grid.query_circle(&Circle::new(Vec2::new(-15.0, 0.0), 6.0)),
+            vec!['b']
+        );
+        assert!(
+            grid.query_circle(&Circle::new(Vec2::new(30.0, 0.0), 1.0))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn grid3_pairs_match_brute_force() {
+        let spheres: Vec<(u32, Sphere)> = (0..50u32)
+            .map(|i| {
+                let f = i as Scalar;
+                let center = Vec3::new((f * 7.3) % 40.0, (f * 3.1) % 12.0, (f * 5.7) % 30.0);
+                (i, Sphere::new(center, 0.5 + (f % 4.0)))
+            })
+            .collect();
+        let mut grid = LooseGrid3::new(8.0, 2.0);
+        for &(id, sphere) in &spheres {
+            grid.insert(id, sphere);
+        }
+        let mut pairs = Vec::new();
+        grid.for_each_overlapping_pair(|a, b| pairs.push((a, b)));
+        pairs.sort();
+
+        let mut expected = Vec::new();
+        for (i, &(a, sa)) in spheres.iter().enumerate() {
+            for &(b, sb) in &spheres[i + 1..] {
+                let reach = sa.radius + sb.radius;
+                if sa.center.distance_squared(sb.center) <= reach * reach {
+                    expected.push((a, b));
+                }
+            }
+        }
+        assert_eq!(pairs, expected);
+    }
+}
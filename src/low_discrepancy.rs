@@ -0,0 +1,245 @@
+use crate::{Scalar, Vec2, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// The largest [`Scalar`] below one, so rounding never produces the excluded upper bound.
+const BELOW_ONE: Scalar = 1.0 - Scalar::EPSILON / 2.0;
+
+/// Returns `index` with its base-`base` digits mirrored around the radix point, in `[0, 1)`:
+/// the van der Corput sequence, and one axis of a [`Halton2`] or [`Halton3`] point.
+///
+/// # Panics
+/// Panics if `base` is less than 2.
+pub fn radical_inverse(mut index: u32, base: u32) -> Scalar {
+    assert!(base >= 2, "radical inverse base must be at least 2");
+    let base = base as u64;
+    let (mut digits, mut scale) = (0u64, 1u64);
+    while index > 0 {
+        digits = digits * base + index as u64 % base;
+        scale *= base;
+        index /= base as u32;
+    }
+    let unit: Scalar = (digits as f64 / scale as f64) as _;
+    unit.min(BELOW_ONE)
+}
+
+/// Returns the fraction `bits / 2^32` as a [`Scalar`] in `[0, 1)`.
+#[inline]
+fn unit_from_bits(bits: u32) -> Scalar {
+    #[cfg(feature = "f32")]
+    return (bits >> 8) as f32 * (1.0 / (1u32 << 24) as f32);
+    #[cfg(feature = "f64")]
+    return bits as f64 * (1.0 / (1u64 << 32) as f64);
+}
+
+/// The 2D Halton sequence, with bases 2 and 3: points in the unit square that fill it evenly
+/// however many have been taken, unlike random points, which clump and leave gaps.
+///
+/// The state is just the next index, so a progressive placement can be stored in a row and
+/// resumed. Index 0 is the corner `(0, 0)`; start at 1 to skip it.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Aabb2, Halton2, Vec2};
+///
+/// let meadow = Aabb2::new(Vec2::new(100.0, 100.0), Vec2::new(200.0, 150.0));
+/// let flowers: Vec<Vec2> = Halton2::new(1).take(20).map(|p| meadow.point_at(p)).collect();
+/// assert!(flowers.iter().all(|&f| meadow.contains_point(f)));
+/// assert_eq!(Halton2::point(1), Vec2::new(0.5, 1.0 / 3.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Halton2 {
+    /// Index of the next point.
+    pub index: u32,
+}
+
+impl Halton2 {
+    #[inline(always)]
+    pub const fn new(index: u32) -> Self {
+        Halton2 { index }
+    }
+
+    /// Returns the point at `index`.
+    #[inline]
+    pub fn point(index: u32) -> Vec2 {
+        Vec2::new(radical_inverse(index, 2), radical_inverse(index, 3))
+    }
+}
+
+impl Iterator for Halton2 {
+    type Item = Vec2;
+
+    /// Returns the point at the current index and advances, wrapping after `u32::MAX`.
+    #[inline]
+    fn next(&mut self) -> Option<Vec2> {
+        let point = Halton2::point(self.index);
+        self.index = self.index.wrapping_add(1);
+        Some(point)
+    }
+}
+
+/// The 3D Halton sequence, with bases 2, 3 and 5; see [`Halton2`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Halton3 {
+    /// Index of the next point.
+    pub index: u32,
+}
+
+impl Halton3 {
+    #[inline(always)]
+    pub const fn new(index: u32) -> Self {
+        Halton3 { index }
+    }
+
+    /// Returns the point at `index`.
+    #[inline]
+    pub fn point(index: u32) -> Vec3 {
+        Vec3::new(
+            radical_inverse(index, 2),
+            radical_inverse(index, 3),
+            radical_inverse(index, 5),
+        )
+    }
+}
+
+impl Iterator for Halton3 {
+    type Item = Vec3;
+
+    /// Returns the point at the current index and advances, wrapping after `u32::MAX`.
+    #[inline]
+    fn next(&mut self) -> Option<Vec3> {
+        let point = Halton3::point(self.index);
+        self.index = self.index.wrapping_add(1);
+        Some(point)
+    }
+}
+
+/// The 2D Sobol sequence: like [`Halton2`], but every power-of-two run of points starting at
+/// a multiple of its length puts exactly one point in each cell of any `2^a` by `2^b` grid
+/// with that many cells, which suits stratified sampling.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::Sobol2;
+///
+/// // The first four points land in different quadrants.
+/// let quadrants: Vec<(bool, bool)> = Sobol2::new(0)
+///     .take(4)
+///     .map(|p| (p.x < 0.5, p.y < 0.5))
+///     .collect();
+/// assert_eq!(quadrants, [(true, true), (false, false), (true, false), (false, true)]);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sobol2 {
+    /// Index of the next point.
+    pub index: u32,
+}
+
+impl Sobol2 {
+    #[inline(always)]
+    pub const fn new(index: u32) -> Self {
+        Sobol2 { index }
+    }
+
+    /// Returns the point at `index`.
+    pub fn point(index: u32) -> Vec2 {
+        // The first axis is the base-2 radical inverse. The second XORs together a direction
+        // number per set bit of `index`, from the primitive polynomial x + 1: each is the
+        // previous one XOR itself shifted right.
+        let x = index.reverse_bits();
+        let (mut y, mut direction, mut bits) = (0u32, 1u32 << 31, index);
+        while bits > 0 {
+            if bits & 1 == 1 {
+                y ^= direction;
+            }
+            direction ^= direction >> 1;
+            bits >>= 1;
+        }
+        Vec2::new(unit_from_bits(x), unit_from_bits(y))
+    }
+}
+
+impl Iterator for Sobol2 {
+    type Item = Vec2;
+
+    /// Returns the point at the current index and advances, wrapping after `u32::MAX`.
+    #[inline]
+    fn next(&mut self) -> Option<Vec2> {
+        let point = Sobol2::point(self.index);
+        self.index = self.index.wrapping_add(1);
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aabb2, Aabb3};
+
+    const EPS: Scalar = 1.0e-6 as Scalar;
+
+    #[test]
+    fn radical_inverse_mirrors_digits() {
+        assert_eq!(radical_inverse(0, 2), 0.0);
+        assert_eq!(radical_inverse(1, 2), 0.5);
+        assert_eq!(radical_inverse(6, 2), 0.375);
+        // 5 is 12 in base 3, mirrored to 0.21, which is 7/9.
+        assert!((radical_inverse(5, 3) - 7.0 / 9.0).abs() <= EPS);
+        assert!(radical_inverse(u32::MAX, 2) < 1.0);
+        assert!(radical_inverse(u32::MAX, 7) < 1.0);
+    }
+
+    #[test]
+    fn halton_points_match_their_bases() {
+        let points: Vec<Vec2> = Halton2::new(1).take(3).collect();
+        let expected = [(0.5, 1.0 / 3.0), (0.25, 2.0 / 3.0), (0.75, 1.0 / 9.0)];
+        for (p, (x, y)) in points.iter().zip(expected) {
+            assert!((p.x - x).abs() <= EPS && (p.y - y).abs() <= EPS, "{p:?}");
+        }
+        let p = Halton3::new(4).next().unwrap();
+        assert!(
+            (p - Vec3::new(0.125, 4.0 / 9.0, 0.8)).length() <= EPS,
+            "{p:?}"
+        );
+
+        let mut resumed = Halton3::new(10);
+        resumed.next();
+        assert_eq!(resumed.index, 11);
+        assert_eq!(resumed.next(), Some(Halton3::point(11)));
+    }
+
+    #[test]
+    fn sobol_points_stratify() {
+        // Each run of 16 aligned points covers every cell of a 4 by 4 grid, and of 2 by 8.
+        for start in [0, 16, 48] {
+            let mut square = [false; 16];
+            let mut strips = [false; 16];
+            for p in Sobol2::new(start).take(16) {
+                square[(p.y * 4.0) as usize * 4 + (p.x * 4.0) as usize] = true;
+                strips[(p.y * 8.0) as usize * 2 + (p.x * 2.0) as usize] = true;
+            }
+            assert!(square.iter().all(|&hit| hit), "{start}");
+            assert!(strips.iter().all(|&hit| hit), "{start}");
+        }
+        assert_eq!(Sobol2::point(3), Vec2::new(0.75, 0.25));
+        let last = Sobol2::point(u32::MAX);
+        assert!(last.x < 1.0 && last.y < 1.0);
+    }
+
+    #[test]
+    fn points_map_into_boxes() {
+        let area = Aabb2::new(Vec2::new(-2.0, 1.0), Vec2::new(2.0, 3.0));
+        assert_eq!(area.point_at(Vec2::new(0.5, 0.25)), Vec2::new(0.0, 1.5));
+        let volume = Aabb3::new(Vec3::ZERO, Vec3::new(2.0, 4.0, 6.0));
+        for p in Halton3::new(0).take(100) {
+            assert!(volume.contains_point(volume.point_at(p)));
+        }
+        assert_eq!(volume.point_at(Vec3::ONE), volume.max);
+    }
+}
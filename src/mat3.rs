@@ -0,0 +1,213 @@
+use crate::{Quat, Scalar, Vec3};
+use core::ops::Mul;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A column-major 3x3 matrix, laid out the same way as `glam::Mat3`.
+///
+/// Mostly useful for linear maps that aren't rotations, such as the change of basis between
+/// two coordinate conventions, which may mirror an axis.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Mat3, Quat, Vec3};
+///
+/// let quarter = std::f64::consts::FRAC_PI_2 as _;
+/// let q = Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), quarter);
+/// let m = Mat3::from_quat(q);
+/// assert!((m * Vec3::new(1.0, 0.0, 0.0)).distance(Vec3::new(0.0, 1.0, 0.0)) < 1.0e-6);
+/// assert!((m.determinant() - 1.0).abs() < 1.0e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mat3 {
+    /// First column.
+    pub x_axis: Vec3,
+    /// Second column.
+    pub y_axis: Vec3,
+    /// Third column.
+    pub z_axis: Vec3,
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat3 {
+    pub const ZERO: Mat3 = Mat3::from_cols(Vec3::ZERO, Vec3::ZERO, Vec3::ZERO);
+    pub const IDENTITY: Mat3 = Mat3::from_cols(
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+    );
+
+    /// Creates a matrix from its three columns.
+    #[inline(always)]
+    pub const fn from_cols(x_axis: Vec3, y_axis: Vec3, z_axis: Vec3) -> Self {
+        Mat3 {
+            x_axis,
+            y_axis,
+            z_axis,
+        }
+    }
+
+    /// Creates the rotation matrix of the unit quaternion `q`.
+    pub fn from_quat(q: Quat) -> Self {
+        Mat3::from_cols(
+            q * Vec3::new(1.0, 0.0, 0.0),
+            q * Vec3::new(0.0, 1.0, 0.0),
+            q * Vec3::new(0.0, 0.0, 1.0),
+        )
+    }
+
+    /// Returns the row at `index` (0-2).
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than 2.
+    #[inline]
+    pub fn row(&self, index: usize) -> Vec3 {
+        match index {
+            0 => Vec3::new(self.x_axis.x, self.y_axis.x, self.z_axis.x),
+            1 => Vec3::new(self.x_axis.y, self.y_axis.y, self.z_axis.y),
+            2 => Vec3::new(self.x_axis.z, self.y_axis.z, self.z_axis.z),
+            _ => panic!("Mat3 row index out of bounds: {index}"),
+        }
+    }
+
+    /// Returns the transpose of this matrix, which is its inverse if it's orthonormal.
+    pub fn transpose(&self) -> Mat3 {
+        Mat3::from_cols(self.row(0), self.row(1), self.row(2))
+    }
+
+    /// Returns the determinant: `1` for rotations and `-1` for orthonormal maps that also
+    /// mirror, such as a change of handedness.
+    #[inline]
+    pub fn determinant(&self) -> Scalar {
+        self.x_axis.dot(self.y_axis.cross(self.z_axis))
+    }
+
+    /// Multiplies this matrix by the column vector `v`.
+    #[inline]
+    pub fn mul_vec3(&self, v: Vec3) -> Vec3 {
+        self.x_axis * v.x + self.y_axis * v.y + self.z_axis * v.z
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    #[inline]
+    fn mul(self, rhs: Mat3) -> Mat3 {
+        Mat3::from_cols(
+            self.mul_vec3(rhs.x_axis),
+            self.mul_vec3(rhs.y_axis),
+            self.mul_vec3(rhs.z_axis),
+        )
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        self.mul_vec3(rhs)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Matrix3<Scalar>> for Mat3 {
+        fn from(m: nalgebra::Matrix3<Scalar>) -> Self {
+            let col = |i: usize| Vec3::new(m[(0, i)], m[(1, i)], m[(2, i)]);
+            Self::from_cols(col(0), col(1), col(2))
+        }
+    }
+
+    impl From<Mat3> for nalgebra::Matrix3<Scalar> {
+        fn from(m: Mat3) -> Self {
+            Self::from_columns(&[m.x_axis.into(), m.y_axis.into(), m.z_axis.into()])
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    #[cfg(feature = "f32")]
+    impl From<glam::Mat3> for Mat3 {
+        fn from(m: glam::Mat3) -> Self {
+            Self::from_cols(m.x_axis.into(), m.y_axis.into(), m.z_axis.into())
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    impl From<Mat3> for glam::Mat3 {
+        fn from(m: Mat3) -> Self {
+            Self::from_cols(m.x_axis.into(), m.y_axis.into(), m.z_axis.into())
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<glam::DMat3> for Mat3 {
+        fn from(m: glam::DMat3) -> Self {
+            Self::from_cols(m.x_axis.into(), m.y_axis.into(), m.z_axis.into())
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<Mat3> for glam::DMat3 {
+        fn from(m: Mat3) -> Self {
+            Self::from_cols(m.x_axis.into(), m.y_axis.into(), m.z_axis.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_is_multiplicative_neutral() {
+        let m = Mat3::from_cols(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(4.0, 5.0, 6.0),
+            Vec3::new(7.0, 8.0, 10.0),
+        );
+        assert_eq!(m * Mat3::IDENTITY, m);
+        assert_eq!(Mat3::IDENTITY * m, m);
+        assert_eq!(Mat3::default(), Mat3::IDENTITY);
+        assert_eq!(m.transpose().row(2), m.z_axis);
+    }
+
+    #[test]
+    fn determinant_detects_mirroring() {
+        let mirror = Mat3::from_cols(
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        );
+        assert_eq!(mirror.determinant(), -1.0);
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.7);
+        assert!((Mat3::from_quat(q).determinant() - 1.0).abs() <= 1.0e-5 as Scalar);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_roundtrip() {
+        let m = Mat3::from_cols(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(4.0, 5.0, 6.0),
+            Vec3::new(7.0, 8.0, 9.0),
+        );
+        let n: nalgebra::Matrix3<Scalar> = m.into();
+        assert_eq!(n[(0, 1)], 4.0 as Scalar);
+        assert_eq!(Mat3::from(n), m);
+    }
+}
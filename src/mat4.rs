@@ -0,0 +1,303 @@
+use crate::{Scalar, Vec3, Vec4, float};
+use core::ops::Mul;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A column-major 4x4 matrix, laid out the same way as `glam::Mat4`.
+///
+/// Projection helpers produce clip space with depth in `[0, 1]`, matching `glam`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Mat4, Vec3};
+///
+/// let m = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+/// assert_eq!(m.transform_point3(Vec3::ZERO), Vec3::new(1.0, 2.0, 3.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mat4 {
+    /// First column.
+    pub x_axis: Vec4,
+    /// Second column.
+    pub y_axis: Vec4,
+    /// Third column.
+    pub z_axis: Vec4,
+    /// Fourth column (translation for affine transforms).
+    pub w_axis: Vec4,
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat4 {
+    pub const ZERO: Mat4 = Mat4::from_cols(Vec4::ZERO, Vec4::ZERO, Vec4::ZERO, Vec4::ZERO);
+    pub const IDENTITY: Mat4 = Mat4::from_cols(
+        Vec4::new(1.0, 0.0, 0.0, 0.0),
+        Vec4::new(0.0, 1.0, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 1.0, 0.0),
+        Vec4::new(0.0, 0.0, 0.0, 1.0),
+    );
+
+    /// Creates a matrix from its four columns.
+    #[inline(always)]
+    pub const fn from_cols(x_axis: Vec4, y_axis: Vec4, z_axis: Vec4, w_axis: Vec4) -> Self {
+        Mat4 {
+            x_axis,
+            y_axis,
+            z_axis,
+            w_axis,
+        }
+    }
+
+    /// Creates an affine transform that translates by `translation`.
+    #[inline]
+    pub const fn from_translation(translation: Vec3) -> Self {
+        let mut m = Self::IDENTITY;
+        m.w_axis = translation.extend(1.0);
+        m
+    }
+
+    /// Returns the row at `index` (0-3).
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than 3.
+    #[inline]
+    pub fn row(&self, index: usize) -> Vec4 {
+        match index {
+            0 => Vec4::new(self.x_axis.x, self.y_axis.x, self.z_axis.x, self.w_axis.x),
+            1 => Vec4::new(self.x_axis.y, self.y_axis.y, self.z_axis.y, self.w_axis.y),
+            2 => Vec4::new(self.x_axis.z, self.y_axis.z, self.z_axis.z, self.w_axis.z),
+            3 => Vec4::new(self.x_axis.w, self.y_axis.w, self.z_axis.w, self.w_axis.w),
+            _ => panic!("Mat4 row index out of bounds: {index}"),
+        }
+    }
+
+    /// Returns the transpose of this matrix.
+    pub fn transpose(&self) -> Mat4 {
+        Mat4::from_cols(self.row(0), self.row(1), self.row(2), self.row(3))
+    }
+
+    /// Multiplies this matrix by the column vector `v`.
+    #[inline]
+    pub fn mul_vec4(&self, v: Vec4) -> Vec4 {
+        Vec4::new(
+            self.row(0).dot(v),
+            self.row(1).dot(v),
+            self.row(2).dot(v),
+            self.row(3).dot(v),
+        )
+    }
+
+    /// Transforms `point` as a position (`w = 1`), without the perspective divide.
+    #[inline]
+    pub fn transform_point3(&self, point: Vec3) -> Vec3 {
+        self.mul_vec4(point.extend(1.0)).truncate()
+    }
+
+    /// Transforms `vector` as a direction (`w = 0`), ignoring translation.
+    #[inline]
+    pub fn transform_vector3(&self, vector: Vec3) -> Vec3 {
+        self.mul_vec4(vector.extend(0.0)).truncate()
+    }
+
+    /// Creates a right-handed view matrix for a camera at `eye` looking along `dir`.
+    ///
+    /// `dir` and `up` are expected to be normalized.
+    pub fn look_to_rh(eye: Vec3, dir: Vec3, up: Vec3) -> Self {
+        let f = dir;
+        let s = f.cross(up).normalize_or_zero(Scalar::EPSILON);
+        let u = s.cross(f);
+        Mat4::from_cols(
+            Vec4::new(s.x, u.x, -f.x, 0.0),
+            Vec4::new(s.y, u.y, -f.y, 0.0),
+            Vec4::new(s.z, u.z, -f.z, 0.0),
+            Vec4::new(-eye.dot(s), -eye.dot(u), eye.dot(f), 1.0),
+        )
+    }
+
+    /// Creates a right-handed perspective projection with depth mapped to `[0, 1]`.
+    ///
+    /// `fov_y_radians` is the full vertical field of view.
+    pub fn perspective_rh(
+        fov_y_radians: Scalar,
+        aspect_ratio: Scalar,
+        z_near: Scalar,
+        z_far: Scalar,
+    ) -> Self {
+        let (sin_fov, cos_fov) = float::sin_cos(0.5 * fov_y_radians);
+        let h = cos_fov / sin_fov;
+        let w = h / aspect_ratio;
+        let r = z_far / (z_near - z_far);
+        Mat4::from_cols(
+            Vec4::new(w, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, h, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, r, -1.0),
+            Vec4::new(0.0, 0.0, r * z_near, 0.0),
+        )
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    #[inline]
+    fn mul(self, rhs: Mat4) -> Mat4 {
+        Mat4::from_cols(
+            self.mul_vec4(rhs.x_axis),
+            self.mul_vec4(rhs.y_axis),
+            self.mul_vec4(rhs.z_axis),
+            self.mul_vec4(rhs.w_axis),
+        )
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    #[inline]
+    fn mul(self, rhs: Vec4) -> Vec4 {
+        self.mul_vec4(rhs)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Matrix4<Scalar>> for Mat4 {
+        fn from(m: nalgebra::Matrix4<Scalar>) -> Self {
+            let col = |i: usize| Vec4::new(m[(0, i)], m[(1, i)], m[(2, i)], m[(3, i)]);
+            Self::from_cols(col(0), col(1), col(2), col(3))
+        }
+    }
+
+    impl From<Mat4> for nalgebra::Matrix4<Scalar> {
+        fn from(m: Mat4) -> Self {
+            Self::from_columns(&[
+                m.x_axis.into(),
+                m.y_axis.into(),
+                m.z_axis.into(),
+                m.w_axis.into(),
+            ])
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    #[cfg(feature = "f32")]
+    impl From<glam::Mat4> for Mat4 {
+        fn from(m: glam::Mat4) -> Self {
+            Self::from_cols(
+                m.x_axis.into(),
+                m.y_axis.into(),
+                m.z_axis.into(),
+                m.w_axis.into(),
+            )
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    impl From<Mat4> for glam::Mat4 {
+        fn from(m: Mat4) -> Self {
+            Self::from_cols(
+                m.x_axis.into(),
+                m.y_axis.into(),
+                m.z_axis.into(),
+                m.w_axis.into(),
+            )
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<glam::DMat4> for Mat4 {
+        fn from(m: glam::DMat4) -> Self {
+            Self::from_cols(
+                m.x_axis.into(),
+                m.y_axis.into(),
+                m.z_axis.into(),
+                m.w_axis.into(),
+            )
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<Mat4> for glam::DMat4 {
+        fn from(m: Mat4) -> Self {
+            Self::from_cols(
+                m.x_axis.into(),
+                m.y_axis.into(),
+                m.z_axis.into(),
+                m.w_axis.into(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_identity() {
+        assert_eq!(Mat4::default(), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn identity_is_multiplicative_neutral() {
+        let m = Mat4::from_translation(Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar));
+        assert_eq!(m * Mat4::IDENTITY, m);
+        assert_eq!(Mat4::IDENTITY * m, m);
+    }
+
+    #[test]
+    fn transform_vector_ignores_translation() {
+        let m = Mat4::from_translation(Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar));
+        let v = Vec3::new(0.0 as Scalar, 1.0 as Scalar, 0.0 as Scalar);
+        assert_eq!(m.transform_vector3(v), v);
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let m = Mat4::from_translation(Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar));
+        let t = m.transpose();
+        assert_eq!(t.row(3), m.w_axis);
+        assert_eq!(t.transpose(), m);
+    }
+
+    #[test]
+    fn look_to_rh_moves_eye_to_origin() {
+        let eye = Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar);
+        let view = Mat4::look_to_rh(
+            eye,
+            Vec3::new(0.0 as Scalar, 0.0 as Scalar, -1.0 as Scalar),
+            Vec3::new(0.0 as Scalar, 1.0 as Scalar, 0.0 as Scalar),
+        );
+        assert_eq!(view.transform_point3(eye), Vec3::ZERO);
+    }
+
+    #[cfg(all(feature = "glam", feature = "f32"))]
+    #[test]
+    fn glam_perspective_matches() {
+        let ours = Mat4::perspective_rh(1.0, 1.5, 0.1, 100.0);
+        let theirs: Mat4 = glam::Mat4::perspective_rh(1.0, 1.5, 0.1, 100.0).into();
+        assert_eq!(ours, theirs);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_roundtrip() {
+        let m = Mat4::from_translation(Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar));
+        let n: nalgebra::Matrix4<Scalar> = m.into();
+        assert_eq!(n[(0, 3)], 1.0 as Scalar);
+        let back: Mat4 = n.into();
+        assert_eq!(back, m);
+    }
+}
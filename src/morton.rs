@@ -0,0 +1,299 @@
+//! Morton (Z-order) codes for quantized positions.
+//!
+//! A Morton code interleaves the bits of integer coordinates into a single integer, so points
+//! close together in space mostly get nearby codes. Storing the code in an indexed table column
+//! turns "what is near here?" into a handful of range scans.
+//!
+//! The raw `encode_*`/`decode_*` functions work on integer grid coordinates. The `*_position`
+//! helpers quantize world positions inside a bounding box onto the finest grid the code allows.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{morton, Aabb3, Vec3};
+//!
+//! assert_eq!(morton::encode_2d_u32(0b11, 0b00), 0b0101);
+//! assert_eq!(morton::decode_3d_u64(morton::encode_3d_u64(7, 8, 9)), (7, 8, 9));
+//!
+//! let world = Aabb3::new(Vec3::splat(-1024.0), Vec3::splat(1024.0));
+//! let code = morton::encode_position_3d(Vec3::new(10.0, 0.0, -3.0), &world);
+//! assert!(morton::decode_position_3d(code, &world).distance(Vec3::new(10.0, 0.0, -3.0)) < 0.01);
+//! ```
+
+use crate::{Aabb2, Aabb3, Scalar, Vec2, Vec3};
+
+/// Bits per axis of a 3D code in a `u32`.
+pub const BITS_3D_U32: u32 = 10;
+/// Bits per axis of a 3D code in a `u64`.
+pub const BITS_3D_U64: u32 = 21;
+
+/// Spreads the low 16 bits of `v` so there is a zero bit between each.
+#[inline]
+fn part1by1_u32(v: u32) -> u32 {
+    let mut x = v & 0x0000_ffff;
+    x = (x | (x << 8)) & 0x00ff_00ff;
+    x = (x | (x << 4)) & 0x0f0f_0f0f;
+    x = (x | (x << 2)) & 0x3333_3333;
+    x = (x | (x << 1)) & 0x5555_5555;
+    x
+}
+
+/// Inverse of [`part1by1_u32`].
+#[inline]
+fn compact1by1_u32(v: u32) -> u32 {
+    let mut x = v & 0x5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333;
+    x = (x | (x >> 2)) & 0x0f0f_0f0f;
+    x = (x | (x >> 4)) & 0x00ff_00ff;
+    x = (x | (x >> 8)) & 0x0000_ffff;
+    x
+}
+
+/// Spreads the low 32 bits of `v` so there is a zero bit between each.
+#[inline]
+fn part1by1_u64(v: u64) -> u64 {
+    let mut x = v & 0x0000_0000_ffff_ffff;
+    x = (x | (x << 16)) & 0x0000_ffff_0000_ffff;
+    x = (x | (x << 8)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    x = (x | (x << 1)) & 0x5555_5555_5555_5555;
+    x
+}
+
+/// Inverse of [`part1by1_u64`].
+#[inline]
+fn compact1by1_u64(v: u64) -> u64 {
+    let mut x = v & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x >> 4)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x >> 8)) & 0x0000_ffff_0000_ffff;
+    x = (x | (x >> 16)) & 0x0000_0000_ffff_ffff;
+    x
+}
+
+/// Spreads the low 10 bits of `v` so there are two zero bits between each.
+#[inline]
+fn part1by2_u32(v: u32) -> u32 {
+    let mut x = v & 0x0000_03ff;
+    x = (x | (x << 16)) & 0xff00_00ff;
+    x = (x | (x << 8)) & 0x0300_f00f;
+    x = (x | (x << 4)) & 0x030c_30c3;
+    x = (x | (x << 2)) & 0x0924_9249;
+    x
+}
+
+/// Inverse of [`part1by2_u32`].
+#[inline]
+fn compact1by2_u32(v: u32) -> u32 {
+    let mut x = v & 0x0924_9249;
+    x = (x | (x >> 2)) & 0x030c_30c3;
+    x = (x | (x >> 4)) & 0x0300_f00f;
+    x = (x | (x >> 8)) & 0xff00_00ff;
+    x = (x | (x >> 16)) & 0x0000_03ff;
+    x
+}
+
+/// Spreads the low 21 bits of `v` so there are two zero bits between each.
+#[inline]
+fn part1by2_u64(v: u64) -> u64 {
+    let mut x = v & 0x001f_ffff;
+    x = (x | (x << 32)) & 0x001f_0000_0000_ffff;
+    x = (x | (x << 16)) & 0x001f_0000_ff00_00ff;
+    x = (x | (x << 8)) & 0x100f_00f0_0f00_f00f;
+    x = (x | (x << 4)) & 0x10c3_0c30_c30c_30c3;
+    x = (x | (x << 2)) & 0x1249_2492_4924_9249;
+    x
+}
+
+/// Inverse of [`part1by2_u64`].
+#[inline]
+fn compact1by2_u64(v: u64) -> u64 {
+    let mut x = v & 0x1249_2492_4924_9249;
+    x = (x | (x >> 2)) & 0x10c3_0c30_c30c_30c3;
+    x = (x | (x >> 4)) & 0x100f_00f0_0f00_f00f;
+    x = (x | (x >> 8)) & 0x001f_0000_ff00_00ff;
+    x = (x | (x >> 16)) & 0x001f_0000_0000_ffff;
+    x = (x | (x >> 32)) & 0x001f_ffff;
+    x
+}
+
+/// Interleaves two 16-bit coordinates into a 32-bit code, `x` in the lowest bit.
+#[inline]
+pub fn encode_2d_u32(x: u16, y: u16) -> u32 {
+    part1by1_u32(x as u32) | (part1by1_u32(y as u32) << 1)
+}
+
+/// Splits a 32-bit code back into its `(x, y)` coordinates.
+#[inline]
+pub fn decode_2d_u32(code: u32) -> (u16, u16) {
+    (
+        compact1by1_u32(code) as u16,
+        compact1by1_u32(code >> 1) as u16,
+    )
+}
+
+/// Interleaves two 32-bit coordinates into a 64-bit code, `x` in the lowest bit.
+#[inline]
+pub fn encode_2d_u64(x: u32, y: u32) -> u64 {
+    part1by1_u64(x as u64) | (part1by1_u64(y as u64) << 1)
+}
+
+/// Splits a 64-bit code back into its `(x, y)` coordinates.
+#[inline]
+pub fn decode_2d_u64(code: u64) -> (u32, u32) {
+    (
+        compact1by1_u64(code) as u32,
+        compact1by1_u64(code >> 1) as u32,
+    )
+}
+
+/// Interleaves three coordinates into a 32-bit code, `x` in the lowest bit.
+///
+/// Only the low [`BITS_3D_U32`] bits of each coordinate are kept.
+#[inline]
+pub fn encode_3d_u32(x: u32, y: u32, z: u32) -> u32 {
+    part1by2_u32(x) | (part1by2_u32(y) << 1) | (part1by2_u32(z) << 2)
+}
+
+/// Splits a 32-bit code back into its `(x, y, z)` coordinates.
+#[inline]
+pub fn decode_3d_u32(code: u32) -> (u32, u32, u32) {
+    (
+        compact1by2_u32(code),
+        compact1by2_u32(code >> 1),
+        compact1by2_u32(code >> 2),
+    )
+}
+
+/// Interleaves three coordinates into a 64-bit code, `x` in the lowest bit.
+///
+/// Only the low [`BITS_3D_U64`] bits of each coordinate are kept.
+#[inline]
+pub fn encode_3d_u64(x: u32, y: u32, z: u32) -> u64 {
+    part1by2_u64(x as u64) | (part1by2_u64(y as u64) << 1) | (part1by2_u64(z as u64) << 2)
+}
+
+/// Splits a 64-bit code back into its `(x, y, z)` coordinates.
+#[inline]
+pub fn decode_3d_u64(code: u64) -> (u32, u32, u32) {
+    (
+        compact1by2_u64(code) as u32,
+        compact1by2_u64(code >> 1) as u32,
+        compact1by2_u64(code >> 2) as u32,
+    )
+}
+
+/// Maps `value` in `[min, max]` onto one of `2^bits` integer cells, clamping values outside.
+///
+/// `bits` must be between 1 and 32.
+#[inline]
+pub fn quantize(value: Scalar, min: Scalar, max: Scalar, bits: u32) -> u32 {
+    let cells = (1u64 << bits) as Scalar;
+    let max_cell = ((1u64 << bits) - 1) as u32;
+    let t = (value - min) / (max - min);
+    // Float-to-int casts saturate, sending negatives and NaN (from an empty range) to zero.
+    ((t * cells) as u64).min(max_cell as u64) as u32
+}
+
+/// Returns the center of cell `cell` of `2^bits` cells spanning `[min, max]`.
+#[inline]
+pub fn dequantize(cell: u32, min: Scalar, max: Scalar, bits: u32) -> Scalar {
+    let cells = (1u64 << bits) as Scalar;
+    min + (cell as Scalar + 0.5) * (max - min) / cells
+}
+
+/// Quantizes `position` inside `bounds` to 32 bits per axis and returns its 64-bit code.
+pub fn encode_position_2d(position: Vec2, bounds: &Aabb2) -> u64 {
+    encode_2d_u64(
+        quantize(position.x, bounds.min.x, bounds.max.x, 32),
+        quantize(position.y, bounds.min.y, bounds.max.y, 32),
+    )
+}
+
+/// Returns the center of the cell encoded by [`encode_position_2d`].
+pub fn decode_position_2d(code: u64, bounds: &Aabb2) -> Vec2 {
+    let (x, y) = decode_2d_u64(code);
+    Vec2::new(
+        dequantize(x, bounds.min.x, bounds.max.x, 32),
+        dequantize(y, bounds.min.y, bounds.max.y, 32),
+    )
+}
+
+/// Quantizes `position` inside `bounds` to [`BITS_3D_U64`] bits per axis and returns its 64-bit
+/// code.
+pub fn encode_position_3d(position: Vec3, bounds: &Aabb3) -> u64 {
+    encode_3d_u64(
+        quantize(position.x, bounds.min.x, bounds.max.x, BITS_3D_U64),
+        quantize(position.y, bounds.min.y, bounds.max.y, BITS_3D_U64),
+        quantize(position.z, bounds.min.z, bounds.max.z, BITS_3D_U64),
+    )
+}
+
+/// Returns the center of the cell encoded by [`encode_position_3d`].
+pub fn decode_position_3d(code: u64, bounds: &Aabb3) -> Vec3 {
+    let (x, y, z) = decode_3d_u64(code);
+    Vec3::new(
+        dequantize(x, bounds.min.x, bounds.max.x, BITS_3D_U64),
+        dequantize(y, bounds.min.y, bounds.max.y, BITS_3D_U64),
+        dequantize(z, bounds.min.z, bounds.max.z, BITS_3D_U64),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interleaves_bits() {
+        assert_eq!(encode_2d_u32(0xffff, 0), 0x5555_5555);
+        assert_eq!(encode_2d_u32(0, 0xffff), 0xaaaa_aaaa);
+        assert_eq!(encode_2d_u64(u32::MAX, 0), 0x5555_5555_5555_5555);
+        assert_eq!(encode_3d_u32(1, 0, 0), 0b001);
+        assert_eq!(encode_3d_u32(0, 1, 1), 0b110);
+        assert_eq!(encode_3d_u32(0x3ff, 0, 0), 0x0924_9249);
+        assert_eq!(encode_3d_u64(0x1f_ffff, 0, 0), 0x1249_2492_4924_9249);
+        // Bits above the per-axis budget are dropped.
+        assert_eq!(encode_3d_u32(0x400, 0, 0), 0);
+    }
+
+    #[test]
+    fn round_trips() {
+        for &(x, y) in &[(0u16, 0u16), (1, 2), (0xabcd, 0x1234), (u16::MAX, u16::MAX)] {
+            assert_eq!(decode_2d_u32(encode_2d_u32(x, y)), (x, y));
+        }
+        for &(x, y) in &[(0u32, 0u32), (123_456_789, 987_654_321), (u32::MAX, 7)] {
+            assert_eq!(decode_2d_u64(encode_2d_u64(x, y)), (x, y));
+        }
+        for &(x, y, z) in &[(0u32, 0u32, 0u32), (1023, 5, 512), (3, 600, 1)] {
+            assert_eq!(decode_3d_u32(encode_3d_u32(x, y, z)), (x, y, z));
+        }
+        for &(x, y, z) in &[(0u32, 0u32, 0u32), (0x1f_ffff, 12345, 1 << 20)] {
+            assert_eq!(decode_3d_u64(encode_3d_u64(x, y, z)), (x, y, z));
+        }
+    }
+
+    #[test]
+    fn quantize_clamps_to_range() {
+        assert_eq!(quantize(-5.0, 0.0, 16.0, 4), 0);
+        assert_eq!(quantize(3.5, 0.0, 16.0, 4), 3);
+        assert_eq!(quantize(16.0, 0.0, 16.0, 4), 15);
+        assert_eq!(quantize(100.0, 0.0, 16.0, 4), 15);
+        assert_eq!(dequantize(3, 0.0, 16.0, 4), 3.5 as Scalar);
+    }
+
+    #[test]
+    fn positions_round_trip_to_cell_centers() {
+        let bounds = Aabb2::new(Vec2::new(0.0, 0.0), Vec2::new(256.0, 256.0));
+        let p = Vec2::new(100.25, 3.5);
+        let decoded = decode_position_2d(encode_position_2d(p, &bounds), &bounds);
+        assert!(decoded.distance(p) < 1.0e-3);
+
+        // Neighbouring positions in the same octant share the high bits of their codes.
+        let bounds = Aabb3::new(Vec3::ZERO, Vec3::splat(1024.0));
+        let a = encode_position_3d(Vec3::new(10.0, 10.0, 10.0), &bounds);
+        let b = encode_position_3d(Vec3::new(11.0, 10.0, 10.0), &bounds);
+        let far = encode_position_3d(Vec3::new(900.0, 10.0, 10.0), &bounds);
+        assert!(a.abs_diff(b) < a.abs_diff(far));
+    }
+}
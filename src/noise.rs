@@ -0,0 +1,1199 @@
+use crate::interp::lerp;
+use crate::rng::{GOLDEN_GAMMA, hash_rand_u64, mix64};
+use crate::{IVec2, IVec3, Scalar, Vec2, Vec3, float};
+use core::ops::{Add, Mul};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A coherent noise function: a seeded field of values where nearby points get similar
+/// values.
+///
+/// Every noise type in the crate implements this for [`Vec2`] and [`Vec3`] and is built from
+/// a `u64` seed, so a generator can swap one for another. Results use only arithmetic and
+/// `floor`, so the same seed gives the same bits on the server, on clients and on every host.
+pub trait Noise<P> {
+    /// Returns the value at `point`, in `[-1, 1]`.
+    fn sample(&self, point: P) -> Scalar;
+}
+
+/// Returns 64 well-mixed bits for a 2D lattice point.
+#[inline]
+pub(crate) fn hash2(seed: u64, x: i32, y: i32) -> u64 {
+    hash_rand_u64(seed, x as u32 as u64, y as u32 as u64)
+}
+
+/// Returns 64 well-mixed bits for a 3D lattice point.
+#[inline]
+pub(crate) fn hash3(seed: u64, x: i32, y: i32, z: i32) -> u64 {
+    mix64(hash2(seed, x, y).wrapping_add(GOLDEN_GAMMA) ^ z as u32 as u64)
+}
+
+const SQRT_2: Scalar = core::f64::consts::SQRT_2 as _;
+/// `sqrt(2 / 3)`.
+const SQRT_2_3: Scalar = 0.816_496_580_927_726 as _;
+
+/// Perlin's quintic fade curve, with zero first and second derivatives at 0 and 1.
+#[inline]
+fn fade(t: Scalar) -> Scalar {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Seeded Perlin gradient noise, with Ken Perlin's improved fade curve.
+///
+/// The value is zero at every integer lattice point and varies smoothly in between, with
+/// features about one unit across; scale the input to change the feature size.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Noise, Perlin, Vec2};
+///
+/// const WORLD_SEED: u64 = 0x5eed;
+/// let height = |x, z| 20.0 * Perlin::new(WORLD_SEED).sample(Vec2::new(x, z) / 64.0);
+/// let h = height(130.0, -7.5);
+/// assert!(h.abs() <= 20.0);
+/// // A client with the same seed computes the same heights.
+/// assert_eq!(h, height(130.0, -7.5));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Perlin {
+    pub seed: u64,
+}
+
+impl Perlin {
+    #[inline(always)]
+    pub const fn new(seed: u64) -> Self {
+        Perlin { seed }
+    }
+
+    /// Returns the dot product of a corner's pseudo-random unit gradient with `(x, y)`.
+    #[inline]
+    fn gradient2(&self, cell_x: i32, cell_y: i32, x: Scalar, y: Scalar) -> Scalar {
+        const D: Scalar = core::f64::consts::FRAC_1_SQRT_2 as _;
+        match hash2(self.seed, cell_x, cell_y) >> 61 {
+            0 => x,
+            1 => -x,
+            2 => y,
+            3 => -y,
+            4 => D * (x + y),
+            5 => D * (y - x),
+            6 => D * (x - y),
+            _ => -D * (x + y),
+        }
+    }
+
+    /// Returns the dot product of one of the twelve cube-edge gradients with `(x, y, z)`.
+    #[inline]
+    fn gradient3(&self, cell: [i32; 3], x: Scalar, y: Scalar, z: Scalar) -> Scalar {
+        let h = hash3(self.seed, cell[0], cell[1], cell[2]) >> 32;
+        match h % 12 {
+            0 => x + y,
+            1 => y - x,
+            2 => x - y,
+            3 => -x - y,
+            4 => x + z,
+            5 => z - x,
+            6 => x - z,
+            7 => -x - z,
+            8 => y + z,
+            9 => z - y,
+            10 => y - z,
+            _ => -y - z,
+        }
+    }
+}
+
+impl Noise<Vec2> for Perlin {
+    fn sample(&self, point: Vec2) -> Scalar {
+        let (fx, fy) = (float::floor(point.x), float::floor(point.y));
+        let (cx, cy) = (fx as i32, fy as i32);
+        let (x, y) = (point.x - fx, point.y - fy);
+        let (u, v) = (fade(x), fade(y));
+
+        let bottom = lerp(
+            self.gradient2(cx, cy, x, y),
+            self.gradient2(cx.wrapping_add(1), cy, x - 1.0, y),
+            u,
+        );
+        let top = lerp(
+            self.gradient2(cx, cy.wrapping_add(1), x, y - 1.0),
+            self.gradient2(cx.wrapping_add(1), cy.wrapping_add(1), x - 1.0, y - 1.0),
+            u,
+        );
+        // Unit gradients reach at most sqrt(1/2); scale that up to one.
+        (lerp(bottom, top, v) * SQRT_2).clamp(-1.0, 1.0)
+    }
+}
+
+impl Noise<Vec3> for Perlin {
+    fn sample(&self, point: Vec3) -> Scalar {
+        let (fx, fy, fz) = (
+            float::floor(point.x),
+            float::floor(point.y),
+            float::floor(point.z),
+        );
+        let (cx, cy, cz) = (fx as i32, fy as i32, fz as i32);
+        let (x, y, z) = (point.x - fx, point.y - fy, point.z - fz);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            self.gradient3(
+                [
+                    cx.wrapping_add(dx),
+                    cy.wrapping_add(dy),
+                    cz.wrapping_add(dz),
+                ],
+                x - dx as Scalar,
+                y - dy as Scalar,
+                z - dz as Scalar,
+            )
+        };
+        let near = lerp(
+            lerp(corner(0, 0, 0), corner(1, 0, 0), u),
+            lerp(corner(0, 1, 0), corner(1, 1, 0), u),
+            v,
+        );
+        let far = lerp(
+            lerp(corner(0, 0, 1), corner(1, 0, 1), u),
+            lerp(corner(0, 1, 1), corner(1, 1, 1), u),
+            v,
+        );
+        // Edge gradients have length sqrt(2) and unit gradients reach at most sqrt(3/4);
+        // scale that up to one.
+        (lerp(near, far, w) * SQRT_2_3).clamp(-1.0, 1.0)
+    }
+}
+
+/// `(sqrt(3) - 1) / 2`, which skews the plane onto the simplex lattice.
+const SKEW_2D: Scalar = 0.366_025_403_784_438_6 as _;
+/// `(3 - sqrt(3)) / 6`, which unskews it again.
+const UNSKEW_2D: Scalar = 0.211_324_865_405_187_1 as _;
+
+/// Seeded OpenSimplex2 noise: gradient noise on a triangular lattice in 2D and a
+/// body-centered cubic one in 3D.
+///
+/// It has fewer of the axis-aligned ridges and grid artifacts that [`Perlin`] shows, at a
+/// similar cost, and takes the same seed and input scale, so the two can be swapped.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Noise, OpenSimplex2, Perlin, Scalar, Vec3};
+///
+/// fn density(noise: &impl Noise<Vec3>, p: Vec3) -> Scalar {
+///     noise.sample(p / 16.0)
+/// }
+///
+/// let p = Vec3::new(10.0, -4.0, 33.0);
+/// let caves = density(&OpenSimplex2::new(9), p);
+/// let blocky = density(&Perlin::new(9), p);
+/// assert!(caves.abs() <= 1.0 && blocky.abs() <= 1.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenSimplex2 {
+    pub seed: u64,
+}
+
+impl OpenSimplex2 {
+    /// Squared radius of each lattice point's 2D kernel.
+    const RADIUS_SQUARED_2D: Scalar = 0.5;
+    /// Squared radius of each lattice point's 3D kernel.
+    const RADIUS_SQUARED_3D: Scalar = 0.6;
+    /// Scales the largest 2D sum to one; the same normalization as the reference
+    /// implementation, whose kernel and gradient lengths match.
+    const SCALE_2D: Scalar = 99.836_854_463_036_47 as _;
+    /// Scales the largest 3D sum, found by numerical search, to one.
+    const SCALE_3D: Scalar = 39.7;
+
+    #[inline(always)]
+    pub const fn new(seed: u64) -> Self {
+        OpenSimplex2 { seed }
+    }
+
+    /// Returns the kernel-weighted dot product of a lattice point's gradient, one of 24
+    /// directions evenly around the circle, with the offset `(x, y)` from that point.
+    #[inline]
+    fn contribution2(&self, cell_x: i32, cell_y: i32, x: Scalar, y: Scalar) -> Scalar {
+        let falloff = Self::RADIUS_SQUARED_2D - x * x - y * y;
+        if falloff <= 0.0 {
+            return 0.0;
+        }
+        let (gx, gy) = GRADIENTS_2D[(hash2(self.seed, cell_x, cell_y) >> 32) as usize % 24];
+        let falloff = falloff * falloff;
+        falloff * falloff * (gx * x + gy * y)
+    }
+
+    /// Like [`contribution2`](Self::contribution2), on the doubled integer coordinates of a
+    /// body-centered cubic lattice point and with 3D gradients.
+    #[inline]
+    fn contribution3(&self, cell: [i32; 3], x: Scalar, y: Scalar, z: Scalar) -> Scalar {
+        let falloff = Self::RADIUS_SQUARED_3D - x * x - y * y - z * z;
+        if falloff <= 0.0 {
+            return 0.0;
+        }
+        let h = hash3(self.seed, cell[0], cell[1], cell[2]) >> 32;
+        let (gx, gy, gz) = GRADIENTS_3D[h as usize % GRADIENTS_3D.len()];
+        let falloff = falloff * falloff;
+        falloff * falloff * (gx * x + gy * y + gz * z)
+    }
+}
+
+/// Unit vectors every 15 degrees.
+const GRADIENTS_2D: [(Scalar, Scalar); 24] = {
+    const C: [Scalar; 7] = [
+        0.0,
+        0.258_819_045_102_520_8 as _,
+        0.5,
+        core::f64::consts::FRAC_1_SQRT_2 as _,
+        0.866_025_403_784_438_6 as _,
+        0.965_925_826_289_068_3 as _,
+        1.0,
+    ];
+    let mut table = [(0.0, 0.0); 24];
+    let mut i = 0;
+    while i < 24 {
+        // cos and sin of i * 15 degrees, from the first quadrant by symmetry.
+        let quadrant = i / 6;
+        let k = i % 6;
+        let (c, s) = (C[6 - k], C[k]);
+        table[i] = match quadrant {
+            0 => (c, s),
+            1 => (-s, c),
+            2 => (-c, -s),
+            _ => (s, -c),
+        };
+        i += 1;
+    }
+    table
+};
+
+/// The twelve cube-edge directions and the eight cube-corner directions, normalized.
+const GRADIENTS_3D: [(Scalar, Scalar, Scalar); 20] = {
+    const E: Scalar = core::f64::consts::FRAC_1_SQRT_2 as _;
+    const C: Scalar = 0.577_350_269_189_625_8 as _;
+    [
+        (E, E, 0.0),
+        (-E, E, 0.0),
+        (E, -E, 0.0),
+        (-E, -E, 0.0),
+        (E, 0.0, E),
+        (-E, 0.0, E),
+        (E, 0.0, -E),
+        (-E, 0.0, -E),
+        (0.0, E, E),
+        (0.0, -E, E),
+        (0.0, E, -E),
+        (0.0, -E, -E),
+        (C, C, C),
+        (-C, C, C),
+        (C, -C, C),
+        (-C, -C, C),
+        (C, C, -C),
+        (-C, C, -C),
+        (C, -C, -C),
+        (-C, -C, -C),
+    ]
+};
+
+impl Noise<Vec2> for OpenSimplex2 {
+    fn sample(&self, point: Vec2) -> Scalar {
+        // Skew onto the lattice, find the triangle containing the point and measure the
+        // offsets to its three corners in unskewed space.
+        let skew = (point.x + point.y) * SKEW_2D;
+        let (fi, fj) = (float::floor(point.x + skew), float::floor(point.y + skew));
+        let (i, j) = (fi as i32, fj as i32);
+        let unskew = (fi + fj) * UNSKEW_2D;
+        let (x0, y0) = (point.x - (fi - unskew), point.y - (fj - unskew));
+        let (di, dj) = if x0 > y0 { (1, 0) } else { (0, 1) };
+        let (x1, y1) = (x0 - di as Scalar + UNSKEW_2D, y0 - dj as Scalar + UNSKEW_2D);
+        let (x2, y2) = (x0 - 1.0 + 2.0 * UNSKEW_2D, y0 - 1.0 + 2.0 * UNSKEW_2D);
+
+        let sum = self.contribution2(i, j, x0, y0)
+            + self.contribution2(i.wrapping_add(di), j.wrapping_add(dj), x1, y1)
+            + self.contribution2(i.wrapping_add(1), j.wrapping_add(1), x2, y2);
+        (sum * Self::SCALE_2D).clamp(-1.0, 1.0)
+    }
+}
+
+impl Noise<Vec3> for OpenSimplex2 {
+    fn sample(&self, point: Vec3) -> Scalar {
+        // Reflect through the plane perpendicular to the main diagonal, which turns the
+        // lattice so that its axes don't line up with the world's.
+        let r = (point.x + point.y + point.z) * (2.0 / 3.0);
+        let (x, y, z) = (r - point.x, r - point.y, r - point.z);
+
+        // The lattice is two cubic grids, the second offset by half a cell. Points beyond
+        // the corners of the cells containing the point are out of the kernel's reach.
+        let mut sum = 0.0;
+        for half in [0, 1] {
+            let offset = half as Scalar * 0.5;
+            let (fx, fy, fz) = (
+                float::floor(x - offset),
+                float::floor(y - offset),
+                float::floor(z - offset),
+            );
+            let (cx, cy, cz) = (fx as i32, fy as i32, fz as i32);
+            let (x0, y0, z0) = (x - offset - fx, y - offset - fy, z - offset - fz);
+            for corner in 0..8 {
+                let (dx, dy, dz) = (corner & 1, (corner >> 1) & 1, corner >> 2);
+                let cell = [
+                    cx.wrapping_add(dx).wrapping_mul(2).wrapping_add(half),
+                    cy.wrapping_add(dy).wrapping_mul(2).wrapping_add(half),
+                    cz.wrapping_add(dz).wrapping_mul(2).wrapping_add(half),
+                ];
+                sum += self.contribution3(
+                    cell,
+                    x0 - dx as Scalar,
+                    y0 - dy as Scalar,
+                    z0 - dz as Scalar,
+                );
+            }
+        }
+        (sum * Self::SCALE_3D).clamp(-1.0, 1.0)
+    }
+}
+
+/// Offset added per octave so that octaves don't share lattice points, where gradient noise
+/// is zero, and line up their features at the origin.
+const OCTAVE_SHIFT_2D: Vec2 = Vec2::new(19.19, 7.73);
+/// The 3D counterpart of [`OCTAVE_SHIFT_2D`].
+const OCTAVE_SHIFT_3D: Vec3 = Vec3::new(19.19, 7.73, 13.37);
+
+/// Sums `octaves` layers of `layer`, each at `lacunarity` times the frequency and `gain`
+/// times the amplitude of the one before, and divides by the total amplitude.
+///
+/// `layer` gets the octave's point and the previous layer's value.
+#[inline]
+fn layered<P>(
+    point: P,
+    shift: P,
+    octaves: u32,
+    lacunarity: Scalar,
+    gain: Scalar,
+    mut layer: impl FnMut(P, Scalar) -> Scalar,
+) -> Scalar
+where
+    P: Copy + Add<Output = P> + Mul<Scalar, Output = P>,
+{
+    let (mut sum, mut total) = (0.0, 0.0);
+    let (mut frequency, mut amplitude) = (1.0, 1.0);
+    let mut previous = 1.0;
+    for octave in 0..octaves.max(1) {
+        previous = layer(point * frequency + shift * octave as Scalar, previous);
+        sum += previous * amplitude;
+        total += amplitude;
+        frequency *= lacunarity;
+        amplitude *= gain;
+    }
+    if total > 0.0 { sum / total } else { 0.0 }
+}
+
+/// Fractal Brownian motion: several octaves of a base noise at rising frequencies and
+/// falling amplitudes, for terrain with both hills and pebbles.
+///
+/// The result is normalized back to `[-1, 1]`. Each octave doubles the cost; detail finer
+/// than a grid cell or a unit of height isn't worth sampling.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Fbm, Noise, OpenSimplex2, Vec2};
+///
+/// let terrain = Fbm::new(OpenSimplex2::new(42), 5);
+/// let height = 30.0 * terrain.sample(Vec2::new(120.0, 48.0) / 256.0);
+/// assert!(height.abs() <= 30.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fbm<N> {
+    pub noise: N,
+    /// Number of layers; zero is treated as one.
+    pub octaves: u32,
+    /// Frequency multiplier between octaves, usually `2.0`.
+    pub lacunarity: Scalar,
+    /// Amplitude multiplier between octaves, usually `0.5`; higher values are rougher.
+    pub gain: Scalar,
+}
+
+impl<N> Fbm<N> {
+    /// Layers `octaves` octaves of `noise` with a lacunarity of 2 and a gain of 0.5.
+    #[inline(always)]
+    pub const fn new(noise: N, octaves: u32) -> Self {
+        Fbm {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl<N: Noise<Vec2>> Noise<Vec2> for Fbm<N> {
+    fn sample(&self, point: Vec2) -> Scalar {
+        let layer = |p, _| self.noise.sample(p);
+        layered(
+            point,
+            OCTAVE_SHIFT_2D,
+            self.octaves,
+            self.lacunarity,
+            self.gain,
+            layer,
+        )
+    }
+}
+
+impl<N: Noise<Vec3>> Noise<Vec3> for Fbm<N> {
+    fn sample(&self, point: Vec3) -> Scalar {
+        let layer = |p, _| self.noise.sample(p);
+        layered(
+            point,
+            OCTAVE_SHIFT_3D,
+            self.octaves,
+            self.lacunarity,
+            self.gain,
+            layer,
+        )
+    }
+}
+
+/// Turbulence: [`Fbm`] over the absolute value of the base noise, which folds its zero
+/// crossings into sharp creases, for billowy clouds, smoke and marble.
+///
+/// Values are in `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Turbulence<N> {
+    pub noise: N,
+    /// Number of layers; zero is treated as one.
+    pub octaves: u32,
+    /// Frequency multiplier between octaves, usually `2.0`.
+    pub lacunarity: Scalar,
+    /// Amplitude multiplier between octaves, usually `0.5`.
+    pub gain: Scalar,
+}
+
+impl<N> Turbulence<N> {
+    /// Layers `octaves` octaves of `noise` with a lacunarity of 2 and a gain of 0.5.
+    #[inline(always)]
+    pub const fn new(noise: N, octaves: u32) -> Self {
+        Turbulence {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl<N: Noise<Vec2>> Noise<Vec2> for Turbulence<N> {
+    fn sample(&self, point: Vec2) -> Scalar {
+        let layer = |p, _| self.noise.sample(p).abs();
+        layered(
+            point,
+            OCTAVE_SHIFT_2D,
+            self.octaves,
+            self.lacunarity,
+            self.gain,
+            layer,
+        )
+    }
+}
+
+impl<N: Noise<Vec3>> Noise<Vec3> for Turbulence<N> {
+    fn sample(&self, point: Vec3) -> Scalar {
+        let layer = |p, _| self.noise.sample(p).abs();
+        layered(
+            point,
+            OCTAVE_SHIFT_3D,
+            self.octaves,
+            self.lacunarity,
+            self.gain,
+            layer,
+        )
+    }
+}
+
+/// Ridged multifractal noise: inverted, squared [`Turbulence`] whose creases become sharp
+/// ridges, for mountain ranges and canyon walls.
+///
+/// Each octave is weighted by the one before it, so fine detail gathers along the ridges
+/// and the valleys between them stay smooth. Ridge crests approach `1` and valleys `-1`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Noise, Perlin, Ridged, Vec2};
+///
+/// let mountains = Ridged::new(Perlin::new(7), 6);
+/// let peak = 400.0 * (mountains.sample(Vec2::new(-3.2, 9.6)) * 0.5 + 0.5);
+/// assert!((0.0..=400.0).contains(&peak));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ridged<N> {
+    pub noise: N,
+    /// Number of layers; zero is treated as one.
+    pub octaves: u32,
+    /// Frequency multiplier between octaves, usually `2.0`.
+    pub lacunarity: Scalar,
+    /// Amplitude multiplier between octaves, usually `0.5`.
+    pub gain: Scalar,
+}
+
+impl<N> Ridged<N> {
+    /// Layers `octaves` octaves of `noise` with a lacunarity of 2 and a gain of 0.5.
+    #[inline(always)]
+    pub const fn new(noise: N, octaves: u32) -> Self {
+        Ridged {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    /// Returns an octave's ridge value in `[0, 1]`, damped where the previous octave was low.
+    #[inline]
+    fn ridge(value: Scalar, previous: Scalar) -> Scalar {
+        let ridge = 1.0 - value.abs();
+        ridge * ridge * (previous * 2.0).clamp(0.0, 1.0)
+    }
+}
+
+impl<N: Noise<Vec2>> Noise<Vec2> for Ridged<N> {
+    fn sample(&self, point: Vec2) -> Scalar {
+        let layer = |p, previous| Self::ridge(self.noise.sample(p), previous);
+        let sum = layered(
+            point,
+            OCTAVE_SHIFT_2D,
+            self.octaves,
+            self.lacunarity,
+            self.gain,
+            layer,
+        );
+        sum * 2.0 - 1.0
+    }
+}
+
+impl<N: Noise<Vec3>> Noise<Vec3> for Ridged<N> {
+    fn sample(&self, point: Vec3) -> Scalar {
+        let layer = |p, previous| Self::ridge(self.noise.sample(p), previous);
+        let sum = layered(
+            point,
+            OCTAVE_SHIFT_3D,
+            self.octaves,
+            self.lacunarity,
+            self.gain,
+            layer,
+        );
+        sum * 2.0 - 1.0
+    }
+}
+
+/// Domain warping: samples `noise` at a point displaced by another noise field, which
+/// swirls and folds its features into rivers, marbling and eroded-looking coastlines.
+///
+/// Each axis of the displacement samples `warp` at a different fixed offset, so the axes
+/// are uncorrelated. Displacement is `strength` times a value in `[-1, 1]` per axis, in
+/// input units.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Fbm, Noise, OpenSimplex2, Vec2, Warp};
+///
+/// let shape = Fbm::new(OpenSimplex2::new(1), 4);
+/// let coast = Warp::new(shape, Fbm::new(OpenSimplex2::new(2), 3), 0.8);
+/// let p = Vec2::new(4.0, 2.5);
+/// assert_ne!(coast.sample(p), shape.sample(p));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warp<N, W> {
+    pub noise: N,
+    pub warp: W,
+    /// Largest displacement along each axis.
+    pub strength: Scalar,
+}
+
+impl<N, W> Warp<N, W> {
+    #[inline(always)]
+    pub const fn new(noise: N, warp: W, strength: Scalar) -> Self {
+        Warp {
+            noise,
+            warp,
+            strength,
+        }
+    }
+}
+
+impl<N: Noise<Vec2>, W: Noise<Vec2>> Noise<Vec2> for Warp<N, W> {
+    fn sample(&self, point: Vec2) -> Scalar {
+        let offset = Vec2::new(
+            self.warp.sample(point),
+            self.warp.sample(point + OCTAVE_SHIFT_2D),
+        );
+        self.noise.sample(point + offset * self.strength)
+    }
+}
+
+impl<N: Noise<Vec3>, W: Noise<Vec3>> Noise<Vec3> for Warp<N, W> {
+    fn sample(&self, point: Vec3) -> Scalar {
+        let offset = Vec3::new(
+            self.warp.sample(point),
+            self.warp.sample(point + OCTAVE_SHIFT_3D),
+            self.warp.sample(point - OCTAVE_SHIFT_3D),
+        );
+        self.noise.sample(point + offset * self.strength)
+    }
+}
+
+/// Returns 24 bits of `bits`, starting at bit `shift`, as a [`Scalar`] in `[0, 1)`.
+#[inline]
+fn unit_bits(bits: u64, shift: u32) -> Scalar {
+    ((bits >> shift) & 0xff_ffff) as Scalar * (1.0 / (1u32 << 24) as Scalar)
+}
+
+/// How [`Worley`] noise measures the distance to feature points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellDistance {
+    /// Straight-line distance; round cells.
+    #[default]
+    Euclidean,
+    /// Sum of the axis distances; diamond-shaped cells with diagonal edges.
+    Manhattan,
+    /// Largest axis distance; square, blocky cells.
+    Chebyshev,
+}
+
+impl CellDistance {
+    /// Returns the length of the offset whose absolute components are `axes`.
+    #[inline]
+    fn length<const N: usize>(self, axes: [Scalar; N]) -> Scalar {
+        match self {
+            CellDistance::Euclidean => float::sqrt(axes.iter().map(|a| a * a).sum()),
+            CellDistance::Manhattan => axes.iter().sum(),
+            CellDistance::Chebyshev => axes.iter().fold(0.0, |max, &a| max.max(a)),
+        }
+    }
+}
+
+/// Which distance [`Worley`] noise turns into its [`Noise::sample`] value.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorleyOutput {
+    /// Distance to the nearest feature point; dimples around each point.
+    #[default]
+    F1,
+    /// Distance to the second nearest feature point; a bulging, cobbled look.
+    F2,
+    /// `F2 - F1`, which is zero along the cell borders; cracks and crackle patterns.
+    F2MinusF1,
+}
+
+/// The nearest feature points to a sample point, as returned by [`Worley::features2`] and
+/// [`Worley::features3`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorleyFeatures<C> {
+    /// Lattice cell owning the nearest feature point; the same for every point in a Voronoi
+    /// cell, so it keys per-cell data such as a biome.
+    pub cell: C,
+    /// Distance to the nearest feature point.
+    pub f1: Scalar,
+    /// Distance to the second nearest feature point.
+    pub f2: Scalar,
+}
+
+impl<C> WorleyFeatures<C> {
+    /// Starts a search with no feature points found yet.
+    #[inline]
+    fn new(cell: C) -> Self {
+        WorleyFeatures {
+            cell,
+            f1: Scalar::INFINITY,
+            f2: Scalar::INFINITY,
+        }
+    }
+
+    /// Records a feature point at `distance` in `cell`.
+    #[inline]
+    fn insert(&mut self, cell: C, distance: Scalar) {
+        if distance < self.f1 {
+            self.f2 = self.f1;
+            self.f1 = distance;
+            self.cell = cell;
+        } else if distance < self.f2 {
+            self.f2 = distance;
+        }
+    }
+}
+
+/// Seeded Worley (cellular) noise: one feature point scattered in every unit lattice cell,
+/// valued by the distance to the nearest ones.
+///
+/// [`features2`](Self::features2) and [`features3`](Self::features3) return the raw
+/// distances and the owning cell, which partitions the plane into Voronoi cells for biomes,
+/// territories or stone tiles. The [`Noise`] value is the distance picked by `output`,
+/// mapped by `2 * d - 1` and clamped to `[-1, 1]`. The search is exact for every metric:
+/// it widens past the neighbouring cells whenever a farther cell could still hold one of
+/// the two nearest points.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Vec2, Worley};
+///
+/// let biomes = Worley::new(0xb10);
+/// let p = Vec2::new(130.0, -12.0) / 48.0;
+/// let here = biomes.features2(p);
+/// // Neighbouring points in the same Voronoi cell get the same biome.
+/// assert_eq!(here.cell, biomes.features2(p + Vec2::new(1.0e-4, 0.0)).cell);
+/// assert!(here.f1 <= here.f2);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Worley {
+    pub seed: u64,
+    pub metric: CellDistance,
+    pub output: WorleyOutput,
+}
+
+impl Worley {
+    /// Euclidean F1 noise.
+    #[inline(always)]
+    pub const fn new(seed: u64) -> Self {
+        Worley {
+            seed,
+            metric: CellDistance::Euclidean,
+            output: WorleyOutput::F1,
+        }
+    }
+
+    /// Returns the feature point of a 2D cell.
+    #[inline]
+    fn point2(&self, cell: IVec2) -> Vec2 {
+        let h = hash2(self.seed, cell.x, cell.y);
+        Vec2::new(
+            cell.x as Scalar + unit_bits(h, 40),
+            cell.y as Scalar + unit_bits(h, 16),
+        )
+    }
+
+    /// Returns the feature point of a 3D cell.
+    #[inline]
+    fn point3(&self, cell: IVec3) -> Vec3 {
+        let h = hash3(self.seed, cell.x, cell.y, cell.z);
+        Vec3::new(
+            cell.x as Scalar + unit_bits(h, 40),
+            cell.y as Scalar + unit_bits(h, 20),
+            cell.z as Scalar + unit_bits(h, 0),
+        )
+    }
+
+    /// Returns the nearest two feature points' distances from `point` and the nearest one's
+    /// cell.
+    pub fn features2(&self, point: Vec2) -> WorleyFeatures<IVec2> {
+        let home = IVec2::floor(point);
+        let mut found = WorleyFeatures::new(home);
+        // Every metric is at least the largest axis distance, which is at least `ring - 1`
+        // for any cell `ring` steps away, so stop once that can't beat the second nearest.
+        let mut ring = 0i32;
+        while ring < 2 || ((ring - 1) as Scalar) < found.f2 {
+            for dy in -ring..=ring {
+                for dx in -ring..=ring {
+                    if dx.abs().max(dy.abs()) != ring {
+                        continue;
+                    }
+                    let cell = home + IVec2::new(dx, dy);
+                    let gap = |p: Scalar, c: i32| (c as Scalar - p).max(p - (c + 1) as Scalar);
+                    if gap(point.x, cell.x).max(gap(point.y, cell.y)) >= found.f2 {
+                        continue;
+                    }
+                    let offset = self.point2(cell) - point;
+                    let distance = self.metric.length([offset.x.abs(), offset.y.abs()]);
+                    found.insert(cell, distance);
+                }
+            }
+            ring += 1;
+        }
+        found
+    }
+
+    /// The 3D counterpart of [`features2`](Self::features2).
+    pub fn features3(&self, point: Vec3) -> WorleyFeatures<IVec3> {
+        let home = IVec3::floor(point);
+        let mut found = WorleyFeatures::new(home);
+        let mut ring = 0i32;
+        while ring < 2 || ((ring - 1) as Scalar) < found.f2 {
+            for dz in -ring..=ring {
+                for dy in -ring..=ring {
+                    for dx in -ring..=ring {
+                        if dx.abs().max(dy.abs()).max(dz.abs()) != ring {
+                            continue;
+                        }
+                        let cell = home + IVec3::new(dx, dy, dz);
+                        let gap = |p: Scalar, c: i32| (c as Scalar - p).max(p - (c + 1) as Scalar);
+                        let gap = gap(point.x, cell.x)
+                            .max(gap(point.y, cell.y))
+                            .max(gap(point.z, cell.z));
+                        if gap >= found.f2 {
+                            continue;
+                        }
+                        let offset = self.point3(cell) - point;
+                        let axes = [offset.x.abs(), offset.y.abs(), offset.z.abs()];
+                        found.insert(cell, self.metric.length(axes));
+                    }
+                }
+            }
+            ring += 1;
+        }
+        found
+    }
+
+    /// Maps found distances to the [`Noise`] range.
+    #[inline]
+    fn value<C>(&self, features: WorleyFeatures<C>) -> Scalar {
+        let distance = match self.output {
+            WorleyOutput::F1 => features.f1,
+            WorleyOutput::F2 => features.f2,
+            WorleyOutput::F2MinusF1 => features.f2 - features.f1,
+        };
+        (distance * 2.0 - 1.0).clamp(-1.0, 1.0)
+    }
+}
+
+impl Noise<Vec2> for Worley {
+    fn sample(&self, point: Vec2) -> Scalar {
+        self.value(self.features2(point))
+    }
+}
+
+impl Noise<Vec3> for Worley {
+    fn sample(&self, point: Vec3) -> Scalar {
+        self.value(self.features3(point))
+    }
+}
+
+/// Returns a pseudo-random [`Scalar`] in `[0, 1)` for the integer `x`.
+///
+/// The `hash*` functions are white noise: stateless, uncorrelated between neighbouring
+/// inputs and the same on every host, for per-cell values such as ore richness or tree
+/// placement. The digits name the input and output dimensions, as in shader code; inputs of
+/// different dimension are hashed independently, so `hash11(seed, 3)` and
+/// `hash21(seed, IVec2::new(3, 0))` are unrelated.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{hash21, hash22, IVec2};
+///
+/// const WORLD_SEED: u64 = 0x5eed;
+/// let tile = IVec2::new(-12, 40);
+/// let has_tree = hash21(WORLD_SEED, tile) < 0.1;
+/// // Somewhere in this one-unit tile, the same on every replay.
+/// let tree = tile.as_vec2() + hash22(WORLD_SEED, tile);
+/// assert_eq!(IVec2::floor(tree), tile);
+/// assert_eq!(has_tree, hash21(WORLD_SEED, tile) < 0.1);
+/// ```
+#[inline]
+pub fn hash11(seed: u64, x: i32) -> Scalar {
+    // A `y` no 32-bit coordinate can produce keeps this apart from `hash21`.
+    unit_bits(hash_rand_u64(seed, x as u32 as u64, u64::MAX), 40)
+}
+
+/// Returns a pseudo-random [`Scalar`] in `[0, 1)` for a 2D integer point; see [`hash11`].
+#[inline]
+pub fn hash21(seed: u64, p: IVec2) -> Scalar {
+    unit_bits(hash2(seed, p.x, p.y), 40)
+}
+
+/// Returns a pseudo-random [`Scalar`] in `[0, 1)` for a 3D integer point; see [`hash11`].
+#[inline]
+pub fn hash31(seed: u64, p: IVec3) -> Scalar {
+    unit_bits(hash3(seed, p.x, p.y, p.z), 40)
+}
+
+/// Returns a pseudo-random vector with components in `[0, 1)` for a 2D integer point; see
+/// [`hash11`].
+#[inline]
+pub fn hash22(seed: u64, p: IVec2) -> Vec2 {
+    let h = hash2(seed, p.x, p.y);
+    Vec2::new(unit_bits(h, 40), unit_bits(h, 16))
+}
+
+/// Returns a pseudo-random vector with components in `[0, 1)` for a 3D integer point; see
+/// [`hash11`].
+#[inline]
+pub fn hash33(seed: u64, p: IVec3) -> Vec3 {
+    let h = hash3(seed, p.x, p.y, p.z);
+    Vec3::new(unit_bits(h, 40), unit_bits(h, 16), unit_bits(mix64(h), 40))
+}
+
+/// Seeded white noise: an independent random value for every unit cell, constant within
+/// the cell.
+///
+/// This is [`hash21`] and [`hash31`] behind the [`Noise`] trait, mapped to `[-1, 1)`, for
+/// mixing per-cell randomness into the same pipelines as coherent noise.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhiteNoise {
+    pub seed: u64,
+}
+
+impl WhiteNoise {
+    #[inline(always)]
+    pub const fn new(seed: u64) -> Self {
+        WhiteNoise { seed }
+    }
+}
+
+impl Noise<Vec2> for WhiteNoise {
+    #[inline]
+    fn sample(&self, point: Vec2) -> Scalar {
+        hash21(self.seed, IVec2::floor(point)) * 2.0 - 1.0
+    }
+}
+
+impl Noise<Vec3> for WhiteNoise {
+    #[inline]
+    fn sample(&self, point: Vec3) -> Scalar {
+        hash31(self.seed, IVec3::floor(point)) * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lattice_points_are_zero() {
+        let noise = Perlin::new(3);
+        for (x, y) in [(0, 0), (5, -2), (-100, 77)] {
+            assert_eq!(noise.sample(Vec2::new(x as Scalar, y as Scalar)), 0.0);
+            let p = Vec3::new(x as Scalar, y as Scalar, 4.0);
+            assert_eq!(noise.sample(p), 0.0);
+        }
+    }
+
+    #[test]
+    fn values_are_bounded_and_continuous() {
+        let noise = Perlin::new(11);
+        let (mut min, mut max) = (Scalar::MAX, Scalar::MIN);
+        for i in 0..2000 {
+            let t = i as Scalar * 0.037;
+            let p = Vec2::new(t, t * 0.61 - 3.0);
+            let a = noise.sample(p);
+            let b = noise.sample(p + Vec2::new(1.0e-3, 0.0));
+            assert!((a - b).abs() <= 0.01, "{a} {b}");
+            let q = Vec3::new(t * 0.3, -t, t * 0.7);
+            let c = noise.sample(q);
+            assert!((c - noise.sample(q + Vec3::new(0.0, 0.0, 1.0e-3))).abs() <= 0.01);
+            for value in [a, c] {
+                assert!((-1.0..=1.0).contains(&value));
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+        // Not stuck near zero.
+        assert!(min < -0.4 && max > 0.4, "{min} {max}");
+    }
+
+    #[test]
+    fn open_simplex_is_bounded_continuous_and_not_flat() {
+        let noise = OpenSimplex2::new(5);
+        let (mut min, mut max) = (Scalar::MAX, Scalar::MIN);
+        for i in 0..3000 {
+            let t = i as Scalar * 0.029;
+            let p = Vec2::new(t * 1.3 - 20.0, -t);
+            let a = noise.sample(p);
+            assert!((a - noise.sample(p + Vec2::new(0.0, 1.0e-3))).abs() <= 0.02);
+            let q = Vec3::new(t, t * 0.45, 7.0 - t * 0.8);
+            let b = noise.sample(q);
+            assert!((b - noise.sample(q + Vec3::new(1.0e-3, 0.0, 0.0))).abs() <= 0.02);
+            for value in [a, b] {
+                assert!((-1.0..=1.0).contains(&value));
+                min = min.min(value);
+                max = max.max(value);
+            }
+        }
+        assert!(min < -0.5 && max > 0.5, "{min} {max}");
+
+        let p = Vec3::new(1.5, -2.25, 3.0);
+        assert_eq!(noise.sample(p), OpenSimplex2::new(5).sample(p));
+        assert_ne!(noise.sample(p), OpenSimplex2::new(6).sample(p));
+    }
+
+    #[test]
+    fn gradient_tables_are_unit_length() {
+        for (x, y) in GRADIENTS_2D {
+            assert!((x * x + y * y - 1.0).abs() <= 1.0e-6);
+        }
+        for (x, y, z) in GRADIENTS_3D {
+            assert!((x * x + y * y + z * z - 1.0).abs() <= 1.0e-6);
+        }
+        let (x, y) = GRADIENTS_2D[7];
+        let angle = (105.0 as Scalar).to_radians();
+        assert!((x - angle.cos()).abs() <= 1.0e-6 && (y - angle.sin()).abs() <= 1.0e-6);
+    }
+
+    #[test]
+    fn seeds_give_different_reproducible_fields() {
+        let p = Vec2::new(3.3, -1.7);
+        assert_eq!(Perlin::new(1).sample(p), Perlin::new(1).sample(p));
+        assert_ne!(Perlin::new(1).sample(p), Perlin::new(2).sample(p));
+        let q = Vec3::new(0.5, 0.25, -9.75);
+        assert_ne!(Perlin::new(1).sample(q), Perlin::new(2).sample(q));
+    }
+
+    #[test]
+    fn fractals_stay_in_range_and_add_detail() {
+        let base = OpenSimplex2::new(8);
+        let fbm = Fbm::new(base, 5);
+        let ridged = Ridged::new(base, 5);
+        let turbulence = Turbulence::new(base, 5);
+        let warped = Warp::new(base, Perlin::new(9), 1.5);
+        let (mut ridge_min, mut ridge_max) = (Scalar::MAX, Scalar::MIN);
+        for i in 0..2000 {
+            let t = i as Scalar * 0.031;
+            let p = Vec2::new(t - 9.0, t * 0.37);
+            let q = Vec3::new(t, -t * 0.8, 2.0);
+            for value in [
+                fbm.sample(p),
+                fbm.sample(q),
+                warped.sample(p),
+                warped.sample(q),
+            ] {
+                assert!((-1.0..=1.0).contains(&value), "{value}");
+            }
+            assert!((0.0..=1.0).contains(&turbulence.sample(p)));
+            assert!((0.0..=1.0).contains(&turbulence.sample(q)));
+            let r = ridged.sample(p);
+            ridge_min = ridge_min.min(r);
+            ridge_max = ridge_max.max(r);
+        }
+        assert!(ridge_min >= -1.0 && ridge_max <= 1.0 && ridge_max > 0.0);
+
+        // One octave is the base noise itself; more octaves change the value.
+        let p = Vec2::new(0.3, 4.1);
+        assert_eq!(Fbm::new(base, 1).sample(p), base.sample(p));
+        assert_eq!(Fbm::new(base, 0).sample(p), base.sample(p));
+        assert_ne!(fbm.sample(p), base.sample(p));
+        // Octaves don't all vanish together on the lattice.
+        assert_ne!(Fbm::new(Perlin::new(1), 3).sample(Vec2::ZERO), 0.0);
+    }
+
+    #[test]
+    fn worley_matches_brute_force() {
+        for metric in [
+            CellDistance::Euclidean,
+            CellDistance::Manhattan,
+            CellDistance::Chebyshev,
+        ] {
+            let noise = Worley {
+                seed: 21,
+                metric,
+                output: WorleyOutput::F2MinusF1,
+            };
+            for i in 0..300 {
+                let t = i as Scalar * 0.173;
+                let p = Vec2::new(t - 20.0, t * 0.41 + 3.0);
+                let mut distances = Vec::new();
+                for dy in -4..=4 {
+                    for dx in -4..=4 {
+                        let cell = IVec2::floor(p) + IVec2::new(dx, dy);
+                        let o = noise.point2(cell) - p;
+                        distances.push((metric.length([o.x.abs(), o.y.abs()]), cell));
+                    }
+                }
+                distances.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let found = noise.features2(p);
+                assert_eq!((found.f1, found.f2), (distances[0].0, distances[1].0));
+                assert_eq!(found.cell, distances[0].1);
+                assert!((-1.0..=1.0).contains(&noise.sample(p)));
+
+                let q = Vec3::new(p.x, -t * 0.3, p.y);
+                let mut distances = Vec::new();
+                for dz in -3..=3 {
+                    for dy in -3..=3 {
+                        for dx in -3..=3 {
+                            let cell = IVec3::floor(q) + IVec3::new(dx, dy, dz);
+                            let o = noise.point3(cell) - q;
+                            distances.push(metric.length([o.x.abs(), o.y.abs(), o.z.abs()]));
+                        }
+                    }
+                }
+                distances.sort_by(|a, b| a.total_cmp(b));
+                let found = noise.features3(q);
+                assert_eq!((found.f1, found.f2), (distances[0], distances[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn worley_feature_points_read_as_minus_one() {
+        let noise = Worley::new(4);
+        let feature = noise.point2(IVec2::new(3, -8));
+        assert_eq!(noise.sample(feature), -1.0);
+        assert_eq!(noise.features2(feature).cell, IVec2::new(3, -8));
+        let edges = Worley {
+            output: WorleyOutput::F2MinusF1,
+            ..noise
+        };
+        assert!(edges.sample(feature) > noise.sample(feature));
+    }
+
+    #[test]
+    fn white_noise_is_uniform_and_uncorrelated() {
+        let (mut sum, mut sum_products, mut buckets) = (0.0, 0.0, [0u32; 10]);
+        let n = 100;
+        for y in -n / 2..n / 2 {
+            for x in -n / 2..n / 2 {
+                let p = IVec2::new(x, y);
+                let value = hash21(7, p);
+                let next = hash21(7, p + IVec2::new(1, 0));
+                sum += value;
+                sum_products += (value - 0.5) * (next - 0.5);
+                buckets[(value * 10.0) as usize] += 1;
+                let v = hash22(7, p);
+                let w = hash33(7, IVec3::new(x, y, 1));
+                for c in [
+                    hash11(7, x),
+                    hash31(7, IVec3::new(y, x, 2)),
+                    v.x,
+                    v.y,
+                    w.x,
+                    w.y,
+                    w.z,
+                ] {
+                    assert!((0.0..1.0).contains(&c));
+                }
+            }
+        }
+        let count = (n * n) as Scalar;
+        assert!((sum / count - 0.5).abs() < 0.01);
+        // Neighbours are uncorrelated: the covariance of a uniform value with itself is 1/12.
+        assert!((sum_products / count).abs() < 0.004);
+        assert!(
+            buckets.iter().all(|&b| (900..1100).contains(&b)),
+            "{buckets:?}"
+        );
+
+        assert_ne!(hash11(7, 3), hash21(7, IVec2::new(3, 0)));
+        assert_ne!(hash21(7, IVec2::new(3, 4)), hash21(8, IVec2::new(3, 4)));
+        let noise = WhiteNoise::new(7);
+        assert_eq!(
+            noise.sample(Vec2::new(3.2, 4.9)),
+            noise.sample(Vec2::new(3.8, 4.1))
+        );
+        assert_eq!(
+            noise.sample(Vec3::new(-0.5, 1.5, 2.5)),
+            hash31(7, IVec3::new(-1, 1, 2)) * 2.0 - 1.0
+        );
+    }
+}
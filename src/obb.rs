@@ -0,0 +1,115 @@
+use crate::{Aabb3, Quat, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// An oriented bounding box: a box with its own rotation.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Obb3, Quat, Vec3};
+///
+/// let crate_box = Obb3::new(Vec3::ZERO, Vec3::new(2.0, 0.5, 0.5), Quat::IDENTITY);
+/// assert!(crate_box.contains_point(Vec3::new(1.5, 0.0, 0.0)));
+/// assert!(!crate_box.contains_point(Vec3::new(0.0, 1.5, 0.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obb3 {
+    /// Center of the box.
+    pub center: Vec3,
+    /// Half the size of the box along each of its local axes.
+    pub half_extents: Vec3,
+    /// Rotation from the box's local space to world space (unit length).
+    pub rotation: Quat,
+}
+
+impl Obb3 {
+    #[inline(always)]
+    pub const fn new(center: Vec3, half_extents: Vec3, rotation: Quat) -> Self {
+        Obb3 {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// Creates an unrotated box covering `aabb`.
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb3) -> Self {
+        Obb3::new(aabb.center(), aabb.half_extents(), Quat::IDENTITY)
+    }
+
+    /// Returns the box's local X, Y and Z axes in world space.
+    pub fn axes(&self) -> [Vec3; 3] {
+        [
+            self.rotation * Vec3::new(1.0, 0.0, 0.0),
+            self.rotation * Vec3::new(0.0, 1.0, 0.0),
+            self.rotation * Vec3::new(0.0, 0.0, 1.0),
+        ]
+    }
+
+    /// Converts a world-space point into the box's local space, relative to its center.
+    #[inline]
+    pub fn to_local(&self, point: Vec3) -> Vec3 {
+        self.rotation.conjugate() * (point - self.center)
+    }
+
+    /// Converts a point in the box's local space into world space.
+    #[inline]
+    pub fn to_world(&self, point: Vec3) -> Vec3 {
+        self.center + self.rotation * point
+    }
+
+    /// Returns the box in its own local space, as an AABB centered on the origin.
+    #[inline]
+    pub fn local_aabb(&self) -> Aabb3 {
+        Aabb3::new(-self.half_extents, self.half_extents)
+    }
+
+    /// Returns `true` if `point` is inside or on the surface of the box.
+    #[inline]
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.local_aabb().contains_point(self.to_local(point))
+    }
+
+    /// Returns the point inside or on the box closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.to_world(self.local_aabb().closest_point(self.to_local(point)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Scalar;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    fn rotated() -> Obb3 {
+        let quarter = core::f64::consts::FRAC_PI_2 as Scalar;
+        Obb3::new(
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, 1.0, 1.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), quarter),
+        )
+    }
+
+    #[test]
+    fn contains_point_follows_rotation() {
+        let obb = rotated();
+        // The long local X axis now points along world Y.
+        assert!(obb.contains_point(Vec3::new(1.0, 1.9, 0.0)));
+        assert!(!obb.contains_point(Vec3::new(2.9, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn closest_point_clamps_in_local_space() {
+        let obb = rotated();
+        let p = obb.closest_point(Vec3::new(5.0, 5.0, 0.0));
+        assert!(p.distance(Vec3::new(2.0, 2.0, 0.0)) <= EPS);
+        let axes = obb.axes();
+        assert!(axes[0].distance(Vec3::new(0.0, 1.0, 0.0)) <= EPS);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{Scalar, Transform, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A movable origin for simulating in coordinates relative to an area of interest.
+///
+/// Float precision falls off with distance from zero: at 100 km an `f32` resolves only about
+/// 8 mm. Simulating relative to an origin near the action keeps the numbers small, and
+/// [`FloatingOrigin::recenter`] moves the origin along once the focus wanders too far,
+/// returning the shift to apply to every relative position.
+///
+/// Recentering snaps the origin to multiples of `recenter_distance`; with a power of two
+/// distance every origin and shift is exactly representable, so rebasing itself never adds
+/// rounding error.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{FloatingOrigin, Vec3};
+///
+/// let mut origin = FloatingOrigin::new(1024.0);
+/// let mut player = Vec3::new(1500.0, 0.0, 10.0);
+/// let mut crate_pos = Vec3::new(1490.0, 0.0, 0.0);
+/// if let Some(shift) = origin.recenter(player) {
+///     player += shift;
+///     crate_pos += shift;
+/// }
+/// assert_eq!(origin.origin, Vec3::new(1024.0, 0.0, 0.0));
+/// assert_eq!(player, Vec3::new(476.0, 0.0, 10.0));
+/// assert_eq!(origin.to_absolute(crate_pos), Vec3::new(1490.0, 0.0, 0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatingOrigin {
+    /// Absolute position of the relative coordinates' zero.
+    pub origin: Vec3,
+    /// How far the focus may stray from the origin before [`FloatingOrigin::recenter`]
+    /// moves it.
+    pub recenter_distance: Scalar,
+}
+
+impl FloatingOrigin {
+    /// Creates an origin at the absolute zero.
+    #[inline]
+    pub const fn new(recenter_distance: Scalar) -> Self {
+        FloatingOrigin {
+            origin: Vec3::ZERO,
+            recenter_distance,
+        }
+    }
+
+    /// Converts an origin-relative position to absolute coordinates.
+    #[inline]
+    pub fn to_absolute(&self, relative: Vec3) -> Vec3 {
+        relative + self.origin
+    }
+
+    /// Converts an absolute position to origin-relative coordinates.
+    #[inline]
+    pub fn to_relative(&self, absolute: Vec3) -> Vec3 {
+        absolute - self.origin
+    }
+
+    /// Converts an origin-relative transform to absolute coordinates.
+    #[inline]
+    pub fn transform_to_absolute(&self, relative: &Transform) -> Transform {
+        Transform {
+            translation: self.to_absolute(relative.translation),
+            ..*relative
+        }
+    }
+
+    /// Converts an absolute transform to origin-relative coordinates.
+    #[inline]
+    pub fn transform_to_relative(&self, absolute: &Transform) -> Transform {
+        Transform {
+            translation: self.to_relative(absolute.translation),
+            ..*absolute
+        }
+    }
+
+    /// Moves the origin to the absolute position `new_origin` and returns the shift to add to
+    /// every relative position.
+    #[inline]
+    pub fn rebase(&mut self, new_origin: Vec3) -> Vec3 {
+        let shift = self.origin - new_origin;
+        self.origin = new_origin;
+        shift
+    }
+
+    /// Moves the origin to `new_origin`, shifting `positions` so they keep their absolute
+    /// positions.
+    pub fn rebase_positions(&mut self, new_origin: Vec3, positions: &mut [Vec3]) {
+        let shift = self.rebase(new_origin);
+        for position in positions {
+            *position += shift;
+        }
+    }
+
+    /// Moves the origin to `new_origin`, shifting `transforms` so they keep their absolute
+    /// positions.
+    pub fn rebase_transforms(&mut self, new_origin: Vec3, transforms: &mut [Transform]) {
+        let shift = self.rebase(new_origin);
+        for transform in transforms {
+            transform.translation += shift;
+        }
+    }
+
+    /// Moves the origin next to the relative position `focus` if it is further than
+    /// `recenter_distance` away on any axis, returning the shift to add to every relative
+    /// position, or `None` if the origin stays.
+    pub fn recenter(&mut self, focus: Vec3) -> Option<Vec3> {
+        let limit = self.recenter_distance;
+        let reach = focus.x.abs().max(focus.y.abs()).max(focus.z.abs());
+        if limit <= 0.0 || limit.is_nan() || reach <= limit {
+            return None;
+        }
+        let snap = |v: Scalar| float::round(v / limit) * limit;
+        let step = Vec3::new(snap(focus.x), snap(focus.y), snap(focus.z));
+        Some(self.rebase(self.origin + step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quat;
+
+    #[test]
+    fn conversions_round_trip() {
+        let origin = FloatingOrigin {
+            origin: Vec3::new(4096.0, 0.0, -8192.0),
+            recenter_distance: 512.0,
+        };
+        let absolute = Vec3::new(4100.5, 2.0, -8190.25);
+        assert_eq!(origin.to_relative(absolute), Vec3::new(4.5, 2.0, 1.75));
+        assert_eq!(origin.to_absolute(origin.to_relative(absolute)), absolute);
+
+        let rotation = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 1.0);
+        let relative = Transform::from_translation_rotation(Vec3::new(1.0, 2.0, 3.0), rotation);
+        let back = origin.transform_to_relative(&origin.transform_to_absolute(&relative));
+        assert_eq!(back, relative);
+    }
+
+    #[test]
+    fn rebasing_keeps_absolute_positions() {
+        let mut origin = FloatingOrigin::new(256.0);
+        let mut positions = [Vec3::new(300.0, 1.0, 0.0), Vec3::new(-20.0, 0.0, 5.0)];
+        let absolute = positions.map(|p| origin.to_absolute(p));
+        origin.rebase_positions(Vec3::new(256.0, 0.0, 0.0), &mut positions);
+        assert_eq!(positions[0], Vec3::new(44.0, 1.0, 0.0));
+        assert_eq!(positions.map(|p| origin.to_absolute(p)), absolute);
+
+        let mut transforms = [Transform::from_translation(Vec3::new(10.0, 0.0, 0.0))];
+        origin.rebase_transforms(Vec3::ZERO, &mut transforms);
+        assert_eq!(transforms[0].translation, Vec3::new(266.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn recenters_only_past_the_threshold() {
+        let mut origin = FloatingOrigin::new(1024.0);
+        assert_eq!(origin.recenter(Vec3::new(1000.0, -1000.0, 1024.0)), None);
+        let shift = origin.recenter(Vec3::new(-2600.0, 0.0, 100.0)).unwrap();
+        assert_eq!(origin.origin, Vec3::new(-3072.0, 0.0, 0.0));
+        assert_eq!(shift, Vec3::new(3072.0, 0.0, 0.0));
+        assert_eq!(FloatingOrigin::new(0.0).recenter(Vec3::splat(1.0e6)), None);
+    }
+}
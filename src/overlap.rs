@@ -0,0 +1,341 @@
+//! Overlap tests between pairs of solids.
+//!
+//! Each pair has a boolean test (`intersects_*`) and a contact query (`contact_*`) that also
+//! reports how to push the shapes apart, e.g. [`Sphere::contact_aabb`].
+
+use crate::{Aabb3, Capsule3, Obb3, Scalar, Sphere, Vec3, float};
+
+/// Contact between two overlapping solids.
+///
+/// Translating the first shape by `normal * depth` separates it from the second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Unit direction pointing from the second shape towards the first.
+    pub normal: Vec3,
+    /// Penetration depth along `normal`.
+    pub depth: Scalar,
+}
+
+impl Contact {
+    #[inline(always)]
+    pub const fn new(normal: Vec3, depth: Scalar) -> Self {
+        Contact { normal, depth }
+    }
+
+    /// Returns the contact as seen from the second shape.
+    #[inline]
+    pub fn flipped(&self) -> Contact {
+        Contact::new(-self.normal, self.depth)
+    }
+
+    /// Returns the translation that pushes the first shape out of the second.
+    #[inline]
+    pub fn push_out(&self) -> Vec3 {
+        self.normal * self.depth
+    }
+}
+
+/// Fallback separation direction for shapes whose centers coincide.
+const FALLBACK_NORMAL: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+
+impl Sphere {
+    /// Returns `true` if this sphere and `other` overlap (touching counts).
+    #[inline]
+    pub fn intersects_sphere(&self, other: &Sphere) -> bool {
+        let radii = self.radius + other.radius;
+        self.center.distance_squared(other.center) <= radii * radii
+    }
+
+    /// Returns the contact with `other`, or `None` if they don't overlap.
+    ///
+    /// Concentric spheres are pushed apart along +Y.
+    pub fn contact_sphere(&self, other: &Sphere) -> Option<Contact> {
+        if !self.intersects_sphere(other) {
+            return None;
+        }
+        let offset = self.center - other.center;
+        let distance = offset.length();
+        let normal = if distance > 0.0 {
+            offset / distance
+        } else {
+            FALLBACK_NORMAL
+        };
+        Some(Contact::new(normal, self.radius + other.radius - distance))
+    }
+
+    /// Returns `true` if this sphere and `aabb` overlap (touching counts).
+    #[inline]
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        aabb.distance_squared_to_point(self.center) <= self.radius * self.radius
+    }
+
+    /// Returns the contact with `aabb`, or `None` if they don't overlap.
+    ///
+    /// If the center is inside the box, the sphere is pushed out through the nearest face.
+    pub fn contact_aabb(&self, aabb: &Aabb3) -> Option<Contact> {
+        let closest = aabb.closest_point(self.center);
+        let offset = self.center - closest;
+        let distance_sq = offset.length_squared();
+        if distance_sq > self.radius * self.radius {
+            return None;
+        }
+        if distance_sq > 0.0 {
+            let distance = float::sqrt(distance_sq);
+            return Some(Contact::new(offset / distance, self.radius - distance));
+        }
+
+        // The center is inside (or on) the box: find the face it is closest to.
+        let to_min = self.center - aabb.min;
+        let to_max = aabb.max - self.center;
+        let faces = [
+            (to_min.x, Vec3::new(-1.0, 0.0, 0.0)),
+            (to_max.x, Vec3::new(1.0, 0.0, 0.0)),
+            (to_min.y, Vec3::new(0.0, -1.0, 0.0)),
+            (to_max.y, Vec3::new(0.0, 1.0, 0.0)),
+            (to_min.z, Vec3::new(0.0, 0.0, -1.0)),
+            (to_max.z, Vec3::new(0.0, 0.0, 1.0)),
+        ];
+        let mut best = faces[0];
+        for face in &faces[1..] {
+            if face.0 < best.0 {
+                best = *face;
+            }
+        }
+        let (face_distance, normal) = best;
+        Some(Contact::new(normal, face_distance + self.radius))
+    }
+
+    /// Returns `true` if this sphere and `obb` overlap (touching counts).
+    #[inline]
+    pub fn intersects_obb(&self, obb: &Obb3) -> bool {
+        obb.closest_point(self.center).distance_squared(self.center) <= self.radius * self.radius
+    }
+
+    /// Returns the contact with `obb`, or `None` if they don't overlap.
+    ///
+    /// If the center is inside the box, the sphere is pushed out through the nearest face.
+    pub fn contact_obb(&self, obb: &Obb3) -> Option<Contact> {
+        let local = Sphere::new(obb.to_local(self.center), self.radius);
+        let contact = local.contact_aabb(&obb.local_aabb())?;
+        Some(Contact::new(obb.rotation * contact.normal, contact.depth))
+    }
+}
+
+impl Obb3 {
+    /// Returns `true` if this box and `other` overlap.
+    ///
+    /// See [`Obb3::contact_obb`] for the meaning of `epsilon`.
+    #[inline]
+    pub fn intersects_obb(&self, other: &Obb3, epsilon: Scalar) -> bool {
+        self.contact_obb(other, epsilon).is_some()
+    }
+
+    /// Returns the minimum translation that separates this box from `other`, or `None` if
+    /// they don't overlap.
+    ///
+    /// Runs the separating axis test over the 15 candidate axes: the 3 face normals of each
+    /// box and the 9 cross products of their edges. Edge pairs whose cross product is shorter
+    /// than `epsilon` are near-parallel; they are skipped, since the face axes already cover
+    /// them and their normalized direction is mostly rounding noise.
+    pub fn contact_obb(&self, other: &Obb3, epsilon: Scalar) -> Option<Contact> {
+        let axes_a = self.axes();
+        let axes_b = other.axes();
+        let offset = self.center - other.center;
+
+        let mut best: Option<Contact> = None;
+        let mut test_axis = |axis: Vec3| -> bool {
+            let length = axis.length();
+            if length <= epsilon {
+                return true;
+            }
+            let axis = axis / length;
+            let distance = offset.dot(axis);
+            let overlap = project_obb(&axes_a, self.half_extents, axis)
+                + project_obb(&axes_b, other.half_extents, axis)
+                - distance.abs();
+            if overlap < 0.0 {
+                return false;
+            }
+            if best.is_none_or(|b| overlap < b.depth) {
+                let normal = if distance < 0.0 { -axis } else { axis };
+                best = Some(Contact::new(normal, overlap));
+            }
+            true
+        };
+
+        for axis in axes_a.iter().chain(axes_b.iter()) {
+            if !test_axis(*axis) {
+                return None;
+            }
+        }
+        for a in &axes_a {
+            for b in &axes_b {
+                if !test_axis(a.cross(*b)) {
+                    return None;
+                }
+            }
+        }
+        best
+    }
+}
+
+impl Capsule3 {
+    /// Returns the distance between the surfaces of this capsule and `other`, or `0.0` if they
+    /// overlap.
+    pub fn distance_to_capsule(&self, other: &Capsule3) -> Scalar {
+        let (a, b) = self.segment().closest_points(&other.segment());
+        (a.distance(b) - self.radius - other.radius).max(0.0)
+    }
+
+    /// Returns `true` if this capsule and `other` overlap (touching counts).
+    pub fn intersects_capsule(&self, other: &Capsule3) -> bool {
+        let (a, b) = self.segment().closest_points(&other.segment());
+        let radii = self.radius + other.radius;
+        a.distance_squared(b) <= radii * radii
+    }
+
+    /// Returns the contact with `other` and the contact point, or `None` if they don't overlap.
+    ///
+    /// The contact point lies halfway between the deepest points of the two surfaces. Capsules
+    /// whose inner segments cross are pushed apart along +Y.
+    pub fn contact_capsule(&self, other: &Capsule3) -> Option<(Contact, Vec3)> {
+        let (a, b) = self.segment().closest_points(&other.segment());
+        let contact = Sphere::new(a, self.radius).contact_sphere(&Sphere::new(b, other.radius))?;
+        let deepest_a = a - contact.normal * self.radius;
+        let deepest_b = b + contact.normal * other.radius;
+        Some((contact, (deepest_a + deepest_b) * 0.5))
+    }
+}
+
+/// Returns the half-length of the projection of a box onto the unit `axis`.
+fn project_obb(axes: &[Vec3; 3], half_extents: Vec3, axis: Vec3) -> Scalar {
+    half_extents.x * axes[0].dot(axis).abs()
+        + half_extents.y * axes[1].dot(axis).abs()
+        + half_extents.z * axes[2].dot(axis).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quat;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn sphere_sphere_contact() {
+        let a = Sphere::new(Vec3::ZERO, 1.0);
+        let b = Sphere::new(Vec3::new(1.5, 0.0, 0.0), 1.0);
+        let contact = a.contact_sphere(&b).unwrap();
+        assert_eq!(contact.normal, Vec3::new(-1.0, 0.0, 0.0));
+        assert!((contact.depth - 0.5 as Scalar).abs() <= EPS);
+        assert_eq!(b.contact_sphere(&a).unwrap(), contact.flipped());
+        let far = Sphere::new(Vec3::new(3.0, 0.0, 0.0), 0.5);
+        assert!(!a.intersects_sphere(&far));
+        assert_eq!(a.contact_sphere(&far), None);
+        assert_eq!(a.contact_sphere(&a).unwrap().normal, FALLBACK_NORMAL);
+    }
+
+    #[test]
+    fn sphere_aabb_contact_outside_and_inside() {
+        let aabb = Aabb3::new(Vec3::ZERO, Vec3::splat(2.0));
+        let outside = Sphere::new(Vec3::new(1.0, 2.5, 1.0), 1.0);
+        let contact = outside.contact_aabb(&aabb).unwrap();
+        assert_eq!(contact.normal, Vec3::new(0.0, 1.0, 0.0));
+        assert!((contact.depth - 0.5 as Scalar).abs() <= EPS);
+
+        let inside = Sphere::new(Vec3::new(1.8, 1.0, 1.0), 0.5);
+        let contact = inside.contact_aabb(&aabb).unwrap();
+        assert_eq!(contact.normal, Vec3::new(1.0, 0.0, 0.0));
+        assert!((contact.depth - 0.7 as Scalar).abs() <= EPS);
+        let pushed = Sphere::new(inside.center + contact.push_out(), inside.radius);
+        assert!(pushed.center.x - pushed.radius >= 2.0 - EPS);
+
+        assert!(!Sphere::new(Vec3::new(3.0, 3.0, 3.0), 1.0).intersects_aabb(&aabb));
+    }
+
+    #[test]
+    fn sphere_obb_contact_uses_box_rotation() {
+        let quarter = core::f64::consts::FRAC_PI_4 as Scalar;
+        let obb = Obb3::new(
+            Vec3::ZERO,
+            Vec3::ONE,
+            Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), quarter),
+        );
+        // The box's corner now points along +X, at a distance of sqrt(2).
+        let sphere = Sphere::new(Vec3::new(1.8, 0.0, 0.0), 0.5);
+        assert!(sphere.intersects_obb(&obb));
+        let contact = sphere.contact_obb(&obb).unwrap();
+        assert!(contact.normal.distance(Vec3::new(1.0, 0.0, 0.0)) <= EPS);
+        let corner = (2.0 as Scalar).sqrt();
+        assert!((contact.depth - (0.5 - (1.8 - corner))).abs() <= EPS);
+        assert!(!Sphere::new(Vec3::new(2.0, 0.0, 0.0), 0.5).intersects_obb(&obb));
+    }
+
+    #[test]
+    fn capsule_capsule_distance_and_contact() {
+        let a = Capsule3::new(Vec3::ZERO, Vec3::new(0.0, 2.0, 0.0), 0.5);
+        let b = Capsule3::new(Vec3::new(0.8, 1.0, -1.0), Vec3::new(0.8, 1.0, 1.0), 0.5);
+        assert!(a.intersects_capsule(&b));
+        assert_eq!(a.distance_to_capsule(&b), 0.0 as Scalar);
+        let (contact, point) = a.contact_capsule(&b).unwrap();
+        assert!(contact.normal.distance(Vec3::new(-1.0, 0.0, 0.0)) <= EPS);
+        assert!((contact.depth - 0.2 as Scalar).abs() <= EPS);
+        assert!(point.distance(Vec3::new(0.4, 1.0, 0.0)) <= EPS);
+
+        let far = Capsule3::new(Vec3::new(2.0, 3.0, 0.0), Vec3::new(2.0, 5.0, 0.0), 0.5);
+        assert!(!a.intersects_capsule(&far));
+        assert_eq!(a.contact_capsule(&far), None);
+        let gap = (2.0 as Scalar * 2.0 + 1.0).sqrt() - 1.0;
+        assert!((a.distance_to_capsule(&far) - gap).abs() <= EPS);
+    }
+
+    #[test]
+    fn obb_obb_face_contact() {
+        let a = Obb3::new(Vec3::ZERO, Vec3::ONE, Quat::IDENTITY);
+        let b = Obb3::new(Vec3::new(1.5, 0.2, 0.0), Vec3::ONE, Quat::IDENTITY);
+        let contact = a.contact_obb(&b, EPS).unwrap();
+        assert!(contact.normal.distance(Vec3::new(-1.0, 0.0, 0.0)) <= EPS);
+        assert!((contact.depth - 0.5 as Scalar).abs() <= EPS);
+        let apart = Obb3::new(Vec3::new(2.1, 0.0, 0.0), Vec3::ONE, Quat::IDENTITY);
+        assert!(!a.intersects_obb(&apart, EPS));
+    }
+
+    #[test]
+    fn obb_obb_rotated_separation() {
+        let eighth = core::f64::consts::FRAC_PI_4 as Scalar;
+        let a = Obb3::new(Vec3::ZERO, Vec3::ONE, Quat::IDENTITY);
+        let diamond = |x: Scalar| {
+            Obb3::new(
+                Vec3::new(x, 0.0, 0.0),
+                Vec3::ONE,
+                Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), eighth),
+            )
+        };
+        // The diamond's corner reaches sqrt(2) towards `a`, so it touches at x = 1 + sqrt(2).
+        let corner = (2.0 as Scalar).sqrt();
+        assert!(a.intersects_obb(&diamond(2.3), EPS));
+        assert!(!a.intersects_obb(&diamond(1.0 + corner + 0.05), EPS));
+        let contact = diamond(2.3).contact_obb(&a, EPS).unwrap();
+        assert!((contact.depth - (1.0 + corner - 2.3)).abs() <= EPS);
+        assert!(contact.normal.distance(Vec3::new(1.0, 0.0, 0.0)) <= EPS);
+    }
+
+    #[test]
+    fn obb_obb_crossing_bars() {
+        // Two long bars crossing at right angles, one stacked on top of the other.
+        let quarter = core::f64::consts::FRAC_PI_2 as Scalar;
+        let bar = Vec3::new(3.0, 0.25, 0.25);
+        let a = Obb3::new(Vec3::ZERO, bar, Quat::IDENTITY);
+        let crossing = |y: Scalar| {
+            Obb3::new(
+                Vec3::new(0.0, y, 0.0),
+                bar,
+                Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), quarter),
+            )
+        };
+        assert!(a.intersects_obb(&crossing(0.4), EPS));
+        assert!(!a.intersects_obb(&crossing(0.6), EPS));
+        // Parallel, identical boxes must not trip over degenerate edge axes.
+        assert!(a.intersects_obb(&a, EPS));
+    }
+}
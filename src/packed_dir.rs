@@ -0,0 +1,148 @@
+use crate::{Scalar, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A unit vector packed into 32 bits with octahedral encoding.
+///
+/// The sphere is projected onto an octahedron, which is unfolded into a square and stored as
+/// two 16-bit coordinates. Every direction decodes to within about 0.004° (7e-5 radians) of
+/// the encoded one, at a third of the size of a [`Vec3`] of `f32`s, which suits facing
+/// directions and surface normals.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{PackedDir, Vec3};
+///
+/// let facing = Vec3::new(0.6, 0.0, -0.8);
+/// let packed = PackedDir::encode(facing);
+/// assert!(packed.decode().distance(facing) < 1.0e-4);
+/// assert_eq!(PackedDir::from_bits(packed.to_bits()), packed);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackedDir {
+    /// The first coordinate in the low 16 bits, the second in the high 16 bits.
+    pub bits: u32,
+}
+
+impl PackedDir {
+    #[inline(always)]
+    pub const fn from_bits(bits: u32) -> Self {
+        PackedDir { bits }
+    }
+
+    #[inline(always)]
+    pub const fn to_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Encodes the direction of `v`, which doesn't need to be normalized.
+    ///
+    /// A zero or non-finite vector encodes +Z.
+    pub fn encode(v: Vec3) -> Self {
+        let l1 = v.x.abs() + v.y.abs() + v.z.abs();
+        if !(l1 > Scalar::EPSILON && l1.is_finite()) {
+            return Self::encode(Vec3::new(0.0, 0.0, 1.0));
+        }
+        let (x, y) = fold(v.x / l1, v.y / l1, v.z < 0.0);
+        PackedDir::from_bits(quantize(x) | (quantize(y) << 16))
+    }
+
+    /// Decodes the packed direction as a unit vector.
+    pub fn decode(&self) -> Vec3 {
+        let x = dequantize(self.bits);
+        let y = dequantize(self.bits >> 16);
+        let z = 1.0 - x.abs() - y.abs();
+        let (x, y) = fold(x, y, z < 0.0);
+        let v = Vec3::new(x, y, z);
+        v * (1.0 / v.length())
+    }
+}
+
+impl From<Vec3> for PackedDir {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        PackedDir::encode(v)
+    }
+}
+
+impl From<PackedDir> for Vec3 {
+    #[inline]
+    fn from(d: PackedDir) -> Self {
+        d.decode()
+    }
+}
+
+/// Mirrors the lower hemisphere's octahedron faces over the diagonals of the square, which
+/// is its own inverse.
+#[inline]
+fn fold(x: Scalar, y: Scalar, lower: bool) -> (Scalar, Scalar) {
+    if lower {
+        ((1.0 - y.abs()) * sign(x), (1.0 - x.abs()) * sign(y))
+    } else {
+        (x, y)
+    }
+}
+
+/// Like `signum`, but `1` for both zeros so the fold never collapses a coordinate.
+#[inline]
+fn sign(v: Scalar) -> Scalar {
+    if v >= 0.0 { 1.0 } else { -1.0 }
+}
+
+/// Steps on each side of zero. Using `0..=65534` rather than the full 16 bits makes `0`, `-1`
+/// and `1` exact, so axis-aligned directions decode exactly.
+const HALF_RANGE: Scalar = 32767.0;
+
+#[inline]
+fn quantize(v: Scalar) -> u32 {
+    (float::round(v.clamp(-1.0, 1.0) * HALF_RANGE) + HALF_RANGE) as u32
+}
+
+#[inline]
+fn dequantize(bits: u32) -> Scalar {
+    ((bits & 0xffff) as Scalar).min(2.0 * HALF_RANGE) / HALF_RANGE - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axes_and_diagonals_round_trip() {
+        for axis in [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+        ] {
+            assert_eq!(PackedDir::encode(axis).decode(), axis);
+        }
+        assert_eq!(
+            PackedDir::encode(Vec3::ZERO).decode(),
+            Vec3::new(0.0, 0.0, 1.0)
+        );
+        let scaled = PackedDir::encode(Vec3::new(-3.0, 4.0, -12.0));
+        let unit = Vec3::new(-3.0, 4.0, -12.0) * (1.0 / 13.0);
+        assert!(scaled.decode().distance(unit) <= 1.0e-4 as Scalar);
+    }
+
+    #[test]
+    fn angular_error_stays_within_the_documented_bound() {
+        // Deterministic points spread over the sphere by a golden-angle spiral.
+        let n = 20_000;
+        for i in 0..n {
+            let z = 1.0 - 2.0 * (i as Scalar + 0.5) / n as Scalar;
+            let r = (1.0 - z * z).sqrt();
+            let phi = i as Scalar * 2.399_963;
+            let v = Vec3::new(r * phi.cos(), r * phi.sin(), z);
+            let decoded = PackedDir::from(v).decode();
+            assert!((decoded.length() - 1.0).abs() <= 1.0e-5 as Scalar);
+            // For small angles the cross product's length is the angle in radians.
+            assert!(v.cross(decoded).length() <= 7.0e-5 as Scalar, "{v:?}");
+        }
+    }
+}
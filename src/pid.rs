@@ -0,0 +1,214 @@
+use crate::interp::ComponentWise;
+use crate::{Scalar, Vec2, Vec3};
+use core::ops::{Add, Mul, Sub};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A PID controller driving a measurement towards a setpoint.
+///
+/// Gains, limits and state are all public fields, so a controller can be stored in a table
+/// row and tuned live. Limits apply per component and are symmetric around zero; the default
+/// of infinity disables them.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::Pid1;
+///
+/// // Hold a hover vehicle at 5 m with a thrust of at most 20 m/s².
+/// let mut altitude = Pid1::new(8.0, 2.0, 4.0);
+/// altitude.output_limit = 20.0;
+/// altitude.integral_limit = 12.0;
+/// let (mut height, mut climb) = (0.0, 0.0);
+/// let dt = 1.0 / 20.0;
+/// for _ in 0..400 {
+///     let thrust = altitude.update(5.0, height, dt);
+///     climb += (thrust - 9.81) * dt;
+///     height += climb * dt;
+/// }
+/// assert!((height - 5.0).abs() < 0.05);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pid<T> {
+    /// Proportional gain.
+    pub kp: Scalar,
+    /// Integral gain.
+    pub ki: Scalar,
+    /// Derivative gain.
+    pub kd: Scalar,
+    /// Largest magnitude of the integral term, which limits windup. NaN means no limit and a
+    /// negative value is treated as zero.
+    pub integral_limit: Scalar,
+    /// Largest magnitude of the output. NaN means no limit and a negative value is treated as
+    /// zero.
+    pub output_limit: Scalar,
+    /// Differentiates the measurement instead of the error, so setpoint jumps don't cause an
+    /// output spike.
+    pub derivative_on_measurement: bool,
+    /// The accumulated integral term, already scaled by `ki`.
+    pub integral: T,
+    /// The error from the last update, for the derivative on error.
+    pub previous_error: T,
+    /// The measurement from the last update, for the derivative on measurement.
+    pub previous_measurement: T,
+    /// `false` until the first update, which has nothing to differentiate against.
+    pub has_previous: bool,
+}
+
+/// A [`Pid`] controller over scalars.
+pub type Pid1 = Pid<Scalar>;
+/// A [`Pid`] controller over 2D vectors.
+pub type Pid2 = Pid<Vec2>;
+/// A [`Pid`] controller over 3D vectors.
+pub type Pid3 = Pid<Vec3>;
+
+impl<T> Pid<T>
+where
+    T: ComponentWise + Default + Add<Output = T> + Sub<Output = T> + Mul<Scalar, Output = T>,
+{
+    /// Creates a controller with the given gains, no limits and derivative on error.
+    #[inline]
+    pub fn new(kp: Scalar, ki: Scalar, kd: Scalar) -> Self {
+        Pid {
+            kp,
+            ki,
+            kd,
+            integral_limit: Scalar::INFINITY,
+            output_limit: Scalar::INFINITY,
+            derivative_on_measurement: false,
+            integral: T::default(),
+            previous_error: T::default(),
+            previous_measurement: T::default(),
+            has_previous: false,
+        }
+    }
+
+    /// Clears the integral and derivative history, keeping gains and limits.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.integral = T::default();
+        self.previous_error = T::default();
+        self.previous_measurement = T::default();
+        self.has_previous = false;
+    }
+
+    /// Advances the controller by `dt` seconds and returns the control output.
+    ///
+    /// A non-positive `dt` returns the proportional and integral terms without changing any
+    /// state.
+    pub fn update(&mut self, setpoint: T, measurement: T, dt: Scalar) -> T {
+        let error = setpoint - measurement;
+        let proportional = error * self.kp;
+        if dt <= 0.0 {
+            return clamp(proportional + self.integral, self.output_limit);
+        }
+
+        self.integral = clamp(self.integral + error * (self.ki * dt), self.integral_limit);
+        let derivative = if !self.has_previous {
+            T::default()
+        } else if self.derivative_on_measurement {
+            (self.previous_measurement - measurement) * (self.kd / dt)
+        } else {
+            (error - self.previous_error) * (self.kd / dt)
+        };
+        self.previous_error = error;
+        self.previous_measurement = measurement;
+        self.has_previous = true;
+
+        clamp(proportional + self.integral + derivative, self.output_limit)
+    }
+}
+
+/// Clamps every component of `value` to `[-limit, limit]`. A NaN `limit` is no limit and a
+/// negative one is treated as zero.
+#[inline]
+fn clamp<T: ComponentWise>(value: T, limit: Scalar) -> T {
+    if limit.is_nan() {
+        return value;
+    }
+    let limit = limit.max(0.0);
+    value.map(|v| v.clamp(-limit, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn terms_combine_and_clamp() {
+        let mut pid = Pid1::new(2.0, 1.0, 0.5);
+        // First update: no derivative yet.
+        assert_eq!(pid.update(3.0, 1.0, 0.5), 2.0 * 2.0 + 1.0);
+        // Error drops from 2 to 1: P = 2, I = 1 + 0.5, D = 0.5 * -1 / 0.5.
+        assert_eq!(pid.update(3.0, 2.0, 0.5), 2.5);
+
+        pid.reset();
+        pid.output_limit = 1.5;
+        assert_eq!(pid.update(10.0, 0.0, 0.1), 1.5);
+        assert_eq!(pid.update(-10.0, 0.0, 0.1), -1.5);
+    }
+
+    #[test]
+    fn negative_and_nan_limits_do_not_panic() {
+        let mut pid = Pid2::new(1.0, 1.0, 0.0);
+        pid.output_limit = -1.0;
+        pid.integral_limit = -1.0;
+        assert_eq!(
+            pid.update(Vec2::new(3.0, -2.0), Vec2::ZERO, 0.5),
+            Vec2::ZERO
+        );
+        assert_eq!(pid.integral, Vec2::ZERO);
+
+        pid.output_limit = Scalar::NAN;
+        pid.integral_limit = Scalar::NAN;
+        let output = pid.update(Vec2::new(3.0, -2.0), Vec2::ZERO, 0.5);
+        assert_eq!(pid.integral, Vec2::new(1.5, -1.0));
+        assert_eq!(output, Vec2::new(4.5, -3.0));
+    }
+
+    #[test]
+    fn integral_limit_prevents_windup() {
+        let mut pid = Pid1::new(0.0, 1.0, 0.0);
+        pid.integral_limit = 2.0;
+        for _ in 0..100 {
+            pid.update(1.0, 0.0, 0.5);
+        }
+        assert_eq!(pid.integral, 2.0);
+        // Unwinding starts immediately once the error reverses.
+        assert_eq!(pid.update(-1.0, 0.0, 0.5), 1.5);
+    }
+
+    #[test]
+    fn derivative_on_measurement_ignores_setpoint_jumps() {
+        let mut on_error = Pid2::new(0.0, 0.0, 1.0);
+        let mut on_measurement = on_error;
+        on_measurement.derivative_on_measurement = true;
+        for pid in [&mut on_error, &mut on_measurement] {
+            pid.update(Vec2::ZERO, Vec2::ZERO, 0.1);
+        }
+        let jump = Vec2::new(5.0, 0.0);
+        assert_eq!(on_error.update(jump, Vec2::ZERO, 0.1).x, 50.0);
+        assert_eq!(on_measurement.update(jump, Vec2::ZERO, 0.1), Vec2::ZERO);
+        // Both damp movement of the measurement itself.
+        let moved = on_measurement.update(jump, Vec2::new(1.0, 0.0), 0.1);
+        assert!((moved.x + 10.0).abs() <= EPS);
+    }
+
+    #[test]
+    fn drives_a_homing_body_to_its_target() {
+        let mut pid = Pid3::new(4.0, 0.0, 3.0);
+        pid.derivative_on_measurement = true;
+        pid.output_limit = 10.0;
+        let target = Vec3::new(10.0, -5.0, 3.0);
+        let (mut position, mut velocity) = (Vec3::ZERO, Vec3::ZERO);
+        let dt = 1.0 / 30.0 as Scalar;
+        for _ in 0..600 {
+            velocity += pid.update(target, position, dt) * dt;
+            position += velocity * dt;
+        }
+        assert!(position.distance(target) <= 1.0e-2 as Scalar);
+    }
+}
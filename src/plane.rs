@@ -0,0 +1,91 @@
+use crate::{Scalar, Vec3, Vec4};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// An infinite plane described by `normal · p + d = 0`.
+///
+/// Points with a positive signed distance lie on the side the normal points towards.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Plane, Vec3};
+///
+/// let ground = Plane::from_point_normal(Vec3::ZERO, Vec3::new(0.0, 1.0, 0.0));
+/// assert_eq!(ground.signed_distance(Vec3::new(4.0, 2.0, -1.0)), 2.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    /// The plane normal. Expected to be unit length for distances to be metric.
+    pub normal: Vec3,
+    /// The plane offset along `normal`, negated (`-normal · point_on_plane`).
+    pub d: Scalar,
+}
+
+impl Plane {
+    #[inline(always)]
+    pub const fn new(normal: Vec3, d: Scalar) -> Self {
+        Plane { normal, d }
+    }
+
+    /// Creates a plane through `point` with the given `normal`.
+    #[inline]
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        Plane::new(normal, -normal.dot(point))
+    }
+
+    /// Creates a plane from the `(a, b, c, d)` coefficients of `ax + by + cz + d = 0`.
+    #[inline]
+    pub const fn from_vec4(v: Vec4) -> Self {
+        Plane::new(v.truncate(), v.w)
+    }
+
+    /// Returns the signed distance from the plane to `point`.
+    ///
+    /// Only metric when `normal` is unit length.
+    #[inline]
+    pub fn signed_distance(&self, point: Vec3) -> Scalar {
+        self.normal.dot(point) + self.d
+    }
+
+    /// Returns this plane with a unit-length normal, or `None` if the normal is below `epsilon`.
+    pub fn try_normalize(&self, epsilon: Scalar) -> Option<Plane> {
+        let len = self.normal.length();
+        if len <= epsilon {
+            None
+        } else {
+            Some(Plane::new(self.normal / len, self.d / len))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_point_normal_contains_point() {
+        let point = Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar);
+        let plane = Plane::from_point_normal(point, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(plane.signed_distance(point), 0.0 as Scalar);
+        assert_eq!(plane.signed_distance(Vec3::ZERO), -3.0 as Scalar);
+    }
+
+    #[test]
+    fn try_normalize_scales_normal_and_offset() {
+        let plane = Plane::new(Vec3::new(0.0, 2.0, 0.0), 4.0 as Scalar);
+        let normalized = plane
+            .try_normalize(1.0e-6 as Scalar)
+            .expect("expected plane");
+        assert_eq!(
+            normalized,
+            Plane::new(Vec3::new(0.0, 1.0, 0.0), 2.0 as Scalar)
+        );
+        assert!(
+            Plane::new(Vec3::ZERO, 1.0)
+                .try_normalize(1.0e-6 as Scalar)
+                .is_none()
+        );
+    }
+}
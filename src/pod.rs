@@ -0,0 +1,98 @@
+//! Zero-copy reinterpretation of slices of vectors and quaternions, for GPU uploads and
+//! binary blobs.
+//!
+//! With the `bytemuck` feature [`Vec2`], [`Vec3`], [`Vec4`] and [`Quat`] are `repr(C)`,
+//! [`Pod`] and [`Zeroable`](bytemuck::Zeroable), so a buffer of positions can be viewed as
+//! flat [`Scalar`]s or bytes without copying. The casts here are thin wrappers over
+//! [`bytemuck`]'s, restricted to the crate's types.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{Scalar, Vec3, pod};
+//!
+//! let positions = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)];
+//! let flat: &[Scalar] = pod::as_scalars(&positions);
+//! assert_eq!(flat, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//! assert_eq!(pod::as_bytes(&positions).len(), 6 * size_of::<Scalar>());
+//! assert_eq!(pod::try_from_scalars::<Vec3>(flat), Ok(&positions[..]));
+//! ```
+
+use crate::{Quat, Scalar, Vec2, Vec3, Vec4};
+use bytemuck::{Pod, PodCastError};
+
+/// A `repr(C)` crate type made only of [`Scalar`]s.
+///
+/// This trait is sealed.
+pub trait ScalarPod: Pod + sealed::Sealed {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! scalar_pod {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+        impl ScalarPod for $t {}
+    )*};
+}
+
+scalar_pod!(Vec2, Vec3, Vec4, Quat);
+
+/// Views `values` as their components, in declaration order.
+#[inline]
+pub fn as_scalars<T: ScalarPod>(values: &[T]) -> &[Scalar] {
+    bytemuck::cast_slice(values)
+}
+
+/// Views `values` as their components, mutably.
+#[inline]
+pub fn as_scalars_mut<T: ScalarPod>(values: &mut [T]) -> &mut [Scalar] {
+    bytemuck::cast_slice_mut(values)
+}
+
+/// Views flat components as values, failing if their count isn't a multiple of the
+/// components per value.
+#[inline]
+pub fn try_from_scalars<T: ScalarPod>(scalars: &[Scalar]) -> Result<&[T], PodCastError> {
+    bytemuck::try_cast_slice(scalars)
+}
+
+/// Views `values` as raw bytes in native endianness.
+#[inline]
+pub fn as_bytes<T: ScalarPod>(values: &[T]) -> &[u8] {
+    bytemuck::cast_slice(values)
+}
+
+/// Views raw bytes in native endianness as values, failing if `bytes` is misaligned or its
+/// length isn't a multiple of the value size.
+#[inline]
+pub fn try_from_bytes<T: ScalarPod>(bytes: &[u8]) -> Result<&[T], PodCastError> {
+    bytemuck::try_cast_slice(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casts_round_trip_without_copying() {
+        let mut rotations = [Quat::IDENTITY, Quat::new(0.0, 1.0, 0.0, 0.0)];
+        as_scalars_mut(&mut rotations)[4] = 0.5;
+        assert_eq!(rotations[1].x, 0.5);
+        let bytes = as_bytes(&rotations);
+        assert_eq!(bytes.as_ptr(), rotations.as_ptr().cast());
+        assert_eq!(try_from_bytes::<Quat>(bytes), Ok(&rotations[..]));
+        let planar: &[Vec2] = try_from_scalars(as_scalars(&rotations)).unwrap();
+        assert_eq!(planar[1], Vec2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn mismatched_lengths_are_rejected() {
+        let scalars = [1.0 as Scalar; 5];
+        assert_eq!(
+            try_from_scalars::<Vec3>(&scalars),
+            Err(PodCastError::OutputSliceWouldHaveSlop)
+        );
+        assert!(try_from_bytes::<Vec4>(&as_bytes(&[Vec4::ZERO])[1..]).is_err());
+    }
+}
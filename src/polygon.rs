@@ -0,0 +1,649 @@
+use crate::{Circle, Scalar, Vec2, float, float_cmp};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Orientation of a polygon's vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    /// Vertices turn counter-clockwise (positive signed area).
+    CounterClockwise,
+    /// Vertices turn clockwise (negative signed area).
+    Clockwise,
+    /// The polygon has no area.
+    Degenerate,
+}
+
+/// Rule deciding which points are inside a (possibly self-intersecting) polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside if a ray from the point crosses the boundary an odd number of times.
+    EvenOdd,
+    /// Inside if the boundary winds around the point a non-zero number of times.
+    NonZero,
+}
+
+/// Contact between a circle and a polygon boundary, as returned by [`Circle::contact_polygon`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolygonContact {
+    /// Minimum translation that moves the circle out of the polygon.
+    pub mtv: Vec2,
+    /// Point on the polygon boundary closest to the circle's center.
+    pub point: Vec2,
+}
+
+/// A simple 2D polygon stored as a closed loop of vertices.
+///
+/// The last vertex connects back to the first; do not repeat the first vertex at the end.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{FillRule, Polygon2, Vec2};
+///
+/// let square = Polygon2::new(vec![
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(2.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+/// ]);
+/// assert_eq!(square.area(), 4.0);
+/// assert!(square.contains_point(Vec2::new(1.0, 1.0), FillRule::NonZero));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygon2 {
+    /// Boundary vertices in order.
+    pub vertices: Vec<Vec2>,
+}
+
+impl Polygon2 {
+    #[inline(always)]
+    pub const fn new(vertices: Vec<Vec2>) -> Self {
+        Polygon2 { vertices }
+    }
+
+    /// Returns an iterator over the boundary edges as `(start, end)` pairs.
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Returns the signed area: positive for counter-clockwise, negative for clockwise winding.
+    pub fn signed_area(&self) -> Scalar {
+        let Some(&origin) = self.vertices.first() else {
+            return 0.0;
+        };
+        // Accumulate relative to the first vertex to keep precision for far-away polygons.
+        let twice_area: Scalar = self
+            .edges()
+            .map(|(a, b)| (a - origin).perp_dot(b - origin))
+            .sum();
+        twice_area * 0.5
+    }
+
+    /// Returns the unsigned area.
+    pub fn area(&self) -> Scalar {
+        self.signed_area().abs()
+    }
+
+    /// Returns the total length of the boundary.
+    pub fn perimeter(&self) -> Scalar {
+        self.edges().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    /// Returns the area centroid, or `None` if the polygon has no area.
+    pub fn centroid(&self) -> Option<Vec2> {
+        let origin = *self.vertices.first()?;
+        let mut twice_area = 0.0;
+        let mut weighted = Vec2::ZERO;
+        let (mut min, mut max) = (Vec2::ZERO, Vec2::ZERO);
+        for (a, b) in self.edges() {
+            let (a, b) = (a - origin, b - origin);
+            let cross = a.perp_dot(b);
+            twice_area += cross;
+            weighted += (a + b) * cross;
+            min = min.min(a);
+            max = max.max(a);
+        }
+        // The area scales with the square of the size, so the threshold does too.
+        let extent = max - min;
+        let extent = extent.x.max(extent.y);
+        if float_cmp::abs_eq(twice_area, 0.0, Scalar::EPSILON * extent * extent) {
+            return None;
+        }
+        Some(origin + weighted / (3.0 * twice_area))
+    }
+
+    /// Returns the orientation of the vertices.
+    pub fn winding(&self) -> Winding {
+        let area = self.signed_area();
+        if area > 0.0 {
+            Winding::CounterClockwise
+        } else if area < 0.0 {
+            Winding::Clockwise
+        } else {
+            Winding::Degenerate
+        }
+    }
+
+    /// Reverses the vertex order, flipping the winding.
+    pub fn reverse(&mut self) {
+        self.vertices.reverse();
+    }
+
+    /// Reorders the vertices so the polygon has the given `winding`.
+    ///
+    /// Degenerate polygons, or a `Winding::Degenerate` target, are left untouched.
+    pub fn set_winding(&mut self, winding: Winding) {
+        let current = self.winding();
+        if current != Winding::Degenerate && winding != Winding::Degenerate && current != winding {
+            self.reverse();
+        }
+    }
+
+    /// Returns the number of times the boundary winds counter-clockwise around `point`.
+    ///
+    /// Clockwise turns count negatively; points on the boundary may report either side.
+    pub fn winding_number(&self, point: Vec2) -> i32 {
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            let side = (b - a).perp_dot(point - a);
+            if a.y <= point.y {
+                if b.y > point.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// Returns `true` if `point` is inside the polygon under the given fill `rule`.
+    pub fn contains_point(&self, point: Vec2, rule: FillRule) -> bool {
+        match rule {
+            FillRule::NonZero => self.winding_number(point) != 0,
+            FillRule::EvenOdd => {
+                let mut inside = false;
+                for (a, b) in self.edges() {
+                    if (a.y > point.y) != (b.y > point.y) {
+                        let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                        if point.x < x {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+        }
+    }
+}
+
+/// A convex 2D polygon with counter-clockwise vertices.
+///
+/// Supports the support-mapping and separating-axis queries used for hull-vs-hull collision.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{ConvexPolygon2, Vec2};
+///
+/// let a = ConvexPolygon2::convex_hull(&[
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(1.0, 0.5),
+///     Vec2::new(2.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+/// ]);
+/// assert_eq!(a.vertices.len(), 4);
+/// assert_eq!(a.support(Vec2::new(1.0, 1.0)), Vec2::new(2.0, 2.0));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvexPolygon2 {
+    /// Boundary vertices in counter-clockwise order.
+    pub vertices: Vec<Vec2>,
+}
+
+impl ConvexPolygon2 {
+    /// Creates a polygon from vertices that are already convex and counter-clockwise.
+    ///
+    /// This is not checked; use [`ConvexPolygon2::convex_hull`] for arbitrary points.
+    #[inline(always)]
+    pub const fn new(vertices: Vec<Vec2>) -> Self {
+        ConvexPolygon2 { vertices }
+    }
+
+    /// Builds the convex hull of `points`, dropping interior and collinear points.
+    pub fn convex_hull(points: &[Vec2]) -> Self {
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        sorted.dedup();
+        if sorted.len() < 3 {
+            return ConvexPolygon2::new(sorted);
+        }
+
+        // Andrew's monotone chain: build the lower hull, then the upper hull.
+        let mut hull = Vec::with_capacity(sorted.len() + 1);
+        push_half_hull(&mut hull, sorted.iter().copied());
+        push_half_hull(&mut hull, sorted.iter().rev().copied());
+        ConvexPolygon2::new(hull)
+    }
+
+    /// Returns an iterator over the boundary edges as `(start, end)` pairs.
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Returns the vertex furthest along `direction`.
+    ///
+    /// Returns `Vec2::ZERO` for an empty polygon.
+    pub fn support(&self, direction: Vec2) -> Vec2 {
+        self.vertices
+            .iter()
+            .copied()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .unwrap_or(Vec2::ZERO)
+    }
+
+    /// Returns the average of the vertices.
+    pub fn center(&self) -> Vec2 {
+        if self.vertices.is_empty() {
+            return Vec2::ZERO;
+        }
+        let sum = self.vertices.iter().fold(Vec2::ZERO, |acc, &v| acc + v);
+        sum / self.vertices.len() as Scalar
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of the polygon.
+    ///
+    /// A polygon without area, such as a single point or collinear vertices, contains
+    /// nothing; [`ConvexPolygon2::closest_point`] still measures against its boundary.
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let Some(&origin) = self.vertices.first() else {
+            return false;
+        };
+        let twice_area: Scalar = self
+            .edges()
+            .map(|(a, b)| (a - origin).perp_dot(b - origin))
+            .sum();
+        twice_area > 0.0
+            && self
+                .edges()
+                .all(|(a, b)| (b - a).perp_dot(point - a) >= 0.0)
+    }
+
+    /// Returns the point on the boundary closest to `point`, with its squared distance.
+    fn closest_boundary_point(&self, point: Vec2) -> (Vec2, Scalar) {
+        self.edges()
+            .map(|(a, b)| {
+                let c = closest_point_on_segment(a, b, point);
+                (c, c.distance_squared(point))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((Vec2::ZERO, Scalar::INFINITY))
+    }
+
+    /// Returns the point inside or on the polygon closest to `point`.
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        if self.contains_point(point) {
+            point
+        } else {
+            self.closest_boundary_point(point).0
+        }
+    }
+
+    /// Returns the distance from the polygon to `point`, or `0.0` if it is inside.
+    pub fn distance_to_point(&self, point: Vec2) -> Scalar {
+        self.closest_point(point).distance(point)
+    }
+
+    /// Returns the minimum translation vector that moves this polygon out of `other`,
+    /// or `None` if they do not overlap.
+    ///
+    /// Uses the separating axis theorem over the edge normals of both polygons.
+    pub fn mtv_convex(&self, other: &ConvexPolygon2) -> Option<Vec2> {
+        let mut best_axis = Vec2::ZERO;
+        let mut best_depth = Scalar::INFINITY;
+        for (a, b) in self.edges().chain(other.edges()) {
+            let Some(axis) = (b - a).perp().try_normalize(Scalar::EPSILON) else {
+                continue;
+            };
+            let (min_a, max_a) = project(&self.vertices, axis);
+            let (min_b, max_b) = project(&other.vertices, axis);
+            let depth = (max_a - min_b).min(max_b - min_a);
+            if depth < 0.0 {
+                return None;
+            }
+            if depth < best_depth {
+                best_depth = depth;
+                best_axis = axis;
+            }
+        }
+        if best_depth == Scalar::INFINITY {
+            return None;
+        }
+        if best_axis.dot(self.center() - other.center()) < 0.0 {
+            best_axis = -best_axis;
+        }
+        Some(best_axis * best_depth)
+    }
+
+    /// Returns `true` if this polygon and `other` overlap (touching counts).
+    pub fn intersects_convex(&self, other: &ConvexPolygon2) -> bool {
+        self.mtv_convex(other).is_some()
+    }
+
+    /// Returns the minimum translation vector that moves this polygon out of `circle`,
+    /// or `None` if they do not overlap.
+    pub fn mtv_circle(&self, circle: &Circle) -> Option<Vec2> {
+        circle_polygon_mtv(self.edges(), self.contains_point(circle.center), circle)
+            .map(|(mtv, _)| -mtv)
+    }
+
+    /// Returns `true` if this polygon and `circle` overlap (touching counts).
+    pub fn intersects_circle(&self, circle: &Circle) -> bool {
+        self.contains_point(circle.center)
+            || self.closest_boundary_point(circle.center).1 <= circle.radius * circle.radius
+    }
+
+    /// Returns the distance between this polygon and `other`, or `0.0` if they overlap.
+    pub fn distance_to_convex(&self, other: &ConvexPolygon2) -> Scalar {
+        if self.intersects_convex(other) {
+            return 0.0;
+        }
+        // For disjoint convex polygons the closest pair always involves a vertex of one of them.
+        let a_to_b = self
+            .vertices
+            .iter()
+            .map(|&v| other.closest_boundary_point(v).1);
+        let b_to_a = other
+            .vertices
+            .iter()
+            .map(|&v| self.closest_boundary_point(v).1);
+        float::sqrt(a_to_b.chain(b_to_a).fold(Scalar::INFINITY, Scalar::min))
+    }
+
+    /// Returns the distance between this polygon and `circle`, or `0.0` if they overlap.
+    pub fn distance_to_circle(&self, circle: &Circle) -> Scalar {
+        (self.distance_to_point(circle.center) - circle.radius).max(0.0)
+    }
+}
+
+impl Circle {
+    /// Returns how to push this circle out of `polygon`, or `None` if they do not overlap.
+    ///
+    /// `rule` decides whether the center is inside a self-intersecting polygon. The circle is
+    /// resolved against the nearest boundary edge only, so in narrow concave corners the pushed
+    /// circle may still touch another edge and the query should be repeated.
+    pub fn contact_polygon(&self, polygon: &Polygon2, rule: FillRule) -> Option<PolygonContact> {
+        let inside = polygon.contains_point(self.center, rule);
+        circle_polygon_mtv(polygon.edges(), inside, self)
+            .map(|(mtv, point)| PolygonContact { mtv, point })
+    }
+
+    /// Returns `true` if this circle and `polygon` overlap (touching counts).
+    pub fn intersects_polygon(&self, polygon: &Polygon2, rule: FillRule) -> bool {
+        self.contact_polygon(polygon, rule).is_some()
+    }
+}
+
+/// Appends one monotone chain of a convex hull, dropping its last point (the next chain's first).
+fn push_half_hull(hull: &mut Vec<Vec2>, points: impl Iterator<Item = Vec2>) {
+    let start = hull.len();
+    for p in points {
+        while hull.len() >= start + 2 {
+            let a = hull[hull.len() - 2];
+            let b = hull[hull.len() - 1];
+            if (b - a).perp_dot(p - a) > 0.0 {
+                break;
+            }
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+}
+
+/// Projects `points` onto `axis`, returning the `(min, max)` interval.
+fn project(points: &[Vec2], axis: Vec2) -> (Scalar, Scalar) {
+    points
+        .iter()
+        .fold((Scalar::INFINITY, Scalar::NEG_INFINITY), |(lo, hi), p| {
+            let d = p.dot(axis);
+            (lo.min(d), hi.max(d))
+        })
+}
+
+/// Returns the point on segment `a..b` closest to `point`.
+pub(crate) fn closest_point_on_segment(a: Vec2, b: Vec2, point: Vec2) -> Vec2 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq <= 0.0 {
+        return a;
+    }
+    let t = ((point - a).dot(ab) / len_sq).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// Shared circle-vs-boundary resolution: returns the vector that moves `circle` out of the
+/// polygon described by `edges`, given whether its center lies inside the polygon, along with
+/// the closest boundary point.
+pub(crate) fn circle_polygon_mtv(
+    edges: impl Iterator<Item = (Vec2, Vec2)>,
+    center_inside: bool,
+    circle: &Circle,
+) -> Option<(Vec2, Vec2)> {
+    let (closest, dist_sq) = edges
+        .map(|(a, b)| {
+            let c = closest_point_on_segment(a, b, circle.center);
+            (c, c.distance_squared(circle.center))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+    let dist = float::sqrt(dist_sq);
+    if center_inside {
+        // Push the circle back across the nearest edge and then clear its radius.
+        let outward = (closest - circle.center)
+            .try_normalize(Scalar::EPSILON)
+            .unwrap_or(Vec2::ZERO);
+        Some((outward * (dist + circle.radius), closest))
+    } else if dist <= circle.radius {
+        let away = (circle.center - closest)
+            .try_normalize(Scalar::EPSILON)
+            .unwrap_or(Vec2::ZERO);
+        Some((away * (circle.radius - dist), closest))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Polygon2 {
+        Polygon2::new(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ])
+    }
+
+    /// A pentagram traced in one stroke, whose center is wound twice.
+    fn star() -> Polygon2 {
+        Polygon2::new(vec![
+            Vec2::new(0.0, 3.0),
+            Vec2::new(1.8, -2.4),
+            Vec2::new(-2.9, 1.0),
+            Vec2::new(2.9, 1.0),
+            Vec2::new(-1.8, -2.4),
+        ])
+    }
+
+    #[test]
+    fn signed_area_depends_on_winding() {
+        let mut p = square();
+        assert_eq!(p.signed_area(), 4.0 as Scalar);
+        assert_eq!(p.winding(), Winding::CounterClockwise);
+        p.reverse();
+        assert_eq!(p.signed_area(), -4.0 as Scalar);
+        assert_eq!(p.winding(), Winding::Clockwise);
+    }
+
+    #[test]
+    fn set_winding_normalizes_order() {
+        let mut p = square();
+        p.reverse();
+        p.set_winding(Winding::CounterClockwise);
+        assert_eq!(p, square());
+    }
+
+    #[test]
+    fn perimeter_and_centroid() {
+        let p = square();
+        assert_eq!(p.perimeter(), 8.0 as Scalar);
+        assert_eq!(p.centroid(), Some(Vec2::new(1.0, 1.0)));
+        let line = Polygon2::new(vec![Vec2::ZERO, Vec2::ONE]);
+        assert_eq!(line.centroid(), None);
+        assert_eq!(line.winding(), Winding::Degenerate);
+
+        let tiny = Polygon2::new(square().vertices.iter().map(|&v| v * 0.5e-4).collect());
+        let centroid = tiny.centroid().unwrap();
+        assert!(centroid.distance(Vec2::splat(0.5e-4)) <= 1.0e-9 as Scalar);
+        let tiny_line = Polygon2::new(vec![Vec2::ZERO, Vec2::splat(1.0e-4)]);
+        assert_eq!(tiny_line.centroid(), None);
+    }
+
+    #[test]
+    fn fill_rules_differ_for_self_intersecting_polygons() {
+        let p = star();
+        assert_eq!(p.winding_number(Vec2::ZERO).abs(), 2);
+        assert!(p.contains_point(Vec2::ZERO, FillRule::NonZero));
+        assert!(!p.contains_point(Vec2::ZERO, FillRule::EvenOdd));
+        assert!(!p.contains_point(Vec2::new(5.0, 0.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn contains_point_for_simple_polygon() {
+        let p = square();
+        for rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            assert!(p.contains_point(Vec2::new(0.5, 1.5), rule));
+            assert!(!p.contains_point(Vec2::new(-0.5, 1.5), rule));
+            assert!(!p.contains_point(Vec2::new(0.5, 2.5), rule));
+        }
+    }
+
+    fn unit_square_at(offset: Vec2) -> ConvexPolygon2 {
+        ConvexPolygon2::new(vec![
+            offset,
+            offset + Vec2::new(1.0, 0.0),
+            offset + Vec2::new(1.0, 1.0),
+            offset + Vec2::new(0.0, 1.0),
+        ])
+    }
+
+    #[test]
+    fn convex_hull_drops_interior_and_collinear_points() {
+        let hull = ConvexPolygon2::convex_hull(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ]);
+        assert_eq!(
+            hull.vertices,
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(2.0, 2.0),
+                Vec2::new(0.0, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn degenerate_convex_polygons_contain_nothing() {
+        let line = ConvexPolygon2::convex_hull(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 2.0),
+        ]);
+        let on_line = Vec2::new(5.0, 5.0);
+        assert!(!line.contains_point(on_line));
+        assert_eq!(line.closest_point(on_line), Vec2::new(2.0, 2.0));
+        let point = ConvexPolygon2::new(vec![Vec2::ONE]);
+        assert!(!point.contains_point(Vec2::new(3.0, -4.0)));
+        assert!(unit_square_at(Vec2::ZERO).contains_point(Vec2::ZERO));
+    }
+
+    #[test]
+    fn mtv_convex_pushes_along_shallowest_axis() {
+        let a = unit_square_at(Vec2::ZERO);
+        let b = unit_square_at(Vec2::new(0.75, 0.1));
+        let mtv = a.mtv_convex(&b).expect("expected overlap");
+        assert_eq!(mtv, Vec2::new(-0.25, 0.0));
+        assert!(!a.intersects_convex(&unit_square_at(Vec2::new(1.5, 0.0))));
+    }
+
+    #[test]
+    fn distance_to_convex_between_separated_squares() {
+        let a = unit_square_at(Vec2::ZERO);
+        let b = unit_square_at(Vec2::new(4.0, 5.0));
+        assert_eq!(a.distance_to_convex(&b), 5.0 as Scalar);
+        assert_eq!(
+            a.distance_to_convex(&unit_square_at(Vec2::splat(0.5))),
+            0.0 as Scalar
+        );
+    }
+
+    #[test]
+    fn circle_queries() {
+        let a = unit_square_at(Vec2::ZERO);
+        let touching = Circle::new(Vec2::new(1.5, 0.5), 0.75);
+        let mtv = a.mtv_circle(&touching).expect("expected overlap");
+        assert!((mtv - Vec2::new(-0.25, 0.0)).length() <= 1.0e-5 as Scalar);
+        assert!(!a.intersects_circle(&Circle::new(Vec2::new(2.0, 2.0), 1.0)));
+        assert!(
+            (a.distance_to_circle(&Circle::new(Vec2::new(4.0, 0.5), 1.0)) - 2.0 as Scalar).abs()
+                <= 1.0e-5 as Scalar
+        );
+        let inside = Circle::new(Vec2::new(0.5, 0.25), 0.1);
+        let mtv = a.mtv_circle(&inside).expect("expected overlap");
+        assert!((mtv - Vec2::new(0.0, 0.35)).length() <= 1.0e-5 as Scalar);
+    }
+
+    #[test]
+    fn circle_contact_with_concave_polygon() {
+        // An L-shaped zone: the notch at the top right is outside.
+        let l_shape = Polygon2::new(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 2.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(2.0, 4.0),
+            Vec2::new(0.0, 4.0),
+        ]);
+        let in_notch = Circle::new(Vec2::new(3.0, 2.25), 0.5);
+        let contact = in_notch
+            .contact_polygon(&l_shape, FillRule::NonZero)
+            .expect("expected overlap");
+        assert!((contact.mtv - Vec2::new(0.0, 0.25)).length() <= 1.0e-5 as Scalar);
+        assert_eq!(contact.point, Vec2::new(3.0, 2.0));
+
+        let inside = Circle::new(Vec2::new(1.0, 3.75), 0.5);
+        let contact = inside
+            .contact_polygon(&l_shape, FillRule::EvenOdd)
+            .expect("expected overlap");
+        assert!((contact.mtv - Vec2::new(0.0, 0.75)).length() <= 1.0e-5 as Scalar);
+        assert_eq!(contact.point, Vec2::new(1.0, 4.0));
+
+        assert!(
+            !Circle::new(Vec2::new(3.0, 3.0), 0.5).intersects_polygon(&l_shape, FillRule::NonZero)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{Scalar, Vec2, Vec3, float};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// An open 2D path through a sequence of points.
+///
+/// Distances along the path are measured in world units from the first point. Queries walk the
+/// segments, so they are linear in the number of points.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Polyline2, Vec2};
+///
+/// let route = Polyline2::new(vec![Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(4.0, 3.0)]);
+/// assert_eq!(route.length(), 7.0);
+/// assert_eq!(route.sample_at_distance(5.0), Some(Vec2::new(4.0, 1.0)));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polyline2 {
+    /// Points along the path, in travel order.
+    pub points: Vec<Vec2>,
+}
+
+impl Polyline2 {
+    #[inline(always)]
+    pub const fn new(points: Vec<Vec2>) -> Self {
+        Polyline2 { points }
+    }
+
+    /// Returns an iterator over the segments as `(start, end)` pairs.
+    pub fn segments(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.points.windows(2).map(|w| (w[0], w[1]))
+    }
+
+    /// Returns the total length of the path.
+    pub fn length(&self) -> Scalar {
+        self.segments().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    /// Returns the segment containing `distance` and the parameter within it.
+    ///
+    /// `distance` is clamped to the path. Zero-length segments are skipped.
+    fn locate(&self, distance: Scalar) -> Option<(Vec2, Vec2, Scalar)> {
+        let mut remaining = distance.max(0.0);
+        let mut last = None;
+        for (a, b) in self.segments() {
+            let len = a.distance(b);
+            if len <= 0.0 {
+                continue;
+            }
+            if remaining <= len {
+                return Some((a, b, remaining / len));
+            }
+            remaining -= len;
+            last = Some((a, b, 1.0));
+        }
+        last
+    }
+
+    /// Returns the point `distance` along the path, clamped to its endpoints.
+    ///
+    /// Returns `None` if the path has no points.
+    pub fn sample_at_distance(&self, distance: Scalar) -> Option<Vec2> {
+        match self.locate(distance) {
+            Some((a, b, t)) => Some(a.lerp(b, t)),
+            None => self.points.first().copied(),
+        }
+    }
+
+    /// Returns the unit travel direction `distance` along the path.
+    ///
+    /// Returns `None` if the path has no segment with a non-zero length.
+    pub fn direction_at_distance(&self, distance: Scalar) -> Option<Vec2> {
+        let (a, b, _) = self.locate(distance)?;
+        (b - a).try_normalize(0.0)
+    }
+
+    /// Returns the point on the path closest to `point` and its distance along the path.
+    ///
+    /// Returns `None` if the path has no points.
+    pub fn closest_point(&self, point: Vec2) -> Option<(Vec2, Scalar)> {
+        let first = *self.points.first()?;
+        let mut best = (first, 0.0, first.distance_squared(point));
+        let mut travelled = 0.0;
+        for (a, b) in self.segments() {
+            let ab = b - a;
+            let len_sq = ab.length_squared();
+            let t = if len_sq > 0.0 {
+                ((point - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let candidate = a + ab * t;
+            let dist_sq = candidate.distance_squared(point);
+            let len = float::sqrt(len_sq);
+            if dist_sq < best.2 {
+                best = (candidate, travelled + len * t, dist_sq);
+            }
+            travelled += len;
+        }
+        Some((best.0, best.1))
+    }
+}
+
+/// An open 3D path through a sequence of points.
+///
+/// Distances along the path are measured in world units from the first point. Queries walk the
+/// segments, so they are linear in the number of points.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Polyline3, Vec3};
+///
+/// let rail = Polyline3::new(vec![Vec3::ZERO, Vec3::new(0.0, 0.0, -10.0)]);
+/// assert_eq!(rail.sample_at_distance(2.5), Some(Vec3::new(0.0, 0.0, -2.5)));
+/// assert_eq!(rail.direction_at_distance(2.5), Some(Vec3::new(0.0, 0.0, -1.0)));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polyline3 {
+    /// Points along the path, in travel order.
+    pub points: Vec<Vec3>,
+}
+
+impl Polyline3 {
+    #[inline(always)]
+    pub const fn new(points: Vec<Vec3>) -> Self {
+        Polyline3 { points }
+    }
+
+    /// Returns an iterator over the segments as `(start, end)` pairs.
+    pub fn segments(&self) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+        self.points.windows(2).map(|w| (w[0], w[1]))
+    }
+
+    /// Returns the total length of the path.
+    pub fn length(&self) -> Scalar {
+        self.segments().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    /// Returns the segment containing `distance` and the parameter within it.
+    ///
+    /// `distance` is clamped to the path. Zero-length segments are skipped.
+    fn locate(&self, distance: Scalar) -> Option<(Vec3, Vec3, Scalar)> {
+        let mut remaining = distance.max(0.0);
+        let mut last = None;
+        for (a, b) in self.segments() {
+            let len = a.distance(b);
+            if len <= 0.0 {
+                continue;
+            }
+            if remaining <= len {
+                return Some((a, b, remaining / len));
+            }
+            remaining -= len;
+            last = Some((a, b, 1.0));
+        }
+        last
+    }
+
+    /// Returns the point `distance` along the path, clamped to its endpoints.
+    ///
+    /// Returns `None` if the path has no points.
+    pub fn sample_at_distance(&self, distance: Scalar) -> Option<Vec3> {
+        match self.locate(distance) {
+            Some((a, b, t)) => Some(a.lerp(b, t)),
+            None => self.points.first().copied(),
+        }
+    }
+
+    /// Returns the unit travel direction `distance` along the path.
+    ///
+    /// Returns `None` if the path has no segment with a non-zero length.
+    pub fn direction_at_distance(&self, distance: Scalar) -> Option<Vec3> {
+        let (a, b, _) = self.locate(distance)?;
+        (b - a).try_normalize(0.0)
+    }
+
+    /// Returns the point on the path closest to `point` and its distance along the path.
+    ///
+    /// Returns `None` if the path has no points.
+    pub fn closest_point(&self, point: Vec3) -> Option<(Vec3, Scalar)> {
+        let first = *self.points.first()?;
+        let mut best = (first, 0.0, first.distance_squared(point));
+        let mut travelled = 0.0;
+        for (a, b) in self.segments() {
+            let ab = b - a;
+            let len_sq = ab.length_squared();
+            let t = if len_sq > 0.0 {
+                ((point - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let candidate = a + ab * t;
+            let dist_sq = candidate.distance_squared(point);
+            let len = float::sqrt(len_sq);
+            if dist_sq < best.2 {
+                best = (candidate, travelled + len * t, dist_sq);
+            }
+            travelled += len;
+        }
+        Some((best.0, best.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l_shape() -> Polyline2 {
+        Polyline2::new(vec![
+            Vec2::ZERO,
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 3.0),
+        ])
+    }
+
+    #[test]
+    fn polyline2_sample_clamps_to_endpoints() {
+        let p = l_shape();
+        assert_eq!(p.length(), 7.0 as Scalar);
+        assert_eq!(p.sample_at_distance(-1.0), Some(Vec2::ZERO));
+        assert_eq!(p.sample_at_distance(2.0), Some(Vec2::new(2.0, 0.0)));
+        assert_eq!(p.sample_at_distance(100.0), Some(Vec2::new(4.0, 3.0)));
+        assert_eq!(Polyline2::default().sample_at_distance(1.0), None);
+    }
+
+    #[test]
+    fn polyline2_direction_skips_zero_length_segments() {
+        let p = l_shape();
+        assert_eq!(p.direction_at_distance(1.0), Some(Vec2::new(1.0, 0.0)));
+        assert_eq!(p.direction_at_distance(5.0), Some(Vec2::new(0.0, 1.0)));
+        let single = Polyline2::new(vec![Vec2::ONE]);
+        assert_eq!(single.sample_at_distance(3.0), Some(Vec2::ONE));
+        assert_eq!(single.direction_at_distance(3.0), None);
+    }
+
+    #[test]
+    fn polyline2_closest_point_reports_distance_along() {
+        let p = l_shape();
+        let (point, along) = p
+            .closest_point(Vec2::new(6.0, 2.0))
+            .expect("expected point");
+        assert_eq!(point, Vec2::new(4.0, 2.0));
+        assert_eq!(along, 6.0 as Scalar);
+    }
+
+    #[test]
+    fn polyline3_samples_and_projects() {
+        let p = Polyline3::new(vec![
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(0.0, 2.0, 2.0),
+        ]);
+        assert_eq!(p.length(), 4.0 as Scalar);
+        assert_eq!(p.sample_at_distance(3.0), Some(Vec3::new(0.0, 1.0, 2.0)));
+        assert_eq!(p.direction_at_distance(3.0), Some(Vec3::new(0.0, 1.0, 0.0)));
+        let (point, along) = p
+            .closest_point(Vec3::new(5.0, 0.5, 1.0))
+            .expect("expected point");
+        assert_eq!(point, Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(along, 1.0 as Scalar);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{IVec2, IVec3, Scalar, Vec2, Vec3, float};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Steps per world unit used by the plain conversions: millimeters for meter-based worlds.
+pub const QPOS_STEPS_PER_UNIT: Scalar = 1000.0;
+
+/// A 3D position quantized to a fixed grid of `1 / steps_per_unit` world units, stored as
+/// integers.
+///
+/// Integer columns are exact, hashable and indexable, and take 12 bytes instead of the 12 or
+/// 24 of a [`Vec3`] without losing precision far from the origin. The plain conversions use
+/// [`QPOS_STEPS_PER_UNIT`] (millimeters); the `_with_scale` variants take any scale, which
+/// must then be used consistently for the column.
+///
+/// Converting to a vector and back at millimeter scale is lossless while every coordinate is
+/// within ±2²² steps (about ±4 km) with `f32`, and across the whole `i32` range with `f64`.
+/// Beyond that, `f32` rounding can move a coordinate by a step.
+/// Converting a vector rounds to the nearest step and saturates at the `i32` limits.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{QPos3, Vec3};
+///
+/// let spawn = QPos3::from_vec3(Vec3::new(12.5, 0.0, -3.25));
+/// assert_eq!(spawn, QPos3::new(12_500, 0, -3_250));
+/// assert_eq!(spawn.to_vec3(), Vec3::new(12.5, 0.0, -3.25));
+/// assert_eq!(QPos3::from_vec3(spawn.to_vec3()), spawn);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QPos3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// A 2D position quantized to a fixed grid. See [`QPos3`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QPos2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl QPos3 {
+    pub const ZERO: QPos3 = QPos3::new(0, 0, 0);
+
+    #[inline(always)]
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        QPos3 { x, y, z }
+    }
+
+    /// Quantizes `v` to millimeters.
+    #[inline]
+    pub fn from_vec3(v: Vec3) -> Self {
+        Self::from_vec3_with_scale(v, QPOS_STEPS_PER_UNIT)
+    }
+
+    /// Returns the position in world units, reading the steps as millimeters.
+    #[inline]
+    pub fn to_vec3(&self) -> Vec3 {
+        self.to_vec3_with_scale(QPOS_STEPS_PER_UNIT)
+    }
+
+    /// Quantizes `v` to steps of `1 / steps_per_unit` world units.
+    #[inline]
+    pub fn from_vec3_with_scale(v: Vec3, steps_per_unit: Scalar) -> Self {
+        QPos3::new(
+            quantize(v.x, steps_per_unit),
+            quantize(v.y, steps_per_unit),
+            quantize(v.z, steps_per_unit),
+        )
+    }
+
+    /// Returns the position in world units for steps of `1 / steps_per_unit` world units.
+    #[inline]
+    pub fn to_vec3_with_scale(&self, steps_per_unit: Scalar) -> Vec3 {
+        Vec3::new(
+            self.x as Scalar / steps_per_unit,
+            self.y as Scalar / steps_per_unit,
+            self.z as Scalar / steps_per_unit,
+        )
+    }
+
+    /// Returns the raw step counts.
+    #[inline]
+    pub const fn as_ivec3(&self) -> IVec3 {
+        IVec3::new(self.x, self.y, self.z)
+    }
+
+    /// Drops the Y component, for top-down positions.
+    #[inline]
+    pub const fn xz(&self) -> QPos2 {
+        QPos2::new(self.x, self.z)
+    }
+}
+
+impl QPos2 {
+    pub const ZERO: QPos2 = QPos2::new(0, 0);
+
+    #[inline(always)]
+    pub const fn new(x: i32, y: i32) -> Self {
+        QPos2 { x, y }
+    }
+
+    /// Quantizes `v` to millimeters.
+    #[inline]
+    pub fn from_vec2(v: Vec2) -> Self {
+        Self::from_vec2_with_scale(v, QPOS_STEPS_PER_UNIT)
+    }
+
+    /// Returns the position in world units, reading the steps as millimeters.
+    #[inline]
+    pub fn to_vec2(&self) -> Vec2 {
+        self.to_vec2_with_scale(QPOS_STEPS_PER_UNIT)
+    }
+
+    /// Quantizes `v` to steps of `1 / steps_per_unit` world units.
+    #[inline]
+    pub fn from_vec2_with_scale(v: Vec2, steps_per_unit: Scalar) -> Self {
+        QPos2::new(quantize(v.x, steps_per_unit), quantize(v.y, steps_per_unit))
+    }
+
+    /// Returns the position in world units for steps of `1 / steps_per_unit` world units.
+    #[inline]
+    pub fn to_vec2_with_scale(&self, steps_per_unit: Scalar) -> Vec2 {
+        Vec2::new(
+            self.x as Scalar / steps_per_unit,
+            self.y as Scalar / steps_per_unit,
+        )
+    }
+
+    /// Returns the raw step counts.
+    #[inline]
+    pub const fn as_ivec2(&self) -> IVec2 {
+        IVec2::new(self.x, self.y)
+    }
+}
+
+#[inline]
+fn quantize(value: Scalar, steps_per_unit: Scalar) -> i32 {
+    float::round(value * steps_per_unit) as i32
+}
+
+impl From<Vec3> for QPos3 {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        QPos3::from_vec3(v)
+    }
+}
+
+impl From<QPos3> for Vec3 {
+    #[inline]
+    fn from(p: QPos3) -> Self {
+        p.to_vec3()
+    }
+}
+
+impl From<Vec2> for QPos2 {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        QPos2::from_vec2(v)
+    }
+}
+
+impl From<QPos2> for Vec2 {
+    #[inline]
+    fn from(p: QPos2) -> Self {
+        p.to_vec2()
+    }
+}
+
+impl Add for QPos3 {
+    type Output = QPos3;
+
+    #[inline]
+    fn add(self, rhs: QPos3) -> QPos3 {
+        QPos3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for QPos3 {
+    #[inline]
+    fn add_assign(&mut self, rhs: QPos3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for QPos3 {
+    type Output = QPos3;
+
+    #[inline]
+    fn sub(self, rhs: QPos3) -> QPos3 {
+        QPos3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for QPos3 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: QPos3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add for QPos2 {
+    type Output = QPos2;
+
+    #[inline]
+    fn add(self, rhs: QPos2) -> QPos2 {
+        QPos2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for QPos2 {
+    #[inline]
+    fn add_assign(&mut self, rhs: QPos2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for QPos2 {
+    type Output = QPos2;
+
+    #[inline]
+    fn sub(self, rhs: QPos2) -> QPos2 {
+        QPos2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for QPos2 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: QPos2) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantizes_to_the_nearest_step() {
+        let p = QPos3::from_vec3(Vec3::new(1.0004, -1.0006, 0.0));
+        assert_eq!(p, QPos3::new(1000, -1001, 0));
+        let coarse = QPos2::from_vec2_with_scale(Vec2::new(2.3, -0.74), 2.0);
+        assert_eq!(coarse, QPos2::new(5, -1));
+        assert_eq!(coarse.to_vec2_with_scale(2.0), Vec2::new(2.5, -0.5));
+        // Out-of-range values saturate instead of wrapping.
+        assert_eq!(QPos3::from_vec3(Vec3::splat(1.0e9)).x, i32::MAX);
+    }
+
+    #[test]
+    fn round_trips_are_lossless_within_range() {
+        #[cfg(feature = "f32")]
+        let limit = 1 << 22;
+        #[cfg(feature = "f64")]
+        let limit = i32::MAX;
+        // Every value in the outermost 2¹⁶ steps, where rounding errors are largest, plus a
+        // stride through the rest.
+        let near_limit = (limit - (1 << 16))..=limit;
+        for x in near_limit.chain((0..limit).step_by(997)) {
+            for x in [x, -x] {
+                let q = QPos2::new(x, 7);
+                assert_eq!(QPos2::from(Vec2::from(q)), q, "{x}");
+            }
+        }
+        let p = QPos3::new(limit, -limit, 12_345);
+        assert_eq!(QPos3::from(Vec3::from(p)), p);
+    }
+
+    #[test]
+    fn integer_arithmetic_is_exact() {
+        let mut p = QPos3::new(1, 2, 3);
+        p += QPos3::new(10, 20, 30);
+        assert_eq!(p - QPos3::new(1, 1, 1), QPos3::new(10, 21, 32));
+        assert_eq!(p.xz(), QPos2::new(11, 33));
+        assert_eq!(p.as_ivec3(), IVec3::new(11, 22, 33));
+    }
+}
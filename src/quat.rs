@@ -1,4 +1,8 @@
-use crate::Scalar;
+use crate::conventions::Axes;
+use crate::sample::Source;
+use crate::{Mat3, MathError, Scalar, Vec3, consts, conventions, float};
+use core::ops::{Add, Mul, Neg, Sub};
+#[cfg(feature = "spacetimedb")]
 use spacetimedb::SpacetimeType;
 
 /// A quaternion representing 3D rotation (orientation).
@@ -17,7 +21,10 @@ use spacetimedb::SpacetimeType;
 /// assert_eq!(q.y, 0.0);
 /// assert_eq!(q.z, 0.0);
 /// ```
-#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Quat {
     /// Vector part (imaginary i)
     pub x: Scalar,
@@ -40,11 +47,370 @@ impl Quat {
     pub const IDENTITY: Self = Self::new(0.0, 0.0, 0.0, 1.0);
 
     #[inline(always)]
+    #[track_caller]
     pub const fn new(x: Scalar, y: Scalar, z: Scalar, w: Scalar) -> Self {
-        Quat { x, y, z, w }
+        check_finite!("Quat::new", Quat { x, y, z, w })
+    }
+
+    /// Returns `true` if no component is NaN or infinite.
+    #[inline]
+    pub const fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
+    /// Creates a rotation of `angle` radians around the unit-length `axis`.
+    pub fn from_axis_angle(axis: Vec3, angle: Scalar) -> Self {
+        let (s, c) = float::sin_cos(angle * 0.5);
+        Quat::new(axis.x * s, axis.y * s, axis.z * s, c)
+    }
+
+    /// Creates a rotation around `v` by `|v|` radians, e.g. an angular velocity times a time
+    /// step.
+    pub fn from_scaled_axis(v: Vec3) -> Self {
+        let angle = v.length();
+        if angle <= Scalar::EPSILON {
+            Quat::IDENTITY
+        } else {
+            Quat::from_axis_angle(v * (1.0 / angle), angle)
+        }
+    }
+
+    /// Creates the rotation described by the rotation matrix `m`, which must be orthonormal
+    /// with determinant `1`.
+    pub fn from_mat3(m: &Mat3) -> Self {
+        let (m00, m01, m02) = (m.x_axis.x, m.y_axis.x, m.z_axis.x);
+        let (m10, m11, m12) = (m.x_axis.y, m.y_axis.y, m.z_axis.y);
+        let (m20, m21, m22) = (m.x_axis.z, m.y_axis.z, m.z_axis.z);
+        // Divide by the largest of the four candidates to stay accurate near 180 degrees.
+        let trace = m00 + m11 + m22;
+        if trace > 0.0 {
+            let s = float::sqrt(trace + 1.0) * 2.0;
+            Quat::new((m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s, 0.25 * s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = float::sqrt(1.0 + m00 - m11 - m22) * 2.0;
+            Quat::new(0.25 * s, (m01 + m10) / s, (m02 + m20) / s, (m21 - m12) / s)
+        } else if m11 > m22 {
+            let s = float::sqrt(1.0 + m11 - m00 - m22) * 2.0;
+            Quat::new((m01 + m10) / s, 0.25 * s, (m12 + m21) / s, (m02 - m20) / s)
+        } else {
+            let s = float::sqrt(1.0 + m22 - m00 - m11) * 2.0;
+            Quat::new((m02 + m20) / s, (m12 + m21) / s, 0.25 * s, (m10 - m01) / s)
+        }
+    }
+
+    /// Creates the rotation that turns the `axes` convention's forward towards `forward`,
+    /// with its up as close to `up` as possible.
+    ///
+    /// Returns [`Quat::IDENTITY`] if `forward` is zero or parallel to `up`.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{Quat, Vec3, conventions};
+    ///
+    /// // The same gameplay code faces east in a Y-up world and in Unreal's Z-up one.
+    /// for axes in [conventions::DEFAULT, conventions::Z_UP_LEFT_HANDED_FWD_POS_X] {
+    ///     let east = Vec3::right_in(&axes);
+    ///     let q = Quat::look_to_in(&axes, east, Vec3::up_in(&axes));
+    ///     assert!((q * Vec3::forward_in(&axes)).distance(east) < 1.0e-6);
+    ///     assert!((q * Vec3::up_in(&axes)).distance(axes.up) < 1.0e-6);
+    /// }
+    /// ```
+    pub fn look_to_in(axes: &Axes, forward: Vec3, up: Vec3) -> Self {
+        let Some(f) = forward.try_normalize(consts::EPSILON) else {
+            return Quat::IDENTITY;
+        };
+        let Some(u) = (up - f * up.dot(f)).try_normalize(consts::EPSILON) else {
+            return Quat::IDENTITY;
+        };
+        // Match the handedness of `axes` so the change is a rotation rather than a mirror.
+        let handedness = axes.right.dot(axes.up.cross(axes.forward)).signum();
+        let r = u.cross(f) * handedness;
+        Quat::from_mat3(&(Mat3::from_cols(r, u, f) * axes.basis().transpose()))
+    }
+
+    /// Returns a rotation drawn uniformly from all rotations.
+    ///
+    /// Random Euler angles bunch orientations around the poles; this uses Shoemake's form of
+    /// the subgroup algorithm, which doesn't.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{Pcg32, Quat};
+    ///
+    /// let mut rng = Pcg32::from_seed(12);
+    /// let debris = Quat::random_uniform(&mut rng);
+    /// assert!((debris.length() - 1.0).abs() < 1.0e-5);
+    /// ```
+    pub fn random_uniform(rng: &mut impl Source) -> Quat {
+        let u = rng.next_unit();
+        let (low, high) = (float::sqrt(1.0 - u), float::sqrt(u));
+        let (s1, c1) = float::sin_cos(consts::TAU * rng.next_unit());
+        let (s2, c2) = float::sin_cos(consts::TAU * rng.next_unit());
+        Quat::new(low * s1, low * c1, high * s2, high * c2)
+    }
+
+    /// Returns a rotation by a uniformly random angle around [`conventions::DEFAULT`]'s up
+    /// axis, e.g. for loot that should land upright but facing anywhere.
+    #[inline]
+    pub fn random_yaw(rng: &mut impl Source) -> Quat {
+        Quat::random_yaw_about(rng, conventions::DEFAULT.up)
+    }
+
+    /// Returns a rotation by a uniformly random angle around the unit-length `up` axis.
+    #[inline]
+    pub fn random_yaw_about(rng: &mut impl Source, up: Vec3) -> Quat {
+        Quat::from_axis_angle(up, consts::TAU * rng.next_unit())
+    }
+
+    /// Returns the conjugate, which is the inverse rotation for unit quaternions.
+    #[inline]
+    pub const fn conjugate(&self) -> Quat {
+        Quat::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Returns the dot product of this quaternion and `other`.
+    #[inline]
+    pub fn dot(&self, other: Quat) -> Scalar {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Returns the length (magnitude) of this quaternion.
+    pub fn length(&self) -> Scalar {
+        float::sqrt(self.dot(*self))
+    }
+
+    /// Returns this quaternion scaled to unit length, or [`Quat::IDENTITY`] if its length is
+    /// below `epsilon`.
+    pub fn normalize_or_identity(&self, epsilon: Scalar) -> Quat {
+        let len = self.length();
+        if len <= epsilon {
+            Quat::IDENTITY
+        } else {
+            Quat::new(self.x / len, self.y / len, self.z / len, self.w / len)
+        }
+    }
+
+    /// Rotates `v` by this (unit) quaternion.
+    #[inline]
+    pub fn mul_vec3(&self, v: Vec3) -> Vec3 {
+        // v' = v + w * t + q x t, where t = 2 * (q x v)
+        let q = Vec3::new(self.x, self.y, self.z);
+        let t = q.cross(v) * 2.0;
+        v + t * self.w + q.cross(t)
+    }
+
+    /// Interpolates linearly towards `end` along the shortest arc and renormalizes.
+    ///
+    /// Cheaper than [`Quat::slerp`] but doesn't rotate at constant angular speed.
+    pub fn nlerp(&self, end: Quat, t: Scalar) -> Quat {
+        let end = if self.dot(end) < 0.0 { -end } else { end };
+        Quat::new(
+            self.x + (end.x - self.x) * t,
+            self.y + (end.y - self.y) * t,
+            self.z + (end.z - self.z) * t,
+            self.w + (end.w - self.w) * t,
+        )
+        .normalize_or_identity(0.0)
+    }
+
+    /// Spherically interpolates towards `end` along the shortest arc at constant angular speed.
+    pub fn slerp(&self, end: Quat, t: Scalar) -> Quat {
+        let mut cos = self.dot(end);
+        let end = if cos < 0.0 {
+            cos = -cos;
+            -end
+        } else {
+            end
+        };
+        // Nearly identical rotations divide by a vanishing sine; nlerp is exact enough there.
+        if cos > 0.9995 {
+            return self.nlerp(end, t);
+        }
+        let angle = float::acos(cos);
+        let sin = float::sin(angle);
+        let a = float::sin((1.0 - t) * angle) / sin;
+        let b = float::sin(t * angle) / sin;
+        Quat::new(
+            self.x * a + end.x * b,
+            self.y * a + end.y * b,
+            self.z * a + end.z * b,
+            self.w * a + end.w * b,
+        )
+    }
+    /// Returns this quaternion scaled to unit length, or [`MathError::NotFinite`] for NaN or
+    /// infinite components and [`MathError::ZeroLength`] for a length at or below `epsilon`.
+    pub fn checked_normalize(&self, epsilon: Scalar) -> Result<Quat, MathError> {
+        if !self.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        let len = self.length();
+        if len <= epsilon {
+            return Err(MathError::ZeroLength);
+        }
+        Ok(Quat::new(
+            self.x / len,
+            self.y / len,
+            self.z / len,
+            self.w / len,
+        ))
+    }
+
+    /// Like [`Quat::from_axis_angle`], but rejects non-finite input and an `axis` whose length
+    /// is off by more than `1e-3` instead of returning a non-unit quaternion.
+    pub fn checked_from_axis_angle(axis: Vec3, angle: Scalar) -> Result<Quat, MathError> {
+        if !axis.is_finite() || !angle.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        check_normalized(axis.length())?;
+        Ok(Quat::from_axis_angle(axis, angle))
+    }
+
+    /// Like [`Quat::slerp`], but rejects non-finite input and quaternions whose length is off
+    /// by more than `1e-3`, which the unchecked version would turn into a skewed result or
+    /// NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{MathError, Quat, Vec3};
+    ///
+    /// let turned = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 1.0);
+    /// assert!(Quat::IDENTITY.checked_slerp(turned, 0.5).is_ok());
+    /// assert!(matches!(
+    ///     Quat::IDENTITY.checked_slerp(Quat::new(0.0, 0.0, 0.0, 2.0), 0.5),
+    ///     Err(MathError::NotNormalized { .. })
+    /// ));
+    /// ```
+    pub fn checked_slerp(&self, end: Quat, t: Scalar) -> Result<Quat, MathError> {
+        if !self.is_finite() || !end.is_finite() || !t.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        check_normalized(self.length())?;
+        check_normalized(end.length())?;
+        Ok(self.slerp(end, t))
+    }
+}
+
+/// Accepts lengths within `1e-3` of one: loose enough for values that have been through a
+/// few operations or a round trip as `f32`, tight enough to catch unnormalized input.
+fn check_normalized(length: Scalar) -> Result<(), MathError> {
+    if (length - 1.0).abs() <= 1.0e-3 {
+        Ok(())
+    } else {
+        Err(MathError::NotNormalized { length })
+    }
+}
+
+impl Neg for Quat {
+    type Output = Quat;
+
+    /// Negates every component. The result represents the same rotation.
+    #[inline]
+    #[track_caller]
+    fn neg(self) -> Quat {
+        check_finite!(
+            "-Quat",
+            Quat {
+                x: -self.x,
+                y: -self.y,
+                z: -self.z,
+                w: -self.w
+            }
+        )
+    }
+}
+
+impl Add for Quat {
+    type Output = Quat;
+
+    /// Adds component-wise. The sum is generally not a unit quaternion.
+    #[inline]
+    #[track_caller]
+    fn add(self, rhs: Quat) -> Quat {
+        check_finite!(
+            "Quat + Quat",
+            Quat {
+                x: self.x + rhs.x,
+                y: self.y + rhs.y,
+                z: self.z + rhs.z,
+                w: self.w + rhs.w
+            }
+        )
+    }
+}
+
+impl Sub for Quat {
+    type Output = Quat;
+
+    /// Subtracts component-wise. The difference is generally not a unit quaternion.
+    #[inline]
+    #[track_caller]
+    fn sub(self, rhs: Quat) -> Quat {
+        check_finite!(
+            "Quat - Quat",
+            Quat {
+                x: self.x - rhs.x,
+                y: self.y - rhs.y,
+                z: self.z - rhs.z,
+                w: self.w - rhs.w
+            }
+        )
     }
 }
 
+impl Mul<Scalar> for Quat {
+    type Output = Quat;
+
+    /// Scales every component. The result is generally not a unit quaternion.
+    #[inline]
+    #[track_caller]
+    fn mul(self, rhs: Scalar) -> Quat {
+        check_finite!(
+            "Quat * Scalar",
+            Quat {
+                x: self.x * rhs,
+                y: self.y * rhs,
+                z: self.z * rhs,
+                w: self.w * rhs
+            }
+        )
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+
+    /// Composes two rotations: `a * b` applies `b` first, then `a`.
+    #[inline]
+    #[track_caller]
+    fn mul(self, rhs: Quat) -> Quat {
+        check_finite!(
+            "Quat * Quat",
+            Quat {
+                x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+                y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+                z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+                w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z
+            }
+        )
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        self.mul_vec3(rhs)
+    }
+}
+
+// SAFETY: `repr(C)` and made only of `Scalar`s, so there's no padding and every bit pattern
+// is a valid value.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Quat {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Quat {}
+
 #[cfg(feature = "nalgebra")]
 mod nalgebra_impls {
     use super::*;
@@ -68,6 +434,18 @@ mod nalgebra_impls {
             }
         }
     }
+
+    impl From<Quat> for nalgebra::Rotation3<Scalar> {
+        fn from(q: Quat) -> Self {
+            nalgebra::UnitQuaternion::from(q).to_rotation_matrix()
+        }
+    }
+
+    impl From<nalgebra::Rotation3<Scalar>> for Quat {
+        fn from(r: nalgebra::Rotation3<Scalar>) -> Self {
+            nalgebra::UnitQuaternion::from_rotation_matrix(&r).into()
+        }
+    }
 }
 
 #[cfg(feature = "glam")]
@@ -103,15 +481,114 @@ mod glam_impls {
     }
 }
 
+#[cfg(feature = "bevy")]
+mod bevy_impls {
+    use super::*;
+
+    #[cfg(feature = "f32")]
+    impl From<bevy_math::Quat> for Quat {
+        fn from(q: bevy_math::Quat) -> Self {
+            Self::new(q.x, q.y, q.z, q.w)
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    impl From<Quat> for bevy_math::Quat {
+        fn from(q: Quat) -> Self {
+            bevy_math::Quat::from_xyzw(q.x, q.y, q.z, q.w)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<bevy_math::DQuat> for Quat {
+        fn from(q: bevy_math::DQuat) -> Self {
+            Self::new(q.x, q.y, q.z, q.w)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<Quat> for bevy_math::DQuat {
+        fn from(q: Quat) -> Self {
+            bevy_math::DQuat::from_xyzw(q.x, q.y, q.z, q.w)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
     #[test]
     fn default_is_identity() {
         assert_eq!(Quat::default(), Quat::IDENTITY);
     }
 
+    #[test]
+    fn from_axis_angle_rotates_right_handed() {
+        let quarter = core::f64::consts::FRAC_PI_2 as Scalar;
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), quarter);
+        let v = q * Vec3::new(1.0, 0.0, 0.0);
+        assert!(v.distance(Vec3::new(0.0, 1.0, 0.0)) <= EPS);
+        let back = q.conjugate() * v;
+        assert!(back.distance(Vec3::new(1.0, 0.0, 0.0)) <= EPS);
+    }
+
+    #[test]
+    fn mul_composes_rotations() {
+        let quarter = core::f64::consts::FRAC_PI_2 as Scalar;
+        let about_z = Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), quarter);
+        let about_x = Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), quarter);
+        // x -> y (about z), then y -> z (about x).
+        let v = (about_x * about_z) * Vec3::new(1.0, 0.0, 0.0);
+        assert!(v.distance(Vec3::new(0.0, 0.0, 1.0)) <= EPS);
+        let scaled = Quat::new(0.0, 0.0, 0.0, 2.0).normalize_or_identity(EPS);
+        assert_eq!(scaled, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn slerp_rotates_at_constant_speed_along_the_short_arc() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let a = Quat::from_axis_angle(axis, 0.2);
+        let b = Quat::from_axis_angle(axis, 1.4);
+        for t in [0.0 as Scalar, 0.25, 0.5, 1.0] {
+            let expected = Quat::from_axis_angle(axis, 0.2 + 1.2 * t);
+            assert!(a.slerp(b, t).dot(expected).abs() >= 1.0 - EPS);
+        }
+        // The same rotation with flipped sign still takes the short way.
+        let mid = a.slerp(-b, 0.5);
+        assert!(mid.dot(Quat::from_axis_angle(axis, 0.8)).abs() >= 1.0 - EPS);
+        let close = a.slerp(a, 0.5);
+        assert!(close.dot(a) >= 1.0 - EPS);
+        assert!(a.nlerp(b, 0.5).dot(Quat::from_axis_angle(axis, 0.8)) >= 1.0 - EPS);
+    }
+
+    #[test]
+    fn random_rotations_cover_the_sphere_evenly() {
+        let mut rng = crate::Pcg32::from_seed(3);
+        let mut near_pole = 0;
+        let mut mean = Vec3::ZERO;
+        for _ in 0..4000 {
+            let q = Quat::random_uniform(&mut rng);
+            assert!((q.length() - 1.0).abs() <= EPS);
+            let v = q * Vec3::new(1.0, 0.0, 0.0);
+            near_pole += (v.y > 0.5) as usize;
+            mean += v;
+        }
+        // The cap above y = 0.5 is a quarter of the sphere; Euler sampling overfills it.
+        assert!((900..1100).contains(&near_pole), "{near_pole}");
+        assert!((mean / 4000.0).length() <= 0.05, "{mean:?}");
+
+        let up = Vec3::new(0.0, 0.0, 1.0);
+        let yaw = Quat::random_yaw_about(&mut rng, up);
+        assert!((yaw * up).distance(up) <= EPS);
+        let y = conventions::DEFAULT.up;
+        assert!((Quat::random_yaw(&mut || 0.25) * y).distance(y) <= EPS);
+        let quarter = Quat::random_yaw(&mut || 0.25) * Vec3::new(1.0, 0.0, 0.0);
+        assert!(quarter.distance(Vec3::new(0.0, 0.0, -1.0)) <= EPS);
+    }
+
     #[cfg(all(feature = "glam", feature = "f32"))]
     #[test]
     fn glam_f32_roundtrip() {
@@ -138,4 +615,79 @@ mod tests {
         let back: Quat = uq.into();
         assert_eq!(back, q);
     }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_rotation_matches() {
+        let q = Quat::from_axis_angle(Vec3::new(0.0, 0.6, 0.8), 1.3);
+        let r: nalgebra::Rotation3<Scalar> = q.into();
+        let v = Vec3::new(1.0, -2.0, 0.5);
+        let rotated: Vec3 = (r * nalgebra::Vector3::from(v)).into();
+        assert!(rotated.distance(q * v) <= EPS);
+        assert!(Quat::from(r).dot(q).abs() >= 1.0 - EPS);
+    }
+
+    #[test]
+    fn checked_variants_report_bad_input() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let q = Quat::from_axis_angle(axis, 0.8);
+        assert_eq!(
+            q.checked_slerp(Quat::IDENTITY, 0.25),
+            Ok(q.slerp(Quat::IDENTITY, 0.25))
+        );
+        assert_eq!(
+            q.checked_slerp(Quat::new(0.0, 0.0, 0.0, 0.5), 0.5),
+            Err(MathError::NotNormalized { length: 0.5 })
+        );
+        assert_eq!(q.checked_slerp(q, Scalar::NAN), Err(MathError::NotFinite));
+        assert_eq!(
+            Quat::checked_from_axis_angle(axis * 2.0, 1.0),
+            Err(MathError::NotNormalized { length: 2.0 })
+        );
+        assert!(Quat::checked_from_axis_angle(axis, 1.0).is_ok());
+        assert_eq!(
+            Quat::new(0.0, 0.0, 0.0, 0.0).checked_normalize(EPS),
+            Err(MathError::ZeroLength)
+        );
+        let unit = Quat::new(0.0, 0.0, 0.0, 3.0)
+            .checked_normalize(EPS)
+            .unwrap();
+        assert!((unit.length() - 1.0).abs() <= EPS);
+    }
+
+    #[test]
+    fn from_mat3_round_trips_every_quadrant() {
+        for (axis, angle) in [
+            (Vec3::new(1.0, 0.0, 0.0), 0.3),
+            (Vec3::new(1.0, 0.0, 0.0), 3.1),
+            (Vec3::new(0.0, 1.0, 0.0), 3.0),
+            (Vec3::new(0.0, 0.0, 1.0), -2.9),
+            (Vec3::new(0.6, 0.0, 0.8), 1.7),
+        ] {
+            let q = Quat::from_axis_angle(axis, angle);
+            let back = Quat::from_mat3(&Mat3::from_quat(q));
+            assert!(back.dot(q).abs() >= 1.0 - EPS, "{axis:?} {angle}");
+        }
+    }
+
+    #[test]
+    fn look_to_in_respects_each_convention() {
+        let target = Vec3::new(1.0, 2.0, -2.0) * (1.0 / 3.0);
+        for axes in [
+            conventions::DEFAULT,
+            conventions::Y_UP_LEFT_HANDED_FWD_POS_Z,
+            conventions::Z_UP_RIGHT_HANDED_FWD_POS_Y,
+            conventions::Z_UP_LEFT_HANDED_FWD_POS_X,
+        ] {
+            let q = Quat::look_to_in(&axes, target, axes.up);
+            assert!((q.length() - 1.0).abs() <= EPS);
+            assert!((q * axes.forward).distance(target) <= EPS);
+            // Up stays in the plane of forward and the requested up.
+            assert!((q * axes.up).dot(axes.up.cross(target)).abs() <= EPS);
+            assert!((q * axes.up).dot(axes.up) > 0.0);
+        }
+        let axes = conventions::DEFAULT;
+        assert_eq!(Quat::look_to_in(&axes, axes.up, axes.up), Quat::IDENTITY);
+        assert_eq!(Quat::look_to_in(&axes, Vec3::ZERO, axes.up), Quat::IDENTITY);
+    }
 }
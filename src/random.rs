@@ -0,0 +1,249 @@
+//! [`rand`] integration, for code that already draws its randomness from a `rand` generator.
+//!
+//! - [`StandardUniform`] gives vectors with every component in `[0, 1)` and rotations
+//!   drawn uniformly from all rotations.
+//! - [`UnitCircle`] and [`UnitSphere`] give unit-length directions.
+//! - [`Vec2`], [`Vec3`] and [`Vec4`] are [`SampleUniform`], so [`Uniform`] picks a vector
+//!   component-wise between two corners.
+//! - [`Pcg32`] and [`SplitMix64`] are [`RngCore`], so a generator stored in a row works
+//!   with every `rand` distribution.
+//!
+//! Every distribution forwards to the matching [`sample`] function, so a
+//! [`Pcg32`] and a `rand` generator producing the same numbers produce the same points.
+//!
+//! # Examples
+//! ```
+//! use rand::Rng;
+//! use rand::distr::{Distribution, Uniform};
+//! use spacetimedb_math::random::UnitSphere;
+//! use spacetimedb_math::{Pcg32, Quat, Vec3};
+//!
+//! let mut rng = Pcg32::from_seed(3);
+//! let facing: Quat = rng.random();
+//! let spark: Vec3 = UnitSphere.sample(&mut rng);
+//! assert!((spark.length() - 1.0).abs() < 1.0e-5);
+//! let room = Uniform::new(Vec3::new(-5.0, 0.0, -5.0), Vec3::new(5.0, 3.0, 5.0)).unwrap();
+//! let spawn = room.sample(&mut rng);
+//! assert!(spawn.y >= 0.0 && spawn.y < 3.0);
+//! assert!((facing.length() - 1.0).abs() < 1.0e-5);
+//! ```
+//!
+//! [`Uniform`]: rand::distr::Uniform
+
+use crate::sample::{self, Source};
+use crate::{Pcg32, Quat, Scalar, SplitMix64, Vec2, Vec3, Vec4};
+use rand::distr::uniform::{Error, SampleBorrow, SampleUniform, UniformSampler};
+use rand::distr::{Distribution, StandardUniform};
+use rand::rand_core::impls;
+use rand::{Rng, RngCore};
+
+/// Reads uniform numbers in `[0, 1)` from a `rand` generator.
+struct RandSource<'a, R: ?Sized>(&'a mut R);
+
+impl<R: Rng + ?Sized> Source for RandSource<'_, R> {
+    #[inline]
+    fn next_unit(&mut self) -> Scalar {
+        self.0.random()
+    }
+}
+
+impl RngCore for Pcg32 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        Pcg32::next_u32(self)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        Pcg32::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+impl RngCore for SplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (SplitMix64::next_u64(self) >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        SplitMix64::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+/// Points on the unit circle, as from [`sample::unit_circle`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UnitCircle;
+
+/// Points on the unit sphere, as from [`sample::unit_sphere`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UnitSphere;
+
+impl Distribution<Vec2> for UnitCircle {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        sample::unit_circle(&mut RandSource(rng))
+    }
+}
+
+impl Distribution<Vec3> for UnitSphere {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        sample::unit_sphere(&mut RandSource(rng))
+    }
+}
+
+impl Distribution<Vec2> for StandardUniform {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2 {
+        Vec2::new(rng.random(), rng.random())
+    }
+}
+
+impl Distribution<Vec3> for StandardUniform {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3 {
+        Vec3::new(rng.random(), rng.random(), rng.random())
+    }
+}
+
+impl Distribution<Vec4> for StandardUniform {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec4 {
+        Vec4::new(rng.random(), rng.random(), rng.random(), rng.random())
+    }
+}
+
+/// Rotations drawn uniformly from all rotations, as from [`Quat::random_uniform`].
+impl Distribution<Quat> for StandardUniform {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quat {
+        Quat::random_uniform(&mut RandSource(rng))
+    }
+}
+
+type ScalarSampler = <Scalar as SampleUniform>::Sampler;
+
+macro_rules! uniform_vector {
+    ($(#[$doc:meta])* $sampler:ident, $vector:ident { $($field:ident),+ }) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $sampler {
+            $($field: ScalarSampler,)+
+        }
+
+        impl UniformSampler for $sampler {
+            type X = $vector;
+
+            fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<$vector> + Sized,
+                B2: SampleBorrow<$vector> + Sized,
+            {
+                let (low, high) = (low.borrow(), high.borrow());
+                Ok($sampler {
+                    $($field: ScalarSampler::new(low.$field, high.$field)?,)+
+                })
+            }
+
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<$vector> + Sized,
+                B2: SampleBorrow<$vector> + Sized,
+            {
+                let (low, high) = (low.borrow(), high.borrow());
+                Ok($sampler {
+                    $($field: ScalarSampler::new_inclusive(low.$field, high.$field)?,)+
+                })
+            }
+
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $vector {
+                $vector { $($field: self.$field.sample(rng),)+ }
+            }
+        }
+
+        impl SampleUniform for $vector {
+            type Sampler = $sampler;
+        }
+    };
+}
+
+uniform_vector! {
+    /// Samples a [`Vec2`] component-wise between two corners; every component range must be
+    /// non-empty.
+    UniformVec2, Vec2 { x, y }
+}
+
+uniform_vector! {
+    /// Samples a [`Vec3`] component-wise between two corners; every component range must be
+    /// non-empty.
+    UniformVec3, Vec3 { x, y, z }
+}
+
+uniform_vector! {
+    /// Samples a [`Vec4`] component-wise between two corners; every component range must be
+    /// non-empty.
+    UniformVec4, Vec4 { x, y, z, w }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distr::Uniform;
+
+    #[test]
+    fn distributions_match_the_sample_functions() {
+        // Two copies of one generator, one read through a closure.
+        let mut a = Pcg32::from_seed(4);
+        let mut b = a.clone();
+        let q: Quat = a.random();
+        assert_eq!(q, Quat::random_uniform(&mut || b.random::<Scalar>()));
+        let v = UnitSphere.sample(&mut a);
+        assert_eq!(v, sample::unit_sphere(&mut || b.random::<Scalar>()));
+        assert!((q.length() - 1.0).abs() < 1.0e-5 && (v.length() - 1.0).abs() < 1.0e-5);
+        let c = UnitCircle.sample(&mut a);
+        assert!((c.length() - 1.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn generators_feed_rand_unchanged() {
+        let mut pcg = Pcg32::new(42, 54);
+        assert_eq!(RngCore::next_u32(&mut pcg), 0xa15c_02b7);
+        let mut bytes = [0u8; 4];
+        pcg.fill_bytes(&mut bytes);
+        let mut replay = Pcg32::new(42, 54);
+        replay.next_u32();
+        assert_eq!(bytes, replay.next_u32().to_le_bytes());
+        let mut mix = SplitMix64::new(0);
+        assert_eq!(RngCore::next_u64(&mut mix), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn uniform_vectors_stay_between_their_corners() {
+        let mut rng = Pcg32::from_seed(9);
+        let (low, high) = (Vec3::new(-2.0, 10.0, 0.0), Vec3::new(2.0, 11.0, 0.5));
+        let uniform = Uniform::new(low, high).unwrap();
+        for _ in 0..1000 {
+            let v = uniform.sample(&mut rng);
+            assert!(v.x >= low.x && v.x < high.x);
+            assert!(v.y >= low.y && v.y < high.y);
+            assert!(v.z >= low.z && v.z < high.z);
+            let unit: Vec2 = rng.random();
+            assert!((0.0..1.0).contains(&unit.x) && (0.0..1.0).contains(&unit.y));
+        }
+        assert!(Uniform::new(Vec2::new(0.0, 1.0), Vec2::new(1.0, 1.0)).is_err());
+        let point = Uniform::new_inclusive(Vec2::ONE, Vec2::ONE)
+            .unwrap()
+            .sample(&mut rng);
+        assert_eq!(point, Vec2::ONE);
+    }
+}
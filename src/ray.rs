@@ -0,0 +1,86 @@
+use crate::{Scalar, Vec2, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A half-line starting at `origin` and extending along `direction`.
+///
+/// Intersection queries report hits as a parameter `t` so that the hit point is `at(t)`.
+/// When `direction` is unit length, `t` is the distance from the origin.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Ray3, Vec3};
+///
+/// let ray = Ray3::new(Vec3::ZERO, Vec3::new(0.0, 0.0, -1.0));
+/// assert_eq!(ray.at(2.0), Vec3::new(0.0, 0.0, -2.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray3 {
+    /// Start of the ray.
+    pub origin: Vec3,
+    /// Direction of travel.
+    pub direction: Vec3,
+}
+
+impl Ray3 {
+    #[inline(always)]
+    pub const fn new(origin: Vec3, direction: Vec3) -> Self {
+        Ray3 { origin, direction }
+    }
+
+    /// Returns the point at parameter `t` along the ray.
+    #[inline]
+    pub fn at(&self, t: Scalar) -> Vec3 {
+        self.origin + self.direction * t
+    }
+}
+
+/// A 2D half-line starting at `origin` and extending along `direction`.
+///
+/// The 2D counterpart of [`Ray3`], e.g. for tile-map line of sight.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Ray2, Vec2};
+///
+/// let ray = Ray2::new(Vec2::ZERO, Vec2::new(1.0, 0.0));
+/// assert_eq!(ray.at(2.0), Vec2::new(2.0, 0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray2 {
+    /// Start of the ray.
+    pub origin: Vec2,
+    /// Direction of travel.
+    pub direction: Vec2,
+}
+
+impl Ray2 {
+    #[inline(always)]
+    pub const fn new(origin: Vec2, direction: Vec2) -> Self {
+        Ray2 { origin, direction }
+    }
+
+    /// Returns the point at parameter `t` along the ray.
+    #[inline]
+    pub fn at(&self, t: Scalar) -> Vec2 {
+        self.origin + self.direction * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_scales_direction() {
+        let ray = Ray3::new(Vec3::ONE, Vec3::new(0.0, 2.0, 0.0));
+        assert_eq!(ray.at(1.5 as Scalar), Vec3::new(1.0, 4.0, 1.0));
+
+        let ray = Ray2::new(Vec2::ONE, Vec2::new(0.0, 2.0));
+        assert_eq!(ray.at(1.5 as Scalar), Vec2::new(1.0, 4.0));
+    }
+}
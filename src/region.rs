@@ -0,0 +1,529 @@
+//! Interest-management cell ids for SpacetimeDB subscriptions.
+//!
+//! The world is divided into square (or cubic) cells of a fixed size, and every cell gets a
+//! stable `u64` id. Store the id of an entity's cell in an indexed column, and a client can
+//! subscribe to its surroundings with `WHERE cell_id IN (...)` using the ids from
+//! [`cells_9`] or [`cells_27`].
+//!
+//! For larger or non-square areas, [`CellRange`] and [`CellRange3`] give inclusive integer
+//! bounds to filter on cell coordinate columns (`WHERE cell_x BETWEEN ...`), and
+//! [`cells_in_radius`] and [`cells_in_frustum`] list the ids of the cells an area touches.
+//! [`update_range`] adds hysteresis so a subscription only changes once its owner has
+//! moved well past the edge of the current one.
+//!
+//! The id layout is fixed, so ids written to a table stay valid across versions:
+//! - 2D: the cell's `x` in the high 32 bits and `y` in the low 32 bits, both as two's complement.
+//! - 3D: `x`, `y`, `z` in consecutive 21-bit fields (z lowest), each biased by `2^20`, so cells
+//!   from `-2^20` to `2^20 - 1` on each axis are representable.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{region, Vec2};
+//!
+//! let cell = region::cell_id(Vec2::new(130.0, -20.0), 64.0);
+//! assert_eq!(region::unpack(cell), (2, -1));
+//!
+//! let ids = region::cells_9(Vec2::new(130.0, -20.0), 64.0);
+//! let list = ids.iter().map(u64::to_string).collect::<Vec<_>>().join(", ");
+//! let query = format!("SELECT * FROM entity WHERE cell_id IN ({list})");
+//! assert!(query.contains(&cell.to_string()));
+//! ```
+
+use crate::{Aabb2, Aabb3, Frustum, IVec2, IVec3, Scalar, Vec2, Vec3, float};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Number of bits per axis in a 3D cell id.
+pub const BITS_3D: u32 = 21;
+const BIAS_3D: i32 = 1 << (BITS_3D - 1);
+const MASK_3D: u64 = (1 << BITS_3D) - 1;
+
+/// Packs 2D cell coordinates into an id.
+#[inline]
+pub const fn pack(x: i32, y: i32) -> u64 {
+    ((x as u32 as u64) << 32) | (y as u32 as u64)
+}
+
+/// Unpacks an id made by [`pack`] into cell coordinates.
+#[inline]
+pub const fn unpack(id: u64) -> (i32, i32) {
+    ((id >> 32) as u32 as i32, id as u32 as i32)
+}
+
+/// Packs 3D cell coordinates into an id.
+///
+/// Coordinates outside `-2^20..2^20` wrap around and alias other cells.
+#[inline]
+pub const fn pack_3d(x: i32, y: i32, z: i32) -> u64 {
+    (to_field(x) << (2 * BITS_3D)) | (to_field(y) << BITS_3D) | to_field(z)
+}
+
+/// Unpacks an id made by [`pack_3d`] into cell coordinates.
+#[inline]
+pub const fn unpack_3d(id: u64) -> (i32, i32, i32) {
+    (
+        from_field(id >> (2 * BITS_3D)),
+        from_field(id >> BITS_3D),
+        from_field(id),
+    )
+}
+
+#[inline]
+const fn to_field(v: i32) -> u64 {
+    (v.wrapping_add(BIAS_3D) as u32 as u64) & MASK_3D
+}
+
+#[inline]
+const fn from_field(bits: u64) -> i32 {
+    (bits & MASK_3D) as i32 - BIAS_3D
+}
+
+/// Returns the coordinates of the cell containing `position`.
+#[inline]
+pub fn cell_coords(position: Vec2, cell_size: Scalar) -> (i32, i32) {
+    (
+        float::floor(position.x / cell_size) as i32,
+        float::floor(position.y / cell_size) as i32,
+    )
+}
+
+/// Returns the coordinates of the cell containing `position`.
+#[inline]
+pub fn cell_coords_3d(position: Vec3, cell_size: Scalar) -> (i32, i32, i32) {
+    (
+        float::floor(position.x / cell_size) as i32,
+        float::floor(position.y / cell_size) as i32,
+        float::floor(position.z / cell_size) as i32,
+    )
+}
+
+/// Returns the id of the cell containing `position`.
+#[inline]
+pub fn cell_id(position: Vec2, cell_size: Scalar) -> u64 {
+    let (x, y) = cell_coords(position, cell_size);
+    pack(x, y)
+}
+
+/// Returns the id of the cell containing `position`.
+#[inline]
+pub fn cell_id_3d(position: Vec3, cell_size: Scalar) -> u64 {
+    let (x, y, z) = cell_coords_3d(position, cell_size);
+    pack_3d(x, y, z)
+}
+
+/// Returns the 2x2 block of cells nearest to `position`: its own cell and the neighbours on the
+/// sides of the cell that `position` is closest to.
+///
+/// Any point within half a cell of `position` lies in one of these cells, at a quarter of the
+/// cost of [`cells_9`].
+pub fn cells_4(position: Vec2, cell_size: Scalar) -> [u64; 4] {
+    let (x, y) = cell_coords(position, cell_size);
+    let local = position / cell_size - Vec2::new(x as Scalar, y as Scalar);
+    let dx = if local.x < 0.5 { -1 } else { 1 };
+    let dy = if local.y < 0.5 { -1 } else { 1 };
+    [
+        pack(x, y),
+        pack(x + dx, y),
+        pack(x, y + dy),
+        pack(x + dx, y + dy),
+    ]
+}
+
+/// Returns the 3x3 block of cells centered on the cell containing `position`, row by row.
+pub fn cells_9(position: Vec2, cell_size: Scalar) -> [u64; 9] {
+    let (x, y) = cell_coords(position, cell_size);
+    let mut ids = [0; 9];
+    for (i, id) in ids.iter_mut().enumerate() {
+        let i = i as i32;
+        *id = pack(x + i % 3 - 1, y + i / 3 - 1);
+    }
+    ids
+}
+
+/// Returns the 3x3x3 block of cells centered on the cell containing `position`.
+pub fn cells_27(position: Vec3, cell_size: Scalar) -> [u64; 27] {
+    let (x, y, z) = cell_coords_3d(position, cell_size);
+    let mut ids = [0; 27];
+    for (i, id) in ids.iter_mut().enumerate() {
+        let i = i as i32;
+        *id = pack_3d(x + i % 3 - 1, y + i / 3 % 3 - 1, z + i / 9 - 1);
+    }
+    ids
+}
+
+/// An inclusive rectangle of 2D cell coordinates.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{region::CellRange, IVec2, Vec2};
+///
+/// let range = CellRange::around(Vec2::new(50.0, 50.0), 30.0, 16.0);
+/// assert_eq!(range, CellRange::new(IVec2::new(1, 1), IVec2::new(5, 5)));
+/// let query = format!(
+///     "SELECT * FROM entity WHERE cell_x BETWEEN {} AND {} AND cell_y BETWEEN {} AND {}",
+///     range.min.x, range.max.x, range.min.y, range.max.y,
+/// );
+/// assert_eq!(range.len(), 25);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellRange {
+    /// Lowest cell on each axis.
+    pub min: IVec2,
+    /// Highest cell on each axis, inclusive.
+    pub max: IVec2,
+}
+
+impl CellRange {
+    #[inline(always)]
+    pub const fn new(min: IVec2, max: IVec2) -> Self {
+        CellRange { min, max }
+    }
+
+    /// Returns the cells touched by the square of half-size `radius` around `position`.
+    #[inline]
+    pub fn around(position: Vec2, radius: Scalar, cell_size: Scalar) -> Self {
+        CellRange::from_aabb(
+            &Aabb2::from_center_half_extents(position, Vec2::splat(radius)),
+            cell_size,
+        )
+    }
+
+    /// Returns the cells touched by `aabb`.
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb2, cell_size: Scalar) -> Self {
+        let (min_x, min_y) = cell_coords(aabb.min, cell_size);
+        let (max_x, max_y) = cell_coords(aabb.max, cell_size);
+        CellRange::new(IVec2::new(min_x, min_y), IVec2::new(max_x, max_y))
+    }
+
+    #[inline]
+    pub fn contains(&self, cell: IVec2) -> bool {
+        (self.min.x..=self.max.x).contains(&cell.x) && (self.min.y..=self.max.y).contains(&cell.y)
+    }
+
+    /// Returns `true` if every cell of `other` is in this range.
+    #[inline]
+    pub fn contains_range(&self, other: &CellRange) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// Returns the number of cells in the range.
+    #[inline]
+    pub fn len(&self) -> usize {
+        axis_len(self.min.x, self.max.x) * axis_len(self.min.y, self.max.y)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the cells, X outermost.
+    pub fn cells(&self) -> impl Iterator<Item = IVec2> + use<> {
+        let (min, max) = (self.min, self.max);
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+    }
+
+    /// Iterates over the ids of the cells, as made by [`pack`].
+    #[inline]
+    pub fn ids(&self) -> impl Iterator<Item = u64> + use<> {
+        self.cells().map(|c| pack(c.x, c.y))
+    }
+}
+
+/// An inclusive box of 3D cell coordinates. See [`CellRange`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellRange3 {
+    /// Lowest cell on each axis.
+    pub min: IVec3,
+    /// Highest cell on each axis, inclusive.
+    pub max: IVec3,
+}
+
+impl CellRange3 {
+    #[inline(always)]
+    pub const fn new(min: IVec3, max: IVec3) -> Self {
+        CellRange3 { min, max }
+    }
+
+    /// Returns the cells touched by the cube of half-size `radius` around `position`.
+    #[inline]
+    pub fn around(position: Vec3, radius: Scalar, cell_size: Scalar) -> Self {
+        CellRange3::from_aabb(
+            &Aabb3::from_center_half_extents(position, Vec3::splat(radius)),
+            cell_size,
+        )
+    }
+
+    /// Returns the cells touched by `aabb`.
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb3, cell_size: Scalar) -> Self {
+        let (min_x, min_y, min_z) = cell_coords_3d(aabb.min, cell_size);
+        let (max_x, max_y, max_z) = cell_coords_3d(aabb.max, cell_size);
+        CellRange3::new(
+            IVec3::new(min_x, min_y, min_z),
+            IVec3::new(max_x, max_y, max_z),
+        )
+    }
+
+    #[inline]
+    pub fn contains(&self, cell: IVec3) -> bool {
+        (self.min.x..=self.max.x).contains(&cell.x)
+            && (self.min.y..=self.max.y).contains(&cell.y)
+            && (self.min.z..=self.max.z).contains(&cell.z)
+    }
+
+    /// Returns `true` if every cell of `other` is in this range.
+    #[inline]
+    pub fn contains_range(&self, other: &CellRange3) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// Returns the number of cells in the range.
+    #[inline]
+    pub fn len(&self) -> usize {
+        axis_len(self.min.x, self.max.x)
+            * axis_len(self.min.y, self.max.y)
+            * axis_len(self.min.z, self.max.z)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the cells, X outermost and Z innermost.
+    pub fn cells(&self) -> impl Iterator<Item = IVec3> + use<> {
+        let (min, max) = (self.min, self.max);
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+        })
+    }
+
+    /// Iterates over the ids of the cells, as made by [`pack_3d`].
+    #[inline]
+    pub fn ids(&self) -> impl Iterator<Item = u64> + use<> {
+        self.cells().map(|c| pack_3d(c.x, c.y, c.z))
+    }
+}
+
+#[inline]
+fn axis_len(min: i32, max: i32) -> usize {
+    (max as i64 - min as i64 + 1).max(0) as usize
+}
+
+/// Returns the ids of the cells within `radius` of `position`, skipping the corners of
+/// [`CellRange::around`] that the circle doesn't reach.
+pub fn cells_in_radius(position: Vec2, radius: Scalar, cell_size: Scalar) -> Vec<u64> {
+    let range = CellRange::around(position, radius, cell_size);
+    range
+        .cells()
+        .filter(|c| {
+            let min = c.as_vec2() * cell_size;
+            let cell = Aabb2::new(min, min + Vec2::splat(cell_size));
+            cell.distance_squared_to_point(position) <= radius * radius
+        })
+        .map(|c| pack(c.x, c.y))
+        .collect()
+}
+
+/// Returns the ids of the cells within `radius` of `position`. See [`cells_in_radius`].
+pub fn cells_in_radius_3d(position: Vec3, radius: Scalar, cell_size: Scalar) -> Vec<u64> {
+    let range = CellRange3::around(position, radius, cell_size);
+    range
+        .cells()
+        .filter(|c| {
+            let min = c.as_vec3() * cell_size;
+            let cell = Aabb3::new(min, min + Vec3::splat(cell_size));
+            cell.distance_squared_to_point(position) <= radius * radius
+        })
+        .map(|c| pack_3d(c.x, c.y, c.z))
+        .collect()
+}
+
+/// Returns the ids of the cells inside `bounds` that intersect `frustum`.
+///
+/// A frustum's planes don't bound it tightly enough to enumerate cells from, so `bounds`
+/// limits the search, typically a cube around the camera with the far distance as its radius.
+pub fn cells_in_frustum(frustum: &Frustum, bounds: &Aabb3, cell_size: Scalar) -> Vec<u64> {
+    CellRange3::from_aabb(bounds, cell_size)
+        .cells()
+        .filter(|c| {
+            let min = c.as_vec3() * cell_size;
+            frustum.intersects_aabb(&Aabb3::new(min, min + Vec3::splat(cell_size)))
+        })
+        .map(|c| pack_3d(c.x, c.y, c.z))
+        .collect()
+}
+
+/// Returns a new range to subscribe to if `current` no longer covers `radius` around
+/// `position`, or `None` to keep it.
+///
+/// The new range covers `radius + margin`, so the owner has to travel about `margin` before
+/// the subscription changes again, instead of flipping back and forth at a cell boundary.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{region, Vec2};
+///
+/// let (radius, margin, cell_size) = (50.0, 20.0, 32.0);
+/// let mut range = region::CellRange::around(Vec2::ZERO, radius + margin, cell_size);
+/// // Small moves keep the subscription...
+/// assert_eq!(region::update_range(&range, Vec2::new(10.0, 0.0), radius, margin, cell_size), None);
+/// // ...until the interest radius reaches past it.
+/// let moved = region::update_range(&range, Vec2::new(60.0, 0.0), radius, margin, cell_size);
+/// assert!(moved.is_some());
+/// ```
+pub fn update_range(
+    current: &CellRange,
+    position: Vec2,
+    radius: Scalar,
+    margin: Scalar,
+    cell_size: Scalar,
+) -> Option<CellRange> {
+    let needed = CellRange::around(position, radius, cell_size);
+    (!current.contains_range(&needed))
+        .then(|| CellRange::around(position, radius + margin.max(0.0), cell_size))
+}
+
+/// 3D variant of [`update_range`].
+pub fn update_range_3d(
+    current: &CellRange3,
+    position: Vec3,
+    radius: Scalar,
+    margin: Scalar,
+    cell_size: Scalar,
+) -> Option<CellRange3> {
+    let needed = CellRange3::around(position, radius, cell_size);
+    (!current.contains_range(&needed))
+        .then(|| CellRange3::around(position, radius + margin.max(0.0), cell_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_round_trip() {
+        for &(x, y) in &[(0, 0), (-1, 1), (i32::MIN, i32::MAX), (123, -456)] {
+            assert_eq!(unpack(pack(x, y)), (x, y));
+        }
+        let limit = (1 << 20) - 1;
+        for &(x, y, z) in &[(0, 0, 0), (-1, 2, -3), (-limit - 1, limit, 7)] {
+            assert_eq!(unpack_3d(pack_3d(x, y, z)), (x, y, z));
+        }
+        // The layout is part of the contract.
+        assert_eq!(pack(1, -1), 0x0000_0001_ffff_ffff);
+        assert_eq!(pack_3d(0, 0, 0), (1 << 62) | (1 << 41) | (1 << 20));
+    }
+
+    #[test]
+    fn negative_positions_floor() {
+        assert_eq!(cell_coords(Vec2::new(-0.5, 0.5), 1.0), (-1, 0));
+        assert_eq!(
+            cell_coords_3d(Vec3::new(-10.0, 9.9, -10.1), 10.0),
+            (-1, 0, -2)
+        );
+        assert_eq!(cell_id(Vec2::new(-0.5, 0.5), 1.0), pack(-1, 0));
+    }
+
+    #[test]
+    fn neighbour_blocks() {
+        // Near the top-left corner of cell (0, 0).
+        let mut four = cells_4(Vec2::new(1.0, 9.0), 10.0);
+        four.sort();
+        let mut expected = [pack(0, 0), pack(-1, 0), pack(0, 1), pack(-1, 1)];
+        expected.sort();
+        assert_eq!(four, expected);
+
+        let nine = cells_9(Vec2::new(5.0, 5.0), 10.0);
+        assert_eq!(nine[0], pack(-1, -1));
+        assert_eq!(nine[4], pack(0, 0));
+        assert_eq!(nine[8], pack(1, 1));
+
+        let cube = cells_27(Vec3::new(-5.0, 5.0, 15.0), 10.0);
+        assert_eq!(cube[13], pack_3d(-1, 0, 1));
+        let mut unique = cube.to_vec();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 27);
+    }
+
+    #[test]
+    fn ranges_cover_the_interest_area() {
+        let range = CellRange::around(Vec2::new(-5.0, 5.0), 10.0, 10.0);
+        assert_eq!(range, CellRange::new(IVec2::new(-2, -1), IVec2::new(0, 1)));
+        assert_eq!(range.len(), 9);
+        assert_eq!(range.ids().count(), 9);
+        assert!(
+            range
+                .ids()
+                .any(|id| id == cell_id(Vec2::new(-5.0, 5.0), 10.0))
+        );
+        assert!(CellRange::new(IVec2::new(1, 0), IVec2::new(0, 0)).is_empty());
+
+        let cube = CellRange3::around(Vec3::new(0.5, 0.5, 0.5), 0.25, 1.0);
+        assert_eq!(cube.len(), 1);
+        assert_eq!(cube.ids().next(), Some(pack_3d(0, 0, 0)));
+        assert!(CellRange3::around(Vec3::ZERO, 1.0, 1.0).contains_range(&cube));
+    }
+
+    #[test]
+    fn radius_queries_skip_unreached_corners() {
+        let ids = cells_in_radius(Vec2::new(5.0, 5.0), 17.0, 10.0);
+        // The 5x5 block minus its four corners, which are over 17 units away.
+        assert_eq!(ids.len(), 21);
+        assert!(!ids.contains(&pack(-2, -2)));
+        assert!(ids.contains(&pack(-1, -1)));
+        assert_eq!(
+            cells_in_radius_3d(Vec3::splat(5.0), 4.0, 10.0),
+            vec![pack_3d(0, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn frustum_queries_keep_cells_in_view() {
+        let frustum = Frustum::from_camera(
+            Vec3::new(5.0, 5.0, 5.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            0.5,
+            1.0,
+            0.1,
+            30.0,
+        );
+        let bounds = Aabb3::from_center_half_extents(Vec3::new(5.0, 5.0, 5.0), Vec3::splat(30.0));
+        let ids = cells_in_frustum(&frustum, &bounds, 10.0);
+        assert!(ids.contains(&pack_3d(0, 0, -1)));
+        assert!(ids.contains(&pack_3d(0, 0, -2)));
+        assert!(!ids.contains(&pack_3d(0, 0, 1)));
+        assert!(!ids.contains(&pack_3d(2, 0, -1)));
+    }
+
+    #[test]
+    fn hysteresis_delays_resubscribing() {
+        let (radius, margin, cell_size) = (10.0, 10.0, 10.0);
+        let range = CellRange::around(Vec2::ZERO, radius + margin, cell_size);
+        // Pacing back and forth across a cell edge never changes the subscription.
+        for x in [-1.0, 1.0, -1.0, 1.0] {
+            assert_eq!(
+                update_range(&range, Vec2::new(x, 0.0), radius, margin, cell_size),
+                None
+            );
+        }
+        let moved = update_range(&range, Vec2::new(22.0, 0.0), radius, margin, cell_size).unwrap();
+        assert!(moved.contains_range(&CellRange::around(Vec2::new(22.0, 0.0), radius, cell_size)));
+
+        let range_3d = CellRange3::around(Vec3::ZERO, radius + margin, cell_size);
+        assert_eq!(
+            update_range_3d(&range_3d, Vec3::splat(1.0), radius, margin, cell_size),
+            None
+        );
+        assert!(update_range_3d(&range_3d, Vec3::splat(25.0), radius, margin, cell_size).is_some());
+    }
+}
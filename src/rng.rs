@@ -0,0 +1,334 @@
+use crate::{Scalar, Vec2, Vec3};
+use core::ops::Range;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// The SplitMix64 generator: one 64-bit word of state, fast and good enough to seed others.
+///
+/// Every seed, zero included, gives a full-period sequence, so it is handy for turning row ids
+/// or timestamps into well-mixed seeds for [`Pcg32`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitMix64 {
+    pub state: u64,
+}
+
+impl SplitMix64 {
+    #[inline(always)]
+    pub const fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        mix64(self.state)
+    }
+}
+
+pub(crate) const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// SplitMix64's output function: a bijective avalanche of all 64 bits.
+#[inline]
+pub(crate) fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The PCG32 (XSH RR) generator: 64-bit state, 32-bit output and independent streams.
+///
+/// The state is plain data, so it can live in a table row and pick up exactly where it left
+/// off; the same seed always replays the same sequence on every host.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::Pcg32;
+///
+/// let mut loot = Pcg32::new(42, 54);
+/// assert_eq!(loot.next_u32(), 0xa15c_02b7);
+/// let roll = loot.range_u32(1..7);
+/// assert!((1..7).contains(&roll));
+/// let mut replay = Pcg32::new(42, 54);
+/// replay.next_u32();
+/// assert_eq!(replay.range_u32(1..7), roll);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pcg32 {
+    pub state: u64,
+    /// Stream selector; always odd.
+    pub increment: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+    /// Creates a generator from a seed and a stream number. Different streams give unrelated
+    /// sequences for the same seed.
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut rng = Pcg32 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Creates a generator from a single number, mixing it with [`SplitMix64`] so nearby
+    /// seeds give unrelated sequences.
+    pub fn from_seed(seed: u64) -> Self {
+        let mut mix = SplitMix64::new(seed);
+        Pcg32::new(mix.next_u64(), mix.next_u64())
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        (high << 32) | self.next_u32() as u64
+    }
+
+    /// Returns a uniform `f32` in `[0, 1)`.
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns a uniform `f64` in `[0, 1)`.
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns a uniform [`Scalar`] in `[0, 1)`.
+    #[inline]
+    pub fn next_scalar(&mut self) -> Scalar {
+        #[cfg(feature = "f32")]
+        return self.next_f32();
+        #[cfg(feature = "f64")]
+        return self.next_f64();
+    }
+
+    /// Returns a uniform value below `bound`, without modulo bias.
+    fn below(&mut self, bound: u32) -> u32 {
+        // Lemire's multiply-and-reject: only the few low products that would favor some
+        // outputs are drawn again.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let m = self.next_u32() as u64 * bound as u64;
+            if m as u32 >= threshold {
+                return (m >> 32) as u32;
+            }
+        }
+    }
+
+    /// Returns a uniform integer in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    #[inline]
+    pub fn range_u32(&mut self, range: Range<u32>) -> u32 {
+        assert!(range.start < range.end, "empty range");
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// Returns a uniform integer in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    #[inline]
+    pub fn range_i32(&mut self, range: Range<i32>) -> i32 {
+        assert!(range.start < range.end, "empty range");
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add(self.below(span) as i32)
+    }
+
+    /// Returns a uniform value between `min` and `max`.
+    ///
+    /// Rounding can occasionally return `max` itself.
+    #[inline]
+    pub fn range_scalar(&mut self, min: Scalar, max: Scalar) -> Scalar {
+        min + (max - min) * self.next_scalar()
+    }
+
+    /// Returns `true` with probability `p`; values outside `[0, 1]` act like the nearest
+    /// bound.
+    #[inline]
+    pub fn chance(&mut self, p: Scalar) -> bool {
+        self.next_scalar() < p
+    }
+}
+
+/// Returns 64 pseudo-random bits determined entirely by `seed`, `entity_id` and `tick`.
+///
+/// Nothing is stored, so the same inputs give the same bits on every host and every replay:
+/// loot jitter or spawn offsets derived from an entity and tick are reproducible without a
+/// generator in the row. Swapping `entity_id` and `tick` gives unrelated bits.
+#[inline]
+pub fn hash_rand_u64(seed: u64, entity_id: u64, tick: u64) -> u64 {
+    let h = mix64(seed.wrapping_add(GOLDEN_GAMMA));
+    let h = mix64(h ^ entity_id);
+    mix64(h.wrapping_add(GOLDEN_GAMMA) ^ tick)
+}
+
+/// Returns a pseudo-random [`Scalar`] in `[0, 1)` determined by `seed`, `entity_id` and `tick`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{hash_rand, hash_rand_vec2, Vec2};
+///
+/// const WORLD_SEED: u64 = 0x5eed;
+/// let (goblin, tick) = (17, 3600);
+/// let gold = 10.0 + 5.0 * hash_rand(WORLD_SEED, goblin, tick);
+/// assert!((10.0..15.0).contains(&gold));
+/// // Recomputing later, or on another machine, gives the same answer.
+/// assert_eq!(gold, 10.0 + 5.0 * hash_rand(WORLD_SEED, goblin, tick));
+/// let spawn = Vec2::new(100.0, 40.0) + hash_rand_vec2(WORLD_SEED, goblin, tick) * 2.0;
+/// assert!((spawn.x - 100.0).abs() <= 2.0 && (spawn.y - 40.0).abs() <= 2.0);
+/// ```
+#[inline]
+pub fn hash_rand(seed: u64, entity_id: u64, tick: u64) -> Scalar {
+    let bits = hash_rand_u64(seed, entity_id, tick);
+    #[cfg(feature = "f32")]
+    return (bits >> 40) as f32 * (1.0 / (1u32 << 24) as f32);
+    #[cfg(feature = "f64")]
+    return (bits >> 11) as f64 * (1.0 / (1u64 << 53) as f64);
+}
+
+/// Returns a generator seeded from `seed`, `entity_id` and `tick`, for when one value per
+/// entity and tick isn't enough.
+#[inline]
+pub fn hash_rng(seed: u64, entity_id: u64, tick: u64) -> Pcg32 {
+    Pcg32::from_seed(hash_rand_u64(seed, entity_id, tick))
+}
+
+/// Returns a pseudo-random vector with components in `[-1, 1)`, determined by `seed`,
+/// `entity_id` and `tick`.
+#[inline]
+pub fn hash_rand_vec2(seed: u64, entity_id: u64, tick: u64) -> Vec2 {
+    let mut rng = hash_rng(seed, entity_id, tick);
+    Vec2::new(rng.range_scalar(-1.0, 1.0), rng.range_scalar(-1.0, 1.0))
+}
+
+/// Returns a pseudo-random vector with components in `[-1, 1)`, determined by `seed`,
+/// `entity_id` and `tick`.
+#[inline]
+pub fn hash_rand_vec3(seed: u64, entity_id: u64, tick: u64) -> Vec3 {
+    let mut rng = hash_rng(seed, entity_id, tick);
+    Vec3::new(
+        rng.range_scalar(-1.0, 1.0),
+        rng.range_scalar(-1.0, 1.0),
+        rng.range_scalar(-1.0, 1.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_sequences() {
+        // From the PCG reference implementation's demo, seeded with 42 on stream 54.
+        let mut pcg = Pcg32::new(42, 54);
+        let expected = [
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+        ];
+        for value in expected {
+            assert_eq!(pcg.next_u32(), value);
+        }
+        assert_eq!(SplitMix64::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn floats_stay_in_the_unit_interval() {
+        let mut rng = Pcg32::from_seed(7);
+        for _ in 0..1000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+            let d = rng.next_f64();
+            assert!((0.0..1.0).contains(&d));
+        }
+    }
+
+    #[test]
+    fn ranges_cover_their_bounds_evenly() {
+        let mut rng = Pcg32::from_seed(1);
+        let mut counts = [0u32; 6];
+        for _ in 0..6000 {
+            counts[rng.range_u32(0..6) as usize] += 1;
+        }
+        assert!(
+            counts.iter().all(|&c| (850..1150).contains(&c)),
+            "{counts:?}"
+        );
+
+        for _ in 0..1000 {
+            let v = rng.range_i32(-3..2);
+            assert!((-3..2).contains(&v));
+        }
+        assert_eq!(rng.range_i32(i32::MAX - 1..i32::MAX), i32::MAX - 1);
+        let full = rng.range_i32(i32::MIN..i32::MAX);
+        assert!(full < i32::MAX);
+
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2300..2700).contains(&hits), "{hits}");
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+
+    #[test]
+    fn nearby_seeds_diverge() {
+        let a = Pcg32::from_seed(1).next_u64();
+        let b = Pcg32::from_seed(2).next_u64();
+        assert_ne!(a, b);
+        assert_eq!(Pcg32::from_seed(1).next_u64(), a);
+    }
+
+    #[test]
+    fn hashes_are_stateless_and_order_sensitive() {
+        assert_eq!(hash_rand_u64(1, 2, 3), hash_rand_u64(1, 2, 3));
+        assert_ne!(hash_rand_u64(1, 2, 3), hash_rand_u64(1, 3, 2));
+        assert_ne!(hash_rand_u64(1, 2, 3), hash_rand_u64(2, 2, 3));
+
+        let mut sum = 0.0;
+        for tick in 0..1000 {
+            let r = hash_rand(9, 4, tick);
+            assert!((0.0..1.0).contains(&r));
+            sum += r;
+        }
+        assert!((sum / 1000.0 - 0.5).abs() <= 0.05 as Scalar);
+
+        let v = hash_rand_vec3(9, 4, 0);
+        for c in v.to_array() {
+            assert!((-1.0..1.0).contains(&c));
+        }
+        assert_eq!(v, hash_rand_vec3(9, 4, 0));
+        assert_ne!(hash_rand_vec2(9, 4, 0), hash_rand_vec2(9, 5, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn empty_range_panics() {
+        Pcg32::from_seed(0).range_u32(3..3);
+    }
+}
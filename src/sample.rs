@@ -0,0 +1,357 @@
+//! Uniformly distributed random points in and on shapes.
+//!
+//! Picking a radius and angle uniformly, or a box point and normalizing it, bunches points
+//! near the center or the box's corners. These functions transform the random numbers so every
+//! area (or volume) is equally likely, which is what spawn placement and scatter effects want.
+//!
+//! Each takes a [`Source`] of uniform numbers in `[0, 1)`: a [`Pcg32`] or any `FnMut() -> Scalar`.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{Circle, Pcg32, Vec2, sample};
+//!
+//! let mut rng = Pcg32::from_seed(7);
+//! let camp = Circle::new(Vec2::new(50.0, 20.0), 8.0);
+//! let spawn = sample::in_circle(&mut rng, &camp);
+//! assert!(camp.contains_point(spawn));
+//!
+//! // A closure works too, for example to replay fixed numbers in a test.
+//! let center = sample::in_circle(&mut || 0.0, &camp);
+//! assert_eq!(center, camp.center);
+//! ```
+
+use crate::{Aabb2, Aabb3, Circle, Pcg32, Scalar, Sphere, Triangle3, Vec2, Vec3, consts, float};
+use alloc::vec::Vec;
+
+/// A source of uniform random numbers in `[0, 1)`.
+pub trait Source {
+    fn next_unit(&mut self) -> Scalar;
+}
+
+impl Source for Pcg32 {
+    #[inline]
+    fn next_unit(&mut self) -> Scalar {
+        self.next_scalar()
+    }
+}
+
+impl<F: FnMut() -> Scalar> Source for F {
+    #[inline]
+    fn next_unit(&mut self) -> Scalar {
+        self()
+    }
+}
+
+/// Returns a point on the unit circle.
+#[inline]
+pub fn unit_circle(rng: &mut impl Source) -> Vec2 {
+    let (sin, cos) = float::sin_cos(consts::TAU * rng.next_unit());
+    Vec2::new(cos, sin)
+}
+
+/// Returns a point on the unit sphere.
+#[inline]
+pub fn unit_sphere(rng: &mut impl Source) -> Vec3 {
+    // Archimedes: height along an axis is uniform on a sphere's surface.
+    let z = 1.0 - 2.0 * rng.next_unit();
+    let ring = float::sqrt((1.0 - z * z).max(0.0));
+    let (sin, cos) = float::sin_cos(consts::TAU * rng.next_unit());
+    Vec3::new(ring * cos, ring * sin, z)
+}
+
+/// Returns a point on the boundary of `circle`.
+#[inline]
+pub fn on_circle(rng: &mut impl Source, circle: &Circle) -> Vec2 {
+    circle.center + unit_circle(rng) * circle.radius
+}
+
+/// Returns a point inside `circle`, that is, in its disk.
+#[inline]
+pub fn in_circle(rng: &mut impl Source, circle: &Circle) -> Vec2 {
+    let r = circle.radius * float::sqrt(rng.next_unit());
+    circle.center + unit_circle(rng) * r
+}
+
+/// Returns a point in the ring around `center` between `inner_radius` and `outer_radius`.
+#[inline]
+pub fn in_annulus(
+    rng: &mut impl Source,
+    center: Vec2,
+    inner_radius: Scalar,
+    outer_radius: Scalar,
+) -> Vec2 {
+    let inner = inner_radius * inner_radius;
+    let outer = outer_radius * outer_radius;
+    let r = float::sqrt(inner + (outer - inner) * rng.next_unit());
+    center + unit_circle(rng) * r
+}
+
+/// Returns a point on the surface of `sphere`.
+#[inline]
+pub fn on_sphere(rng: &mut impl Source, sphere: &Sphere) -> Vec3 {
+    sphere.center + unit_sphere(rng) * sphere.radius
+}
+
+/// Returns a point inside `sphere`.
+#[inline]
+pub fn in_sphere(rng: &mut impl Source, sphere: &Sphere) -> Vec3 {
+    let r = sphere.radius * float::cbrt(rng.next_unit());
+    sphere.center + unit_sphere(rng) * r
+}
+
+/// Returns a point inside `aabb`.
+#[inline]
+pub fn in_aabb2(rng: &mut impl Source, aabb: &Aabb2) -> Vec2 {
+    aabb.point_at(Vec2::new(rng.next_unit(), rng.next_unit()))
+}
+
+/// Returns a point inside `aabb`.
+#[inline]
+pub fn in_aabb3(rng: &mut impl Source, aabb: &Aabb3) -> Vec3 {
+    aabb.point_at(Vec3::new(rng.next_unit(), rng.next_unit(), rng.next_unit()))
+}
+
+/// Returns barycentric weights for a uniform point in a triangle; they are non-negative and
+/// sum to one.
+#[inline]
+pub fn triangle_weights(rng: &mut impl Source) -> (Scalar, Scalar, Scalar) {
+    let (mut u, mut v) = (rng.next_unit(), rng.next_unit());
+    // Points in the far half of the unit square fold back into the triangle.
+    if u + v > 1.0 {
+        (u, v) = (1.0 - u, 1.0 - v);
+    }
+    (1.0 - u - v, u, v)
+}
+
+/// Returns a point inside the 2D triangle `a`, `b`, `c`.
+#[inline]
+pub fn in_triangle2(rng: &mut impl Source, a: Vec2, b: Vec2, c: Vec2) -> Vec2 {
+    let (wa, wb, wc) = triangle_weights(rng);
+    a * wa + b * wb + c * wc
+}
+
+/// Returns a point on `triangle`.
+#[inline]
+pub fn in_triangle(rng: &mut impl Source, triangle: &Triangle3) -> Vec3 {
+    let (wa, wb, wc) = triangle_weights(rng);
+    triangle.a * wa + triangle.b * wb + triangle.c * wc
+}
+
+/// The most cells [`jittered_grid`] will fill, so a tiny `spacing` can't exhaust memory.
+pub const MAX_GRID_CELLS: usize = 1 << 24;
+
+/// Returns one point per `spacing`-sized cell of a grid over `aabb`, each offset from its
+/// cell's center by up to `jitter` times half the cell, in row-major order from `aabb.min`.
+///
+/// A `jitter` of `0` is a regular grid and `1` lets points reach anywhere in their cell.
+/// Points are never closer than the gap the jitter leaves between cells, and the grid
+/// covers large spawn areas in linear time, where Poisson disk sampling does far more work.
+/// Each of `relax_iterations` then pushes apart neighbours nearer than `spacing`, keeping
+/// every point in its own cell, for a more even, blue-noise-like spread. Cells on the far
+/// edges are clipped to `aabb`. A `spacing` that isn't finite and positive, an empty or
+/// unbounded `aabb`, or a grid of more than [`MAX_GRID_CELLS`] cells gives no points.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Aabb2, Pcg32, Vec2, sample};
+///
+/// let mut rng = Pcg32::from_seed(12);
+/// let forest = Aabb2::new(Vec2::ZERO, Vec2::new(200.0, 100.0));
+/// let trees = sample::jittered_grid(&mut rng, &forest, 5.0, 0.8, 2);
+/// assert_eq!(trees.len(), 40 * 20);
+/// assert!(trees.iter().all(|&t| forest.contains_point(t)));
+/// ```
+pub fn jittered_grid(
+    rng: &mut impl Source,
+    aabb: &Aabb2,
+    spacing: Scalar,
+    jitter: Scalar,
+    relax_iterations: u32,
+) -> Vec<Vec2> {
+    let size = aabb.max - aabb.min;
+    if !(spacing.is_finite() && spacing > 0.0 && size.x > 0.0 && size.y > 0.0) {
+        return Vec::new();
+    }
+    let columns = float::ceil(size.x / spacing);
+    let rows = float::ceil(size.y / spacing);
+    // Also true for an unbounded `aabb`, whose cell counts are infinite.
+    if columns * rows > MAX_GRID_CELLS as Scalar {
+        return Vec::new();
+    }
+    let (columns, rows) = (columns as usize, rows as usize);
+    let cell = |column: usize, row: usize| {
+        let min = aabb.min + Vec2::new(column as Scalar, row as Scalar) * spacing;
+        Aabb2::new(min, (min + Vec2::splat(spacing)).min(aabb.max))
+    };
+
+    let mut points = Vec::with_capacity(columns * rows);
+    for row in 0..rows {
+        for column in 0..columns {
+            let cell = cell(column, row);
+            let reach = cell.half_extents() * jitter;
+            let offset = Vec2::new(
+                reach.x * (rng.next_unit() * 2.0 - 1.0),
+                reach.y * (rng.next_unit() * 2.0 - 1.0),
+            );
+            points.push(cell.closest_point(cell.center() + offset));
+        }
+    }
+
+    for _ in 0..relax_iterations {
+        let previous = points.clone();
+        for row in 0..rows {
+            for column in 0..columns {
+                let point = previous[row * columns + column];
+                let mut push = Vec2::ZERO;
+                for other_row in row.saturating_sub(1)..(row + 2).min(rows) {
+                    for other_column in column.saturating_sub(1)..(column + 2).min(columns) {
+                        let away = point - previous[other_row * columns + other_column];
+                        let distance = away.length();
+                        if distance > 0.0 && distance < spacing {
+                            // A quarter of the overlap per neighbour, so a crowded point
+                            // doesn't overshoot.
+                            push += away * (0.25 * (spacing - distance) / distance);
+                        }
+                    }
+                }
+                points[row * columns + column] = cell(column, row).closest_point(point + push);
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+    const SAMPLES: usize = 4000;
+
+    #[test]
+    fn boundaries_are_hit_exactly() {
+        let mut rng = Pcg32::from_seed(1);
+        let circle = Circle::new(Vec2::new(3.0, -1.0), 2.0);
+        let sphere = Sphere::new(Vec3::new(0.0, 1.0, 2.0), 0.5);
+        for _ in 0..100 {
+            let p = on_circle(&mut rng, &circle);
+            assert!((p.distance(circle.center) - 2.0).abs() <= EPS);
+            let p = on_sphere(&mut rng, &sphere);
+            assert!((p.distance(sphere.center) - 0.5).abs() <= EPS);
+        }
+    }
+
+    #[test]
+    fn disk_and_ball_are_not_clustered() {
+        let mut rng = Pcg32::from_seed(2);
+        let circle = Circle::new(Vec2::ZERO, 1.0);
+        let sphere = Sphere::new(Vec3::ZERO, 1.0);
+        let mut inner_disk = 0;
+        let mut inner_ball = 0;
+        for _ in 0..SAMPLES {
+            let p = in_circle(&mut rng, &circle);
+            assert!(p.length() <= 1.0);
+            inner_disk += (p.length() < 0.5) as usize;
+            let p = in_sphere(&mut rng, &sphere);
+            assert!(p.length() <= 1.0);
+            inner_ball += (p.length() < 0.5) as usize;
+        }
+        // Half the radius holds a quarter of the disk's area and an eighth of the ball's volume.
+        assert!((900..1100).contains(&inner_disk), "{inner_disk}");
+        assert!((420..580).contains(&inner_ball), "{inner_ball}");
+    }
+
+    #[test]
+    fn sphere_surface_covers_both_hemispheres_evenly() {
+        let mut rng = Pcg32::from_seed(3);
+        let upper = (0..SAMPLES)
+            .filter(|_| unit_sphere(&mut rng).z > 0.5)
+            .count();
+        // The cap above z = 0.5 is a quarter of the surface.
+        assert!((900..1100).contains(&upper), "{upper}");
+    }
+
+    #[test]
+    fn annulus_stays_in_its_ring() {
+        let mut rng = Pcg32::from_seed(4);
+        let mut outer_half = 0;
+        for _ in 0..SAMPLES {
+            let p = in_annulus(&mut rng, Vec2::new(1.0, 1.0), 2.0, 4.0);
+            let r = p.distance(Vec2::new(1.0, 1.0));
+            assert!((2.0 - EPS..=4.0 + EPS).contains(&r), "{r}");
+            outer_half += (r > 3.0) as usize;
+        }
+        // The ring from 3 to 4 holds 7/12 of the area.
+        assert!((2220..2450).contains(&outer_half), "{outer_half}");
+    }
+
+    #[test]
+    fn boxes_and_triangles_contain_their_samples() {
+        let mut rng = Pcg32::from_seed(5);
+        let rect = Aabb2::new(Vec2::new(-1.0, 2.0), Vec2::new(3.0, 4.0));
+        let cube = Aabb3::new(Vec3::ZERO, Vec3::new(1.0, 2.0, 3.0));
+        let triangle = Triangle3::new(
+            Vec3::ZERO,
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        );
+        let mut centroid = Vec3::ZERO;
+        for _ in 0..SAMPLES {
+            assert!(rect.contains_point(in_aabb2(&mut rng, &rect)));
+            assert!(cube.contains_point(in_aabb3(&mut rng, &cube)));
+            let p = in_triangle(&mut rng, &triangle);
+            assert!(p.x >= 0.0 && p.y >= 0.0 && p.x + p.y <= 2.0 + EPS && p.z == 0.0);
+            centroid += p;
+        }
+        // The mean of uniform samples approaches the centroid, (2/3, 2/3).
+        centroid /= SAMPLES as Scalar;
+        assert!((centroid.x - 2.0 / 3.0).abs() <= 0.05, "{centroid:?}");
+        assert!((centroid.y - 2.0 / 3.0).abs() <= 0.05, "{centroid:?}");
+
+        let (a, b, c) = (Vec2::ZERO, Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+        let p = in_triangle2(&mut || 0.9, a, b, c);
+        assert!((p - Vec2::new(0.1, 0.1)).length() <= EPS);
+    }
+
+    #[test]
+    fn jittered_grid_keeps_one_point_per_cell() {
+        let mut rng = Pcg32::from_seed(9);
+        let area = Aabb2::new(Vec2::new(-10.0, 5.0), Vec2::new(11.0, 15.0));
+        let points = jittered_grid(&mut rng, &area, 2.0, 1.0, 0);
+        // 10.5 by 5 cells, the last column clipped.
+        assert_eq!(points.len(), 11 * 5);
+        for (i, p) in points.iter().enumerate() {
+            let (column, row) = (i % 11, i / 11);
+            let cell = Vec2::new(-10.0 + 2.0 * column as Scalar, 5.0 + 2.0 * row as Scalar);
+            assert!((cell.x..=cell.x + 2.0).contains(&p.x), "{i} {p:?}");
+            assert!((cell.y..=cell.y + 2.0).contains(&p.y), "{i} {p:?}");
+            assert!(area.contains_point(*p));
+        }
+
+        let regular = jittered_grid(&mut rng, &area, 2.0, 0.0, 0);
+        assert_eq!(regular[0], Vec2::new(-9.0, 6.0));
+        assert_eq!(regular[10], Vec2::new(10.5, 6.0));
+        assert!(jittered_grid(&mut rng, &area, 0.0, 1.0, 0).is_empty());
+        assert!(jittered_grid(&mut rng, &area, Scalar::NAN, 1.0, 0).is_empty());
+        assert!(jittered_grid(&mut rng, &area, Scalar::INFINITY, 1.0, 0).is_empty());
+        assert!(jittered_grid(&mut rng, &area, 1.0e-6, 1.0, 0).is_empty());
+    }
+
+    #[test]
+    fn relaxation_spreads_close_pairs_apart() {
+        let area = Aabb2::new(Vec2::ZERO, Vec2::splat(60.0));
+        let closest = |points: &[Vec2]| {
+            let mut closest = Scalar::MAX;
+            for (i, a) in points.iter().enumerate() {
+                for b in &points[i + 1..] {
+                    closest = closest.min(a.distance(*b));
+                }
+            }
+            closest
+        };
+        let raw = jittered_grid(&mut Pcg32::from_seed(5), &area, 3.0, 1.0, 0);
+        let relaxed = jittered_grid(&mut Pcg32::from_seed(5), &area, 3.0, 1.0, 4);
+        assert!(closest(&relaxed) > closest(&raw) * 2.0);
+        assert!(relaxed.iter().all(|&p| area.contains_point(p)));
+    }
+}
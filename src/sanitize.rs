@@ -0,0 +1,257 @@
+//! Sanitization of client-supplied values before they reach game state.
+//!
+//! Reducers receive whatever a client sends, including NaN, infinities and quaternions that
+//! were never normalized. These functions either return a value that is safe to store, clamped
+//! into range where that is a reasonable correction, or a [`SanitizeError`] saying why the
+//! input was rejected.
+
+use crate::interp::ComponentWise;
+use crate::{Quat, Scalar, Vec2, Vec3};
+use core::fmt;
+use core::ops::{Div, Mul};
+
+/// Why client input was rejected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SanitizeError {
+    /// A component was NaN or infinite.
+    NotFinite,
+    /// A quaternion's length was further from `1` than the tolerance allows.
+    NotNormalized {
+        /// The quaternion's actual length.
+        length: Scalar,
+    },
+}
+
+impl fmt::Display for SanitizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanitizeError::NotFinite => f.write_str("value is NaN or infinite"),
+            SanitizeError::NotNormalized { length } => {
+                write!(f, "quaternion is not normalized (length {length})")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SanitizeError {}
+
+/// Rejects a non-finite `value`, otherwise clamps it into `[min, max]`.
+#[inline]
+pub fn scalar(value: Scalar, min: Scalar, max: Scalar) -> Result<Scalar, SanitizeError> {
+    finite(value)?;
+    Ok(clamp(value, min, max))
+}
+
+/// Rejects a non-finite `v`, otherwise shortens it to at most `max_length`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{sanitize, Scalar, Vec2};
+///
+/// let input = sanitize::vec2(Vec2::new(3.0, 4.0), 1.0).unwrap();
+/// assert!((input.length() - 1.0).abs() < 1.0e-6);
+/// let poisoned = Vec2 { x: Scalar::NAN, y: 0.0 };
+/// assert!(sanitize::vec2(poisoned, 1.0).is_err());
+/// ```
+pub fn vec2(v: Vec2, max_length: Scalar) -> Result<Vec2, SanitizeError> {
+    finite(v.x)?;
+    finite(v.y)?;
+    let scale = v.x.abs().max(v.y.abs());
+    Ok(clamp_length(v, scale, max_length, |v| v.length()))
+}
+
+/// Rejects a non-finite `v`, otherwise shortens it to at most `max_length`.
+pub fn vec3(v: Vec3, max_length: Scalar) -> Result<Vec3, SanitizeError> {
+    finite(v.x)?;
+    finite(v.y)?;
+    finite(v.z)?;
+    let scale = v.x.abs().max(v.y.abs()).max(v.z.abs());
+    Ok(clamp_length(v, scale, max_length, |v| v.length()))
+}
+
+/// Rejects a non-finite `v`, otherwise clamps each component into the box from `min` to
+/// `max`.
+pub fn vec2_in(v: Vec2, min: Vec2, max: Vec2) -> Result<Vec2, SanitizeError> {
+    finite(v.x)?;
+    finite(v.y)?;
+    Ok(v.map3(min, max, clamp))
+}
+
+/// Rejects a non-finite `v`, otherwise clamps each component into the box from `min` to
+/// `max`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{sanitize, Vec3};
+///
+/// let world_min = Vec3::new(-100.0, 0.0, -100.0);
+/// let world_max = Vec3::new(100.0, 50.0, 100.0);
+/// let spawn = sanitize::vec3_in(Vec3::new(20.0, -3.0, 500.0), world_min, world_max);
+/// assert_eq!(spawn, Ok(Vec3::new(20.0, 0.0, 100.0)));
+/// ```
+pub fn vec3_in(v: Vec3, min: Vec3, max: Vec3) -> Result<Vec3, SanitizeError> {
+    finite(v.x)?;
+    finite(v.y)?;
+    finite(v.z)?;
+    Ok(v.map3(min, max, clamp))
+}
+
+/// Rejects a non-finite `q` or one whose length differs from `1` by more than `tolerance`,
+/// otherwise returns it renormalized.
+///
+/// A small tolerance such as `1e-3` absorbs the rounding of a client's own math and of
+/// network encodings while still rejecting garbage.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{sanitize, Quat};
+///
+/// assert!(sanitize::quat(Quat::new(0.0, 0.0, 0.0, 1.0005), 1.0e-3).is_ok());
+/// assert!(sanitize::quat(Quat::new(0.0, 0.0, 0.0, 2.0), 1.0e-3).is_err());
+/// ```
+pub fn quat(q: Quat, tolerance: Scalar) -> Result<Quat, SanitizeError> {
+    finite(q.x)?;
+    finite(q.y)?;
+    finite(q.z)?;
+    finite(q.w)?;
+    let length = q.length();
+    if (length - 1.0).abs() > tolerance || length <= 0.0 {
+        return Err(SanitizeError::NotNormalized { length });
+    }
+    let inv = 1.0 / length;
+    Ok(Quat::new(q.x * inv, q.y * inv, q.z * inv, q.w * inv))
+}
+
+#[inline]
+fn finite(value: Scalar) -> Result<(), SanitizeError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(SanitizeError::NotFinite)
+    }
+}
+
+/// Shortens `v` to at most `max_length`, given its largest component magnitude `scale`.
+///
+/// `v` is measured after dividing by `scale`, since its length can overflow even when every
+/// component is finite.
+#[inline]
+fn clamp_length<V>(v: V, scale: Scalar, max_length: Scalar, length: impl Fn(V) -> Scalar) -> V
+where
+    V: Copy + Div<Scalar, Output = V> + Mul<Scalar, Output = V>,
+{
+    if scale == 0.0 {
+        return v;
+    }
+    let unit = v / scale;
+    let unit_length = length(unit);
+    if scale * unit_length <= max_length {
+        v
+    } else {
+        unit * (max_length.max(0.0) / unit_length)
+    }
+}
+
+/// Like `Scalar::clamp`, but doesn't panic on an inverted range.
+#[inline]
+fn clamp(value: Scalar, min: Scalar, max: Scalar) -> Scalar {
+    value.max(min).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn non_finite_input_is_rejected() {
+        // Literals, since `Vec3::new` rejects these under the `validate` feature.
+        for bad in [Scalar::NAN, Scalar::INFINITY, Scalar::NEG_INFINITY] {
+            assert_eq!(scalar(bad, 0.0, 1.0), Err(SanitizeError::NotFinite));
+            assert_eq!(
+                vec2(Vec2 { x: 0.0, y: bad }, 1.0),
+                Err(SanitizeError::NotFinite)
+            );
+            assert_eq!(
+                vec3_in(
+                    Vec3 {
+                        x: bad,
+                        ..Vec3::ZERO
+                    },
+                    Vec3::ZERO,
+                    Vec3::ONE
+                ),
+                Err(SanitizeError::NotFinite)
+            );
+            assert_eq!(
+                quat(
+                    Quat {
+                        z: bad,
+                        ..Quat::IDENTITY
+                    },
+                    1.0
+                ),
+                Err(SanitizeError::NotFinite)
+            );
+        }
+    }
+
+    #[test]
+    fn magnitudes_and_components_are_clamped() {
+        assert_eq!(scalar(7.0, 0.0, 5.0), Ok(5.0));
+        assert_eq!(scalar(-1.0, 0.0, 5.0), Ok(0.0));
+        let v = vec3(Vec3::new(0.0, 0.0, -10.0), 2.0).unwrap();
+        assert!(v.distance(Vec3::new(0.0, 0.0, -2.0)) <= EPS);
+        assert_eq!(
+            vec3(Vec3::new(1.0, 0.0, 0.0), 2.0),
+            Ok(Vec3::new(1.0, 0.0, 0.0))
+        );
+        assert_eq!(vec3(Vec3::new(1.0, 0.0, 0.0), -1.0), Ok(Vec3::ZERO));
+        assert_eq!(vec3(Vec3::ZERO, 0.0), Ok(Vec3::ZERO));
+        // The length of these overflows, though every component is finite.
+        let huge = vec3(Vec3::splat(Scalar::MAX), 3.0).unwrap();
+        assert!(huge.distance(Vec3::splat(3.0 / (3.0 as Scalar).sqrt())) <= EPS);
+        let huge = vec2(Vec2::new(Scalar::MAX, -Scalar::MAX), 2.0).unwrap();
+        assert!(huge.distance(Vec2::new(1.0, -1.0) * (2.0 as Scalar).sqrt()) <= EPS);
+        assert_eq!(
+            vec2_in(
+                Vec2::new(-5.0, 5.0),
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, 1.0)
+            ),
+            Ok(Vec2::new(-1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn quaternions_are_renormalized_within_tolerance() {
+        let q = quat(Quat::new(0.0, 0.7072, 0.0, 0.7072), 1.0e-3).unwrap();
+        assert!((q.length() - 1.0).abs() <= EPS);
+        assert_eq!(
+            quat(Quat::new(0.0, 0.0, 0.0, 0.0), 1.0e-3),
+            Err(SanitizeError::NotNormalized { length: 0.0 })
+        );
+        assert_eq!(
+            quat(Quat::new(0.0, 0.0, 0.0, 0.0), 2.0),
+            Err(SanitizeError::NotNormalized { length: 0.0 })
+        );
+        assert!(matches!(
+            quat(Quat::new(0.0, 0.0, 0.0, 1.1), 1.0e-3),
+            Err(SanitizeError::NotNormalized { .. })
+        ));
+    }
+
+    #[test]
+    fn errors_describe_the_problem() {
+        assert_eq!(
+            SanitizeError::NotFinite.to_string(),
+            "value is NaN or infinite"
+        );
+        assert!(
+            SanitizeError::NotNormalized { length: 2.0 }
+                .to_string()
+                .contains("length 2")
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use crate::{float, interp};
+use core::ops::Range;
+
 /// The scalar type used throughout this crate.
 #[cfg(feature = "f64")]
 pub use f64 as Scalar;
@@ -5,3 +8,161 @@ pub use f64 as Scalar;
 /// The scalar type used throughout this crate.
 #[cfg(feature = "f32")]
 pub use f32 as Scalar;
+
+/// Everyday helpers on [`Scalar`] that the standard library doesn't provide.
+///
+/// For the same helpers over vectors too, see [`crate::interp`].
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Scalar, ScalarExt};
+///
+/// let health: Scalar = 35.0;
+/// let bar = health.inverse_lerp(0.0, 140.0).clamp01();
+/// assert_eq!(bar, 0.25);
+/// // A door that swings open and shut every two seconds.
+/// assert_eq!((3.5 as Scalar).ping_pong(2.0), 0.5);
+/// // Turn rate limited to 90 degrees per tick.
+/// assert_eq!((10.0 as Scalar).move_towards(200.0, 90.0), 100.0);
+/// ```
+pub trait ScalarExt: Copy {
+    /// Clamps to `[0, 1]`.
+    fn clamp01(self) -> Self;
+
+    /// Linearly interpolates from `self` (at `t = 0`) to `to` (at `t = 1`), without clamping
+    /// `t`.
+    fn lerp(self, to: Self, t: Self) -> Self;
+
+    /// Returns where `self` lies between `a` and `b`: `0` at `a`, `1` at `b`, unclamped; `0`
+    /// if `a == b`.
+    fn inverse_lerp(self, a: Self, b: Self) -> Self;
+
+    /// Maps `self` from `from` onto `to`, extrapolating outside `from`.
+    fn remap(self, from: Range<Self>, to: Range<Self>) -> Self;
+
+    /// Bounces between `0` and `length`: rises from `0` at `0` to `length` at `length`, falls
+    /// back to `0` at `2 * length` and repeats, for negative values too.
+    fn ping_pong(self, length: Self) -> Self;
+
+    /// Returns `self - floor(self)`, in `[0, 1)` even for negative values.
+    ///
+    /// The standard library's `fract` keeps the sign, so `-0.25` gives `-0.25` there and
+    /// `0.75` here; the different name keeps the two from being confused.
+    fn fract_floor(self) -> Self;
+
+    /// Returns `true` if `self` and `other` differ by at most `epsilon`.
+    fn approx_eq(self, other: Self, epsilon: Self) -> bool;
+
+    /// Rounds to the nearest multiple of `step`; a `step` of zero or less leaves the value
+    /// unchanged. See [`crate::canonical::snap`] for snapping before hashing.
+    fn snap(self, step: Self) -> Self;
+
+    /// Moves towards `target` by at most `max_delta`, stopping at `target` instead of
+    /// overshooting. A negative or NaN `max_delta` doesn't move at all.
+    fn move_towards(self, target: Self, max_delta: Self) -> Self;
+}
+
+impl ScalarExt for Scalar {
+    #[inline]
+    fn clamp01(self) -> Scalar {
+        self.clamp(0.0, 1.0)
+    }
+
+    #[inline]
+    fn lerp(self, to: Scalar, t: Scalar) -> Scalar {
+        interp::lerp(self, to, t)
+    }
+
+    #[inline]
+    fn inverse_lerp(self, a: Scalar, b: Scalar) -> Scalar {
+        interp::inverse_lerp(a, b, self)
+    }
+
+    #[inline]
+    fn remap(self, from: Range<Scalar>, to: Range<Scalar>) -> Scalar {
+        interp::remap(self, from, to)
+    }
+
+    #[inline]
+    fn ping_pong(self, length: Scalar) -> Scalar {
+        if length <= 0.0 {
+            return 0.0;
+        }
+        let t = (self / (2.0 * length)).fract_floor() * 2.0 * length;
+        length - (t - length).abs()
+    }
+
+    #[inline]
+    fn fract_floor(self) -> Scalar {
+        let fract = self - float::floor(self);
+        // Tiny negative values round up to exactly 1.0.
+        if fract >= 1.0 { 0.0 } else { fract }
+    }
+
+    #[inline]
+    fn approx_eq(self, other: Scalar, epsilon: Scalar) -> bool {
+        (self - other).abs() <= epsilon
+    }
+
+    #[inline]
+    fn snap(self, step: Scalar) -> Scalar {
+        if step > 0.0 {
+            float::round(self / step) * step
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn move_towards(self, target: Scalar, max_delta: Scalar) -> Scalar {
+        let max_delta = max_delta.max(0.0);
+        let delta = target - self;
+        if delta.abs() <= max_delta {
+            target
+        } else {
+            self + max_delta.copysign(delta)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolation_helpers_round_trip() {
+        let x = 2.5 as Scalar;
+        assert_eq!((1.0 as Scalar).lerp(3.0, x.inverse_lerp(0.0, 5.0)), 2.0);
+        assert_eq!(x.remap(0.0..5.0, 10.0..0.0), 5.0);
+        assert_eq!(x.inverse_lerp(1.0, 1.0), 0.0);
+        assert_eq!((-0.5 as Scalar).clamp01(), 0.0);
+        assert_eq!((1.5 as Scalar).clamp01(), 1.0);
+    }
+
+    #[test]
+    fn periodic_helpers_handle_negative_values() {
+        assert_eq!((-0.25 as Scalar).fract_floor(), 0.75);
+        assert_eq!((3.25 as Scalar).fract_floor(), 0.25);
+        assert_eq!((-1.0e-30 as Scalar).fract_floor(), 0.0);
+        for (x, expected) in [(0.0, 0.0), (1.5, 1.5), (3.0, 1.0), (4.0, 0.0), (-0.5, 0.5)] {
+            assert!(
+                (x as Scalar).ping_pong(2.0).approx_eq(expected, 1.0e-6),
+                "{x}"
+            );
+        }
+        assert_eq!((7.0 as Scalar).ping_pong(0.0), 0.0);
+    }
+
+    #[test]
+    fn stepping_helpers() {
+        assert_eq!((0.37 as Scalar).snap(0.25), 0.25);
+        assert_eq!((-0.9 as Scalar).snap(0.5), -1.0);
+        assert_eq!((0.37 as Scalar).snap(0.0), 0.37);
+        assert_eq!((5.0 as Scalar).move_towards(-5.0, 3.0), 2.0);
+        assert_eq!((5.0 as Scalar).move_towards(6.0, 3.0), 6.0);
+        assert_eq!((5.0 as Scalar).move_towards(6.0, -3.0), 5.0);
+        assert_eq!((5.0 as Scalar).move_towards(6.0, Scalar::NAN), 5.0);
+        assert!((1.0 as Scalar).approx_eq(1.0 + 1.0e-4, 1.0e-3));
+        assert!(!(1.0 as Scalar).approx_eq(1.1, 1.0e-3));
+    }
+}
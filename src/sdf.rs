@@ -0,0 +1,147 @@
+//! Signed distance functions for primitive shapes, and combinators to build scenes from them.
+//!
+//! Distances are negative inside a shape, zero on its surface and positive outside. Shapes are
+//! centered on the origin; translate the query point into the shape's local space first.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{sdf, Vec3};
+//!
+//! // A safe zone: a sphere with a box-shaped hole carved out of it.
+//! let zone = |p: Vec3| sdf::subtract(sdf::sphere(p, 10.0), sdf::cuboid(p, Vec3::splat(2.0)));
+//! assert!(zone(Vec3::new(5.0, 0.0, 0.0)) < 0.0);
+//! assert!(zone(Vec3::ZERO) > 0.0);
+//! ```
+
+use crate::{Scalar, Vec2, Vec3, interp};
+
+/// Distance to a sphere of `radius`.
+#[inline]
+pub fn sphere(p: Vec3, radius: Scalar) -> Scalar {
+    p.length() - radius
+}
+
+/// Distance to a box with the given `half_extents`.
+pub fn cuboid(p: Vec3, half_extents: Vec3) -> Scalar {
+    let q = Vec3::new(p.x.abs(), p.y.abs(), p.z.abs()) - half_extents;
+    let outside = q.max(Vec3::ZERO).length();
+    let inside = q.x.max(q.y).max(q.z).min(0.0);
+    outside + inside
+}
+
+/// Distance to a box with the given `half_extents` whose edges are rounded by `radius`.
+///
+/// The rounding is added on the outside, so the box grows by `radius` on every side.
+#[inline]
+pub fn rounded_cuboid(p: Vec3, half_extents: Vec3, radius: Scalar) -> Scalar {
+    cuboid(p, half_extents) - radius
+}
+
+/// Distance to the capsule around the segment from `a` to `b` with `radius`.
+pub fn capsule(p: Vec3, a: Vec3, b: Vec3, radius: Scalar) -> Scalar {
+    let pa = p - a;
+    let ba = b - a;
+    let len_sq = ba.length_squared();
+    let h = if len_sq > 0.0 {
+        (pa.dot(ba) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (pa - ba * h).length() - radius
+}
+
+/// Distance to a torus lying in the XZ plane around the Y axis.
+///
+/// `major_radius` is the distance from the center to the middle of the tube and
+/// `minor_radius` the radius of the tube.
+pub fn torus(p: Vec3, major_radius: Scalar, minor_radius: Scalar) -> Scalar {
+    let q = Vec2::new(p.xz().length() - major_radius, p.y);
+    q.length() - minor_radius
+}
+
+/// Distance to the union of two shapes.
+#[inline]
+pub fn union(a: Scalar, b: Scalar) -> Scalar {
+    a.min(b)
+}
+
+/// Distance to the intersection of two shapes.
+#[inline]
+pub fn intersect(a: Scalar, b: Scalar) -> Scalar {
+    a.max(b)
+}
+
+/// Distance to shape `a` with shape `b` carved out of it.
+#[inline]
+pub fn subtract(a: Scalar, b: Scalar) -> Scalar {
+    a.max(-b)
+}
+
+/// Smooth minimum of `a` and `b`, blending over a band of width `k`.
+///
+/// Uses the polynomial smooth-min, which equals `a.min(b)` once the inputs are more than `k`
+/// apart. A `k` of zero is a plain minimum.
+#[inline]
+pub fn smooth_min(a: Scalar, b: Scalar, k: Scalar) -> Scalar {
+    interp::smin(a, b, k)
+}
+
+/// Union of two shapes with the seam rounded over a band of width `k`.
+#[inline]
+pub fn smooth_union(a: Scalar, b: Scalar, k: Scalar) -> Scalar {
+    smooth_min(a, b, k)
+}
+
+/// Intersection of two shapes with the seam rounded over a band of width `k`.
+#[inline]
+pub fn smooth_intersect(a: Scalar, b: Scalar, k: Scalar) -> Scalar {
+    -smooth_min(-a, -b, k)
+}
+
+/// Shape `a` with `b` carved out of it, the seam rounded over a band of width `k`.
+#[inline]
+pub fn smooth_subtract(a: Scalar, b: Scalar, k: Scalar) -> Scalar {
+    -smooth_min(-a, b, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn primitives_are_signed() {
+        assert_eq!(sphere(Vec3::new(3.0, 4.0, 0.0), 2.0), 3.0 as Scalar);
+        assert_eq!(sphere(Vec3::ZERO, 2.0), -2.0 as Scalar);
+
+        let half = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(cuboid(Vec3::new(4.0, 6.0, 0.0), half), 5.0 as Scalar);
+        assert_eq!(cuboid(Vec3::new(0.5, 0.0, 0.0), half), -0.5 as Scalar);
+        assert_eq!(
+            rounded_cuboid(Vec3::new(2.0, 0.0, 0.0), half, 0.5),
+            0.5 as Scalar
+        );
+
+        let a = Vec3::ZERO;
+        let b = Vec3::new(0.0, 2.0, 0.0);
+        assert_eq!(capsule(Vec3::new(1.0, 1.0, 0.0), a, b, 0.5), 0.5 as Scalar);
+        assert_eq!(capsule(Vec3::new(0.0, 4.0, 0.0), a, b, 0.5), 1.5 as Scalar);
+
+        assert!((torus(Vec3::new(0.0, 0.0, 3.0), 3.0, 0.5) + 0.5).abs() <= EPS);
+        assert!((torus(Vec3::ZERO, 3.0, 0.5) - 2.5).abs() <= EPS);
+    }
+
+    #[test]
+    fn combinators() {
+        assert_eq!(union(1.0, -2.0), -2.0 as Scalar);
+        assert_eq!(intersect(1.0, -2.0), 1.0 as Scalar);
+        assert_eq!(subtract(-3.0, -1.0), 1.0 as Scalar);
+        // Far apart the smooth versions match the sharp ones; close together they bulge.
+        assert_eq!(smooth_union(1.0, 5.0, 0.5), 1.0 as Scalar);
+        assert!((smooth_min(1.0, 1.0, 0.5) - 0.875).abs() <= EPS);
+        assert!((smooth_intersect(1.0, 1.0, 0.5) - 1.125).abs() <= EPS);
+        assert!((smooth_subtract(1.0, -1.0, 0.5) - 1.125).abs() <= EPS);
+        assert_eq!(smooth_min(1.0, 2.0, 0.0), 1.0 as Scalar);
+    }
+}
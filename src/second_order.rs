@@ -0,0 +1,226 @@
+use crate::{Quat, Scalar, Vec2, Vec3, consts, float};
+use core::ops::{Add, Mul, Sub};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A value [`SecondOrderDynamics`] can follow.
+pub trait SecondOrderValue:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Scalar, Output = Self>
+{
+    /// Returns the representation of `self` that is closest to `current`, for types where one
+    /// value has several representations.
+    #[inline]
+    fn align_to(self, current: Self) -> Self {
+        let _ = current;
+        self
+    }
+
+    /// Projects a freshly integrated value back onto the valid set, e.g. unit quaternions.
+    #[inline]
+    fn renormalize(self) -> Self {
+        self
+    }
+}
+
+impl SecondOrderValue for Scalar {}
+impl SecondOrderValue for Vec2 {}
+impl SecondOrderValue for Vec3 {}
+
+impl SecondOrderValue for Quat {
+    /// Flips `self` into the hemisphere of `current`, so rotations follow the short arc.
+    #[inline]
+    fn align_to(self, current: Self) -> Self {
+        if self.dot(current) < 0.0 { -self } else { self }
+    }
+
+    #[inline]
+    fn renormalize(self) -> Self {
+        self.normalize_or_identity(0.0)
+    }
+}
+
+/// A second-order system that follows a moving target, shaped by three parameters:
+///
+/// - `frequency` (hertz): how quickly the system responds and how fast it oscillates.
+/// - `damping`: `0` vibrates forever, below `1` overshoots, `1` is critically damped and above
+///   `1` settles more slowly without overshooting.
+/// - `response`: `0` eases in from rest, `1` reacts immediately, above `1` overshoots the start
+///   of a movement (`2` follows a steadily moving target without lagging behind) and below `0`
+///   anticipates it by first moving the other way.
+///
+/// [`SecondOrderDynamics::update`] estimates the target's velocity from its previous value
+/// and integrates with constants matched to `dt`, which keeps long or uneven ticks stable.
+/// The state is plain data, so it can live in a table row next to the entity it animates.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{SecondOrderDynamics, Vec3};
+///
+/// let mut turret_aim = SecondOrderDynamics::new(Vec3::ZERO, 1.5, 0.7, 2.0);
+/// let target = Vec3::new(4.0, 0.0, 0.0);
+/// for _ in 0..120 {
+///     turret_aim.update(target, 1.0 / 20.0);
+/// }
+/// assert!(turret_aim.position.distance(target) < 1.0e-3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecondOrderDynamics<T> {
+    pub position: T,
+    pub velocity: T,
+    /// Natural frequency in hertz.
+    pub frequency: Scalar,
+    /// Damping ratio.
+    pub damping: Scalar,
+    /// Initial response to a change in the target.
+    pub response: Scalar,
+    /// Target from the previous update, used to estimate the target's velocity.
+    pub previous_target: T,
+}
+
+impl<T: SecondOrderValue> SecondOrderDynamics<T> {
+    /// Creates a system at rest at `position`.
+    #[inline]
+    pub fn new(position: T, frequency: Scalar, damping: Scalar, response: Scalar) -> Self {
+        SecondOrderDynamics {
+            position,
+            velocity: position * 0.0,
+            frequency,
+            damping,
+            response,
+            previous_target: position,
+        }
+    }
+
+    /// Puts the system at rest at `position`, e.g. after a teleport.
+    #[inline]
+    pub fn reset(&mut self, position: T) {
+        self.position = position;
+        self.velocity = position * 0.0;
+        self.previous_target = position;
+    }
+
+    /// Advances the system by `dt` seconds towards `target` and returns the new position.
+    ///
+    /// The target's velocity is estimated from the target passed to the previous update. A
+    /// non-positive `dt` leaves the system unchanged.
+    pub fn update(&mut self, target: T, dt: Scalar) -> T {
+        if dt <= 0.0 {
+            return self.position;
+        }
+        let target = target.align_to(self.position);
+        let target_velocity = (target - self.previous_target.align_to(self.position)) * (1.0 / dt);
+        self.update_with_velocity(target, target_velocity, dt)
+    }
+
+    /// Advances the system by `dt` seconds towards `target`, which is moving at
+    /// `target_velocity`, and returns the new position.
+    ///
+    /// A non-positive `dt` leaves the system unchanged.
+    pub fn update_with_velocity(&mut self, target: T, target_velocity: T, dt: Scalar) -> T {
+        if dt <= 0.0 {
+            return self.position;
+        }
+        let target = target.align_to(self.position);
+        self.previous_target = target;
+
+        let omega = 2.0 * consts::PI * self.frequency;
+        let zeta = self.damping;
+        let k1 = zeta / (consts::PI * self.frequency);
+        let k2 = 1.0 / (omega * omega);
+        let k3 = self.response * zeta / omega;
+        // Match the discrete poles to the continuous ones when the step is long enough to
+        // destabilize the naive integrator, otherwise just clamp `k2`.
+        let (k1, k2) = if omega * dt < zeta {
+            (k1, k2.max(dt * dt / 2.0 + dt * k1 / 2.0).max(dt * k1))
+        } else {
+            let t1 = float::exp(-zeta * omega * dt);
+            let d = omega * float::sqrt((zeta * zeta - 1.0).abs());
+            let alpha = if zeta <= 1.0 {
+                2.0 * t1 * float::cos(dt * d)
+            } else {
+                2.0 * t1 * float::cosh(dt * d)
+            };
+            let beta = t1 * t1;
+            let t2 = dt / (1.0 + beta - alpha);
+            ((1.0 - beta) * t2, dt * t2)
+        };
+
+        self.position = (self.position + self.velocity * dt).renormalize();
+        let acceleration =
+            (target + target_velocity * k3 - self.position - self.velocity * k1) * (1.0 / k2);
+        self.velocity = self.velocity + acceleration * dt;
+        self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-3 as Scalar;
+
+    fn settle<T: SecondOrderValue>(system: &mut SecondOrderDynamics<T>, target: T, dt: Scalar) {
+        for _ in 0..(20.0 / dt) as usize {
+            system.update(target, dt);
+        }
+    }
+
+    #[test]
+    fn settles_on_the_target_for_any_tick_length() {
+        for dt in [1.0 / 60.0 as Scalar, 0.1, 0.5, 2.0] {
+            let mut system = SecondOrderDynamics::new(0.0 as Scalar, 2.0, 0.5, 0.0);
+            settle(&mut system, 3.0, dt);
+            assert!((system.position - 3.0).abs() <= EPS, "dt = {dt}");
+            assert!(system.position.is_finite());
+        }
+    }
+
+    #[test]
+    fn damping_and_response_shape_the_motion() {
+        let trace = |damping: Scalar, response: Scalar| {
+            let mut system = SecondOrderDynamics::new(Vec2::ZERO, 1.0, damping, response);
+            let mut min: Scalar = 0.0;
+            let mut max: Scalar = 0.0;
+            for _ in 0..600 {
+                let x = system.update(Vec2::new(1.0, 0.0), 1.0 / 60.0).x;
+                min = min.min(x);
+                max = max.max(x);
+            }
+            (min, max)
+        };
+        let (_, max) = trace(0.3, 0.0);
+        assert!(max > 1.2);
+        let (min, max) = trace(1.0, 0.0);
+        assert!(min >= 0.0 && max <= 1.0 + EPS);
+        let (min, _) = trace(1.0, -1.0);
+        assert!(min < 0.0);
+    }
+
+    #[test]
+    fn follows_a_moving_target_without_lag_when_responsive() {
+        // With `response = 2` the estimated target velocity cancels the steady-state lag.
+        let mut system = SecondOrderDynamics::new(Vec3::ZERO, 2.0, 1.0, 2.0);
+        let velocity = Vec3::new(1.0, 0.0, 0.0);
+        let dt = 1.0 / 30.0 as Scalar;
+        let mut target = Vec3::ZERO;
+        for _ in 0..300 {
+            target += velocity * dt;
+            system.update(target, dt);
+        }
+        assert!(system.position.distance(target) <= 1.0e-2 as Scalar);
+        assert!(system.velocity.distance(velocity) <= 1.0e-2 as Scalar);
+    }
+
+    #[test]
+    fn rotations_stay_unit_and_take_the_short_arc() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let mut system = SecondOrderDynamics::new(Quat::IDENTITY, 1.0, 1.0, 0.0);
+        // The same rotation as a quarter turn, written in the far hemisphere.
+        let target = -Quat::from_axis_angle(axis, 1.5);
+        settle(&mut system, target, 1.0 / 30.0);
+        assert!((system.position.length() - 1.0).abs() <= EPS);
+        assert!(system.position.dot(-target) >= 1.0 - EPS);
+    }
+}
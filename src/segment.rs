@@ -0,0 +1,374 @@
+use crate::{Scalar, Vec2, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 2D line segment from `start` to `end`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Segment2, SegmentIntersection, Vec2};
+///
+/// let wall = Segment2::new(Vec2::new(0.0, -1.0), Vec2::new(0.0, 1.0));
+/// let sight = Segment2::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0));
+/// assert!(matches!(
+///     wall.intersect(&sight, 1.0e-6),
+///     SegmentIntersection::Point { .. }
+/// ));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment2 {
+    /// Start point.
+    pub start: Vec2,
+    /// End point.
+    pub end: Vec2,
+}
+
+/// How two 2D segments relate, as returned by [`Segment2::intersect`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentIntersection {
+    /// The segments cross or touch at a single point.
+    ///
+    /// `t` and `u` are the parameters of the point along the first and second segment.
+    Point { point: Vec2, t: Scalar, u: Scalar },
+    /// The segments lie on the same line.
+    ///
+    /// `overlap` is the shared parameter range along the first segment, if any.
+    Collinear { overlap: Option<(Scalar, Scalar)> },
+    /// The segments are parallel and on distinct lines.
+    Parallel,
+    /// The segments are not parallel and do not meet.
+    Disjoint,
+}
+
+impl Segment2 {
+    #[inline(always)]
+    pub const fn new(start: Vec2, end: Vec2) -> Self {
+        Segment2 { start, end }
+    }
+
+    /// Returns the point at parameter `t` (`0.0` is `start`, `1.0` is `end`).
+    #[inline]
+    pub fn at(&self, t: Scalar) -> Vec2 {
+        self.start.lerp(self.end, t)
+    }
+
+    /// Returns the length of the segment.
+    pub fn length(&self) -> Scalar {
+        self.start.distance(self.end)
+    }
+
+    /// Classifies how this segment meets `other`.
+    ///
+    /// `epsilon` is a tolerance on the sine of the angle between the segments (and between a
+    /// segment and the offset to the other) used to detect parallel and collinear cases.
+    pub fn intersect(&self, other: &Segment2, epsilon: Scalar) -> SegmentIntersection {
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let qp = other.start - self.start;
+        let r_len_sq = r.length_squared();
+        let s_len_sq = s.length_squared();
+
+        // Zero-length segments are points: they either lie on the other segment or miss it.
+        if r_len_sq <= 0.0 || s_len_sq <= 0.0 {
+            let (point, t, u) = if r_len_sq <= 0.0 {
+                (
+                    self.start,
+                    Some(0.0),
+                    other.parameter_of(self.start, epsilon),
+                )
+            } else {
+                (
+                    other.start,
+                    self.parameter_of(other.start, epsilon),
+                    Some(0.0),
+                )
+            };
+            return match (t, u) {
+                (Some(t), Some(u)) => SegmentIntersection::Point { point, t, u },
+                _ => SegmentIntersection::Disjoint,
+            };
+        }
+
+        let r_len = float::sqrt(r_len_sq);
+        let denom = r.perp_dot(s);
+        if denom.abs() <= epsilon * r_len * float::sqrt(s_len_sq) {
+            if qp.perp_dot(r).abs() > epsilon * r_len * qp.length() {
+                return SegmentIntersection::Parallel;
+            }
+            // Project `other` onto `self` and clip to [0, 1].
+            let t0 = qp.dot(r) / r_len_sq;
+            let t1 = t0 + s.dot(r) / r_len_sq;
+            let (lo, hi) = (t0.min(t1).max(0.0), t0.max(t1).min(1.0));
+            let overlap = (lo <= hi).then_some((lo, hi));
+            return SegmentIntersection::Collinear { overlap };
+        }
+
+        let t = qp.perp_dot(s) / denom;
+        let u = qp.perp_dot(r) / denom;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            SegmentIntersection::Point {
+                point: self.at(t),
+                t,
+                u,
+            }
+        } else {
+            SegmentIntersection::Disjoint
+        }
+    }
+
+    /// Returns the parameter of `point` along this segment if it lies on it, within `epsilon`
+    /// (relative to the segment length).
+    fn parameter_of(&self, point: Vec2, epsilon: Scalar) -> Option<Scalar> {
+        let r = self.end - self.start;
+        let len_sq = r.length_squared();
+        let offset = point - self.start;
+        if len_sq <= 0.0 {
+            return (offset.length() <= epsilon).then_some(0.0);
+        }
+        let t = offset.dot(r) / len_sq;
+        let on_line = offset.perp_dot(r).abs() <= epsilon * len_sq;
+        (on_line && (0.0..=1.0).contains(&t)).then_some(t)
+    }
+
+    /// Returns `true` if the segments share at least one point.
+    pub fn intersects(&self, other: &Segment2, epsilon: Scalar) -> bool {
+        matches!(
+            self.intersect(other, epsilon),
+            SegmentIntersection::Point { .. } | SegmentIntersection::Collinear { overlap: Some(_) }
+        )
+    }
+}
+
+/// A 3D line segment from `start` to `end`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Segment3, Vec3};
+///
+/// let a = Segment3::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+/// let b = Segment3::new(Vec3::new(0.0, 1.0, -1.0), Vec3::new(0.0, 1.0, 1.0));
+/// let (on_a, on_b) = a.closest_points(&b);
+/// assert_eq!(on_a, Vec3::ZERO);
+/// assert_eq!(on_b, Vec3::new(0.0, 1.0, 0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment3 {
+    /// Start point.
+    pub start: Vec3,
+    /// End point.
+    pub end: Vec3,
+}
+
+impl Segment3 {
+    #[inline(always)]
+    pub const fn new(start: Vec3, end: Vec3) -> Self {
+        Segment3 { start, end }
+    }
+
+    /// Returns the point at parameter `t` (`0.0` is `start`, `1.0` is `end`).
+    #[inline]
+    pub fn at(&self, t: Scalar) -> Vec3 {
+        self.start.lerp(self.end, t)
+    }
+
+    /// Returns the length of the segment.
+    pub fn length(&self) -> Scalar {
+        self.start.distance(self.end)
+    }
+
+    /// Returns the parameter of the point on the segment closest to `point`.
+    pub fn closest_parameter(&self, point: Vec3) -> Scalar {
+        let ab = self.end - self.start;
+        let len_sq = ab.length_squared();
+        if len_sq <= 0.0 {
+            return 0.0;
+        }
+        ((point - self.start).dot(ab) / len_sq).clamp(0.0, 1.0)
+    }
+
+    /// Returns the point on the segment closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.at(self.closest_parameter(point))
+    }
+
+    /// Returns the parameters `(s, t)` of the closest pair of points between this segment and
+    /// `other`.
+    ///
+    /// For parallel segments any closest pair may be returned.
+    pub fn closest_parameters(&self, other: &Segment3) -> (Scalar, Scalar) {
+        closest_parameters(
+            self.start,
+            self.end - self.start,
+            1.0,
+            other.start,
+            other.end - other.start,
+        )
+    }
+
+    /// Returns the closest pair of points `(on_self, on_other)` between this segment and
+    /// `other`.
+    #[inline]
+    pub fn closest_points(&self, other: &Segment3) -> (Vec3, Vec3) {
+        let (s, t) = self.closest_parameters(other);
+        (self.at(s), other.at(t))
+    }
+}
+
+/// Returns the parameters `(s, t)` of the closest points between `p1 + s * d1` with
+/// `s` in `[0, s_max]` and `p2 + t * d2` with `t` in `[0, 1]`.
+///
+/// `s_max` may be infinite to treat the first primitive as a ray.
+pub(crate) fn closest_parameters(
+    p1: Vec3,
+    d1: Vec3,
+    s_max: Scalar,
+    p2: Vec3,
+    d2: Vec3,
+) -> (Scalar, Scalar) {
+    let r = p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(r);
+
+    if a <= 0.0 && e <= 0.0 {
+        return (0.0, 0.0);
+    }
+    if a <= 0.0 {
+        return (0.0, (f / e).clamp(0.0, 1.0));
+    }
+    let c = d1.dot(r);
+    if e <= 0.0 {
+        return ((-c / a).clamp(0.0, s_max), 0.0);
+    }
+
+    let b = d1.dot(d2);
+    let denom = a * e - b * b;
+    let mut s = if denom > 0.0 {
+        ((b * f - c * e) / denom).clamp(0.0, s_max)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, s_max);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, s_max);
+    }
+    (s, t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-6 as Scalar;
+
+    fn seg(ax: Scalar, ay: Scalar, bx: Scalar, by: Scalar) -> Segment2 {
+        Segment2::new(Vec2::new(ax, ay), Vec2::new(bx, by))
+    }
+
+    #[test]
+    fn crossing_segments_report_point_and_parameters() {
+        let a = seg(0.0, 0.0, 4.0, 0.0);
+        let b = seg(1.0, -1.0, 1.0, 3.0);
+        assert_eq!(
+            a.intersect(&b, EPS),
+            SegmentIntersection::Point {
+                point: Vec2::new(1.0, 0.0),
+                t: 0.25,
+                u: 0.25
+            }
+        );
+    }
+
+    #[test]
+    fn touching_endpoints_intersect() {
+        let a = seg(0.0, 0.0, 1.0, 1.0);
+        let b = seg(1.0, 1.0, 2.0, 0.0);
+        assert!(a.intersects(&b, EPS));
+    }
+
+    #[test]
+    fn non_parallel_segments_can_miss() {
+        let a = seg(0.0, 0.0, 1.0, 0.0);
+        let b = seg(2.0, -1.0, 2.0, 1.0);
+        assert_eq!(a.intersect(&b, EPS), SegmentIntersection::Disjoint);
+    }
+
+    #[test]
+    fn parallel_and_collinear_are_classified() {
+        let a = seg(0.0, 0.0, 4.0, 0.0);
+        assert_eq!(
+            a.intersect(&seg(0.0, 1.0, 4.0, 1.0), EPS),
+            SegmentIntersection::Parallel
+        );
+        assert_eq!(
+            a.intersect(&seg(5.0, 0.0, 3.0, 0.0), EPS),
+            SegmentIntersection::Collinear {
+                overlap: Some((0.75, 1.0))
+            }
+        );
+        assert_eq!(
+            a.intersect(&seg(5.0, 0.0, 6.0, 0.0), EPS),
+            SegmentIntersection::Collinear { overlap: None }
+        );
+    }
+
+    #[test]
+    fn degenerate_segments_are_points() {
+        let a = seg(0.0, 0.0, 4.0, 0.0);
+        let on = seg(2.0, 0.0, 2.0, 0.0);
+        let off_end = seg(6.0, 0.0, 6.0, 0.0);
+        let off_line = seg(2.0, 1.0, 2.0, 1.0);
+        assert_eq!(
+            a.intersect(&on, EPS),
+            SegmentIntersection::Point {
+                point: Vec2::new(2.0, 0.0),
+                t: 0.5,
+                u: 0.0
+            }
+        );
+        assert!(on.intersects(&a, EPS));
+        assert!(on.intersects(&on, EPS));
+        assert!(!off_end.intersects(&a, EPS));
+        assert!(!off_line.intersects(&a, EPS));
+        assert!(!off_line.intersects(&on, EPS));
+    }
+
+    #[test]
+    fn segment3_closest_points_for_skew_and_parallel() {
+        let a = Segment3::new(Vec3::ZERO, Vec3::new(2.0, 0.0, 0.0));
+        let skew = Segment3::new(Vec3::new(3.0, 1.0, -1.0), Vec3::new(3.0, 1.0, 1.0));
+        // The infinite lines meet at x = 3, past the end of `a`.
+        assert_eq!(
+            a.closest_points(&skew),
+            (Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 0.0))
+        );
+        let parallel = Segment3::new(Vec3::new(1.0, 1.0, 0.0), Vec3::new(5.0, 1.0, 0.0));
+        let (on_a, on_parallel) = a.closest_points(&parallel);
+        assert_eq!(on_a.distance(on_parallel), 1.0 as Scalar);
+    }
+
+    #[test]
+    fn segment3_degenerate_segments_are_points() {
+        let point = Segment3::new(Vec3::ONE, Vec3::ONE);
+        let a = Segment3::new(Vec3::ZERO, Vec3::new(0.0, 4.0, 0.0));
+        assert_eq!(
+            a.closest_points(&point),
+            (Vec3::new(0.0, 1.0, 0.0), Vec3::ONE)
+        );
+        assert_eq!(
+            point.closest_points(&a),
+            (Vec3::ONE, Vec3::new(0.0, 1.0, 0.0))
+        );
+        assert_eq!(point.closest_points(&point), (Vec3::ONE, Vec3::ONE));
+        assert_eq!(a.closest_parameter(Vec3::new(0.0, 9.0, 0.0)), 1.0 as Scalar);
+    }
+}
@@ -0,0 +1,169 @@
+//! Recursive shadowcasting field of view over tile grids.
+//!
+//! The grid is described by an `opaque` callback, so it works with any map storage. Opaque
+//! tiles that are lit (the faces of walls) count as visible; tiles behind them do not.
+//!
+//! Shadowcasting is not perfectly symmetric: in rare configurations around pillars, `a` can
+//! see `b` without `b` seeing `a`. Use [`crate::grid_line`] when a strictly mutual check is
+//! required.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{shadowcast, IVec2};
+//!
+//! // A single pillar east of the viewer.
+//! let pillar = IVec2::new(2, 0);
+//! let visible = shadowcast::visible_cells(IVec2::ZERO, 6, |c| c == pillar);
+//! assert!(visible.contains(&pillar));
+//! assert!(!visible.contains(&IVec2::new(4, 0)));
+//! assert!(visible.contains(&IVec2::new(0, 4)));
+//! ```
+
+use crate::{IVec2, Scalar};
+use std::collections::HashSet;
+
+/// Octant transforms mapping `(column, row)` in the canonical octant to grid offsets.
+const OCTANTS: [[i32; 4]; 8] = [
+    [1, 0, 0, 1],
+    [0, 1, 1, 0],
+    [0, -1, 1, 0],
+    [-1, 0, 0, 1],
+    [-1, 0, 0, -1],
+    [0, -1, -1, 0],
+    [0, 1, -1, 0],
+    [1, 0, 0, -1],
+];
+
+/// Returns every cell visible from `origin` within `radius` tiles (Euclidean, inclusive).
+///
+/// The origin itself is always visible.
+pub fn visible_cells(
+    origin: IVec2,
+    radius: u32,
+    opaque: impl FnMut(IVec2) -> bool,
+) -> HashSet<IVec2> {
+    let mut visible = HashSet::new();
+    for_each_visible(origin, radius, opaque, |cell| {
+        visible.insert(cell);
+    });
+    visible
+}
+
+/// Calls `visit` with every cell visible from `origin` within `radius` tiles.
+///
+/// Cells on the boundary between octants may be visited more than once; use
+/// [`visible_cells`] for a deduplicated set.
+pub fn for_each_visible(
+    origin: IVec2,
+    radius: u32,
+    mut opaque: impl FnMut(IVec2) -> bool,
+    mut visit: impl FnMut(IVec2),
+) {
+    visit(origin);
+    let mut caster = Caster {
+        origin,
+        radius: radius as i32,
+        opaque: &mut opaque,
+        visit: &mut visit,
+    };
+    for transform in OCTANTS {
+        caster.cast(1, 1.0, 0.0, transform);
+    }
+}
+
+struct Caster<'a, O, V> {
+    origin: IVec2,
+    radius: i32,
+    opaque: &'a mut O,
+    visit: &'a mut V,
+}
+
+impl<O: FnMut(IVec2) -> bool, V: FnMut(IVec2)> Caster<'_, O, V> {
+    /// Scans rows outwards from `row`, lighting cells between the `start` and `end` slopes and
+    /// recursing into the unblocked part of the view whenever a wall starts.
+    fn cast(&mut self, row: i32, mut start: Scalar, end: Scalar, [xx, xy, yx, yy]: [i32; 4]) {
+        if start < end {
+            return;
+        }
+        let radius_sq = self.radius * self.radius;
+        let mut next_start = start;
+        for j in row..=self.radius {
+            let dy = -j;
+            let mut blocked = false;
+            for dx in -j..=0 {
+                let left = (dx as Scalar - 0.5) / (dy as Scalar + 0.5);
+                let right = (dx as Scalar + 0.5) / (dy as Scalar - 0.5);
+                if start < right {
+                    continue;
+                }
+                if end > left {
+                    break;
+                }
+                let cell = self.origin + IVec2::new(dx * xx + dy * xy, dx * yx + dy * yy);
+                if dx * dx + dy * dy <= radius_sq {
+                    (self.visit)(cell);
+                }
+                let is_opaque = (self.opaque)(cell);
+                if blocked {
+                    if is_opaque {
+                        next_start = right;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if is_opaque && j < self.radius {
+                    blocked = true;
+                    self.cast(j + 1, start, left, [xx, xy, yx, yy]);
+                    next_start = right;
+                }
+            }
+            if blocked {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_ground_sees_the_whole_disc() {
+        let visible = visible_cells(IVec2::new(3, -2), 5, |_| false);
+        for x in -6..=6 {
+            for y in -6..=6 {
+                let inside = x * x + y * y <= 25;
+                assert_eq!(visible.contains(&IVec2::new(3 + x, -2 + y)), inside);
+            }
+        }
+    }
+
+    #[test]
+    fn walls_are_lit_and_block_what_is_behind() {
+        // A closed 7x7 room centered on the origin.
+        let wall = |c: IVec2| c.x.abs() == 3 || c.y.abs() == 3;
+        let visible = visible_cells(IVec2::ZERO, 10, wall);
+        assert_eq!(visible.len(), 49);
+        assert!(visible.contains(&IVec2::new(3, 3)));
+        assert!(!visible.contains(&IVec2::new(4, 0)));
+
+        // A pillar north of the viewer shadows the cells straight behind it only.
+        let pillar = IVec2::new(0, 2);
+        let visible = visible_cells(IVec2::ZERO, 8, |c| c == pillar);
+        assert!(visible.contains(&pillar));
+        assert!(!visible.contains(&IVec2::new(0, 3)));
+        assert!(!visible.contains(&IVec2::new(0, 7)));
+        assert!(visible.contains(&IVec2::new(3, 6)));
+        assert!(visible.contains(&IVec2::new(0, -7)));
+    }
+
+    #[test]
+    fn zero_radius_sees_only_the_origin() {
+        let visible = visible_cells(IVec2::new(1, 1), 0, |_| true);
+        assert_eq!(
+            visible.into_iter().collect::<Vec<_>>(),
+            vec![IVec2::new(1, 1)]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Collide-and-slide helpers for kinematic character movement.
+//!
+//! After a sweep reports contacts, the remaining motion is projected onto the contact planes so
+//! the character slides along walls and floors instead of stopping dead or pushing into them.
+//!
+//! # Examples
+//! ```
+//! use spacetimedb_math::{slide, Vec3};
+//!
+//! // Walking diagonally into a wall facing +X keeps only the motion along the wall.
+//! let motion = Vec3::new(-1.0, 0.0, 1.0);
+//! let wall = Vec3::new(1.0, 0.0, 0.0);
+//! assert_eq!(slide::slide(motion, &[wall], 1.0e-6), Vec3::new(0.0, 0.0, 1.0));
+//! ```
+
+use crate::{Scalar, Vec3};
+
+/// Removes the part of `motion` that points into the plane with unit `normal`.
+///
+/// Motion already moving away from the plane is returned unchanged.
+#[inline]
+pub fn clip(motion: Vec3, normal: Vec3) -> Vec3 {
+    let into = motion.dot(normal);
+    if into < 0.0 {
+        motion - normal * into
+    } else {
+        motion
+    }
+}
+
+/// Projects `motion` so it no longer points into any of the contact planes with unit `normals`.
+///
+/// A single blocking plane is slid along. When clipping against one plane pushes the motion
+/// into another, the two planes form a crease (e.g. a wall meeting a sloped floor) and the
+/// motion is constrained to the crease line. If three or more planes block every option, the
+/// result is zero.
+///
+/// `epsilon` is the slack allowed when checking that the result doesn't point into a plane,
+/// and the minimum squared length of a crease direction before two planes count as parallel.
+pub fn slide(motion: Vec3, normals: &[Vec3], epsilon: Scalar) -> Vec3 {
+    let clear_of_all = |candidate: Vec3| normals.iter().all(|n| candidate.dot(*n) >= -epsilon);
+    if clear_of_all(motion) {
+        return motion;
+    }
+
+    for &normal in normals {
+        if motion.dot(normal) < 0.0 {
+            let clipped = clip(motion, normal);
+            if clear_of_all(clipped) {
+                return clipped;
+            }
+        }
+    }
+
+    for (i, &a) in normals.iter().enumerate() {
+        for &b in &normals[i + 1..] {
+            let crease = a.cross(b);
+            let len_sq = crease.length_squared();
+            if len_sq <= epsilon {
+                continue;
+            }
+            let along = crease * (crease.dot(motion) / len_sq);
+            if clear_of_all(along) {
+                return along;
+            }
+        }
+    }
+    Vec3::ZERO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn clip_only_removes_inward_motion() {
+        let floor = Vec3::new(0.0, 1.0, 0.0);
+        assert_eq!(
+            clip(Vec3::new(1.0, -2.0, 0.0), floor),
+            Vec3::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            clip(Vec3::new(1.0, 2.0, 0.0), floor),
+            Vec3::new(1.0, 2.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn slide_follows_crease_between_two_walls() {
+        // A corner between walls facing +X and +Z: pushing into it slides along neither wall,
+        // but moving up the crease (Y) is still allowed.
+        let walls = [Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)];
+        let result = slide(Vec3::new(-1.0, 0.5, -1.0), &walls, EPS);
+        assert!(result.distance(Vec3::new(0.0, 0.5, 0.0)) <= EPS);
+
+        // A V-shaped notch: clipping against either wall pushes into the other, and the
+        // vertical crease leaves nothing of a horizontal push.
+        let inv_sqrt2 = 1.0 / (2.0 as Scalar).sqrt();
+        let wedge = [
+            Vec3::new(inv_sqrt2, 0.0, inv_sqrt2),
+            Vec3::new(-inv_sqrt2, 0.0, inv_sqrt2),
+        ];
+        let result = slide(Vec3::new(0.2, 0.0, -1.0), &wedge, EPS);
+        assert!(result.length() <= EPS);
+    }
+
+    #[test]
+    fn slide_keeps_motion_away_from_planes() {
+        let normals = [Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0)];
+        let motion = Vec3::new(1.0, 1.0, 0.0);
+        assert_eq!(slide(motion, &normals, EPS), motion);
+        // Sliding along the floor into a wall stops at the wall, but keeps moving along it.
+        let result = slide(Vec3::new(-1.0, -1.0, 1.0), &normals, EPS);
+        assert!(result.distance(Vec3::new(0.0, 0.0, 1.0)) <= EPS);
+
+        let boxed_in = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        ];
+        assert_eq!(slide(-Vec3::ONE, &boxed_in, EPS), Vec3::ZERO);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{CubicHermite, Interpolate, Scalar, SplinePoint};
+use alloc::vec::Vec;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A value received at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot<T> {
+    /// Time in seconds on the sender's clock.
+    pub time: Scalar,
+    pub value: T,
+    /// Rate of change of `value` per second, if the sender provided it.
+    pub velocity: Option<T>,
+}
+
+/// Timestamped samples of a remote value, read back a fixed delay in the past.
+///
+/// Rendering `delay` seconds behind the newest data means there are usually snapshots on
+/// both sides of the rendered time, so motion is interpolated instead of extrapolated and
+/// late packets only cost accuracy, not jitter. Samples may arrive out of order; samples more
+/// than `max_age` older than the newest are dropped, keeping one to interpolate from.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{SnapshotBuffer, Vec3};
+///
+/// let mut remote = SnapshotBuffer::new(0.25, 1.0);
+/// remote.push(1.0, Vec3::new(0.0, 0.0, 0.0));
+/// remote.push(2.0, Vec3::new(2.0, 0.0, 0.0));
+/// remote.push(1.5, Vec3::new(1.0, 0.0, 0.0));
+/// // At 2.0 the buffer renders 1.75, halfway between the last two snapshots.
+/// assert_eq!(remote.sample_delayed(2.0), Some(Vec3::new(1.5, 0.0, 0.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SnapshotBuffer<T> {
+    /// Seconds between the current time and the rendered time.
+    pub delay: Scalar,
+    /// Seconds of history to keep behind the newest snapshot.
+    pub max_age: Scalar,
+    snapshots: Vec<Snapshot<T>>,
+}
+
+impl<T: Interpolate> SnapshotBuffer<T> {
+    /// Creates an empty buffer.
+    #[inline]
+    pub fn new(delay: Scalar, max_age: Scalar) -> Self {
+        SnapshotBuffer {
+            delay,
+            max_age,
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Returns the stored snapshots, oldest first.
+    #[inline]
+    pub fn snapshots(&self) -> &[Snapshot<T>] {
+        &self.snapshots
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Returns the newest snapshot.
+    #[inline]
+    pub fn latest(&self) -> Option<&Snapshot<T>> {
+        self.snapshots.last()
+    }
+
+    /// Removes every snapshot.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Records `value` at `time`.
+    #[inline]
+    pub fn push(&mut self, time: Scalar, value: T) {
+        self.insert(Snapshot {
+            time,
+            value,
+            velocity: None,
+        });
+    }
+
+    /// Records `value` and its rate of change at `time`, enabling
+    /// [`SnapshotBuffer::sample_hermite`].
+    #[inline]
+    pub fn push_with_velocity(&mut self, time: Scalar, value: T, velocity: T) {
+        self.insert(Snapshot {
+            time,
+            value,
+            velocity: Some(velocity),
+        });
+    }
+
+    /// Records `snapshot`, replacing any snapshot with the same time, then drops expired ones.
+    pub fn insert(&mut self, snapshot: Snapshot<T>) {
+        let index = self.snapshots.partition_point(|s| s.time < snapshot.time);
+        match self.snapshots.get_mut(index) {
+            Some(existing) if existing.time == snapshot.time => *existing = snapshot,
+            _ => self.snapshots.insert(index, snapshot),
+        }
+
+        let cutoff = self.snapshots[self.snapshots.len() - 1].time - self.max_age;
+        // Keep the newest snapshot at or before the cutoff as the start of the first span.
+        let expired = self.snapshots.partition_point(|s| s.time <= cutoff);
+        if expired > 1 {
+            self.snapshots.drain(..expired - 1);
+        }
+    }
+
+    /// Returns the value at `time - delay`. See [`SnapshotBuffer::sample`].
+    #[inline]
+    pub fn sample_delayed(&self, now: Scalar) -> Option<T> {
+        self.sample(now - self.delay)
+    }
+
+    /// Returns the value at `time`, blending linearly between the surrounding snapshots and
+    /// holding the oldest or newest value outside them, or `None` if the buffer is empty.
+    pub fn sample(&self, time: Scalar) -> Option<T> {
+        match self.span(time)? {
+            Span::Between(a, b, t) => Some(a.value.interpolate(&b.value, t)),
+            Span::Hold(s) => Some(s.value),
+        }
+    }
+
+    /// Returns the snapshots around `time` and the fraction of the way between them, or the
+    /// single snapshot to hold when `time` is outside the buffer.
+    fn span(&self, time: Scalar) -> Option<Span<'_, T>> {
+        let first = self.snapshots.first()?;
+        let next = self.snapshots.partition_point(|s| s.time <= time);
+        if next == 0 {
+            return Some(Span::Hold(first));
+        }
+        let Some(b) = self.snapshots.get(next) else {
+            return Some(Span::Hold(&self.snapshots[next - 1]));
+        };
+        let a = &self.snapshots[next - 1];
+        Some(Span::Between(a, b, (time - a.time) / (b.time - a.time)))
+    }
+}
+
+impl<P: SplinePoint + Interpolate> SnapshotBuffer<P> {
+    /// Like [`SnapshotBuffer::sample`], but follows a cubic Hermite curve matching the
+    /// recorded velocities, which keeps curved motion smooth at sparse snapshot rates.
+    ///
+    /// Spans where either snapshot lacks a velocity are blended linearly.
+    pub fn sample_hermite(&self, time: Scalar) -> Option<P> {
+        match self.span(time)? {
+            Span::Between(a, b, t) => match (a.velocity, b.velocity) {
+                (Some(va), Some(vb)) => {
+                    let dt = b.time - a.time;
+                    Some(CubicHermite::new(a.value, va * dt, b.value, vb * dt).position(t))
+                }
+                _ => Some(a.value.interpolate(&b.value, t)),
+            },
+            Span::Hold(s) => Some(s.value),
+        }
+    }
+
+    /// Returns the Hermite value at `now - delay`. See [`SnapshotBuffer::sample_hermite`].
+    #[inline]
+    pub fn sample_hermite_delayed(&self, now: Scalar) -> Option<P> {
+        self.sample_hermite(now - self.delay)
+    }
+}
+
+enum Span<'a, T> {
+    /// Two consecutive snapshots and the fraction of the way from the first to the second.
+    Between(&'a Snapshot<T>, &'a Snapshot<T>, Scalar),
+    /// The oldest or newest snapshot, for times outside the buffer.
+    Hold(&'a Snapshot<T>),
+}
+
+/// Rebuilds the buffer through [`SnapshotBuffer::insert`], so snapshots that arrive out of
+/// order, repeated or expired are stored as if they had been pushed.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de> + Interpolate> serde::Deserialize<'de> for SnapshotBuffer<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "SnapshotBuffer")]
+        struct Fields<T> {
+            delay: Scalar,
+            max_age: Scalar,
+            snapshots: Vec<Snapshot<T>>,
+        }
+
+        let fields = Fields::<T>::deserialize(deserializer)?;
+        let mut buffer = SnapshotBuffer::new(fields.delay, fields.max_age);
+        for snapshot in fields.snapshots {
+            buffer.insert(snapshot);
+        }
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Quat, Transform, Vec3};
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn orders_replaces_and_expires_snapshots() {
+        let mut buffer = SnapshotBuffer::new(0.1, 0.5);
+        assert_eq!(buffer.sample(0.0), None::<Scalar>);
+        for (time, value) in [(0.2, 2.0), (0.0, 0.0), (0.1, 1.0), (0.1, 1.5)] {
+            buffer.push(time, value);
+        }
+        let times: Vec<Scalar> = buffer.snapshots().iter().map(|s| s.time).collect();
+        assert_eq!(times, vec![0.0, 0.1, 0.2]);
+        assert_eq!(buffer.snapshots()[1].value, 1.5);
+
+        buffer.push(0.65, 6.5);
+        // 0.65 - 0.5 = 0.15: 0.1 survives to start the span containing the cutoff.
+        let times: Vec<Scalar> = buffer.snapshots().iter().map(|s| s.time).collect();
+        assert_eq!(times, vec![0.1, 0.2, 0.65]);
+        assert_eq!(buffer.latest().map(|s| s.value), Some(6.5));
+    }
+
+    #[test]
+    fn samples_hold_outside_the_buffer() {
+        let mut buffer = SnapshotBuffer::new(0.2, 10.0);
+        buffer.push(1.0, Vec3::new(1.0, 0.0, 0.0));
+        buffer.push(2.0, Vec3::new(3.0, 0.0, 0.0));
+        assert_eq!(buffer.sample(0.0), Some(Vec3::new(1.0, 0.0, 0.0)));
+        assert_eq!(buffer.sample(5.0), Some(Vec3::new(3.0, 0.0, 0.0)));
+        assert_eq!(buffer.sample_delayed(1.7), Some(Vec3::new(2.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn hermite_follows_recorded_velocities() {
+        // A body circling the origin at one radian per second.
+        let at = |t: Scalar| Vec3::new(t.cos(), t.sin(), 0.0);
+        let velocity = |t: Scalar| Vec3::new(-t.sin(), t.cos(), 0.0);
+        let mut buffer = SnapshotBuffer::new(0.0, 10.0);
+        for i in 0..4 {
+            let t = i as Scalar * 0.5;
+            buffer.push_with_velocity(t, at(t), velocity(t));
+        }
+        let t = 0.75 as Scalar;
+        let hermite = buffer.sample_hermite(t).unwrap();
+        let linear = buffer.sample(t).unwrap();
+        assert!(hermite.distance(at(t)) <= 1.0e-3 as Scalar);
+        assert!(linear.distance(at(t)) > 1.0e-2 as Scalar);
+
+        buffer.push(2.0, at(2.0));
+        assert_eq!(buffer.sample_hermite(1.75), buffer.sample(1.75));
+    }
+
+    #[test]
+    fn rotations_and_transforms_interpolate() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let mut rotations = SnapshotBuffer::new(0.0, 1.0);
+        rotations.push(0.0, Quat::IDENTITY);
+        rotations.push(1.0, Quat::from_axis_angle(axis, 1.0));
+        let q = rotations.sample(0.5).unwrap();
+        assert!(q.dot(Quat::from_axis_angle(axis, 0.5)) >= 1.0 - EPS);
+
+        let mut transforms = SnapshotBuffer::new(0.0, 1.0);
+        transforms.push(0.0, Transform::IDENTITY);
+        transforms.push(1.0, Transform::from_translation(Vec3::new(4.0, 0.0, 0.0)));
+        let mid = transforms.sample(0.25).unwrap();
+        assert_eq!(mid.translation, Vec3::new(1.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,458 @@
+use crate::{Aabb2, Aabb3, Scalar, Vec2, Vec3};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A uniform grid broadphase over 2D points, keyed by cell.
+///
+/// Items are identified by a small copyable id (e.g. an entity id) and stored at a position.
+/// Only occupied cells take memory, so the grid can cover an unbounded world.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{SpatialHashGrid2, Vec2};
+///
+/// let mut grid = SpatialHashGrid2::new(10.0);
+/// grid.insert(1u64, Vec2::new(0.0, 0.0));
+/// grid.insert(2u64, Vec2::new(3.0, 4.0));
+/// grid.insert(3u64, Vec2::new(50.0, 0.0));
+///
+/// let mut near = grid.query_radius(Vec2::ZERO, 5.0);
+/// near.sort();
+/// assert_eq!(near, vec![1, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialHashGrid2<T> {
+    cell_size: Scalar,
+    cells: HashMap<[i32; 2], Vec<T>>,
+    positions: HashMap<T, Vec2>,
+}
+
+impl<T: Copy + Eq + Hash> SpatialHashGrid2<T> {
+    /// Creates an empty grid with square cells of `cell_size`.
+    ///
+    /// A cell size close to the typical query radius works best.
+    pub fn new(cell_size: Scalar) -> Self {
+        SpatialHashGrid2 {
+            cell_size,
+            cells: HashMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Returns the cell size.
+    #[inline]
+    pub fn cell_size(&self) -> Scalar {
+        self.cell_size
+    }
+
+    /// Returns the number of items in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the grid holds no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the position of `id`, if it is in the grid.
+    #[inline]
+    pub fn position(&self, id: T) -> Option<Vec2> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Returns the cell containing `position`.
+    #[inline]
+    pub fn cell_of(&self, position: Vec2) -> [i32; 2] {
+        [
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Inserts `id` at `position`, moving it if it is already present.
+    pub fn insert(&mut self, id: T, position: Vec2) {
+        self.update(id, position);
+    }
+
+    /// Removes `id`, returning its position if it was present.
+    pub fn remove(&mut self, id: T) -> Option<Vec2> {
+        let position = self.positions.remove(&id)?;
+        let cell = self.cell_of(position);
+        if let Some(items) = self.cells.get_mut(&cell) {
+            items.retain(|&other| other != id);
+            if items.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+        Some(position)
+    }
+
+    /// Moves `id` to `position`, inserting it if it is not present.
+    ///
+    /// Moves within the same cell only update the stored position.
+    pub fn update(&mut self, id: T, position: Vec2) {
+        let cell = self.cell_of(position);
+        if let Some(old) = self.positions.insert(id, position) {
+            if self.cell_of(old) == cell {
+                return;
+            }
+            let old_cell = self.cell_of(old);
+            if let Some(items) = self.cells.get_mut(&old_cell) {
+                items.retain(|&other| other != id);
+                if items.is_empty() {
+                    self.cells.remove(&old_cell);
+                }
+            }
+        }
+        self.cells.entry(cell).or_default().push(id);
+    }
+
+    /// Removes every item, keeping the cell size.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.positions.clear();
+    }
+
+    /// Calls `f` for every item inside or on the boundary of `aabb`.
+    pub fn for_each_in_aabb(&self, aabb: &Aabb2, mut f: impl FnMut(T, Vec2)) {
+        let [x0, y0] = self.cell_of(aabb.min);
+        let [x1, y1] = self.cell_of(aabb.max);
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                for &id in self.cells.get(&[x, y]).into_iter().flatten() {
+                    let position = self.positions[&id];
+                    if aabb.contains_point(position) {
+                        f(id, position);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns every item inside or on the boundary of `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb2) -> Vec<T> {
+        let mut found = Vec::new();
+        self.for_each_in_aabb(aabb, |id, _| found.push(id));
+        found
+    }
+
+    /// Returns every item within `radius` of `center`.
+    pub fn query_radius(&self, center: Vec2, radius: Scalar) -> Vec<T> {
+        let bounds = Aabb2::from_center_half_extents(center, Vec2::splat(radius));
+        let mut found = Vec::new();
+        self.for_each_in_aabb(&bounds, |id, position| {
+            if position.distance_squared(center) <= radius * radius {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    /// Calls `f` once for every unordered pair of items within `radius` of each other.
+    pub fn for_each_pair_within(&self, radius: Scalar, mut f: impl FnMut(T, T)) {
+        let reach = (radius / self.cell_size).ceil() as i32;
+        let radius_sq = radius * radius;
+        for (&[cx, cy], items) in &self.cells {
+            for (i, &a) in items.iter().enumerate() {
+                let pa = self.positions[&a];
+                for &b in &items[i + 1..] {
+                    if pa.distance_squared(self.positions[&b]) <= radius_sq {
+                        f(a, b);
+                    }
+                }
+                // Visit only the "forward" half of the neighbourhood so each pair of cells is
+                // seen from one side.
+                for dx in 0..=reach {
+                    for dy in -reach..=reach {
+                        if dx == 0 && dy <= 0 {
+                            continue;
+                        }
+                        for &b in self.cells.get(&[cx + dx, cy + dy]).into_iter().flatten() {
+                            if pa.distance_squared(self.positions[&b]) <= radius_sq {
+                                f(a, b);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A uniform grid broadphase over 3D points, keyed by cell.
+///
+/// The 3D counterpart of [`SpatialHashGrid2`].
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{SpatialHashGrid3, Vec3};
+///
+/// let mut grid = SpatialHashGrid3::new(8.0);
+/// grid.insert(1u32, Vec3::ZERO);
+/// grid.insert(2u32, Vec3::new(0.0, 6.0, 0.0));
+///
+/// let mut pairs = Vec::new();
+/// grid.for_each_pair_within(7.0, |a, b| pairs.push((a.min(b), a.max(b))));
+/// assert_eq!(pairs, vec![(1, 2)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialHashGrid3<T> {
+    cell_size: Scalar,
+    cells: HashMap<[i32; 3], Vec<T>>,
+    positions: HashMap<T, Vec3>,
+}
+
+impl<T: Copy + Eq + Hash> SpatialHashGrid3<T> {
+    /// Creates an empty grid with cubic cells of `cell_size`.
+    ///
+    /// A cell size close to the typical query radius works best.
+    pub fn new(cell_size: Scalar) -> Self {
+        SpatialHashGrid3 {
+            cell_size,
+            cells: HashMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Returns the cell size.
+    #[inline]
+    pub fn cell_size(&self) -> Scalar {
+        self.cell_size
+    }
+
+    /// Returns the number of items in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the grid holds no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the position of `id`, if it is in the grid.
+    #[inline]
+    pub fn position(&self, id: T) -> Option<Vec3> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Returns the cell containing `position`.
+    #[inline]
+    pub fn cell_of(&self, position: Vec3) -> [i32; 3] {
+        [
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+            (position.z / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Inserts `id` at `position`, moving it if it is already present.
+    pub fn insert(&mut self, id: T, position: Vec3) {
+        self.update(id, position);
+    }
+
+    /// Removes `id`, returning its position if it was present.
+    pub fn remove(&mut self, id: T) -> Option<Vec3> {
+        let position = self.positions.remove(&id)?;
+        let cell = self.cell_of(position);
+        if let Some(items) = self.cells.get_mut(&cell) {
+            items.retain(|&other| other != id);
+            if items.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+        Some(position)
+    }
+
+    /// Moves `id` to `position`, inserting it if it is not present.
+    ///
+    /// Moves within the same cell only update the stored position.
+    pub fn update(&mut self, id: T, position: Vec3) {
+        let cell = self.cell_of(position);
+        if let Some(old) = self.positions.insert(id, position) {
+            if self.cell_of(old) == cell {
+                return;
+            }
+            let old_cell = self.cell_of(old);
+            if let Some(items) = self.cells.get_mut(&old_cell) {
+                items.retain(|&other| other != id);
+                if items.is_empty() {
+                    self.cells.remove(&old_cell);
+                }
+            }
+        }
+        self.cells.entry(cell).or_default().push(id);
+    }
+
+    /// Removes every item, keeping the cell size.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.positions.clear();
+    }
+
+    /// Calls `f` for every item inside or on the boundary of `aabb`.
+    pub fn for_each_in_aabb(&self, aabb: &Aabb3, mut f: impl FnMut(T, Vec3)) {
+        let [x0, y0, z0] = self.cell_of(aabb.min);
+        let [x1, y1, z1] = self.cell_of(aabb.max);
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                for z in z0..=z1 {
+                    for &id in self.cells.get(&[x, y, z]).into_iter().flatten() {
+                        let position = self.positions[&id];
+                        if aabb.contains_point(position) {
+                            f(id, position);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns every item inside or on the boundary of `aabb`.
+    pub fn query_aabb(&self, aabb: &Aabb3) -> Vec<T> {
+        let mut found = Vec::new();
+        self.for_each_in_aabb(aabb, |id, _| found.push(id));
+        found
+    }
+
+    /// Returns every item within `radius` of `center`.
+    pub fn query_radius(&self, center: Vec3, radius: Scalar) -> Vec<T> {
+        let bounds = Aabb3::from_center_half_extents(center, Vec3::splat(radius));
+        let mut found = Vec::new();
+        self.for_each_in_aabb(&bounds, |id, position| {
+            if position.distance_squared(center) <= radius * radius {
+                found.push(id);
+            }
+        });
+        found
+    }
+
+    /// Calls `f` once for every unordered pair of items within `radius` of each other.
+    pub fn for_each_pair_within(&self, radius: Scalar, mut f: impl FnMut(T, T)) {
+        let reach = (radius / self.cell_size).ceil() as i32;
+        let radius_sq = radius * radius;
+        for (&[cx, cy, cz], items) in &self.cells {
+            for (i, &a) in items.iter().enumerate() {
+                let pa = self.positions[&a];
+                for &b in &items[i + 1..] {
+                    if pa.distance_squared(self.positions[&b]) <= radius_sq {
+                        f(a, b);
+                    }
+                }
+                // Visit only the "forward" half of the neighbourhood so each pair of cells is
+                // seen from one side.
+                for dx in 0..=reach {
+                    for dy in -reach..=reach {
+                        for dz in -reach..=reach {
+                            if (dx, dy, dz) <= (0, 0, 0) {
+                                continue;
+                            }
+                            let cell = [cx + dx, cy + dy, cz + dz];
+                            for &b in self.cells.get(&cell).into_iter().flatten() {
+                                if pa.distance_squared(self.positions[&b]) <= radius_sq {
+                                    f(a, b);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Brute-force reference for pair enumeration, as sorted `(low, high)` pairs.
+    fn brute_pairs(points: &[(u32, Vec3)], radius: Scalar) -> Vec<(u32, u32)> {
+        let mut pairs = Vec::new();
+        for (i, &(a, pa)) in points.iter().enumerate() {
+            for &(b, pb) in &points[i + 1..] {
+                if pa.distance(pb) <= radius {
+                    pairs.push((a.min(b), a.max(b)));
+                }
+            }
+        }
+        pairs.sort();
+        pairs
+    }
+
+    #[test]
+    fn grid2_insert_update_remove() {
+        let mut grid = SpatialHashGrid2::new(1.0);
+        grid.insert('a', Vec2::new(0.5, 0.5));
+        grid.insert('b', Vec2::new(-3.5, 2.0));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.cell_of(Vec2::new(-3.5, 2.0)), [-4, 2]);
+
+        grid.update('a', Vec2::new(10.0, 10.0));
+        assert!(grid.query_radius(Vec2::ZERO, 1.0).is_empty());
+        assert_eq!(grid.query_radius(Vec2::new(10.0, 9.5), 1.0), vec!['a']);
+
+        assert_eq!(grid.remove('b'), Some(Vec2::new(-3.5, 2.0)));
+        assert_eq!(grid.remove('b'), None);
+        assert_eq!(grid.len(), 1);
+        let everything = Aabb2::new(Vec2::splat(-100.0), Vec2::splat(100.0));
+        assert_eq!(grid.query_aabb(&everything), vec!['a']);
+    }
+
+    #[test]
+    fn grid2_pairs_report_each_pair_once() {
+        let mut grid = SpatialHashGrid2::new(1.0);
+        grid.insert(1, Vec2::new(0.9, 0.0));
+        grid.insert(2, Vec2::new(1.1, 0.0));
+        grid.insert(3, Vec2::new(1.1, 2.5));
+        grid.insert(4, Vec2::new(0.95, 0.1));
+        let mut pairs = Vec::new();
+        grid.for_each_pair_within(0.5, |a: i32, b: i32| pairs.push((a.min(b), a.max(b))));
+        pairs.sort();
+        assert_eq!(pairs, vec![(1, 2), (1, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn grid3_pairs_match_brute_force() {
+        // Deterministic scatter, including negative coordinates and a radius wider than a cell.
+        let points: Vec<(u32, Vec3)> = (0..60u32)
+            .map(|i| {
+                let f = i as Scalar;
+                let p = Vec3::new(
+                    (f * 7.3) % 13.0 - 6.0,
+                    (f * 3.1) % 9.0 - 4.0,
+                    (f * 5.7) % 11.0 - 5.0,
+                );
+                (i, p)
+            })
+            .collect();
+        let mut grid = SpatialHashGrid3::new(1.5);
+        for &(id, p) in &points {
+            grid.insert(id, p);
+        }
+        for radius in [1.0, 2.5] {
+            let mut pairs = Vec::new();
+            grid.for_each_pair_within(radius, |a, b| pairs.push((a.min(b), a.max(b))));
+            pairs.sort();
+            assert_eq!(pairs, brute_pairs(&points, radius));
+        }
+    }
+
+    #[test]
+    fn grid3_queries() {
+        let mut grid = SpatialHashGrid3::new(2.0);
+        grid.insert(1u8, Vec3::new(1.0, 1.0, 1.0));
+        grid.insert(2u8, Vec3::new(3.0, 1.0, 1.0));
+        grid.insert(3u8, Vec3::new(-5.0, 0.0, 0.0));
+        let mut found = grid.query_aabb(&Aabb3::new(Vec3::ZERO, Vec3::splat(3.0)));
+        found.sort();
+        assert_eq!(found, vec![1, 2]);
+        assert_eq!(grid.query_radius(Vec3::new(-4.0, 0.0, 0.0), 1.0), vec![3]);
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{Scalar, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A sphere described by its `center` and `radius`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Sphere, Vec3};
+///
+/// let s = Sphere::new(Vec3::ZERO, 2.0);
+/// assert!(s.contains_point(Vec3::new(1.0, 1.0, 0.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    /// Center of the sphere.
+    pub center: Vec3,
+    /// Radius of the sphere.
+    pub radius: Scalar,
+}
+
+impl Sphere {
+    #[inline(always)]
+    pub const fn new(center: Vec3, radius: Scalar) -> Self {
+        Sphere { center, radius }
+    }
+
+    /// Returns `true` if `point` is inside or on the surface of the sphere.
+    #[inline]
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_point_includes_surface() {
+        let s = Sphere::new(Vec3::ZERO, 1.0 as Scalar);
+        assert!(s.contains_point(Vec3::new(1.0, 0.0, 0.0)));
+        assert!(!s.contains_point(Vec3::new(1.0, 0.1, 0.0)));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{CubicBezier, Scalar, Vec2, Vec3, float};
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Sub};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A point type splines and curves can be built over.
+pub trait SplinePoint:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Scalar, Output = Self>
+{
+    /// Returns the distance to `other`.
+    fn distance_to(self, other: Self) -> Scalar;
+
+    /// Returns the length of the point as a vector from the origin.
+    fn length(self) -> Scalar;
+}
+
+impl SplinePoint for Vec2 {
+    #[inline]
+    fn distance_to(self, other: Self) -> Scalar {
+        self.distance(other)
+    }
+
+    #[inline]
+    fn length(self) -> Scalar {
+        Vec2::length(&self)
+    }
+}
+
+impl SplinePoint for Vec3 {
+    #[inline]
+    fn distance_to(self, other: Self) -> Scalar {
+        self.distance(other)
+    }
+
+    #[inline]
+    fn length(self) -> Scalar {
+        Vec3::length(&self)
+    }
+}
+
+/// A cubic Hermite segment from `p0` to `p1` with tangents `m0` and `m1`, parameterized over
+/// `t` in `[0, 1]`.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{CubicHermite, Vec2};
+///
+/// let tangent = Vec2::new(1.0, 0.0);
+/// let segment = CubicHermite::new(Vec2::ZERO, tangent, Vec2::new(1.0, 0.0), tangent);
+/// assert_eq!(segment.position(0.5), Vec2::new(0.5, 0.0));
+/// assert_eq!(segment.derivative(1.0), Vec2::new(1.0, 0.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicHermite<P> {
+    pub p0: P,
+    pub m0: P,
+    pub p1: P,
+    pub m1: P,
+}
+
+impl<P: SplinePoint> CubicHermite<P> {
+    #[inline(always)]
+    pub const fn new(p0: P, m0: P, p1: P, m1: P) -> Self {
+        CubicHermite { p0, m0, p1, m1 }
+    }
+
+    /// Returns the point at `t`.
+    pub fn position(&self, t: Scalar) -> P {
+        let (t2, t3) = (t * t, t * t * t);
+        self.p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+            + self.m0 * (t3 - 2.0 * t2 + t)
+            + self.p1 * (3.0 * t2 - 2.0 * t3)
+            + self.m1 * (t3 - t2)
+    }
+
+    /// Returns the derivative with respect to `t` at `t`.
+    pub fn derivative(&self, t: Scalar) -> P {
+        let t2 = t * t;
+        self.p0 * (6.0 * t2 - 6.0 * t)
+            + self.m0 * (3.0 * t2 - 4.0 * t + 1.0)
+            + self.p1 * (6.0 * t - 6.0 * t2)
+            + self.m1 * (3.0 * t2 - 2.0 * t)
+    }
+
+    /// Returns the same curve as a cubic Bezier.
+    #[inline]
+    pub fn to_bezier(&self) -> CubicBezier<P> {
+        CubicBezier::new(
+            self.p0,
+            self.p0 + self.m0 * (1.0 / 3.0),
+            self.p1 - self.m1 * (1.0 / 3.0),
+            self.p1,
+        )
+    }
+}
+
+/// How a [`CatmullRom`] spline spaces its knots.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CatmullRomKind {
+    /// Evenly spaced knots. Cheapest, but can overshoot and loop on uneven spacing.
+    Uniform,
+    /// Knots spaced by the square root of the distance between points. Never forms cusps or
+    /// self-intersections within a segment, which makes it the usual choice for paths.
+    #[default]
+    Centripetal,
+    /// Knots spaced by the distance between points. Hugs the control polygon tightly.
+    Chordal,
+}
+
+impl CatmullRomKind {
+    #[inline]
+    fn alpha(self) -> Scalar {
+        match self {
+            CatmullRomKind::Uniform => 0.0,
+            CatmullRomKind::Centripetal => 0.5,
+            CatmullRomKind::Chordal => 1.0,
+        }
+    }
+}
+
+/// A Catmull-Rom spline passing through every control point.
+///
+/// The spline is parameterized globally by `t` in `[0, n - 1]` for `n` points: the integer
+/// part selects the segment and the fraction the position along it, so `t = i` is exactly
+/// `points[i]`. The end segments extrapolate a phantom point by mirroring their neighbour.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{CatmullRom, CatmullRomKind, Vec3};
+///
+/// let path = CatmullRom::new(
+///     vec![Vec3::ZERO, Vec3::new(4.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 4.0)],
+///     CatmullRomKind::Centripetal,
+/// );
+/// assert_eq!(path.segment_count(), 2);
+/// assert_eq!(path.position(1.0), Vec3::new(4.0, 0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CatmullRom<P> {
+    points: Vec<P>,
+    kind: CatmullRomKind,
+}
+
+/// A [`CatmullRom`] spline over 2D points.
+pub type CatmullRom2 = CatmullRom<Vec2>;
+/// A [`CatmullRom`] spline over 3D points.
+pub type CatmullRom3 = CatmullRom<Vec3>;
+
+impl<P: SplinePoint> CatmullRom<P> {
+    /// Creates a spline through `points`.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than two points.
+    pub fn new(points: Vec<P>, kind: CatmullRomKind) -> Self {
+        assert!(
+            points.len() >= 2,
+            "Catmull-Rom spline needs at least two points"
+        );
+        CatmullRom { points, kind }
+    }
+
+    /// Returns the control points.
+    #[inline]
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// Returns how the segments are parameterized.
+    #[inline]
+    pub fn kind(&self) -> CatmullRomKind {
+        self.kind
+    }
+
+    /// Returns the number of segments, one less than the number of points.
+    #[inline]
+    pub fn segment_count(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    /// Returns segment `index` (from `points[index]` to `points[index + 1]`) as a Hermite
+    /// segment over `[0, 1]`.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.segment_count()`.
+    pub fn segment(&self, index: usize) -> CubicHermite<P> {
+        let p1 = self.points[index];
+        let p2 = self.points[index + 1];
+        let p0 = if index > 0 {
+            self.points[index - 1]
+        } else {
+            p1 * 2.0 - p2
+        };
+        let p3 = match self.points.get(index + 2) {
+            Some(&p) => p,
+            None => p2 * 2.0 - p1,
+        };
+
+        catmull_rom_segment([p0, p1, p2, p3], self.kind)
+    }
+
+    /// Returns the point at global parameter `t`, clamped to `[0, n - 1]`.
+    pub fn position(&self, t: Scalar) -> P {
+        let (index, local) = self.locate(t);
+        self.segment(index).position(local)
+    }
+
+    /// Returns the derivative with respect to the global parameter at `t`, clamped to
+    /// `[0, n - 1]`.
+    pub fn derivative(&self, t: Scalar) -> P {
+        let (index, local) = self.locate(t);
+        self.segment(index).derivative(local)
+    }
+
+    /// Splits a global parameter into a segment index and a local parameter in `[0, 1]`.
+    fn locate(&self, t: Scalar) -> (usize, Scalar) {
+        let last = self.segment_count();
+        let t = t.clamp(0.0, last as Scalar);
+        let index = (float::floor(t) as usize).min(last - 1);
+        (index, t - index as Scalar)
+    }
+}
+
+/// Returns the Hermite segment from `p1` to `p2` of the Catmull-Rom spline through `points`.
+pub(crate) fn catmull_rom_segment<P: SplinePoint>(
+    [p0, p1, p2, p3]: [P; 4],
+    kind: CatmullRomKind,
+) -> CubicHermite<P> {
+    let alpha = kind.alpha();
+    let d01 = knot_interval(p0, p1, alpha);
+    let d12 = knot_interval(p1, p2, alpha);
+    let d23 = knot_interval(p2, p3, alpha);
+    // Tangents of the non-uniform spline, rescaled from knot time to `[0, 1]`.
+    let m1 =
+        ((p1 - p0) * (1.0 / d01) - (p2 - p0) * (1.0 / (d01 + d12)) + (p2 - p1) * (1.0 / d12)) * d12;
+    let m2 =
+        ((p2 - p1) * (1.0 / d12) - (p3 - p1) * (1.0 / (d12 + d23)) + (p3 - p2) * (1.0 / d23)) * d12;
+    CubicHermite::new(p1, m1, p2, m2)
+}
+
+/// Returns the knot spacing `|b - a|^alpha`, falling back to `1` for coincident points so
+/// repeated control points don't divide by zero.
+#[inline]
+fn knot_interval<P: SplinePoint>(a: P, b: P, alpha: Scalar) -> Scalar {
+    let d = float::powf(a.distance_to(b), alpha);
+    if d > Scalar::EPSILON { d } else { 1.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    fn zigzag(kind: CatmullRomKind) -> CatmullRom2 {
+        CatmullRom::new(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(1.0, 3.0),
+                Vec2::new(1.5, 3.2),
+                Vec2::new(6.0, -1.0),
+                Vec2::new(7.0, 0.0),
+            ],
+            kind,
+        )
+    }
+
+    #[test]
+    fn spline_interpolates_control_points() {
+        for kind in [
+            CatmullRomKind::Uniform,
+            CatmullRomKind::Centripetal,
+            CatmullRomKind::Chordal,
+        ] {
+            let spline = zigzag(kind);
+            for (i, &p) in spline.points.iter().enumerate() {
+                assert!(spline.position(i as Scalar).distance(p) <= EPS);
+            }
+            assert_eq!(spline.position(-1.0), spline.points[0]);
+            assert!(spline.position(10.0).distance(spline.points[4]) <= EPS);
+        }
+    }
+
+    #[test]
+    fn spline_is_c1_at_joints() {
+        let spline = zigzag(CatmullRomKind::Centripetal);
+        for i in 0..spline.segment_count() - 1 {
+            let end = spline.segment(i).derivative(1.0);
+            let start = spline.segment(i + 1).derivative(0.0);
+            // The local parameters span different knot intervals, so only directions match.
+            assert!(end.perp_dot(start).abs() <= EPS * end.length() * start.length());
+            assert!(end.dot(start) > 0.0);
+        }
+    }
+
+    #[test]
+    fn derivative_matches_finite_differences() {
+        let spline = zigzag(CatmullRomKind::Uniform);
+        let h = 1.0e-3 as Scalar;
+        for t in [0.3 as Scalar, 1.5, 2.9, 3.4] {
+            let numeric = (spline.position(t + h) - spline.position(t - h)) * (0.5 / h);
+            assert!(numeric.distance(spline.derivative(t)) <= 1.0e-2 as Scalar);
+        }
+        // Uniform Catmull-Rom tangents are half the chord between the neighbours.
+        let m = spline.derivative(1.0);
+        assert!(m.distance((spline.points[2] - spline.points[0]) * 0.5) <= EPS);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least two points")]
+    fn new_needs_two_points() {
+        CatmullRom::new(vec![Vec3::ZERO], CatmullRomKind::Uniform);
+    }
+}
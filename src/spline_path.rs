@@ -0,0 +1,330 @@
+use crate::spline::catmull_rom_segment;
+use crate::{CatmullRomKind, CubicBezier, Scalar, SplinePoint, Vec2, Vec3, float};
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// How the control points of a [`SplinePath`] are interpreted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplinePathKind {
+    /// The path passes through every control point.
+    CatmullRom(CatmullRomKind),
+    /// Consecutive cubic Bezier segments sharing endpoints: `[p0, c0, c1, p1, c2, c3, p2, ...]`.
+    CubicBezier,
+}
+
+/// A path through control points that can be traversed at constant speed.
+///
+/// Construction precomputes an arc-length table, so [`SplinePath::position_at_distance`] is a
+/// binary search plus one curve evaluation. The whole path, table included, is a
+/// `SpacetimeType` and can be stored in a table row (e.g. a patrol route).
+///
+/// A looped path joins its last control point back to its first; distances wrap around it.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{CatmullRomKind, SplinePath, Vec2};
+///
+/// let patrol = SplinePath::catmull_rom(
+///     vec![
+///         Vec2::new(0.0, 0.0),
+///         Vec2::new(10.0, 0.0),
+///         Vec2::new(10.0, 10.0),
+///         Vec2::new(0.0, 10.0),
+///     ],
+///     CatmullRomKind::Centripetal,
+///     true,
+///     0.01,
+/// );
+/// assert!(patrol.length() > 40.0);
+/// // One full lap lands back at the start.
+/// let start = patrol.position_at_distance(0.0);
+/// assert!(patrol.position_at_distance(patrol.length()).distance(start) < 1.0e-3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplinePath<P> {
+    points: Vec<P>,
+    kind: SplinePathKind,
+    looped: bool,
+    /// Cumulative distance at each table sample, starting at `0`.
+    distances: Vec<Scalar>,
+    /// Global parameter (segment index plus local `t`) at each table sample.
+    params: Vec<Scalar>,
+}
+
+/// A [`SplinePath`] over 2D points.
+pub type SplinePath2 = SplinePath<Vec2>;
+/// A [`SplinePath`] over 3D points.
+pub type SplinePath3 = SplinePath<Vec3>;
+
+impl<P: SplinePoint> SplinePath<P> {
+    /// Builds a Catmull-Rom path through `points`.
+    ///
+    /// `tolerance` bounds the distance between the curve and the polyline used to measure it.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than two points.
+    pub fn catmull_rom(
+        points: Vec<P>,
+        kind: CatmullRomKind,
+        looped: bool,
+        tolerance: Scalar,
+    ) -> Self {
+        assert!(points.len() >= 2, "spline path needs at least two points");
+        Self::build(points, SplinePathKind::CatmullRom(kind), looped, tolerance)
+    }
+
+    /// Builds a path of cubic Bezier segments.
+    ///
+    /// An open path takes `3n + 1` points for `n` segments; a looped path takes `3n`, the last
+    /// segment ending at the first point.
+    ///
+    /// # Panics
+    /// Panics if the number of points doesn't describe at least one segment.
+    pub fn bezier(points: Vec<P>, looped: bool, tolerance: Scalar) -> Self {
+        let valid = if looped {
+            !points.is_empty() && points.len().is_multiple_of(3)
+        } else {
+            points.len() >= 4 && points.len() % 3 == 1
+        };
+        assert!(
+            valid,
+            "Bezier spline path needs 3n + 1 points, or 3n when looped"
+        );
+        Self::build(points, SplinePathKind::CubicBezier, looped, tolerance)
+    }
+
+    fn build(points: Vec<P>, kind: SplinePathKind, looped: bool, tolerance: Scalar) -> Self {
+        let mut path = SplinePath {
+            points,
+            kind,
+            looped,
+            distances: vec![0.0],
+            params: vec![0.0],
+        };
+        let mut length = 0.0;
+        for index in 0..path.segment_count() {
+            let samples = path.segment(index).flatten_with_t(tolerance);
+            for pair in samples.windows(2) {
+                length += pair[0].1.distance_to(pair[1].1);
+                path.distances.push(length);
+                path.params.push(index as Scalar + pair[1].0);
+            }
+        }
+        path
+    }
+
+    /// Returns the control points.
+    #[inline]
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// Returns how the control points are interpreted.
+    #[inline]
+    pub fn kind(&self) -> SplinePathKind {
+        self.kind
+    }
+
+    /// Returns `true` if the path joins back to its start.
+    #[inline]
+    pub fn is_looped(&self) -> bool {
+        self.looped
+    }
+
+    /// Returns the number of curve segments.
+    pub fn segment_count(&self) -> usize {
+        let n = self.points.len();
+        match (self.kind, self.looped) {
+            (SplinePathKind::CatmullRom(_), false) => n - 1,
+            (SplinePathKind::CatmullRom(_), true) => n,
+            (SplinePathKind::CubicBezier, false) => (n - 1) / 3,
+            (SplinePathKind::CubicBezier, true) => n / 3,
+        }
+    }
+
+    /// Returns segment `index` as a cubic Bezier.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.segment_count()`.
+    pub fn segment(&self, index: usize) -> CubicBezier<P> {
+        assert!(index < self.segment_count(), "segment index out of range");
+        let n = self.points.len();
+        let at = |i: usize| self.points[i % n];
+        match self.kind {
+            SplinePathKind::CubicBezier => {
+                let i = 3 * index;
+                CubicBezier::new(at(i), at(i + 1), at(i + 2), at(i + 3))
+            }
+            SplinePathKind::CatmullRom(kind) => {
+                let (p1, p2) = (at(index), at(index + 1));
+                let p0 = match (index, self.looped) {
+                    (0, false) => p1 * 2.0 - p2,
+                    _ => at(index + n - 1),
+                };
+                let p3 = if !self.looped && index + 2 >= n {
+                    p2 * 2.0 - p1
+                } else {
+                    at(index + 2)
+                };
+                catmull_rom_segment([p0, p1, p2, p3], kind).to_bezier()
+            }
+        }
+    }
+
+    /// Returns the total length of the path.
+    #[inline]
+    pub fn length(&self) -> Scalar {
+        *self.distances.last().unwrap_or(&0.0)
+    }
+
+    /// Returns the global parameter (segment index plus local `t`) at `distance` along the
+    /// path.
+    ///
+    /// Distances wrap around a looped path and are clamped to the ends of an open one.
+    pub fn param_at_distance(&self, distance: Scalar) -> Scalar {
+        let length = self.length();
+        let distance = if self.looped && length > 0.0 {
+            float::rem_euclid(distance, length)
+        } else {
+            distance.clamp(0.0, length)
+        };
+        let i = self.distances.partition_point(|&d| d < distance);
+        if i == 0 {
+            return 0.0;
+        }
+        if i == self.distances.len() {
+            return self.segment_count() as Scalar;
+        }
+        let (d0, d1) = (self.distances[i - 1], self.distances[i]);
+        let (u0, u1) = (self.params[i - 1], self.params[i]);
+        u0 + (u1 - u0) * (distance - d0) / (d1 - d0)
+    }
+
+    /// Returns the point at global parameter `u`, clamped to `[0, segment_count]`.
+    pub fn position(&self, u: Scalar) -> P {
+        let (index, t) = self.locate(u);
+        self.segment(index).evaluate(t)
+    }
+
+    /// Returns the derivative with respect to the global parameter at `u`, clamped to
+    /// `[0, segment_count]`.
+    pub fn derivative(&self, u: Scalar) -> P {
+        let (index, t) = self.locate(u);
+        self.segment(index).derivative(t)
+    }
+
+    /// Returns the point `distance` along the path.
+    #[inline]
+    pub fn position_at_distance(&self, distance: Scalar) -> P {
+        self.position(self.param_at_distance(distance))
+    }
+
+    /// Returns the unit direction of travel `distance` along the path, or a zero vector where
+    /// the path stops moving (e.g. at repeated control points).
+    pub fn tangent_at_distance(&self, distance: Scalar) -> P {
+        let d = self.derivative(self.param_at_distance(distance));
+        let length = d.length();
+        if length > Scalar::EPSILON {
+            d * (1.0 / length)
+        } else {
+            d * 0.0
+        }
+    }
+
+    fn locate(&self, u: Scalar) -> (usize, Scalar) {
+        let last = self.segment_count();
+        let u = u.clamp(0.0, last as Scalar);
+        let index = (float::floor(u) as usize).min(last - 1);
+        (index, u - index as Scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-3 as Scalar;
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn catmull_rom_path_passes_through_points() {
+        for looped in [false, true] {
+            let path = SplinePath::catmull_rom(square(), CatmullRomKind::Centripetal, looped, 0.01);
+            assert_eq!(path.segment_count(), if looped { 4 } else { 3 });
+            for (i, &p) in square().iter().enumerate() {
+                assert!(path.position(i as Scalar).distance(p) <= EPS);
+            }
+        }
+        // Closing the loop adds the segment from the last point back to the first.
+        let open = SplinePath::catmull_rom(square(), CatmullRomKind::Uniform, false, 0.01);
+        let looped = SplinePath::catmull_rom(square(), CatmullRomKind::Uniform, true, 0.01);
+        assert!(looped.length() > open.length() + 9.0);
+        assert!(looped.position(4.0).distance(square()[0]) <= EPS);
+    }
+
+    #[test]
+    fn constant_speed_traversal() {
+        let path = SplinePath::catmull_rom(square(), CatmullRomKind::Centripetal, true, 0.001);
+        let steps = 50;
+        let step = path.length() / steps as Scalar;
+        for i in 0..steps {
+            let a = path.position_at_distance(step * i as Scalar);
+            let b = path.position_at_distance(step * (i + 1) as Scalar);
+            assert!((a.distance(b) - step).abs() <= 0.02);
+            let tangent = path.tangent_at_distance(step * i as Scalar);
+            assert!((tangent.length() - 1.0).abs() <= EPS);
+        }
+        // Distances wrap on a loop.
+        let d = path.length() * 0.3;
+        let wrapped = path.position_at_distance(d + 2.0 * path.length());
+        assert!(wrapped.distance(path.position_at_distance(d)) <= EPS);
+        assert!(
+            path.position_at_distance(-d)
+                .distance(path.position_at_distance(path.length() - d))
+                <= EPS
+        );
+    }
+
+    #[test]
+    fn bezier_path_segments_and_clamping() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(5.0, 0.0, 0.0),
+            Vec3::new(6.0, 0.0, 0.0),
+        ];
+        let path = SplinePath::bezier(points, false, 0.01);
+        assert_eq!(path.segment_count(), 2);
+        assert!((path.length() - 6.0).abs() <= EPS);
+        assert!(
+            path.position_at_distance(2.5)
+                .distance(Vec3::new(2.5, 0.0, 0.0))
+                <= EPS
+        );
+        assert_eq!(path.position_at_distance(100.0), Vec3::new(6.0, 0.0, 0.0));
+        assert_eq!(path.position_at_distance(-1.0), Vec3::ZERO);
+        assert_eq!(path.tangent_at_distance(3.0), Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "3n + 1 points")]
+    fn bezier_needs_whole_segments() {
+        SplinePath::bezier(square(), true, 0.01);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{Scalar, Vec2, Vec3, consts, float, float_cmp};
+use core::ops::{Add, Mul, Sub};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A damped spring pulling a value towards a target.
+///
+/// The spring has unit mass and obeys `x'' = -stiffness * (x - target) - damping * x'`.
+/// [`Spring::update`] advances it with the closed-form solution of that equation rather than a
+/// numerical integrator, so the result is the same whether a second is simulated in one step
+/// or in sixty, and no step size makes it explode.
+///
+/// The state is plain data, so a spring can be stored in a table row and updated each tick.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Spring3, Vec3};
+///
+/// let mut camera = Spring3::critically_damped(Vec3::ZERO, 2.0);
+/// let target = Vec3::new(10.0, 0.0, 0.0);
+/// for _ in 0..60 {
+///     camera.update(target, 1.0 / 30.0);
+/// }
+/// assert!(camera.position.distance(target) < 0.01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spring<T> {
+    pub position: T,
+    pub velocity: T,
+    /// Restoring acceleration per unit of displacement.
+    pub stiffness: Scalar,
+    /// Opposing acceleration per unit of velocity.
+    pub damping: Scalar,
+}
+
+/// A [`Spring`] over scalars.
+pub type Spring1 = Spring<Scalar>;
+/// A [`Spring`] over 2D vectors.
+pub type Spring2 = Spring<Vec2>;
+/// A [`Spring`] over 3D vectors.
+pub type Spring3 = Spring<Vec3>;
+
+impl<T> Spring<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Scalar, Output = T>,
+{
+    /// Creates a spring at rest at `position` from raw stiffness and damping coefficients.
+    #[inline]
+    pub fn new(position: T, stiffness: Scalar, damping: Scalar) -> Self {
+        Spring {
+            position,
+            velocity: position * 0.0,
+            stiffness,
+            damping,
+        }
+    }
+
+    /// Creates a spring at rest at `position` that oscillates at `frequency` hertz when
+    /// undamped, with the given damping ratio.
+    ///
+    /// A ratio of `1` is critically damped, below `1` overshoots and above `1` approaches
+    /// the target more slowly without overshooting.
+    #[inline]
+    pub fn from_frequency(position: T, frequency: Scalar, damping_ratio: Scalar) -> Self {
+        let omega = consts::TAU * frequency;
+        Self::new(position, omega * omega, 2.0 * damping_ratio * omega)
+    }
+
+    /// Creates a critically damped spring, the fastest response that never overshoots.
+    #[inline]
+    pub fn critically_damped(position: T, frequency: Scalar) -> Self {
+        Self::from_frequency(position, frequency, 1.0)
+    }
+
+    /// Returns the undamped angular frequency in radians per second.
+    #[inline]
+    pub fn angular_frequency(&self) -> Scalar {
+        float::sqrt(self.stiffness.max(0.0))
+    }
+
+    /// Returns the damping ratio, or infinity for a spring without stiffness.
+    #[inline]
+    pub fn damping_ratio(&self) -> Scalar {
+        self.damping / (2.0 * self.angular_frequency())
+    }
+
+    /// Advances the spring by `dt` seconds towards `target` and returns the new position.
+    ///
+    /// A non-positive `dt` leaves the spring unchanged.
+    pub fn update(&mut self, target: T, dt: Scalar) -> T {
+        if dt > 0.0 {
+            let [pp, pv, vp, vv] = self.coefficients(dt);
+            let offset = self.position - target;
+            let velocity = self.velocity;
+            self.position = target + offset * pp + velocity * pv;
+            self.velocity = offset * vp + velocity * vv;
+        }
+        self.position
+    }
+
+    /// Returns the scalars mapping the initial offset and velocity to their values after `t`
+    /// seconds: `[offset->offset, velocity->offset, offset->velocity, velocity->velocity]`.
+    fn coefficients(&self, t: Scalar) -> [Scalar; 4] {
+        let omega = self.angular_frequency();
+        let c = self.damping;
+        if omega <= Scalar::EPSILON {
+            // No restoring force: velocity decays and the offset drifts.
+            if c.abs() <= Scalar::EPSILON {
+                return [1.0, t, 0.0, 1.0];
+            }
+            let e = float::exp(-c * t);
+            return [1.0, (1.0 - e) / c, 0.0, e];
+        }
+        let zeta = c / (2.0 * omega);
+        if float_cmp::abs_eq(zeta, 1.0, 1.0e-4) {
+            let e = float::exp(-omega * t);
+            [
+                (1.0 + omega * t) * e,
+                t * e,
+                -omega * omega * t * e,
+                (1.0 - omega * t) * e,
+            ]
+        } else if zeta < 1.0 {
+            let a = zeta * omega;
+            let wd = omega * float::sqrt(1.0 - zeta * zeta);
+            let e = float::exp(-a * t);
+            let (sin, cos) = float::sin_cos(wd * t);
+            [
+                e * (cos + a / wd * sin),
+                e * sin / wd,
+                -e * omega * omega / wd * sin,
+                e * (cos - a / wd * sin),
+            ]
+        } else {
+            let root = omega * float::sqrt(zeta * zeta - 1.0);
+            let (r1, r2) = (-zeta * omega + root, -zeta * omega - root);
+            let (e1, e2) = (float::exp(r1 * t), float::exp(r2 * t));
+            let inv = 1.0 / (r1 - r2);
+            [
+                (r1 * e2 - r2 * e1) * inv,
+                (e1 - e2) * inv,
+                r1 * r2 * (e2 - e1) * inv,
+                (r1 * e1 - r2 * e2) * inv,
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn result_is_independent_of_step_size() {
+        for ratio in [0.3 as Scalar, 1.0, 2.5] {
+            let mut coarse = Spring1::from_frequency(0.0, 1.5, ratio);
+            let mut fine = coarse;
+            coarse.update(5.0, 0.8);
+            for _ in 0..80 {
+                fine.update(5.0, 0.01);
+            }
+            assert!((coarse.position - fine.position).abs() <= EPS, "{ratio}");
+            assert!((coarse.velocity - fine.velocity).abs() <= 1.0e-3 as Scalar);
+        }
+    }
+
+    #[test]
+    fn damping_ratio_controls_overshoot() {
+        let peak = |ratio: Scalar| {
+            let mut spring = Spring2::from_frequency(Vec2::ZERO, 1.0, ratio);
+            let mut peak: Scalar = 0.0;
+            for _ in 0..300 {
+                peak = peak.max(spring.update(Vec2::new(1.0, 0.0), 0.02).x);
+            }
+            assert!((spring.position.x - 1.0).abs() <= 1.0e-2 as Scalar);
+            peak
+        };
+        assert!(peak(0.2) > 1.3);
+        assert!(peak(1.0) <= 1.0);
+        assert!(peak(3.0) <= 1.0);
+    }
+
+    #[test]
+    fn huge_steps_settle_instead_of_exploding() {
+        let mut spring = Spring3::new(Vec3::ZERO, 400.0, 5.0);
+        spring.velocity = Vec3::new(0.0, 50.0, 0.0);
+        let target = Vec3::new(1.0, 2.0, 3.0);
+        spring.update(target, 100.0);
+        assert!(spring.position.distance(target) <= EPS);
+        assert!(spring.velocity.length() <= EPS);
+        assert_eq!(spring.update(Vec3::ZERO, 0.0), spring.position);
+    }
+
+    #[test]
+    fn spring_without_stiffness_only_damps() {
+        let mut spring = Spring1::new(0.0, 0.0, 2.0);
+        spring.velocity = 4.0;
+        spring.update(100.0, 50.0);
+        assert!((spring.position - 2.0).abs() <= EPS);
+        assert!(spring.velocity.abs() <= EPS);
+    }
+}
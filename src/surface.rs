@@ -0,0 +1,183 @@
+//! Navigation on the surface of a sphere, such as a small planet.
+//!
+//! Positions are ordinary world-space points kept on a [`Sphere`]'s surface; directions are
+//! tangent vectors. The planet's axis is +Y, so "north" points towards the +Y pole, and
+//! tangent frames are right-handed [`Axes`] like the ones in [`conventions`](crate::conventions).
+
+use crate::conventions::Axes;
+use crate::{Quat, Scalar, Sphere, Vec3, consts, float};
+
+impl Sphere {
+    /// Returns the outward unit normal at the surface point above `point`.
+    ///
+    /// The center itself has no defined normal and returns +Y.
+    #[inline]
+    pub fn surface_normal(&self, point: Vec3) -> Vec3 {
+        (point - self.center).normalize_or(0.0, Vec3::new(0.0, 1.0, 0.0))
+    }
+
+    /// Returns the point on the surface directly above or below `point`.
+    #[inline]
+    pub fn project_to_surface(&self, point: Vec3) -> Vec3 {
+        self.center + self.surface_normal(point) * self.radius
+    }
+
+    /// Returns the local frame at the surface point above `point`: `up` along the normal,
+    /// `forward` towards the +Y pole and `right` towards the east.
+    ///
+    /// At the poles, where north is undefined, `forward` is the direction of -Z instead.
+    pub fn tangent_frame(&self, point: Vec3) -> Axes {
+        let up = self.surface_normal(point);
+        Axes::try_right_handed(up, Vec3::new(0.0, 1.0, 0.0), consts::EPSILON)
+            .or_else(|| Axes::try_right_handed(up, Vec3::new(0.0, 0.0, -1.0), consts::EPSILON))
+            .unwrap_or(crate::conventions::DEFAULT)
+    }
+
+    /// Returns the angle in radians between the directions of `a` and `b` from the center.
+    pub fn central_angle(&self, a: Vec3, b: Vec3) -> Scalar {
+        let (na, nb) = (self.surface_normal(a), self.surface_normal(b));
+        // atan2 stays accurate for nearby points, where acos of the dot product doesn't.
+        float::atan2(na.cross(nb).length(), na.dot(nb))
+    }
+
+    /// Returns the length of the shortest path along the surface between the surface points
+    /// above `a` and `b`.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{Scalar, Sphere, Vec3};
+    ///
+    /// let planet = Sphere::new(Vec3::ZERO, 100.0);
+    /// let equator = Vec3::new(100.0, 0.0, 0.0);
+    /// let pole = Vec3::new(0.0, 100.0, 0.0);
+    /// let quarter = 100.0 * core::f64::consts::FRAC_PI_2 as Scalar;
+    /// assert!((planet.great_circle_distance(equator, pole) - quarter).abs() < 1.0e-3);
+    /// ```
+    #[inline]
+    pub fn great_circle_distance(&self, a: Vec3, b: Vec3) -> Scalar {
+        self.central_angle(a, b) * self.radius
+    }
+
+    /// Moves a fraction `t` of the way from the surface point above `a` to the one above `b`
+    /// along the great circle through them, at constant speed.
+    ///
+    /// For antipodal points every great circle is equally short; one through the east of `a`
+    /// is used.
+    pub fn surface_slerp(&self, a: Vec3, b: Vec3, t: Scalar) -> Vec3 {
+        let (na, nb) = (self.surface_normal(a), self.surface_normal(b));
+        let axis = na.cross(nb);
+        let angle = float::atan2(axis.length(), na.dot(nb));
+        let axis = axis.try_normalize(consts::EPSILON).unwrap_or_else(|| {
+            let east = self.tangent_frame(a).right;
+            na.cross(east)
+        });
+        let n = Quat::from_axis_angle(axis, angle * t).mul_vec3(na);
+        self.center + n * self.radius
+    }
+
+    /// Moves the surface point above `point` by `velocity` for `dt` seconds, following the
+    /// curve of the surface, and returns the new point and velocity.
+    ///
+    /// Only the tangential part of `velocity` counts. The returned velocity is the same
+    /// motion carried along to the new point, so repeated steps trace a great circle.
+    ///
+    /// # Examples
+    /// ```
+    /// use spacetimedb_math::{Scalar, Sphere, Vec3};
+    ///
+    /// let planet = Sphere::new(Vec3::ZERO, 10.0);
+    /// let mut position = Vec3::new(0.0, 0.0, 10.0);
+    /// let mut velocity = Vec3::new(0.0, 1.0, 0.0);
+    /// // A quarter of the circumference takes a quarter turn.
+    /// let quarter_time = 10.0 * core::f64::consts::FRAC_PI_2 as Scalar;
+    /// for _ in 0..100 {
+    ///     (position, velocity) = planet.move_on_surface(position, velocity, quarter_time / 100.0);
+    /// }
+    /// assert!(position.distance(Vec3::new(0.0, 10.0, 0.0)) < 1.0e-3);
+    /// assert!(velocity.distance(Vec3::new(0.0, 0.0, -1.0)) < 1.0e-4);
+    /// ```
+    pub fn move_on_surface(&self, point: Vec3, velocity: Vec3, dt: Scalar) -> (Vec3, Vec3) {
+        let up = self.surface_normal(point);
+        let tangent = velocity - up * velocity.dot(up);
+        let speed = tangent.length();
+        if speed <= 0.0 || self.radius <= 0.0 {
+            return (self.center + up * self.radius, tangent);
+        }
+        let axis = up.cross(tangent) * (1.0 / speed);
+        let turn = Quat::from_axis_angle(axis, speed * dt / self.radius);
+        (
+            self.center + turn.mul_vec3(up) * self.radius,
+            turn.mul_vec3(tangent),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn tangent_frames_point_north_and_east() {
+        let planet = Sphere::new(Vec3::new(5.0, 5.0, 5.0), 2.0);
+        let frame = planet.tangent_frame(Vec3::new(5.0, 5.0, 10.0));
+        assert!(frame.up.distance(Vec3::new(0.0, 0.0, 1.0)) <= EPS);
+        assert!(frame.forward.distance(Vec3::new(0.0, 1.0, 0.0)) <= EPS);
+        assert!(frame.right.distance(Vec3::new(1.0, 0.0, 0.0)) <= EPS);
+
+        let pole = planet.tangent_frame(Vec3::new(5.0, 9.0, 5.0));
+        assert!(pole.up.distance(Vec3::new(0.0, 1.0, 0.0)) <= EPS);
+        assert!(pole.forward.distance(Vec3::new(0.0, 0.0, -1.0)) <= EPS);
+        assert!(pole.right.dot(pole.up).abs() <= EPS);
+    }
+
+    #[test]
+    fn projects_and_measures_along_the_surface() {
+        let planet = Sphere::new(Vec3::ZERO, 10.0);
+        assert!(
+            planet
+                .project_to_surface(Vec3::new(0.0, 3.0, 4.0))
+                .distance(Vec3::new(0.0, 6.0, 8.0))
+                <= EPS
+        );
+        let a = Vec3::new(10.0, 0.0, 0.0);
+        let pi = core::f64::consts::PI as Scalar;
+        assert!((planet.great_circle_distance(a, -a) - 10.0 * pi).abs() <= 1.0e-3 as Scalar);
+        assert_eq!(planet.great_circle_distance(a, a * 3.0), 0.0);
+        let near = Vec3::new(10.0, 0.001, 0.0);
+        assert!((planet.great_circle_distance(a, near) - 0.001).abs() <= 1.0e-6 as Scalar);
+    }
+
+    #[test]
+    fn slerp_stays_on_the_surface() {
+        let planet = Sphere::new(Vec3::new(1.0, 0.0, 0.0), 3.0);
+        let a = Vec3::new(4.0, 0.0, 0.0);
+        let b = Vec3::new(1.0, 0.0, 3.0);
+        let mid = planet.surface_slerp(a, b, 0.5);
+        assert!((mid.distance(planet.center) - 3.0).abs() <= EPS);
+        assert!(
+            (planet.great_circle_distance(a, mid) - planet.great_circle_distance(mid, b)).abs()
+                <= EPS
+        );
+        assert!(planet.surface_slerp(a, b, 1.0).distance(b) <= EPS);
+
+        // Antipodal points still get a path, through the east of `a`.
+        let opposite = Vec3::new(-2.0, 0.0, 0.0);
+        let halfway = planet.surface_slerp(a, opposite, 0.5);
+        assert!((halfway.distance(planet.center) - 3.0).abs() <= EPS);
+        assert!(halfway.distance(Vec3::new(1.0, 0.0, -3.0)) <= EPS);
+    }
+
+    #[test]
+    fn moving_ignores_the_normal_component() {
+        let planet = Sphere::new(Vec3::ZERO, 1.0);
+        let start = Vec3::new(0.0, 0.0, 1.0);
+        let (p, v) = planet.move_on_surface(start, Vec3::new(0.0, 0.0, 5.0), 1.0);
+        assert_eq!((p, v), (start, Vec3::ZERO));
+        let (p, v) = planet.move_on_surface(start, Vec3::new(1.0, 0.0, 3.0), 0.1);
+        assert!((p.length() - 1.0).abs() <= EPS);
+        assert!(v.dot(p).abs() <= EPS);
+        assert!((v.length() - 1.0).abs() <= EPS);
+    }
+}
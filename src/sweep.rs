@@ -0,0 +1,309 @@
+//! Continuous (swept) collision tests for moving shapes.
+//!
+//! A sweep moves a shape by `motion` over one step and reports the first time of impact as a
+//! fraction of that motion, so fast movers can't tunnel through thin geometry between ticks.
+
+use crate::{Aabb3, Capsule3, Plane, Ray3, Scalar, Sphere, Vec3};
+
+/// First impact found by a sweep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepHit {
+    /// Fraction of the motion, in `[0, 1]`, at which the shapes first touch.
+    pub time: Scalar,
+    /// Unit contact normal, pointing from the obstacle towards the moving shape.
+    pub normal: Vec3,
+}
+
+impl SweepHit {
+    #[inline(always)]
+    pub const fn new(time: Scalar, normal: Vec3) -> Self {
+        SweepHit { time, normal }
+    }
+
+    /// Returns the part of `motion` left over after moving up to the impact.
+    #[inline]
+    pub fn remaining_motion(&self, motion: Vec3) -> Vec3 {
+        motion * (1.0 - self.time)
+    }
+}
+
+impl Sphere {
+    /// Sweeps this sphere by `motion` against `other`, returning the first impact.
+    ///
+    /// Spheres that already overlap report a hit at time `0.0`.
+    pub fn sweep_sphere(&self, motion: Vec3, other: &Sphere) -> Option<SweepHit> {
+        if let Some(contact) = self.contact_sphere(other) {
+            return Some(SweepHit::new(0.0, contact.normal));
+        }
+        let expanded = Sphere::new(other.center, self.radius + other.radius);
+        let ray = Ray3::new(self.center, motion);
+        let (t, _) = ray.intersect_sphere(&expanded)?;
+        if t > 1.0 {
+            return None;
+        }
+        let normal = (ray.at(t) - other.center) / expanded.radius;
+        Some(SweepHit::new(t, normal))
+    }
+
+    /// Sweeps this sphere by `motion` against `plane`, returning the first impact.
+    ///
+    /// The plane's normal is expected to be unit length. The sphere can hit the plane from
+    /// either side; spheres already touching it report a hit at time `0.0`.
+    pub fn sweep_plane(&self, motion: Vec3, plane: &Plane) -> Option<SweepHit> {
+        let distance = plane.signed_distance(self.center);
+        let side = if distance >= 0.0 { 1.0 } else { -1.0 };
+        let normal = plane.normal * side;
+        let gap = distance.abs() - self.radius;
+        if gap <= 0.0 {
+            return Some(SweepHit::new(0.0, normal));
+        }
+        let approach = -motion.dot(normal);
+        if approach <= 0.0 || gap > approach {
+            return None;
+        }
+        Some(SweepHit::new(gap / approach, normal))
+    }
+
+    /// Sweeps this sphere by `motion` against `aabb`, returning the first impact.
+    ///
+    /// Spheres that already overlap the box report a hit at time `0.0`.
+    pub fn sweep_aabb(&self, motion: Vec3, aabb: &Aabb3) -> Option<SweepHit> {
+        if let Some(contact) = self.contact_aabb(aabb) {
+            return Some(SweepHit::new(0.0, contact.normal));
+        }
+
+        // The swept volume is hit where the center's path enters the box rounded by the
+        // radius. Test against the box grown by the radius first; that's exact on faces.
+        let ray = Ray3::new(self.center, motion);
+        let hit = ray.intersect_aabb(&aabb.expand_by(self.radius))?;
+        if hit.t_enter > 1.0 {
+            return None;
+        }
+        let point = ray.at(hit.t_enter);
+
+        // Outside the box on two or three axes, the grown box has square edges and corners
+        // where the rounded box has capsules along the box's edges.
+        let low = [
+            point.x < aabb.min.x,
+            point.y < aabb.min.y,
+            point.z < aabb.min.z,
+        ];
+        let high = [
+            point.x > aabb.max.x,
+            point.y > aabb.max.y,
+            point.z > aabb.max.z,
+        ];
+        let outside = (0..3).filter(|&i| low[i] || high[i]).count();
+        if outside < 2 {
+            return Some(SweepHit::new(hit.t_enter, hit.normal));
+        }
+
+        let min = aabb.min.to_array();
+        let max = aabb.max.to_array();
+        let corner = Vec3::from_array(core::array::from_fn(
+            |i| if high[i] { max[i] } else { min[i] },
+        ));
+        let mut best: Option<SweepHit> = None;
+        for axis in 0..3 {
+            // On a corner all three edges meeting there are candidates; on an edge only the
+            // edge along the axis the point is inside of.
+            if outside == 2 && (low[axis] || high[axis]) {
+                continue;
+            }
+            let mut start = corner.to_array();
+            let mut end = start;
+            start[axis] = min[axis];
+            end[axis] = max[axis];
+            let edge = Capsule3::new(Vec3::from_array(start), Vec3::from_array(end), self.radius);
+            if let Some(edge_hit) = ray.intersect_capsule(&edge)
+                && edge_hit.t <= 1.0
+                && best.is_none_or(|b| edge_hit.t < b.time)
+            {
+                best = Some(SweepHit::new(edge_hit.t, edge_hit.normal));
+            }
+        }
+        best
+    }
+}
+
+impl Aabb3 {
+    /// Sweeps this box by `motion` against the static box `other`, returning the first impact.
+    ///
+    /// Boxes that only touch and slide along each other don't hit. Boxes that already overlap
+    /// report a hit at time `0.0` with the normal of the axis of least penetration.
+    pub fn sweep_aabb(&self, motion: Vec3, other: &Aabb3) -> Option<SweepHit> {
+        let self_min = self.min.to_array();
+        let self_max = self.max.to_array();
+        let other_min = other.min.to_array();
+        let other_max = other.max.to_array();
+        let velocity = motion.to_array();
+
+        if let Some(overlap) = self.intersection(other) {
+            let depth = (overlap.max - overlap.min).to_array();
+            if depth.iter().all(|&d| d > 0.0) {
+                let axis = (0..3)
+                    .min_by(|&a, &b| depth[a].total_cmp(&depth[b]))
+                    .unwrap_or(0);
+                let side = if self_min[axis] + self_max[axis] >= other_min[axis] + other_max[axis] {
+                    1.0
+                } else {
+                    -1.0
+                };
+                return Some(SweepHit::new(0.0, axis_normal(axis, side)));
+            }
+        }
+
+        // Slab test on the Minkowski difference: each axis contributes the interval of time
+        // during which the boxes overlap along it.
+        let mut enter = Scalar::NEG_INFINITY;
+        let mut exit = Scalar::INFINITY;
+        let mut normal = Vec3::ZERO;
+        for axis in 0..3 {
+            let v = velocity[axis];
+            if v == 0.0 {
+                if self_max[axis] < other_min[axis] || self_min[axis] > other_max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let (t_in, t_out, side) = if v > 0.0 {
+                (
+                    (other_min[axis] - self_max[axis]) / v,
+                    (other_max[axis] - self_min[axis]) / v,
+                    -1.0,
+                )
+            } else {
+                (
+                    (other_max[axis] - self_min[axis]) / v,
+                    (other_min[axis] - self_max[axis]) / v,
+                    1.0,
+                )
+            };
+            if t_in > enter {
+                enter = t_in;
+                normal = axis_normal(axis, side);
+            }
+            exit = exit.min(t_out);
+        }
+        if !(0.0..=1.0).contains(&enter) || enter >= exit {
+            return None;
+        }
+        Some(SweepHit::new(enter, normal))
+    }
+}
+
+/// Returns the unit vector along `axis` (0, 1 or 2 for X, Y or Z) with the given sign.
+fn axis_normal(axis: usize, sign: Scalar) -> Vec3 {
+    let mut normal = [0.0; 3];
+    normal[axis] = sign;
+    Vec3::from_array(normal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn sweep_sphere_finds_time_of_impact() {
+        let bullet = Sphere::new(Vec3::ZERO, 0.5);
+        let target = Sphere::new(Vec3::new(10.0, 0.0, 0.0), 1.0);
+        let hit = bullet
+            .sweep_sphere(Vec3::new(20.0, 0.0, 0.0), &target)
+            .unwrap();
+        assert!((hit.time - 8.5 / 20.0).abs() <= EPS);
+        assert!(hit.normal.distance(Vec3::new(-1.0, 0.0, 0.0)) <= EPS);
+        assert_eq!(bullet.sweep_sphere(Vec3::new(5.0, 0.0, 0.0), &target), None);
+        assert_eq!(
+            bullet.sweep_sphere(Vec3::new(0.0, 20.0, 0.0), &target),
+            None
+        );
+    }
+
+    #[test]
+    fn sweep_plane_from_either_side() {
+        let wall = Plane::from_point_normal(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0));
+        let s = Sphere::new(Vec3::new(3.0, 0.0, 0.0), 1.0);
+        let hit = s.sweep_plane(Vec3::new(-4.0, 1.0, 0.0), &wall).unwrap();
+        assert!((hit.time - 0.5).abs() <= EPS);
+        assert_eq!(hit.normal, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(s.sweep_plane(Vec3::new(4.0, 0.0, 0.0), &wall), None);
+        assert_eq!(s.sweep_plane(Vec3::new(-1.0, 0.0, 0.0), &wall), None);
+
+        let behind = Sphere::new(Vec3::new(-3.0, 0.0, 0.0), 1.0);
+        let hit = behind.sweep_plane(Vec3::new(8.0, 0.0, 0.0), &wall).unwrap();
+        assert!((hit.time - 0.25).abs() <= EPS);
+        assert_eq!(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn sweep_aabb_hits_faces_edges_and_corners() {
+        let aabb = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        let s = Sphere::new(Vec3::new(-2.0, 0.5, 0.5), 0.5);
+
+        // Face: straight into the -X side.
+        let hit = s.sweep_aabb(Vec3::new(4.0, 0.0, 0.0), &aabb).unwrap();
+        assert!((hit.time - 0.375).abs() <= EPS);
+        assert_eq!(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+
+        // Edge: cuts across the grown box's square edge but misses the rounded one.
+        let grazing = Sphere::new(Vec3::new(-2.0, 1.1, 0.5), 0.5);
+        assert_eq!(grazing.sweep_aabb(Vec3::new(4.0, -4.0, 0.0), &aabb), None);
+
+        // Edge: hits the rounded edge along Z.
+        let low = Sphere::new(Vec3::new(-2.0, -0.3, 0.5), 0.5);
+        let hit = low.sweep_aabb(Vec3::new(4.0, 0.0, 0.0), &aabb).unwrap();
+        let expected_x = -(0.25 as Scalar - 0.09).sqrt();
+        assert!((hit.time - (expected_x + 2.0) / 4.0).abs() <= EPS);
+        assert!(hit.normal.distance(Vec3::new(expected_x, -0.3, 0.0) / 0.5) <= EPS);
+
+        // Corner: diagonal approach towards the origin corner.
+        let diagonal = Sphere::new(Vec3::splat(-2.0), 0.5);
+        let hit = diagonal.sweep_aabb(Vec3::splat(2.0), &aabb).unwrap();
+        let to_corner = (3.0 as Scalar * 4.0).sqrt();
+        assert!((hit.time - (to_corner - 0.5) / to_corner).abs() <= EPS);
+        let inv_sqrt3 = 1.0 / (3.0 as Scalar).sqrt();
+        assert!(hit.normal.distance(Vec3::splat(-inv_sqrt3)) <= EPS);
+    }
+
+    #[test]
+    fn sweep_aabb_starting_in_contact() {
+        let aabb = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        let s = Sphere::new(Vec3::new(1.2, 0.5, 0.5), 0.5);
+        let hit = s.sweep_aabb(Vec3::new(1.0, 0.0, 0.0), &aabb).unwrap();
+        assert_eq!(hit.time, 0.0 as Scalar);
+        assert_eq!(hit.normal, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn aabb_sweep_reports_time_normal_and_remaining_motion() {
+        let player = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        let wall = Aabb3::new(Vec3::new(3.0, -5.0, -5.0), Vec3::new(4.0, 5.0, 5.0));
+        let motion = Vec3::new(4.0, 2.0, 0.0);
+        let hit = player.sweep_aabb(motion, &wall).unwrap();
+        assert_eq!(hit.time, 0.5 as Scalar);
+        assert_eq!(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(hit.remaining_motion(motion), Vec3::new(2.0, 1.0, 0.0));
+
+        assert_eq!(player.sweep_aabb(Vec3::new(1.5, 0.0, 0.0), &wall), None);
+        assert_eq!(player.sweep_aabb(Vec3::new(-4.0, 0.0, 0.0), &wall), None);
+        assert_eq!(player.sweep_aabb(Vec3::new(4.0, 0.0, 12.0), &wall), None);
+    }
+
+    #[test]
+    fn aabb_sweep_touching_and_overlapping() {
+        let floor = Aabb3::new(Vec3::new(-5.0, -1.0, -5.0), Vec3::new(5.0, 0.0, 5.0));
+        let standing = Aabb3::new(Vec3::ZERO, Vec3::ONE);
+        // Walking along the floor slides; falling into it hits immediately.
+        assert_eq!(standing.sweep_aabb(Vec3::new(1.0, 0.0, 0.0), &floor), None);
+        let hit = standing
+            .sweep_aabb(Vec3::new(1.0, -1.0, 0.0), &floor)
+            .unwrap();
+        assert_eq!(hit, SweepHit::new(0.0, Vec3::new(0.0, 1.0, 0.0)));
+
+        let sunk = Aabb3::new(Vec3::new(0.0, -0.25, 0.0), Vec3::new(1.0, 0.75, 1.0));
+        let hit = sunk.sweep_aabb(Vec3::ZERO, &floor).unwrap();
+        assert_eq!(hit, SweepHit::new(0.0, Vec3::new(0.0, 1.0, 0.0)));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Scalar, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// An accumulator that turns irregular frame times into a whole number of fixed steps.
+///
+/// Scheduled reducers rarely fire exactly on time. Feeding the real elapsed time into
+/// [`FixedTimestep::advance`] returns how many steps of exactly `step` seconds to simulate,
+/// carrying the remainder over to the next call, so the simulation is deterministic whatever
+/// the jitter. [`FixedTimestep::alpha`] is how far the leftover time reaches into the next
+/// step, for blending rendered state between the last two steps.
+///
+/// At most `max_steps` steps run per call; time beyond that is dropped so a long stall
+/// can't snowball into ever longer catch-up frames.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::FixedTimestep;
+///
+/// let mut clock = FixedTimestep::new(0.25);
+/// assert_eq!(clock.advance(0.625), 2);
+/// assert_eq!(clock.alpha(), 0.5);
+/// assert_eq!(clock.advance(0.125), 1);
+/// assert_eq!(clock.alpha(), 0.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedTimestep {
+    /// Length of one step in seconds.
+    pub step: Scalar,
+    /// Time carried over from earlier calls, less than `step` between calls.
+    pub accumulator: Scalar,
+    /// Most steps a single call may return.
+    pub max_steps: u32,
+}
+
+impl FixedTimestep {
+    /// Default cap on the steps run per call.
+    pub const DEFAULT_MAX_STEPS: u32 = 8;
+
+    /// Creates an empty accumulator with [`FixedTimestep::DEFAULT_MAX_STEPS`].
+    #[inline(always)]
+    pub const fn new(step: Scalar) -> Self {
+        FixedTimestep {
+            step,
+            accumulator: 0.0,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+        }
+    }
+
+    /// Adds `dt` seconds and returns the number of fixed steps to run.
+    ///
+    /// Negative and NaN times are ignored, as is a step of zero or less.
+    pub fn advance(&mut self, dt: Scalar) -> u32 {
+        if self.step <= 0.0 || self.step.is_nan() {
+            return 0;
+        }
+        if dt > 0.0 {
+            self.accumulator += dt;
+        }
+        let available = float::floor(self.accumulator / self.step);
+        if available >= self.max_steps as Scalar {
+            // Drop the backlog, keeping only the fraction so `alpha` stays meaningful.
+            self.accumulator = (self.accumulator - available * self.step).max(0.0);
+            return self.max_steps;
+        }
+        let steps = available as u32;
+        self.accumulator = (self.accumulator - steps as Scalar * self.step).max(0.0);
+        steps
+    }
+
+    /// Returns how far the carried-over time reaches into the next step, in `[0, 1)`.
+    #[inline]
+    pub fn alpha(&self) -> Scalar {
+        if self.step > 0.0 {
+            (self.accumulator / self.step).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Discards any carried-over time.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carries_the_remainder_between_calls() {
+        let mut clock = FixedTimestep::new(0.1);
+        let mut steps = 0;
+        for _ in 0..30 {
+            steps += clock.advance(0.035);
+        }
+        // 30 * 35 ms is 1.05 s: ten whole steps and half of another.
+        assert_eq!(steps, 10);
+        assert!((clock.alpha() - 0.5).abs() <= 1.0e-3 as Scalar);
+        clock.reset();
+        assert_eq!(clock.alpha(), 0.0);
+    }
+
+    #[test]
+    fn caps_steps_and_ignores_bad_input() {
+        let mut clock = FixedTimestep::new(0.5);
+        clock.max_steps = 3;
+        assert_eq!(clock.advance(10.25), 3);
+        assert_eq!(clock.accumulator, 0.25);
+        assert_eq!(clock.advance(-4.0), 0);
+        assert_eq!(clock.advance(Scalar::NAN), 0);
+        assert_eq!(clock.accumulator, 0.25);
+        assert_eq!(FixedTimestep::new(0.0).advance(1.0), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Scalar;
+use crate::{FixedTimestep, Scalar};
 use spacetimedb::Timestamp;
 
 const MICROS_PER_SEC: Scalar = 1_000_000.0;
@@ -21,3 +21,27 @@ where
 {
     try_delta_time(now, last).unwrap_or_else(fallback)
 }
+
+impl FixedTimestep {
+    /// Adds the time from `last` to `now` and returns the number of fixed steps to run.
+    ///
+    /// Runs no steps if `last` is after `now`.
+    #[inline]
+    pub fn advance_between(&mut self, now: Timestamp, last: Timestamp) -> u32 {
+        self.advance(try_delta_time(now, last).unwrap_or(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advances_between_timestamps() {
+        let mut clock = FixedTimestep::new(0.25);
+        let last = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let now = Timestamp::from_micros_since_unix_epoch(1_600_000);
+        assert_eq!(clock.advance_between(now, last), 2);
+        assert_eq!(clock.advance_between(last, now), 0);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{Quat, Scalar, Vec3};
+use core::ops::Mul;
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A translation, rotation and non-uniform scale, applied in the order scale, rotate,
+/// translate.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Quat, Transform, Vec3};
+///
+/// let quarter = std::f64::consts::FRAC_PI_2 as _;
+/// let t = Transform::new(
+///     Vec3::new(10.0, 0.0, 0.0),
+///     Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), quarter),
+///     Vec3::splat(2.0),
+/// );
+/// let p = t.transform_point(Vec3::new(1.0, 0.0, 0.0));
+/// assert!(p.distance(Vec3::new(10.0, 2.0, 0.0)) < 1.0e-5);
+/// assert!(t.inverse().transform_point(p).distance(Vec3::new(1.0, 0.0, 0.0)) < 1.0e-5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// The identity transform.
+    pub const IDENTITY: Transform = Transform::new(Vec3::ZERO, Quat::IDENTITY, Vec3::ONE);
+
+    #[inline(always)]
+    pub const fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Creates a transform that only translates.
+    #[inline]
+    pub const fn from_translation(translation: Vec3) -> Self {
+        Transform::new(translation, Quat::IDENTITY, Vec3::ONE)
+    }
+
+    /// Creates a transform that only rotates.
+    #[inline]
+    pub const fn from_rotation(rotation: Quat) -> Self {
+        Transform::new(Vec3::ZERO, rotation, Vec3::ONE)
+    }
+
+    /// Creates a rigid transform from a translation and rotation.
+    #[inline]
+    pub const fn from_translation_rotation(translation: Vec3, rotation: Quat) -> Self {
+        Transform::new(translation, rotation, Vec3::ONE)
+    }
+
+    /// Transforms `point` as a position.
+    #[inline]
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.rotation * scale(point, self.scale) + self.translation
+    }
+
+    /// Transforms `vector` as a direction, ignoring translation.
+    #[inline]
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        self.rotation * scale(vector, self.scale)
+    }
+
+    /// Returns the inverse transform.
+    ///
+    /// Exact for uniform scale. With non-uniform scale combined with rotation the true inverse
+    /// has shear, which this type can't hold, and the result is approximate.
+    pub fn inverse(&self) -> Transform {
+        let rotation = self.rotation.conjugate();
+        let inv_scale = Vec3::new(1.0 / self.scale.x, 1.0 / self.scale.y, 1.0 / self.scale.z);
+        let translation = scale(rotation * -self.translation, inv_scale);
+        Transform::new(translation, rotation, inv_scale)
+    }
+
+    /// Interpolates towards `end`: translation and scale linearly, rotation by
+    /// [`Quat::slerp`].
+    pub fn lerp(&self, end: &Transform, t: Scalar) -> Transform {
+        Transform::new(
+            self.translation.lerp(end.translation, t),
+            self.rotation.slerp(end.rotation, t),
+            self.scale.lerp(end.scale, t),
+        )
+    }
+}
+
+impl Mul for Transform {
+    type Output = Transform;
+
+    /// Composes two transforms: `a * b` applies `b` first, then `a`.
+    ///
+    /// Like [`Transform::inverse`], exact when `self` has uniform scale.
+    #[inline]
+    fn mul(self, rhs: Transform) -> Transform {
+        Transform::new(
+            self.transform_point(rhs.translation),
+            self.rotation * rhs.rotation,
+            scale(self.scale, rhs.scale),
+        )
+    }
+}
+
+impl Mul<Vec3> for Transform {
+    type Output = Vec3;
+
+    /// Transforms `rhs` as a position.
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        self.transform_point(rhs)
+    }
+}
+
+#[inline]
+fn scale(v: Vec3, s: Vec3) -> Vec3 {
+    Vec3::new(v.x * s.x, v.y * s.y, v.z * s.z)
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Isometry3<Scalar>> for Transform {
+        fn from(iso: nalgebra::Isometry3<Scalar>) -> Self {
+            Transform::from_translation_rotation(iso.translation.into(), iso.rotation.into())
+        }
+    }
+
+    /// Isometries can't scale, so the scale is dropped.
+    impl From<Transform> for nalgebra::Isometry3<Scalar> {
+        fn from(t: Transform) -> Self {
+            nalgebra::Isometry3::from_parts(t.translation.into(), t.rotation.into())
+        }
+    }
+}
+
+// Bevy's transforms are `f32` only.
+#[cfg(all(feature = "bevy", feature = "f32"))]
+mod bevy_impls {
+    use super::*;
+    use bevy_transform::components::{GlobalTransform, Transform as BevyTransform};
+
+    impl From<BevyTransform> for Transform {
+        fn from(t: BevyTransform) -> Self {
+            Transform::new(t.translation.into(), t.rotation.into(), t.scale.into())
+        }
+    }
+
+    impl From<Transform> for BevyTransform {
+        fn from(t: Transform) -> Self {
+            BevyTransform {
+                translation: t.translation.into(),
+                rotation: t.rotation.into(),
+                scale: t.scale.into(),
+            }
+        }
+    }
+
+    /// Decomposes the global transform; shear from non-uniformly scaled parents is lost.
+    impl From<GlobalTransform> for Transform {
+        fn from(t: GlobalTransform) -> Self {
+            t.compute_transform().into()
+        }
+    }
+
+    impl From<Transform> for GlobalTransform {
+        fn from(t: Transform) -> Self {
+            GlobalTransform::from(BevyTransform::from(t))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    fn sample() -> Transform {
+        let axis = Vec3::new(1.0, 2.0, 2.0) * (1.0 / 3.0);
+        Transform::new(
+            Vec3::new(3.0, -1.0, 4.0),
+            Quat::from_axis_angle(axis, 0.7),
+            Vec3::splat(1.5),
+        )
+    }
+
+    #[test]
+    fn composition_matches_sequential_application() {
+        let a = sample();
+        let b = Transform::new(
+            Vec3::new(-2.0, 0.5, 1.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), -1.1),
+            Vec3::new(1.0, 2.0, 0.5),
+        );
+        let p = Vec3::new(0.3, -0.8, 2.0);
+        let expected = a.transform_point(b.transform_point(p));
+        assert!((a * b).transform_point(p).distance(expected) <= EPS);
+        assert_eq!(Transform::IDENTITY * p, p);
+        assert!(a.transform_vector(Vec3::ZERO).distance(Vec3::ZERO) <= EPS);
+    }
+
+    #[test]
+    fn inverse_undoes_uniformly_scaled_transforms() {
+        let t = sample();
+        let p = Vec3::new(5.0, 6.0, -7.0);
+        assert!(
+            t.inverse()
+                .transform_point(t.transform_point(p))
+                .distance(p)
+                <= 1.0e-4 as Scalar
+        );
+        let round_trip = t * t.inverse();
+        assert!(round_trip.translation.distance(Vec3::ZERO) <= 1.0e-4 as Scalar);
+        assert!(round_trip.scale.distance(Vec3::ONE) <= EPS);
+    }
+
+    #[test]
+    fn lerp_blends_each_part() {
+        let a = Transform::from_translation(Vec3::new(0.0, 0.0, 0.0));
+        let b = Transform::new(
+            Vec3::new(10.0, 0.0, 0.0),
+            Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 1.0),
+            Vec3::splat(3.0),
+        );
+        let mid = a.lerp(&b, 0.5);
+        assert_eq!(mid.translation, Vec3::new(5.0, 0.0, 0.0));
+        assert_eq!(mid.scale, Vec3::splat(2.0));
+        let half_turn = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 0.5);
+        assert!(mid.rotation.dot(half_turn) >= 1.0 - EPS);
+    }
+
+    #[cfg(all(feature = "bevy", feature = "f32"))]
+    #[test]
+    fn bevy_roundtrip() {
+        use bevy_transform::components::{GlobalTransform, Transform as BevyTransform};
+
+        let t = sample();
+        let bevy: BevyTransform = t.into();
+        assert_eq!(bevy.translation, bevy_math::Vec3::from(t.translation));
+        assert_eq!(Transform::from(bevy), t);
+        let p = Vec3::new(1.0, -2.0, 0.5);
+        let global = GlobalTransform::from(t);
+        let moved: Vec3 = global.transform_point(p.into()).into();
+        assert!(moved.distance(t.transform_point(p)) <= 1.0e-4 as Scalar);
+        let back = Transform::from(global);
+        assert!(back.translation.distance(t.translation) <= EPS);
+        assert!(back.rotation.dot(t.rotation).abs() >= 1.0 - EPS);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_isometry_roundtrip() {
+        let t = Transform::from_translation_rotation(sample().translation, sample().rotation);
+        let iso: nalgebra::Isometry3<Scalar> = t.into();
+        let p = Vec3::new(1.0, -2.0, 0.5);
+        let moved: Vec3 = iso.transform_point(&p.into()).into();
+        assert!(moved.distance(t.transform_point(p)) <= EPS);
+        let back = Transform::from(iso);
+        assert!(back.translation.distance(t.translation) <= EPS);
+        assert!(back.rotation.dot(t.rotation) >= 1.0 - EPS);
+        assert_eq!(back.scale, Vec3::ONE);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Scalar, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 3D triangle with vertices `a`, `b`, `c`.
+///
+/// The front face is the side from which the vertices appear counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Triangle3, Vec3};
+///
+/// let t = Triangle3::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+/// assert_eq!(t.area(), 0.5);
+/// assert_eq!(t.normal(), Some(Vec3::new(0.0, 0.0, 1.0)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle3 {
+    /// First vertex.
+    pub a: Vec3,
+    /// Second vertex.
+    pub b: Vec3,
+    /// Third vertex.
+    pub c: Vec3,
+}
+
+impl Triangle3 {
+    #[inline(always)]
+    pub const fn new(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        Triangle3 { a, b, c }
+    }
+
+    /// Returns the unnormalized face normal, `(b - a) × (c - a)`, whose length is twice the area.
+    #[inline]
+    pub fn scaled_normal(&self) -> Vec3 {
+        (self.b - self.a).cross(self.c - self.a)
+    }
+
+    /// Returns the unit face normal, or `None` for a degenerate triangle.
+    pub fn normal(&self) -> Option<Vec3> {
+        self.scaled_normal().try_normalize(0.0)
+    }
+
+    /// Returns the area of the triangle.
+    pub fn area(&self) -> Scalar {
+        self.scaled_normal().length() * 0.5
+    }
+
+    /// Returns the centroid (average of the vertices).
+    #[inline]
+    pub fn centroid(&self) -> Vec3 {
+        (self.a + self.b + self.c) / 3.0
+    }
+
+    /// Returns the point with barycentric weights `weights` for `(a, b, c)`.
+    #[inline]
+    pub fn point_from_barycentric(&self, weights: Vec3) -> Vec3 {
+        self.a * weights.x + self.b * weights.y + self.c * weights.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_follows_counter_clockwise_winding() {
+        let t = Triangle3::new(
+            Vec3::ZERO,
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        );
+        assert_eq!(t.normal(), Some(Vec3::new(0.0, 1.0, 0.0)));
+        let flat = Triangle3::new(Vec3::ZERO, Vec3::ONE, Vec3::splat(2.0));
+        assert_eq!(flat.normal(), None);
+        assert_eq!(flat.area(), 0.0 as Scalar);
+    }
+
+    #[test]
+    fn barycentric_weights_select_vertices() {
+        let t = Triangle3::new(
+            Vec3::ZERO,
+            Vec3::new(3.0, 0.0, 0.0),
+            Vec3::new(0.0, 3.0, 0.0),
+        );
+        assert_eq!(t.point_from_barycentric(Vec3::new(0.0, 1.0, 0.0)), t.b);
+        assert_eq!(
+            t.point_from_barycentric(Vec3::splat(1.0 / 3.0)),
+            t.centroid()
+        );
+    }
+}
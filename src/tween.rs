@@ -0,0 +1,303 @@
+use crate::{Quat, Scalar, Transform, Vec2, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A value that can be blended between two endpoints.
+pub trait Interpolate: Copy {
+    /// Returns the value a fraction `t` of the way from `self` to `end`.
+    ///
+    /// `t` may leave `[0, 1]` for easings that overshoot.
+    fn interpolate(&self, end: &Self, t: Scalar) -> Self;
+}
+
+impl Interpolate for Scalar {
+    #[inline]
+    fn interpolate(&self, end: &Self, t: Scalar) -> Self {
+        self + (end - self) * t
+    }
+}
+
+impl Interpolate for Vec2 {
+    #[inline]
+    fn interpolate(&self, end: &Self, t: Scalar) -> Self {
+        self.lerp(*end, t)
+    }
+}
+
+impl Interpolate for Vec3 {
+    #[inline]
+    fn interpolate(&self, end: &Self, t: Scalar) -> Self {
+        self.lerp(*end, t)
+    }
+}
+
+impl Interpolate for Quat {
+    #[inline]
+    fn interpolate(&self, end: &Self, t: Scalar) -> Self {
+        self.slerp(*end, t)
+    }
+}
+
+impl Interpolate for Transform {
+    #[inline]
+    fn interpolate(&self, end: &Self, t: Scalar) -> Self {
+        self.lerp(end, t)
+    }
+}
+
+/// Easing curves mapping linear progress in `[0, 1]` to eased progress.
+///
+/// Every curve maps `0` to `0` and `1` to `1`. `In` curves start slowly, `Out` curves end
+/// slowly, and `InOut` curves do both.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    /// Overshoots slightly before settling, like a snapping UI element.
+    BackOut,
+    /// Bounces against the end value a few times.
+    BounceOut,
+    Smoothstep,
+}
+
+impl Easing {
+    /// Applies the curve to `t`, which is clamped to `[0, 1]` first.
+    pub fn apply(&self, t: Scalar) -> Scalar {
+        const HALF_PI: Scalar = core::f64::consts::FRAC_PI_2 as _;
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t) * (1.0 - t) * (1.0 - t),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 4.0 * (1.0 - t) * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Easing::SineIn => 1.0 - float::cos(t * HALF_PI),
+            Easing::SineOut => float::sin(t * HALF_PI),
+            Easing::SineInOut => 0.5 - 0.5 * float::cos(t * 2.0 * HALF_PI),
+            Easing::ExpoIn => {
+                if t == 0.0 {
+                    0.0
+                } else {
+                    float::powf(2.0, 10.0 * t - 10.0)
+                }
+            }
+            Easing::ExpoOut => {
+                if t == 1.0 {
+                    1.0
+                } else {
+                    1.0 - float::powf(2.0, -10.0 * t)
+                }
+            }
+            Easing::BackOut => {
+                const C1: Scalar = 1.70158;
+                let u = t - 1.0;
+                1.0 + (C1 + 1.0) * u * u * u + C1 * u * u
+            }
+            Easing::BounceOut => {
+                const N: Scalar = 7.5625;
+                const D: Scalar = 2.75;
+                if t < 1.0 / D {
+                    N * t * t
+                } else if t < 2.0 / D {
+                    let u = t - 1.5 / D;
+                    N * u * u + 0.75
+                } else if t < 2.5 / D {
+                    let u = t - 2.25 / D;
+                    N * u * u + 0.9375
+                } else {
+                    let u = t - 2.625 / D;
+                    N * u * u + 0.984375
+                }
+            }
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// An in-flight animation from `start` to `end` over `duration` seconds.
+///
+/// The tween is plain data, so it can be stored in a table row and advanced by a scheduled
+/// reducer each tick.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Easing, Tween, Vec3};
+///
+/// let mut door = Tween::new(Vec3::ZERO, Vec3::new(0.0, 3.0, 0.0), 2.0, Easing::Linear);
+/// door.advance(0.5);
+/// assert_eq!(door.sample(), Vec3::new(0.0, 0.75, 0.0));
+/// assert!(door.advance(10.0));
+/// assert_eq!(door.sample(), Vec3::new(0.0, 3.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tween<T> {
+    pub start: T,
+    pub end: T,
+    /// Total length in seconds.
+    pub duration: Scalar,
+    pub easing: Easing,
+    /// Seconds advanced so far, never more than `duration`.
+    pub elapsed: Scalar,
+}
+
+impl<T: Interpolate> Tween<T> {
+    /// Creates a tween that hasn't started yet.
+    #[inline]
+    pub fn new(start: T, end: T, duration: Scalar, easing: Easing) -> Self {
+        Tween {
+            start,
+            end,
+            duration,
+            easing,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advances the tween by `dt` seconds and returns `true` once it has finished.
+    #[inline]
+    pub fn advance(&mut self, dt: Scalar) -> bool {
+        self.elapsed = (self.elapsed + dt).clamp(0.0, self.duration.max(0.0));
+        self.is_finished()
+    }
+
+    /// Returns linear progress in `[0, 1]`, before easing.
+    #[inline]
+    pub fn progress(&self) -> Scalar {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Returns `true` once the full duration has elapsed.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// Returns the seconds left until the tween finishes.
+    #[inline]
+    pub fn remaining(&self) -> Scalar {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    /// Returns the current value.
+    #[inline]
+    pub fn sample(&self) -> T {
+        self.start
+            .interpolate(&self.end, self.easing.apply(self.progress()))
+    }
+
+    /// Rewinds the tween to its start.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    const ALL: [Easing; 15] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::BackOut,
+        Easing::BounceOut,
+        Easing::Smoothstep,
+    ];
+
+    #[test]
+    fn easings_hit_both_ends() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() <= EPS, "{easing:?}");
+            assert!((easing.apply(1.0) - 1.0).abs() <= EPS, "{easing:?}");
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0));
+        }
+        assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+        assert!(Easing::BackOut.apply(0.8) > 1.0);
+    }
+
+    #[test]
+    fn tween_advances_and_clamps() {
+        let mut tween = Tween::new(10.0 as Scalar, 20.0, 4.0, Easing::QuadIn);
+        assert_eq!(tween.sample(), 10.0);
+        assert!(!tween.advance(2.0));
+        assert_eq!(tween.sample(), 12.5);
+        assert_eq!(tween.remaining(), 2.0);
+        assert!(tween.advance(3.0));
+        assert_eq!(tween.elapsed, 4.0);
+        assert_eq!(tween.sample(), 20.0);
+        tween.reset();
+        assert_eq!(tween.progress(), 0.0);
+
+        let instant = Tween::new(Vec2::ZERO, Vec2::ONE, 0.0, Easing::Linear);
+        assert!(instant.is_finished());
+        assert_eq!(instant.sample(), Vec2::ONE);
+    }
+
+    #[test]
+    fn tween_rotations_and_transforms() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let mut spin = Tween::new(
+            Quat::IDENTITY,
+            Quat::from_axis_angle(axis, 2.0),
+            1.0,
+            Easing::Linear,
+        );
+        spin.advance(0.25);
+        assert!(spin.sample().dot(Quat::from_axis_angle(axis, 0.5)) >= 1.0 - EPS);
+
+        let mut lift = Tween::new(
+            Transform::IDENTITY,
+            Transform::from_translation(Vec3::new(0.0, 4.0, 0.0)),
+            2.0,
+            Easing::Smoothstep,
+        );
+        lift.advance(1.0);
+        assert_eq!(lift.sample().translation, Vec3::new(0.0, 2.0, 0.0));
+    }
+}
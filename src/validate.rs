@@ -0,0 +1,247 @@
+//! Server-side validation of client-reported movement.
+//!
+//! Each check returns the measured amount next to the allowed one instead of a bare `bool`,
+//! so a reducer can log by how much a move was over, tolerate small overshoots, or clamp the
+//! move to the limit. Non-finite input never passes.
+
+use crate::{Quat, Scalar, Vec3, float};
+
+/// Result of a distance-based movement check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveCheck {
+    /// Distance actually moved.
+    pub distance: Scalar,
+    /// Largest distance that passes.
+    pub allowed: Scalar,
+}
+
+impl MoveCheck {
+    #[inline(always)]
+    pub const fn new(distance: Scalar, allowed: Scalar) -> Self {
+        MoveCheck { distance, allowed }
+    }
+
+    /// Returns `true` if the move is within the limit.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.distance <= self.allowed
+    }
+
+    /// Returns how far past the limit the move went, `0.0` if it passed, or infinity for
+    /// non-finite input.
+    #[inline]
+    pub fn exceeded_by(&self) -> Scalar {
+        exceeded_by(self.distance, self.allowed)
+    }
+
+    /// Returns `next`, pulled back along the move from `prev` to the allowed distance if it
+    /// went too far. Non-finite moves stay at `prev`.
+    pub fn clamp(&self, prev: Vec3, next: Vec3) -> Vec3 {
+        if self.passed() {
+            next
+        } else if self.distance.is_finite() && self.allowed > 0.0 {
+            prev + (next - prev) * (self.allowed / self.distance)
+        } else {
+            prev
+        }
+    }
+}
+
+/// Result of a rotation rate check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurnCheck {
+    /// Angle actually turned, in radians in `[0, π]`.
+    pub angle: Scalar,
+    /// Largest angle that passes, in radians.
+    pub allowed: Scalar,
+}
+
+impl TurnCheck {
+    #[inline(always)]
+    pub const fn new(angle: Scalar, allowed: Scalar) -> Self {
+        TurnCheck { angle, allowed }
+    }
+
+    /// Returns `true` if the turn is within the limit.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.angle <= self.allowed
+    }
+
+    /// Returns how many radians past the limit the turn went, `0.0` if it passed, or infinity
+    /// for non-finite input.
+    #[inline]
+    pub fn exceeded_by(&self) -> Scalar {
+        exceeded_by(self.angle, self.allowed)
+    }
+
+    /// Returns `next`, turned back towards `prev` to the allowed angle if it turned too far.
+    /// Non-finite turns stay at `prev`.
+    pub fn clamp(&self, prev: Quat, next: Quat) -> Quat {
+        if self.passed() {
+            next
+        } else if self.angle.is_finite() && self.allowed > 0.0 {
+            prev.slerp(next, self.allowed / self.angle)
+        } else {
+            prev
+        }
+    }
+}
+
+#[inline]
+fn exceeded_by(value: Scalar, allowed: Scalar) -> Scalar {
+    if value <= allowed {
+        0.0
+    } else if (value - allowed).is_nan() {
+        Scalar::INFINITY
+    } else {
+        value - allowed
+    }
+}
+
+/// Checks a move from `prev` to `next` over `dt` seconds against `max_speed` units per second.
+///
+/// A zero or negative `dt` allows no movement at all.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{validate, Vec3};
+///
+/// let check = validate::max_speed_check(Vec3::ZERO, Vec3::new(0.0, 0.0, 3.0), 0.5, 4.0);
+/// assert!(!check.passed());
+/// assert_eq!(check.exceeded_by(), 1.0);
+/// assert_eq!(check.clamp(Vec3::ZERO, Vec3::new(0.0, 0.0, 3.0)), Vec3::new(0.0, 0.0, 2.0));
+/// ```
+#[inline]
+pub fn max_speed_check(prev: Vec3, next: Vec3, dt: Scalar, max_speed: Scalar) -> MoveCheck {
+    MoveCheck::new(move_distance(prev, next), max_speed * dt.max(0.0))
+}
+
+/// Checks a single move from `prev` to `next` against `max_distance`, regardless of elapsed
+/// time, to catch teleports that a speed check over a long `dt` would let through.
+#[inline]
+pub fn teleport_check(prev: Vec3, next: Vec3, max_distance: Scalar) -> MoveCheck {
+    MoveCheck::new(move_distance(prev, next), max_distance)
+}
+
+/// Checks a turn from unit quaternion `prev_rot` to `next_rot` over `dt` seconds against
+/// `max_rate` radians per second, measuring the shortest way round.
+///
+/// A zero or negative `dt` allows no turning at all.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{validate, Quat, Vec3};
+///
+/// let up = Vec3::new(0.0, 1.0, 0.0);
+/// let next = Quat::from_axis_angle(up, 1.0);
+/// let check = validate::max_turn_rate_check(Quat::IDENTITY, next, 0.25, 2.0);
+/// assert!(!check.passed());
+/// assert!((check.exceeded_by() - 0.5).abs() < 1.0e-5);
+/// ```
+pub fn max_turn_rate_check(
+    prev_rot: Quat,
+    next_rot: Quat,
+    dt: Scalar,
+    max_rate: Scalar,
+) -> TurnCheck {
+    TurnCheck::new(angle_between(prev_rot, next_rot), max_rate * dt.max(0.0))
+}
+
+/// Returns the distance moved, or NaN for non-finite positions without doing arithmetic on
+/// them, so untrusted input can't trip the `validate` feature's assertions.
+fn move_distance(prev: Vec3, next: Vec3) -> Scalar {
+    if prev.is_finite() && next.is_finite() {
+        prev.distance(next)
+    } else {
+        Scalar::NAN
+    }
+}
+
+/// Returns the shortest angle in radians rotating `from` onto `to`, or NaN if either is
+/// non-finite.
+///
+/// Uses `atan2` of the relative rotation's parts rather than `acos` of the dot product, which
+/// loses most of its precision for the small angles a single tick produces.
+fn angle_between(from: Quat, to: Quat) -> Scalar {
+    if !(from.is_finite() && to.is_finite()) {
+        return Scalar::NAN;
+    }
+    let d = to * from.conjugate();
+    let sin_half = float::sqrt(d.x * d.x + d.y * d.y + d.z * d.z);
+    2.0 * float::atan2(sin_half, d.w.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn speed_checks_scale_with_time() {
+        let prev = Vec3::new(1.0, 0.0, 1.0);
+        let next = Vec3::new(4.0, 0.0, 5.0);
+        let ok = max_speed_check(prev, next, 1.0, 5.0);
+        assert!(ok.passed());
+        assert_eq!(ok.exceeded_by(), 0.0);
+        assert_eq!(ok.clamp(prev, next), next);
+
+        let fast = max_speed_check(prev, next, 0.5, 5.0);
+        assert_eq!(fast, MoveCheck::new(5.0, 2.5));
+        assert_eq!(fast.exceeded_by(), 2.5);
+        assert!((fast.clamp(prev, next).distance(prev) - 2.5).abs() <= EPS);
+
+        let frozen = max_speed_check(prev, next, -1.0, 5.0);
+        assert_eq!(frozen.allowed, 0.0);
+        assert_eq!(frozen.clamp(prev, next), prev);
+        assert!(max_speed_check(prev, prev, 0.0, 5.0).passed());
+    }
+
+    #[test]
+    fn non_finite_moves_never_pass() {
+        // Literals, since `Vec3::new` rejects these under the `validate` feature.
+        let prev = Vec3::ZERO;
+        let next = Vec3 {
+            x: Scalar::NAN,
+            y: 0.0,
+            z: 0.0,
+        };
+        let check = max_speed_check(prev, next, 1.0, 10.0);
+        assert!(!check.passed());
+        assert_eq!(check.exceeded_by(), Scalar::INFINITY);
+        assert_eq!(check.clamp(prev, next), prev);
+        const INFINITE: Vec3 = Vec3 {
+            x: Scalar::INFINITY,
+            y: 0.0,
+            z: 0.0,
+        };
+        let far = teleport_check(prev, INFINITE, 10.0);
+        assert!(!far.passed());
+        assert_eq!(far.clamp(prev, INFINITE), prev);
+    }
+
+    #[test]
+    fn teleport_check_ignores_time() {
+        let check = teleport_check(Vec3::ZERO, Vec3::new(0.0, 30.0, 0.0), 20.0);
+        assert_eq!(check.exceeded_by(), 10.0);
+        assert!(teleport_check(Vec3::ZERO, Vec3::new(0.0, 20.0, 0.0), 20.0).passed());
+    }
+
+    #[test]
+    fn turn_checks_measure_the_short_way_round() {
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let prev = Quat::from_axis_angle(up, 3.0);
+        // 0.4 radians across the ±π seam, where the quaternion flips sign.
+        let next = Quat::from_axis_angle(up, -2.883_185_3);
+        let check = max_turn_rate_check(prev, next, 0.06, 5.0);
+        assert!((check.angle - 0.4).abs() <= 1.0e-4 as Scalar);
+        assert!((check.exceeded_by() - 0.1).abs() <= 1.0e-4 as Scalar);
+        let clamped = check.clamp(prev, next);
+        assert!((angle_between(prev, clamped) - 0.3).abs() <= 1.0e-3 as Scalar);
+
+        let tiny = Quat::from_axis_angle(up, 3.0 + 1.0e-3);
+        assert!((angle_between(prev, tiny) - 1.0e-3).abs() <= 1.0e-5 as Scalar);
+        assert!(max_turn_rate_check(prev, prev, 0.016, 1.0).passed());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{Scalar, Vec3};
+use crate::{MathError, Scalar, Vec3, float};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
 use spacetimedb::SpacetimeType;
 
 /// A 2D vector using `x/y`.
@@ -10,8 +12,10 @@ use spacetimedb::SpacetimeType;
 /// assert_eq!(v.x, 1.0);
 /// assert_eq!(v.y, 2.0);
 /// ```
-#[derive(SpacetimeType, Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Vec2 {
     pub x: Scalar,
     pub y: Scalar,
@@ -22,8 +26,22 @@ impl Vec2 {
     pub const ONE: Vec2 = Vec2::new(1.0, 1.0);
 
     #[inline(always)]
+    #[track_caller]
     pub const fn new(x: Scalar, y: Scalar) -> Self {
-        Vec2 { x, y }
+        check_finite!("Vec2::new", Vec2 { x, y })
+    }
+
+    /// Creates a vector with all components set to `v`.
+    #[inline(always)]
+    #[track_caller]
+    pub const fn splat(v: Scalar) -> Self {
+        check_finite!("Vec2::splat", Vec2 { x: v, y: v })
+    }
+
+    /// Returns `true` if no component is NaN or infinite.
+    #[inline]
+    pub const fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
     }
 
     /// Extend this vector into 3D by inserting `y` as the middle component.
@@ -53,6 +71,41 @@ impl Vec2 {
         self.x * other.x + self.y * other.y
     }
 
+    /// Returns the 2D cross product (`self.x * other.y - self.y * other.x`).
+    ///
+    /// Positive when `other` is counter-clockwise from this vector.
+    #[inline]
+    pub fn perp_dot(&self, other: Vec2) -> Scalar {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns this vector rotated 90° counter-clockwise.
+    #[inline]
+    pub const fn perp(&self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// Linearly interpolates between this vector and `other` by `t` (unclamped).
+    #[inline]
+    pub fn lerp(&self, other: Vec2, t: Scalar) -> Vec2 {
+        Vec2::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
+    /// Returns the component-wise minimum of this vector and `other`.
+    #[inline]
+    pub fn min(&self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of this vector and `other`.
+    #[inline]
+    pub fn max(&self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
     /// Returns the squared length (magnitude) of this vector.
     #[inline]
     pub fn length_squared(&self) -> Scalar {
@@ -61,7 +114,7 @@ impl Vec2 {
 
     /// Returns the length (magnitude) of this vector.
     pub fn length(&self) -> Scalar {
-        self.length_squared().sqrt()
+        float::sqrt(self.length_squared())
     }
 
     /// Returns the squared distance between this vector and `other`.
@@ -74,7 +127,7 @@ impl Vec2 {
 
     /// Returns the distance between this vector and `other`.
     pub fn distance(&self, other: Vec2) -> Scalar {
-        self.distance_squared(other).sqrt()
+        float::sqrt(self.distance_squared(other))
     }
 
     /// Returns a normalized vector, or `fallback` if length is below `epsilon`.
@@ -84,7 +137,7 @@ impl Vec2 {
         if len_sq <= epsilon_sq {
             fallback
         } else {
-            let len = len_sq.sqrt();
+            let len = float::sqrt(len_sq);
             Vec2::new(self.x / len, self.y / len)
         }
     }
@@ -94,6 +147,16 @@ impl Vec2 {
         self.normalize_or(epsilon, Vec2::ZERO)
     }
 
+    /// Normalizes this vector, or reports why it has no direction: [`MathError::NotFinite`]
+    /// for NaN or infinite components and [`MathError::ZeroLength`] for a length at or below
+    /// `epsilon`.
+    pub fn checked_normalize(&self, epsilon: Scalar) -> Result<Vec2, MathError> {
+        if !self.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        self.try_normalize(epsilon).ok_or(MathError::ZeroLength)
+    }
+
     /// Attempts to normalize this vector, returning `None` if length is below `epsilon`.
     pub fn try_normalize(&self, epsilon: Scalar) -> Option<Vec2> {
         let len_sq = self.length_squared();
@@ -101,12 +164,136 @@ impl Vec2 {
         if len_sq <= epsilon_sq {
             None
         } else {
-            let len = len_sq.sqrt();
+            let len = float::sqrt(len_sq);
             Some(Vec2::new(self.x / len, self.y / len))
         }
     }
 }
 
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    #[inline]
+    #[track_caller]
+    fn add(self, rhs: Vec2) -> Vec2 {
+        check_finite!(
+            "Vec2 + Vec2",
+            Vec2 {
+                x: self.x + rhs.x,
+                y: self.y + rhs.y
+            }
+        )
+    }
+}
+
+impl AddAssign for Vec2 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    #[inline]
+    #[track_caller]
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        check_finite!(
+            "Vec2 - Vec2",
+            Vec2 {
+                x: self.x - rhs.x,
+                y: self.y - rhs.y
+            }
+        )
+    }
+}
+
+impl SubAssign for Vec2 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<Scalar> for Vec2 {
+    type Output = Vec2;
+
+    #[inline]
+    #[track_caller]
+    fn mul(self, rhs: Scalar) -> Vec2 {
+        check_finite!(
+            "Vec2 * Scalar",
+            Vec2 {
+                x: self.x * rhs,
+                y: self.y * rhs
+            }
+        )
+    }
+}
+
+impl Mul<Vec2> for Scalar {
+    type Output = Vec2;
+
+    #[inline]
+    fn mul(self, rhs: Vec2) -> Vec2 {
+        rhs * self
+    }
+}
+
+impl MulAssign<Scalar> for Vec2 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Scalar) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<Scalar> for Vec2 {
+    type Output = Vec2;
+
+    #[inline]
+    #[track_caller]
+    fn div(self, rhs: Scalar) -> Vec2 {
+        check_finite!(
+            "Vec2 / Scalar",
+            Vec2 {
+                x: self.x / rhs,
+                y: self.y / rhs
+            }
+        )
+    }
+}
+
+impl DivAssign<Scalar> for Vec2 {
+    #[inline]
+    fn div_assign(&mut self, rhs: Scalar) {
+        *self = *self / rhs;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    #[inline]
+    #[track_caller]
+    fn neg(self) -> Vec2 {
+        check_finite!(
+            "-Vec2",
+            Vec2 {
+                x: -self.x,
+                y: -self.y
+            }
+        )
+    }
+}
+
+// SAFETY: `repr(C)` and made only of `Scalar`s, so there's no padding and every bit pattern
+// is a valid value.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Vec2 {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Vec2 {}
+
 #[cfg(feature = "nalgebra")]
 mod nalgebra_impls {
     use super::*;
@@ -124,6 +311,20 @@ mod nalgebra_impls {
             nalgebra::Vector2::new(v.x, v.y)
         }
     }
+
+    impl From<nalgebra::Point2<Scalar>> for Vec2 {
+        #[inline(always)]
+        fn from(p: nalgebra::Point2<Scalar>) -> Self {
+            Vec2::new(p.x, p.y)
+        }
+    }
+
+    impl From<Vec2> for nalgebra::Point2<Scalar> {
+        #[inline(always)]
+        fn from(v: Vec2) -> Self {
+            nalgebra::Point2::new(v.x, v.y)
+        }
+    }
 }
 
 #[cfg(feature = "glam")]
@@ -163,6 +364,43 @@ mod glam_impls {
     }
 }
 
+#[cfg(feature = "bevy")]
+mod bevy_impls {
+    use super::*;
+
+    #[cfg(feature = "f32")]
+    impl From<bevy_math::Vec2> for Vec2 {
+        #[inline(always)]
+        fn from(v: bevy_math::Vec2) -> Self {
+            Vec2::new(v.x, v.y)
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    impl From<Vec2> for bevy_math::Vec2 {
+        #[inline(always)]
+        fn from(v: Vec2) -> Self {
+            bevy_math::Vec2::new(v.x, v.y)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<bevy_math::DVec2> for Vec2 {
+        #[inline(always)]
+        fn from(v: bevy_math::DVec2) -> Self {
+            Vec2::new(v.x, v.y)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<Vec2> for bevy_math::DVec2 {
+        #[inline(always)]
+        fn from(v: Vec2) -> Self {
+            bevy_math::DVec2::new(v.x, v.y)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,6 +491,37 @@ mod tests {
         assert!((length - 1.0 as Scalar).abs() <= epsilon);
     }
 
+    #[test]
+    fn vec2_lerp_interpolates_between_endpoints() {
+        let a = Vec2::new(1.0 as Scalar, 2.0 as Scalar);
+        let b = Vec2::new(3.0 as Scalar, 6.0 as Scalar);
+        assert_eq!(a.lerp(b, 0.0 as Scalar), a);
+        assert_eq!(a.lerp(b, 1.0 as Scalar), b);
+        assert_eq!(
+            a.lerp(b, 0.5 as Scalar),
+            Vec2::new(2.0 as Scalar, 4.0 as Scalar)
+        );
+    }
+
+    #[test]
+    fn vec2_arithmetic_operators_are_component_wise() {
+        let a = Vec2::new(1.0 as Scalar, 2.0 as Scalar);
+        let b = Vec2::new(4.0 as Scalar, 6.0 as Scalar);
+        assert_eq!(a + b, Vec2::new(5.0 as Scalar, 8.0 as Scalar));
+        assert_eq!(b - a, Vec2::new(3.0 as Scalar, 4.0 as Scalar));
+        assert_eq!(a * 2.0 as Scalar, Vec2::new(2.0 as Scalar, 4.0 as Scalar));
+        assert_eq!(b / 2.0 as Scalar, Vec2::new(2.0 as Scalar, 3.0 as Scalar));
+        assert_eq!(-a, Vec2::new(-1.0 as Scalar, -2.0 as Scalar));
+    }
+
+    #[test]
+    fn vec2_perp_dot_is_positive_for_counter_clockwise() {
+        let x = Vec2::new(1.0 as Scalar, 0.0 as Scalar);
+        assert_eq!(x.perp(), Vec2::new(0.0 as Scalar, 1.0 as Scalar));
+        assert_eq!(x.perp_dot(x.perp()), 1.0 as Scalar);
+        assert_eq!(x.perp().perp_dot(x), -1.0 as Scalar);
+    }
+
     #[cfg(feature = "nalgebra")]
     #[test]
     fn vec2_nalgebra_round_trip() {
@@ -263,6 +532,10 @@ mod tests {
 
         let back: Vec2 = n.into();
         assert_eq!(back, v);
+
+        let p: nalgebra::Point2<Scalar> = v.into();
+        assert_eq!(p, nalgebra::Point2::new(3.0, 4.0));
+        assert_eq!(Vec2::from(p), v);
     }
 
     #[cfg(all(feature = "glam", feature = "f32"))]
@@ -288,4 +561,24 @@ mod tests {
         let back: Vec2 = g.into();
         assert_eq!(back, v);
     }
+
+    #[test]
+    fn checked_normalize_reports_why() {
+        assert_eq!(
+            Vec2::ZERO.checked_normalize(1.0e-6),
+            Err(MathError::ZeroLength)
+        );
+        assert_eq!(
+            Vec2 {
+                x: Scalar::NAN,
+                y: 1.0
+            }
+            .checked_normalize(1.0e-6),
+            Err(MathError::NotFinite)
+        );
+        assert_eq!(
+            Vec2::new(0.0, -2.0).checked_normalize(1.0e-6),
+            Ok(Vec2::new(0.0, -1.0))
+        );
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{Scalar, Vec2};
+use crate::conventions::Axes;
+use crate::{MathError, Scalar, Vec2, float};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
 use spacetimedb::SpacetimeType;
 
 /// A 3-dimensional vector with `x`, `y`, and `z` components.
@@ -12,8 +15,10 @@ use spacetimedb::SpacetimeType;
 /// assert_eq!(v.y, 2.0);
 /// assert_eq!(v.z, 3.0);
 /// ```
-#[derive(SpacetimeType, Debug, Default, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Vec3 {
     /// X component.
     pub x: Scalar,
@@ -29,8 +34,34 @@ impl Vec3 {
     pub const ONE: Vec3 = Vec3::new(1.0, 1.0, 1.0);
 
     #[inline(always)]
+    #[track_caller]
     pub const fn new(x: Scalar, y: Scalar, z: Scalar) -> Self {
-        Vec3 { x, y, z }
+        check_finite!("Vec3::new", Vec3 { x, y, z })
+    }
+
+    /// Creates a vector with all components set to `v`.
+    #[inline(always)]
+    #[track_caller]
+    pub const fn splat(v: Scalar) -> Self {
+        check_finite!("Vec3::splat", Vec3 { x: v, y: v, z: v })
+    }
+
+    /// Returns `true` if no component is NaN or infinite.
+    #[inline]
+    pub const fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Creates a vector from an `[x, y, z]` array.
+    #[inline]
+    pub const fn from_array(a: [Scalar; 3]) -> Self {
+        Vec3::new(a[0], a[1], a[2])
+    }
+
+    /// Returns the components as an `[x, y, z]` array.
+    #[inline]
+    pub const fn to_array(&self) -> [Scalar; 3] {
+        [self.x, self.y, self.z]
     }
 
     /// Returns the XY components of this vector.
@@ -61,6 +92,36 @@ impl Vec3 {
         )
     }
 
+    /// Linearly interpolates between this vector and `other` by `t` (unclamped).
+    #[inline]
+    pub fn lerp(&self, other: Vec3, t: Scalar) -> Vec3 {
+        Vec3::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+            self.z + (other.z - self.z) * t,
+        )
+    }
+
+    /// Returns the component-wise minimum of this vector and `other`.
+    #[inline]
+    pub fn min(&self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Returns the component-wise maximum of this vector and `other`.
+    #[inline]
+    pub fn max(&self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
     /// Returns the squared length (magnitude) of this vector.
     #[inline]
     pub fn length_squared(&self) -> Scalar {
@@ -69,7 +130,7 @@ impl Vec3 {
 
     /// Returns the length (magnitude) of this vector.
     pub fn length(&self) -> Scalar {
-        self.length_squared().sqrt()
+        float::sqrt(self.length_squared())
     }
 
     /// Returns the squared distance between this vector and `other`.
@@ -83,7 +144,7 @@ impl Vec3 {
 
     /// Returns the distance between this vector and `other`.
     pub fn distance(&self, other: Vec3) -> Scalar {
-        self.distance_squared(other).sqrt()
+        float::sqrt(self.distance_squared(other))
     }
 
     /// Returns a normalized vector, or `fallback` if length is below `epsilon`.
@@ -93,7 +154,7 @@ impl Vec3 {
         if len_sq <= epsilon_sq {
             fallback
         } else {
-            let len = len_sq.sqrt();
+            let len = float::sqrt(len_sq);
             Vec3::new(self.x / len, self.y / len, self.z / len)
         }
     }
@@ -103,6 +164,16 @@ impl Vec3 {
         self.normalize_or(epsilon, Vec3::ZERO)
     }
 
+    /// Normalizes this vector, or reports why it has no direction: [`MathError::NotFinite`]
+    /// for NaN or infinite components and [`MathError::ZeroLength`] for a length at or below
+    /// `epsilon`.
+    pub fn checked_normalize(&self, epsilon: Scalar) -> Result<Vec3, MathError> {
+        if !self.is_finite() {
+            return Err(MathError::NotFinite);
+        }
+        self.try_normalize(epsilon).ok_or(MathError::ZeroLength)
+    }
+
     /// Attempts to normalize this vector, returning `None` if length is below `epsilon`.
     pub fn try_normalize(&self, epsilon: Scalar) -> Option<Vec3> {
         let len_sq = self.length_squared();
@@ -110,12 +181,160 @@ impl Vec3 {
         if len_sq <= epsilon_sq {
             None
         } else {
-            let len = len_sq.sqrt();
+            let len = float::sqrt(len_sq);
             Some(Vec3::new(self.x / len, self.y / len, self.z / len))
         }
     }
+
+    /// Returns the forward direction of the `axes` convention, for code that shouldn't
+    /// hardcode which axis points ahead.
+    #[inline]
+    pub const fn forward_in(axes: &Axes) -> Vec3 {
+        axes.forward
+    }
+
+    /// Returns the up direction of the `axes` convention.
+    #[inline]
+    pub const fn up_in(axes: &Axes) -> Vec3 {
+        axes.up
+    }
+
+    /// Returns the right direction of the `axes` convention.
+    #[inline]
+    pub const fn right_in(axes: &Axes) -> Vec3 {
+        axes.right
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    #[inline]
+    #[track_caller]
+    fn add(self, rhs: Vec3) -> Vec3 {
+        check_finite!(
+            "Vec3 + Vec3",
+            Vec3 {
+                x: self.x + rhs.x,
+                y: self.y + rhs.y,
+                z: self.z + rhs.z
+            }
+        )
+    }
+}
+
+impl AddAssign for Vec3 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Vec3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    #[inline]
+    #[track_caller]
+    fn sub(self, rhs: Vec3) -> Vec3 {
+        check_finite!(
+            "Vec3 - Vec3",
+            Vec3 {
+                x: self.x - rhs.x,
+                y: self.y - rhs.y,
+                z: self.z - rhs.z
+            }
+        )
+    }
+}
+
+impl SubAssign for Vec3 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Vec3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<Scalar> for Vec3 {
+    type Output = Vec3;
+
+    #[inline]
+    #[track_caller]
+    fn mul(self, rhs: Scalar) -> Vec3 {
+        check_finite!(
+            "Vec3 * Scalar",
+            Vec3 {
+                x: self.x * rhs,
+                y: self.y * rhs,
+                z: self.z * rhs
+            }
+        )
+    }
+}
+
+impl Mul<Vec3> for Scalar {
+    type Output = Vec3;
+
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        rhs * self
+    }
+}
+
+impl MulAssign<Scalar> for Vec3 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Scalar) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<Scalar> for Vec3 {
+    type Output = Vec3;
+
+    #[inline]
+    #[track_caller]
+    fn div(self, rhs: Scalar) -> Vec3 {
+        check_finite!(
+            "Vec3 / Scalar",
+            Vec3 {
+                x: self.x / rhs,
+                y: self.y / rhs,
+                z: self.z / rhs
+            }
+        )
+    }
+}
+
+impl DivAssign<Scalar> for Vec3 {
+    #[inline]
+    fn div_assign(&mut self, rhs: Scalar) {
+        *self = *self / rhs;
+    }
 }
 
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    #[inline]
+    #[track_caller]
+    fn neg(self) -> Vec3 {
+        check_finite!(
+            "-Vec3",
+            Vec3 {
+                x: -self.x,
+                y: -self.y,
+                z: -self.z
+            }
+        )
+    }
+}
+
+// SAFETY: `repr(C)` and made only of `Scalar`s, so there's no padding and every bit pattern
+// is a valid value.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Vec3 {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Vec3 {}
+
 #[cfg(feature = "nalgebra")]
 mod nalgebra_impls {
     use super::*;
@@ -133,16 +352,30 @@ mod nalgebra_impls {
         }
     }
 
-    impl From<Vec3> for nalgebra::Translation3<f32> {
+    impl From<Vec3> for nalgebra::Translation3<Scalar> {
         #[inline(always)]
         fn from(v: Vec3) -> Self {
             Self::new(v.x, v.y, v.z)
         }
     }
 
-    impl From<nalgebra::Translation3<f32>> for Vec3 {
+    impl From<nalgebra::Translation3<Scalar>> for Vec3 {
         #[inline(always)]
-        fn from(v: nalgebra::Translation3<f32>) -> Self {
+        fn from(v: nalgebra::Translation3<Scalar>) -> Self {
+            Self::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<nalgebra::Point3<Scalar>> for Vec3 {
+        #[inline(always)]
+        fn from(p: nalgebra::Point3<Scalar>) -> Self {
+            Self::new(p.x, p.y, p.z)
+        }
+    }
+
+    impl From<Vec3> for nalgebra::Point3<Scalar> {
+        #[inline(always)]
+        fn from(v: Vec3) -> Self {
             Self::new(v.x, v.y, v.z)
         }
     }
@@ -189,10 +422,54 @@ mod glam_impls {
     }
 }
 
+#[cfg(feature = "bevy")]
+mod bevy_impls {
+    use super::*;
+
+    #[cfg(feature = "f32")]
+    impl From<bevy_math::Vec3> for Vec3 {
+        #[inline(always)]
+        fn from(v: bevy_math::Vec3) -> Self {
+            Vec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    impl From<Vec3> for bevy_math::Vec3 {
+        #[inline(always)]
+        fn from(v: Vec3) -> Self {
+            bevy_math::Vec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<bevy_math::DVec3> for Vec3 {
+        #[inline(always)]
+        fn from(v: bevy_math::DVec3) -> Self {
+            Vec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<Vec3> for bevy_math::DVec3 {
+        #[inline(always)]
+        fn from(v: Vec3) -> Self {
+            bevy_math::DVec3::new(v.x, v.y, v.z)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "validate")]
+    #[should_panic(expected = "Vec3 / Scalar produced a non-finite value")]
+    fn validate_catches_division_by_zero() {
+        let _ = Vec3::ONE / 0.0;
+    }
+
     #[test]
     fn constants_match_constructor() {
         assert_eq!(
@@ -279,6 +556,55 @@ mod tests {
         assert!((length - 1.0 as Scalar).abs() <= epsilon);
     }
 
+    #[test]
+    fn vec3_lerp_interpolates_between_endpoints() {
+        let a = Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar);
+        let b = Vec3::new(3.0 as Scalar, 6.0 as Scalar, 5.0 as Scalar);
+        assert_eq!(a.lerp(b, 0.0 as Scalar), a);
+        assert_eq!(a.lerp(b, 1.0 as Scalar), b);
+        assert_eq!(
+            a.lerp(b, 0.5 as Scalar),
+            Vec3::new(2.0 as Scalar, 4.0 as Scalar, 4.0 as Scalar)
+        );
+    }
+
+    #[test]
+    fn vec3_arithmetic_operators_are_component_wise() {
+        let a = Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar);
+        let b = Vec3::new(4.0 as Scalar, 5.0 as Scalar, 6.0 as Scalar);
+        assert_eq!(
+            a + b,
+            Vec3::new(5.0 as Scalar, 7.0 as Scalar, 9.0 as Scalar)
+        );
+        assert_eq!(b - a, Vec3::splat(3.0 as Scalar));
+        assert_eq!(
+            a * 2.0 as Scalar,
+            Vec3::new(2.0 as Scalar, 4.0 as Scalar, 6.0 as Scalar)
+        );
+        assert_eq!(
+            b / 2.0 as Scalar,
+            Vec3::new(2.0 as Scalar, 2.5 as Scalar, 3.0 as Scalar)
+        );
+        assert_eq!(
+            -a,
+            Vec3::new(-1.0 as Scalar, -2.0 as Scalar, -3.0 as Scalar)
+        );
+    }
+
+    #[test]
+    fn vec3_min_max_are_component_wise() {
+        let a = Vec3::new(1.0 as Scalar, 5.0 as Scalar, 3.0 as Scalar);
+        let b = Vec3::new(4.0 as Scalar, 2.0 as Scalar, 6.0 as Scalar);
+        assert_eq!(
+            a.min(b),
+            Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar)
+        );
+        assert_eq!(
+            a.max(b),
+            Vec3::new(4.0 as Scalar, 5.0 as Scalar, 6.0 as Scalar)
+        );
+    }
+
     #[cfg(feature = "nalgebra")]
     #[test]
     fn nalgebra_roundtrip() {
@@ -286,6 +612,12 @@ mod tests {
         let v: Vec3 = n.into();
         let back: nalgebra::Vector3<Scalar> = v.into();
         assert_eq!(back, nalgebra::Vector3::new(v.x, v.y, v.z));
+        let p: nalgebra::Point3<Scalar> = v.into();
+        assert_eq!(p, nalgebra::Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vec3::from(p), v);
+        let t: nalgebra::Translation3<Scalar> = v.into();
+        assert_eq!(t.transform_point(&p), nalgebra::Point3::new(2.0, 4.0, 6.0));
+        assert_eq!(Vec3::from(t), v);
     }
 
     #[cfg(all(feature = "glam", feature = "f32"))]
@@ -305,4 +637,25 @@ mod tests {
         let back: glam::DVec3 = v.into();
         assert_eq!(back, g);
     }
+
+    #[test]
+    fn checked_normalize_reports_why() {
+        assert_eq!(
+            Vec3::ZERO.checked_normalize(1.0e-6),
+            Err(MathError::ZeroLength)
+        );
+        assert_eq!(
+            Vec3 {
+                x: Scalar::INFINITY,
+                y: 0.0,
+                z: 0.0
+            }
+            .checked_normalize(1.0e-6),
+            Err(MathError::NotFinite)
+        );
+        assert_eq!(
+            Vec3::new(0.0, 3.0, 0.0).checked_normalize(1.0e-6),
+            Ok(Vec3::new(0.0, 1.0, 0.0))
+        );
+    }
 }
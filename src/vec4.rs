@@ -0,0 +1,166 @@
+use crate::{Scalar, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A 4-dimensional vector with `x`, `y`, `z`, and `w` components.
+///
+/// Mostly used as a homogeneous coordinate and as the column type of [`Mat4`](crate::Mat4).
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Vec3, Vec4};
+///
+/// let v = Vec3::new(1.0, 2.0, 3.0).extend(1.0);
+/// assert_eq!(v, Vec4::new(1.0, 2.0, 3.0, 1.0));
+/// assert_eq!(v.truncate(), Vec3::new(1.0, 2.0, 3.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Vec4 {
+    /// X component.
+    pub x: Scalar,
+    /// Y component.
+    pub y: Scalar,
+    /// Z component.
+    pub z: Scalar,
+    /// W component.
+    pub w: Scalar,
+}
+
+impl Vec4 {
+    pub const ZERO: Vec4 = Vec4::new(0.0, 0.0, 0.0, 0.0);
+    pub const ONE: Vec4 = Vec4::new(1.0, 1.0, 1.0, 1.0);
+
+    #[inline(always)]
+    #[track_caller]
+    pub const fn new(x: Scalar, y: Scalar, z: Scalar, w: Scalar) -> Self {
+        check_finite!("Vec4::new", Vec4 { x, y, z, w })
+    }
+
+    /// Returns `true` if no component is NaN or infinite.
+    #[inline]
+    pub const fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
+    /// Returns the XYZ components of this vector, dropping `w`.
+    #[inline]
+    pub const fn truncate(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the dot product of this vector and `other`.
+    #[inline]
+    pub fn dot(&self, other: Vec4) -> Scalar {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+}
+
+impl Vec3 {
+    /// Extend this vector into 4D by appending `w` as the last component.
+    #[inline]
+    pub const fn extend(&self, w: Scalar) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+}
+
+// SAFETY: `repr(C)` and made only of `Scalar`s, so there's no padding and every bit pattern
+// is a valid value.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Vec4 {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Vec4 {}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+
+    impl From<nalgebra::Vector4<Scalar>> for Vec4 {
+        #[inline(always)]
+        fn from(v: nalgebra::Vector4<Scalar>) -> Self {
+            Self::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    impl From<Vec4> for nalgebra::Vector4<Scalar> {
+        #[inline(always)]
+        fn from(v: Vec4) -> Self {
+            Self::new(v.x, v.y, v.z, v.w)
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    #[cfg(feature = "f32")]
+    impl From<glam::Vec4> for Vec4 {
+        #[inline(always)]
+        fn from(v: glam::Vec4) -> Self {
+            Self::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    #[cfg(feature = "f32")]
+    impl From<Vec4> for glam::Vec4 {
+        #[inline(always)]
+        fn from(v: Vec4) -> Self {
+            Self::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<glam::DVec4> for Vec4 {
+        #[inline(always)]
+        fn from(v: glam::DVec4) -> Self {
+            Self::new(v.x, v.y, v.z, v.w)
+        }
+    }
+
+    #[cfg(feature = "f64")]
+    impl From<Vec4> for glam::DVec4 {
+        #[inline(always)]
+        fn from(v: Vec4) -> Self {
+            Self::new(v.x, v.y, v.z, v.w)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec4_dot_is_sum_of_component_products() {
+        let a = Vec4::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar, 4.0 as Scalar);
+        let b = Vec4::new(5.0 as Scalar, 6.0 as Scalar, 7.0 as Scalar, 8.0 as Scalar);
+        assert_eq!(a.dot(b), 70.0 as Scalar);
+    }
+
+    #[test]
+    fn vec3_extend_then_truncate_round_trips() {
+        let v = Vec3::new(1.0 as Scalar, 2.0 as Scalar, 3.0 as Scalar);
+        assert_eq!(v.extend(4.0 as Scalar).truncate(), v);
+    }
+
+    #[cfg(all(feature = "glam", feature = "f32"))]
+    #[test]
+    fn glam_roundtrip_f32() {
+        let g = glam::Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let v: Vec4 = g.into();
+        let back: glam::Vec4 = v.into();
+        assert_eq!(back, g);
+    }
+
+    #[cfg(all(feature = "glam", feature = "f64"))]
+    #[test]
+    fn glam_roundtrip_f64() {
+        let g = glam::DVec4::new(1.0, 2.0, 3.0, 4.0);
+        let v: Vec4 = g.into();
+        let back: glam::DVec4 = v.into();
+        assert_eq!(back, g);
+    }
+}
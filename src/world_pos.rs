@@ -0,0 +1,193 @@
+use crate::{ChunkCoords, IVec3, Scalar, Vec3};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// Edge length in world units of the sectors a [`WorldPos`] is split into.
+///
+/// A power of two, so sector origins are exact in any float type.
+pub const WORLD_SECTOR_SIZE: Scalar = 1024.0;
+
+/// A position in a very large world: an integer sector plus an offset inside it.
+///
+/// A plain [`Vec3`] of `f32`s resolves only about 8 mm at 100 km from the origin. Splitting
+/// off the integer sector keeps the offset small, so every position has the same precision
+/// out to the `i32` sector limits (about two billion km), and the difference between two
+/// nearby positions is exact no matter where they are.
+///
+/// In canonical form each `local` component lies in `[0, WORLD_SECTOR_SIZE)`. Arithmetic
+/// returns canonical positions; [`WorldPos::normalize`] canonicalizes one built by hand.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{IVec3, Scalar, Vec3, WorldPos};
+///
+/// // Two ships near each other, millions of km from the origin.
+/// let far = IVec3::new(2_000_000_000, 0, -2_000_000_000);
+/// let a = WorldPos::new(far, Vec3::new(1023.5, 0.0, 0.25));
+/// let b = a + Vec3::new(1.0, 0.0, -0.5);
+/// assert_eq!(b.sector, IVec3::new(2_000_000_001, 0, -2_000_000_001));
+/// assert_eq!(b - a, Vec3::new(1.0, 0.0, -0.5));
+/// assert_eq!(a.distance(b), Scalar::sqrt(1.25));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorldPos {
+    /// The sector, in units of [`WORLD_SECTOR_SIZE`].
+    pub sector: IVec3,
+    /// The offset from the sector's minimum corner.
+    pub local: Vec3,
+}
+
+impl WorldPos {
+    pub const ZERO: WorldPos = WorldPos::new(IVec3::ZERO, Vec3::ZERO);
+
+    /// Creates a position from its parts, without normalizing.
+    #[inline(always)]
+    pub const fn new(sector: IVec3, local: Vec3) -> Self {
+        WorldPos { sector, local }
+    }
+
+    /// Splits an absolute position into sector and offset.
+    #[inline]
+    pub fn from_vec3(v: Vec3) -> Self {
+        WorldPos::new(IVec3::ZERO, v).normalize()
+    }
+
+    /// Returns the absolute position, which loses precision far from the origin.
+    #[inline]
+    pub fn to_vec3(&self) -> Vec3 {
+        self.sector.as_vec3() * WORLD_SECTOR_SIZE + self.local
+    }
+
+    /// Returns the same position with `local` moved into `[0, WORLD_SECTOR_SIZE)`.
+    #[inline]
+    pub fn normalize(&self) -> Self {
+        let (carry, local) = ChunkCoords::new(WORLD_SECTOR_SIZE).to_chunk_local(self.local);
+        WorldPos::new(self.sector + carry, local)
+    }
+
+    /// Returns `true` if the position is in canonical form.
+    #[inline]
+    pub fn is_normalized(&self) -> bool {
+        let range = 0.0..WORLD_SECTOR_SIZE;
+        range.contains(&self.local.x)
+            && range.contains(&self.local.y)
+            && range.contains(&self.local.z)
+    }
+
+    /// Returns the offset from `self` to `other`, exact for nearby positions.
+    #[inline]
+    pub fn offset_to(&self, other: &WorldPos) -> Vec3 {
+        let sectors = Vec3::new(
+            (other.sector.x as i64 - self.sector.x as i64) as Scalar,
+            (other.sector.y as i64 - self.sector.y as i64) as Scalar,
+            (other.sector.z as i64 - self.sector.z as i64) as Scalar,
+        );
+        sectors * WORLD_SECTOR_SIZE + (other.local - self.local)
+    }
+
+    #[inline]
+    pub fn distance_squared(&self, other: WorldPos) -> Scalar {
+        self.offset_to(&other).length_squared()
+    }
+
+    #[inline]
+    pub fn distance(&self, other: WorldPos) -> Scalar {
+        self.offset_to(&other).length()
+    }
+}
+
+impl From<Vec3> for WorldPos {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        WorldPos::from_vec3(v)
+    }
+}
+
+impl Sub for WorldPos {
+    type Output = Vec3;
+
+    #[inline]
+    fn sub(self, rhs: WorldPos) -> Vec3 {
+        rhs.offset_to(&self)
+    }
+}
+
+impl Add<Vec3> for WorldPos {
+    type Output = WorldPos;
+
+    #[inline]
+    fn add(self, rhs: Vec3) -> WorldPos {
+        WorldPos::new(self.sector, self.local + rhs).normalize()
+    }
+}
+
+impl AddAssign<Vec3> for WorldPos {
+    #[inline]
+    fn add_assign(&mut self, rhs: Vec3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec3> for WorldPos {
+    type Output = WorldPos;
+
+    #[inline]
+    fn sub(self, rhs: Vec3) -> WorldPos {
+        WorldPos::new(self.sector, self.local - rhs).normalize()
+    }
+}
+
+impl SubAssign<Vec3> for WorldPos {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Vec3) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_into_canonical_form() {
+        let p = WorldPos::from_vec3(Vec3::new(-1.0, 2048.0, 1500.0));
+        assert_eq!(p.sector, IVec3::new(-1, 2, 1));
+        assert_eq!(p.local, Vec3::new(1023.0, 0.0, 476.0));
+        assert!(p.is_normalized());
+        assert_eq!(p.to_vec3(), Vec3::new(-1.0, 2048.0, 1500.0));
+
+        let raw = WorldPos::new(IVec3::new(5, 0, 0), Vec3::new(-3000.0, 1024.0, 0.0));
+        assert!(!raw.is_normalized());
+        let canonical = raw.normalize();
+        assert_eq!(canonical.sector, IVec3::new(2, 1, 0));
+        assert_eq!(canonical.local, Vec3::new(72.0, 0.0, 0.0));
+        assert_eq!(canonical - raw, Vec3::ZERO);
+    }
+
+    #[test]
+    fn differences_are_exact_far_from_the_origin() {
+        let a = WorldPos::new(
+            IVec3::new(i32::MAX, 7, i32::MIN),
+            Vec3::new(0.125, 3.0, 1000.0),
+        );
+        let mut b = a;
+        b += Vec3::new(-0.25, 2000.0, 48.0);
+        assert_eq!(b.sector, IVec3::new(i32::MAX - 1, 8, i32::MIN + 1));
+        assert_eq!(b - a, Vec3::new(-0.25, 2000.0, 48.0));
+        assert_eq!(a - b, Vec3::new(0.25, -2000.0, -48.0));
+        b -= Vec3::new(-0.25, 2000.0, 48.0);
+        assert_eq!(b, a);
+        assert_eq!(a.distance_squared(a + Vec3::new(3.0, 4.0, 0.0)), 25.0);
+    }
+
+    #[test]
+    fn sector_differences_widen_before_subtracting() {
+        let low = WorldPos::new(IVec3::new(i32::MIN, 0, 0), Vec3::ZERO);
+        let high = WorldPos::new(IVec3::new(i32::MAX, 0, 0), Vec3::ZERO);
+        let span = (high - low).x;
+        assert_eq!(span, (u32::MAX as Scalar) * WORLD_SECTOR_SIZE);
+    }
+}
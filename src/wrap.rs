@@ -0,0 +1,156 @@
+use crate::{Scalar, Vec3, float};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A world that wraps around on X and Z, like the surface of a torus.
+///
+/// Positions live in `[0, size_x)` and `[0, size_z)`; walking off one edge comes back in at the
+/// opposite one. Y never wraps, and an axis with a size of zero or less doesn't wrap either.
+/// Distances and directions take the shortest way, which may cross the seam, so aggro
+/// ranges and interest checks keep working near the edges.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Vec3, WrapWorld};
+///
+/// let world = WrapWorld::new(1000.0, 1000.0);
+/// let a = Vec3::new(995.0, 0.0, 500.0);
+/// let b = Vec3::new(5.0, 0.0, 500.0);
+/// assert_eq!(world.distance(a, b), 10.0);
+/// assert_eq!(world.direction_to(a, b), Vec3::new(1.0, 0.0, 0.0));
+/// assert_eq!(world.wrap_position(Vec3::new(1010.0, 3.0, -20.0)), Vec3::new(10.0, 3.0, 980.0));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WrapWorld {
+    /// Width of the world along X, or zero or less to not wrap on X.
+    pub size_x: Scalar,
+    /// Depth of the world along Z, or zero or less to not wrap on Z.
+    pub size_z: Scalar,
+}
+
+impl WrapWorld {
+    #[inline(always)]
+    pub const fn new(size_x: Scalar, size_z: Scalar) -> Self {
+        WrapWorld { size_x, size_z }
+    }
+
+    /// Returns `position` moved into the world's bounds on the wrapping axes.
+    #[inline]
+    pub fn wrap_position(&self, position: Vec3) -> Vec3 {
+        Vec3::new(
+            wrap_axis(position.x, self.size_x),
+            position.y,
+            wrap_axis(position.z, self.size_z),
+        )
+    }
+
+    /// Returns the shortest offset from `from` to `to`, which may cross the seam.
+    #[inline]
+    pub fn delta(&self, from: Vec3, to: Vec3) -> Vec3 {
+        Vec3::new(
+            delta_axis(to.x - from.x, self.size_x),
+            to.y - from.y,
+            delta_axis(to.z - from.z, self.size_z),
+        )
+    }
+
+    #[inline]
+    pub fn distance_squared(&self, a: Vec3, b: Vec3) -> Scalar {
+        self.delta(a, b).length_squared()
+    }
+
+    /// Returns the shortest distance between `a` and `b`.
+    #[inline]
+    pub fn distance(&self, a: Vec3, b: Vec3) -> Scalar {
+        self.delta(a, b).length()
+    }
+
+    /// Returns the unit direction of the shortest path from `from` to `to`, or zero if they
+    /// coincide.
+    #[inline]
+    pub fn direction_to(&self, from: Vec3, to: Vec3) -> Vec3 {
+        self.delta(from, to).normalize_or_zero(0.0)
+    }
+
+    /// Interpolates from `a` towards `b` along the shortest path, wrapping the result.
+    #[inline]
+    pub fn lerp(&self, a: Vec3, b: Vec3, t: Scalar) -> Vec3 {
+        self.wrap_position(a + self.delta(a, b) * t)
+    }
+}
+
+#[inline]
+fn wrap_axis(v: Scalar, size: Scalar) -> Scalar {
+    if size <= 0.0 || size.is_nan() {
+        return v;
+    }
+    let wrapped = float::rem_euclid(v, size);
+    // A tiny negative input can round up to exactly `size`, which is the same place as zero.
+    if wrapped >= size { 0.0 } else { wrapped }
+}
+
+#[inline]
+fn delta_axis(d: Scalar, size: Scalar) -> Scalar {
+    if size > 0.0 {
+        d - size * float::round(d / size)
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-4 as Scalar;
+
+    #[test]
+    fn positions_wrap_on_x_and_z_only() {
+        let world = WrapWorld::new(100.0, 50.0);
+        let p = world.wrap_position(Vec3::new(-30.0, -500.0, 120.0));
+        assert_eq!(p, Vec3::new(70.0, -500.0, 20.0));
+        assert_eq!(world.wrap_position(Vec3::new(100.0, 0.0, 50.0)), Vec3::ZERO);
+        let tiny = world.wrap_position(Vec3::new(-1.0e-9, 0.0, 0.0));
+        assert!(tiny.x >= 0.0 && tiny.x < 100.0);
+
+        // A zero size leaves that axis alone.
+        let strip = WrapWorld::new(100.0, 0.0);
+        assert_eq!(
+            strip.wrap_position(Vec3::new(150.0, 0.0, -75.0)),
+            Vec3::new(50.0, 0.0, -75.0)
+        );
+        assert_eq!(strip.distance(Vec3::ZERO, Vec3::new(0.0, 0.0, 90.0)), 90.0);
+    }
+
+    #[test]
+    fn distances_take_the_shortest_way() {
+        let world = WrapWorld::new(100.0, 100.0);
+        let corner = Vec3::new(1.0, 0.0, 1.0);
+        let opposite = Vec3::new(99.0, 0.0, 99.0);
+        assert_eq!(world.delta(corner, opposite), Vec3::new(-2.0, 0.0, -2.0));
+        assert!((world.distance(corner, opposite) - Scalar::sqrt(8.0)).abs() <= EPS);
+        assert_eq!(world.distance_squared(opposite, corner), 8.0);
+        // Positions outside the bounds measure the same as their wrapped equivalents.
+        assert!(
+            (world.distance(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(201.0, 0.0, 0.0)) - 2.0).abs()
+                <= EPS
+        );
+        assert_eq!(world.direction_to(corner, corner), Vec3::ZERO);
+        assert_eq!(
+            world.direction_to(Vec3::new(50.0, 0.0, 2.0), Vec3::new(50.0, 0.0, 98.0)),
+            Vec3::new(0.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn lerp_crosses_the_seam() {
+        let world = WrapWorld::new(100.0, 100.0);
+        let a = Vec3::new(90.0, 0.0, 50.0);
+        let b = Vec3::new(20.0, 10.0, 50.0);
+        assert!(world.lerp(a, b, 0.5).distance(Vec3::new(5.0, 5.0, 50.0)) <= EPS);
+        assert!(world.lerp(a, b, 1.0).distance(b) <= EPS);
+        assert_eq!(world.lerp(a, b, 0.0), a);
+    }
+}
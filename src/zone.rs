@@ -0,0 +1,237 @@
+use crate::polygon::closest_point_on_segment;
+use crate::{Aabb2, Aabb3, Circle, FillRule, Polygon2, Scalar, Sphere, Vec2, Vec3};
+#[cfg(feature = "spacetimedb")]
+use spacetimedb::SpacetimeType;
+
+/// A shape that can measure how far a point is from its boundary.
+pub trait ZoneShape {
+    /// The point type the shape is tested against.
+    type Point: Copy;
+
+    /// Returns the distance from `point` to the boundary: negative inside, positive outside.
+    fn signed_distance(&self, point: Self::Point) -> Scalar;
+}
+
+impl ZoneShape for Circle {
+    type Point = Vec2;
+
+    #[inline]
+    fn signed_distance(&self, point: Vec2) -> Scalar {
+        point.distance(self.center) - self.radius
+    }
+}
+
+impl ZoneShape for Sphere {
+    type Point = Vec3;
+
+    #[inline]
+    fn signed_distance(&self, point: Vec3) -> Scalar {
+        point.distance(self.center) - self.radius
+    }
+}
+
+impl ZoneShape for Aabb2 {
+    type Point = Vec2;
+
+    fn signed_distance(&self, point: Vec2) -> Scalar {
+        let center = (self.min + self.max) * 0.5;
+        let d = point - center;
+        let q = Vec2::new(d.x.abs(), d.y.abs()) - (self.max - center);
+        q.max(Vec2::ZERO).length() + q.x.max(q.y).min(0.0)
+    }
+}
+
+impl ZoneShape for Aabb3 {
+    type Point = Vec3;
+
+    #[inline]
+    fn signed_distance(&self, point: Vec3) -> Scalar {
+        let center = (self.min + self.max) * 0.5;
+        crate::sdf::cuboid(point - center, self.max - center)
+    }
+}
+
+impl ZoneShape for Polygon2 {
+    type Point = Vec2;
+
+    /// Inside follows [`FillRule::NonZero`]. An empty polygon is infinitely far away.
+    fn signed_distance(&self, point: Vec2) -> Scalar {
+        let distance = self
+            .edges()
+            .map(|(a, b)| closest_point_on_segment(a, b, point).distance(point))
+            .fold(Scalar::INFINITY, Scalar::min);
+        if self.contains_point(point, FillRule::NonZero) {
+            -distance
+        } else {
+            distance
+        }
+    }
+}
+
+/// What happened to a point's membership in a [`Zone`] during an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ZoneEvent {
+    /// The point was outside and has now entered.
+    Entered,
+    /// The point was inside and has now left.
+    Exited,
+    /// The point was inside and still is.
+    Inside,
+    /// The point was outside and still is.
+    Outside,
+}
+
+impl ZoneEvent {
+    /// Returns `true` if the point is in the zone after the update.
+    #[inline]
+    pub const fn is_inside(&self) -> bool {
+        matches!(self, ZoneEvent::Entered | ZoneEvent::Inside)
+    }
+
+    /// Returns `true` if membership changed.
+    #[inline]
+    pub const fn changed(&self) -> bool {
+        matches!(self, ZoneEvent::Entered | ZoneEvent::Exited)
+    }
+}
+
+/// A trigger region with hysteresis, so a point hovering on the boundary doesn't flap
+/// between entering and leaving.
+///
+/// Thresholds are signed distances from the shape's boundary, negative inside. A point
+/// enters once its distance is at most `enter_distance` and leaves once it is greater than
+/// `exit_distance`; between the two it keeps its current membership. Keep `enter_distance`
+/// at or below `exit_distance`, or a point can enter and leave on alternate updates.
+///
+/// # Examples
+/// ```
+/// use spacetimedb_math::{Circle, Vec2, Zone, ZoneEvent};
+///
+/// // Enter one unit inside the circle, leave one unit outside it.
+/// let zone = Zone::new(Circle::new(Vec2::ZERO, 10.0), -1.0, 1.0);
+/// let mut inside = false;
+/// for (x, expected) in [
+///     (9.5, ZoneEvent::Outside),
+///     (8.5, ZoneEvent::Entered),
+///     (10.5, ZoneEvent::Inside),
+///     (11.5, ZoneEvent::Exited),
+/// ] {
+///     let event = zone.update(Vec2::new(x, 0.0), inside);
+///     assert_eq!(event, expected);
+///     inside = event.is_inside();
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "spacetimedb", derive(SpacetimeType))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Zone<T> {
+    /// The region's boundary.
+    pub shape: T,
+    /// Signed distance at or below which an outside point enters.
+    pub enter_distance: Scalar,
+    /// Signed distance above which an inside point leaves.
+    pub exit_distance: Scalar,
+}
+
+impl<T: ZoneShape> Zone<T> {
+    #[inline(always)]
+    pub const fn new(shape: T, enter_distance: Scalar, exit_distance: Scalar) -> Self {
+        Zone {
+            shape,
+            enter_distance,
+            exit_distance,
+        }
+    }
+
+    /// Returns the membership event for `point`, given whether it was inside before.
+    ///
+    /// A NaN point never enters and always leaves.
+    pub fn update(&self, point: T::Point, currently_inside: bool) -> ZoneEvent {
+        let distance = self.shape.signed_distance(point);
+        match currently_inside {
+            true if distance <= self.exit_distance => ZoneEvent::Inside,
+            true => ZoneEvent::Exited,
+            false if distance <= self.enter_distance => ZoneEvent::Entered,
+            false => ZoneEvent::Outside,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: Scalar = 1.0e-5 as Scalar;
+
+    #[test]
+    fn signed_distances_are_negative_inside() {
+        let circle = Circle::new(Vec2::new(1.0, 1.0), 2.0);
+        assert!((circle.signed_distance(Vec2::new(1.0, 1.0)) + 2.0).abs() <= EPS);
+        let sphere = Sphere::new(Vec3::ZERO, 1.0);
+        assert!((sphere.signed_distance(Vec3::new(0.0, 3.0, 0.0)) - 2.0).abs() <= EPS);
+
+        let rect = Aabb2::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0));
+        assert!((rect.signed_distance(Vec2::new(1.0, 1.0)) + 1.0).abs() <= EPS);
+        assert!((rect.signed_distance(Vec2::new(7.0, 6.0)) - 5.0).abs() <= EPS);
+        let cube = Aabb3::new(Vec3::ZERO, Vec3::splat(2.0));
+        assert!((cube.signed_distance(Vec3::new(1.0, 1.0, 0.5)) + 0.5).abs() <= EPS);
+        assert!((cube.signed_distance(Vec3::new(1.0, 5.0, 1.0)) - 3.0).abs() <= EPS);
+    }
+
+    #[test]
+    fn polygon_distance_uses_the_nearest_edge() {
+        // An L shape, so the nearest edge isn't always on the hull.
+        let l = Polygon2 {
+            vertices: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(4.0, 0.0),
+                Vec2::new(4.0, 1.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(1.0, 4.0),
+                Vec2::new(0.0, 4.0),
+            ],
+        };
+        assert!((l.signed_distance(Vec2::new(3.0, 0.25)) + 0.25).abs() <= EPS);
+        assert!((l.signed_distance(Vec2::new(2.0, 2.0)) - 1.0).abs() <= EPS);
+        assert_eq!(
+            Polygon2 { vertices: vec![] }.signed_distance(Vec2::ZERO),
+            Scalar::INFINITY
+        );
+    }
+
+    #[test]
+    fn hysteresis_keeps_membership_between_thresholds() {
+        let zone = Zone::new(Sphere::new(Vec3::ZERO, 5.0), -0.5, 0.5);
+        let edge = Vec3::new(5.0, 0.0, 0.0);
+        assert_eq!(zone.update(edge, false), ZoneEvent::Outside);
+        assert_eq!(zone.update(edge, true), ZoneEvent::Inside);
+        assert_eq!(
+            zone.update(Vec3::new(4.0, 0.0, 0.0), false),
+            ZoneEvent::Entered
+        );
+        assert_eq!(
+            zone.update(Vec3::new(6.0, 0.0, 0.0), true),
+            ZoneEvent::Exited
+        );
+        assert!(ZoneEvent::Exited.changed() && !ZoneEvent::Exited.is_inside());
+        assert!(!ZoneEvent::Inside.changed() && ZoneEvent::Inside.is_inside());
+    }
+
+    #[test]
+    #[cfg_attr(
+        feature = "validate",
+        ignore = "the sphere distance subtracts the NaN, which `validate` asserts on"
+    )]
+    fn nan_points_are_outside() {
+        let zone = Zone::new(Sphere::new(Vec3::ZERO, 5.0), -0.5, 0.5);
+        let nan = Vec3 {
+            x: Scalar::NAN,
+            y: Scalar::NAN,
+            z: Scalar::NAN,
+        };
+        assert_eq!(zone.update(nan, false), ZoneEvent::Outside);
+        assert_eq!(zone.update(nan, true), ZoneEvent::Exited);
+    }
+}